@@ -17,6 +17,8 @@ interface DocumentLoadOutputPort {
   didCompleteLoading: () => void;
   didFailLoading: () => void;
   showError: (message: string) => void;
+  /** The file at `path` was rejected as not markdown; it can be reopened as markdown anyway. */
+  offerOpenAsMarkdown: (path: string) => void;
   clearError: () => void;
   didLoadDocument: (requestedPath: string, document: MarkdownDocument) => void;
   renderDocument: (document: MarkdownDocument) => Promise<void>;
@@ -33,6 +35,7 @@ interface DocumentLoadUseCaseDeps {
 export class DocumentLoadUseCase {
   private readonly deps: DocumentLoadUseCaseDeps;
   private currentLoadNonce = 0;
+  private readonly openedAsMarkdown = new Set<string>();
 
   constructor(deps: DocumentLoadUseCaseDeps) {
     this.deps = deps;
@@ -42,6 +45,11 @@ export class DocumentLoadUseCase {
     this.currentLoadNonce += 1;
   }
 
+  /** Loads `path` as markdown from now on, including reloads, whatever its file type. */
+  openAsMarkdown(path: string): void {
+    this.openedAsMarkdown.add(path);
+  }
+
   async load(path: string, options: DocumentLoadOptions): Promise<DocumentLoadResult> {
     if (this.deps.isDisposed()) {
       return 'stale';
@@ -54,7 +62,8 @@ export class DocumentLoadUseCase {
     try {
       const loadedMarkdown = await this.deps.markdownLoader.loadMarkdownFile(
         path,
-        renderPreferencesFromSettings(this.deps.getSettings())
+        renderPreferencesFromSettings(this.deps.getSettings()),
+        this.openedAsMarkdown.has(path)
       );
 
       if (this.isStale(nonce)) {
@@ -76,9 +85,11 @@ export class DocumentLoadUseCase {
           return 'stale';
         }
 
-        await this.deps.markdownWatch.startMarkdownWatch(loadedMarkdown.path);
-        if (this.isStale(nonce)) {
-          return 'stale';
+        if ((loadedMarkdown.origin ?? 'file') === 'file') {
+          await this.deps.markdownWatch.startMarkdownWatch(loadedMarkdown.path);
+          if (this.isStale(nonce)) {
+            return 'stale';
+          }
         }
       }
 
@@ -91,6 +102,9 @@ export class DocumentLoadUseCase {
 
       const message = typeof error === 'string' ? error : error instanceof Error ? error.message : 'Unknown error';
       this.deps.output.showError(message);
+      if (isNotMarkdownError(message)) {
+        this.deps.output.offerOpenAsMarkdown(path);
+      }
       this.deps.output.didFailLoading();
       return loadedDocument ? 'failed-after-load' : 'failed-before-load';
     }
@@ -100,3 +114,7 @@ export class DocumentLoadUseCase {
     return nonce !== this.currentLoadNonce || this.deps.isDisposed();
   }
 }
+
+export function isNotMarkdownError(message: string): boolean {
+  return message.toLowerCase().includes('not a markdown file');
+}
@@ -30,25 +30,32 @@ describe('link-navigation', () => {
     ).toEqual({ type: 'blocked-external-protocol', protocol: 'javascript:' });
   });
 
-  it('classifies linked markdown files for in-app open', () => {
+  it('classifies linked markdown files as local files', () => {
     expect(resolveDocumentLinkIntent({ href: './nested/readme.md', documentPath })).toEqual({
-      type: 'open-markdown-file',
+      type: 'open-file',
       path: '/tmp/docs/nested/readme.md',
     });
   });
 
-  it('classifies UNC file URL markdown links for in-app open', () => {
+  it('classifies UNC file URL markdown links as local files', () => {
     expect(
       resolveDocumentLinkIntent({ href: 'file://server/share/notes.md', documentPath })
     ).toEqual({
-      type: 'open-markdown-file',
+      type: 'open-file',
       path: '//server/share/notes.md',
     });
   });
 
-  it('classifies linked non-markdown files for external open', () => {
+  it('classifies linked csv tables as local files', () => {
+    expect(resolveDocumentLinkIntent({ href: './data/prices.csv', documentPath })).toEqual({
+      type: 'open-file',
+      path: '/tmp/docs/data/prices.csv',
+    });
+  });
+
+  it('classifies linked non-markdown files as local files', () => {
     expect(resolveDocumentLinkIntent({ href: './assets/sample.txt', documentPath })).toEqual({
-      type: 'open-local-file',
+      type: 'open-file',
       path: '/tmp/docs/assets/sample.txt',
     });
   });
@@ -1,9 +1,4 @@
-import {
-  baseDirectoryFileUrl,
-  filePathToFileUrl,
-  isMarkdownPath,
-  withoutFragment,
-} from './path-utils';
+import { baseDirectoryFileUrl, filePathToFileUrl, withoutFragment } from './path-utils';
 
 const ALLOWED_EXTERNAL_PROTOCOLS = new Set(['http:', 'https:', 'mailto:', 'tel:']);
 
@@ -12,8 +7,8 @@ export type LinkIntent =
   | { type: 'blocked-external-protocol'; protocol: string }
   | { type: 'scroll-to-anchor'; fragment: string }
   | { type: 'open-external-url'; url: string }
-  | { type: 'open-markdown-file'; path: string }
-  | { type: 'open-local-file'; path: string };
+  /** A local file; the backend decides whether it opens in the viewer or externally. */
+  | { type: 'open-file'; path: string };
 
 export function resolveDocumentLinkIntent(input: {
   href: string;
@@ -48,10 +43,7 @@ export function resolveDocumentLinkIntent(input: {
     if (!targetPath) {
       return { type: 'none' };
     }
-    if (isMarkdownPath(targetPath)) {
-      return { type: 'open-markdown-file', path: targetPath };
-    }
-    return { type: 'open-local-file', path: targetPath };
+    return { type: 'open-file', path: targetPath };
   } catch {
     return { type: 'none' };
   }
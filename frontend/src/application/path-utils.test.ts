@@ -3,7 +3,6 @@ import { describe, expect, it } from 'vitest';
 import {
   baseDirectoryFileUrl,
   filePathToFileUrl,
-  isMarkdownPath,
   normalizePathForCompare,
   withoutFragment,
 } from './path-utils';
@@ -18,13 +17,6 @@ describe('path-utils', () => {
     expect(normalizePathForCompare('/Users/ME/Notes.md')).toBe('/Users/ME/Notes.md');
   });
 
-  it('detects markdown paths with query/hash and mixed case', () => {
-    expect(isMarkdownPath('/tmp/guide.MD')).toBe(true);
-    expect(isMarkdownPath('/tmp/guide.md?line=12')).toBe(true);
-    expect(isMarkdownPath('/tmp/guide.markdown#section-a')).toBe(true);
-    expect(isMarkdownPath('/tmp/guide.txt')).toBe(false);
-  });
-
   it('drops URL fragments while keeping base URL', () => {
     expect(withoutFragment('file:///tmp/doc.md#overview')).toBe('file:///tmp/doc.md');
     expect(withoutFragment('file:///tmp/doc.md')).toBe('file:///tmp/doc.md');
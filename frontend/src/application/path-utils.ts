@@ -1,5 +1,3 @@
-const MARKDOWN_EXTENSIONS = ['md', 'markdown', 'mdown', 'mkd', 'mkdn'];
-
 export function normalizePathForCompare(path: string): string {
   const normalized = path.replaceAll('\\', '/');
   if (isWindowsStylePath(normalized)) {
@@ -8,11 +6,6 @@ export function normalizePathForCompare(path: string): string {
   return normalized;
 }
 
-export function isMarkdownPath(path: string): boolean {
-  const clean = path.toLowerCase().split('#')[0].split('?')[0];
-  return MARKDOWN_EXTENSIONS.some((extension) => clean.endsWith(`.${extension}`));
-}
-
 export function withoutFragment(url: string): string {
   const hashIndex = url.indexOf('#');
   return hashIndex === -1 ? url : url.slice(0, hashIndex);
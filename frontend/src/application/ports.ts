@@ -16,8 +16,18 @@ export interface MarkdownFilePicker {
   pickMarkdownFile(): Promise<string | null>;
 }
 
+export interface DocumentTypeChecker {
+  /** Whether `path` opens without `asMarkdown`, decided by the backend's file type rules. */
+  isViewableDocument(path: string): Promise<boolean>;
+}
+
 export interface MarkdownFileLoader {
-  loadMarkdownFile(path: string, preferences: RenderPreferences): Promise<MarkdownDocument>;
+  /** `asMarkdown` opens files that are not recognised as markdown instead of failing. */
+  loadMarkdownFile(
+    path: string,
+    preferences: RenderPreferences,
+    asMarkdown?: boolean,
+  ): Promise<MarkdownDocument>;
 }
 
 export interface MarkdownWatchController {
@@ -40,6 +50,7 @@ export interface OpenPathRequestEvents {
 
 export interface MarkdownGateway
   extends MarkdownFilePicker,
+    DocumentTypeChecker,
     MarkdownFileLoader,
     MarkdownWatchController,
     MarkdownFileUpdateEvents,
@@ -76,6 +87,8 @@ export interface DiagnosticsReportWriter {
 export interface ViewerSettingsStore {
   load(): ViewerSettings;
   save(next: ViewerSettings): void;
+  /** Settings saved elsewhere: by another window, or by editing the settings file by hand. */
+  onSettingsChanged?(handler: (next: ViewerSettings) => void): Promise<() => void>;
 }
 
 export interface ScrollMemoryStore {
@@ -3,6 +3,9 @@ import { sanitizeMeasureWidth } from './reader-layout';
 
 export type ThemePreset = 'paper' | 'slate' | 'contrast-light' | 'contrast-dark';
 
+/** Render preferences kept in `settings.toml` that the viewer has no controls for. */
+export type RenderOptions = Omit<RenderPreferences, 'performanceMode' | 'wordCountRules'>;
+
 export interface ViewerSettings {
   performanceMode: boolean;
   safeMode: boolean;
@@ -13,6 +16,7 @@ export interface ViewerSettings {
   tocAutoExpand: boolean;
   tocCollapsed: Record<string, boolean>;
   wordCountRules: WordCountRules;
+  renderOptions: RenderOptions;
 }
 
 export const DEFAULT_SETTINGS: ViewerSettings = {
@@ -29,6 +33,7 @@ export const DEFAULT_SETTINGS: ViewerSettings = {
     includeCode: false,
     includeFrontMatter: false,
   },
+  renderOptions: {},
 };
 
 const FONT_SCALE_RANGE = { min: 0.85, max: 1.3 };
@@ -87,11 +92,14 @@ export function mergeViewerSettings(
       ),
     },
     tocCollapsed,
+    // Validated by the settings file reader, so only the shape is checked here.
+    renderOptions: { ...asRecord(source.renderOptions) } as RenderOptions,
   };
 }
 
 export function toRenderPreferences(settings: ViewerSettings): RenderPreferences {
   return {
+    ...settings.renderOptions,
     performanceMode: settings.performanceMode,
     wordCountRules: {
       includeLinks: settings.wordCountRules.includeLinks,
@@ -10,6 +10,26 @@ export interface TocEntry {
   text: string;
 }
 
+/**
+ * Only `file` documents are watched for changes; piped `stdin` documents are held in memory,
+ * `remote` ones are fetched from their URL, which is also their `path`, and `archive` ones are
+ * read from a zip entry (`docs.zip!/intro.md`) or a gzip file.
+ */
+export type DocumentOrigin = 'file' | 'stdin' | 'remote' | 'archive';
+
+/** Named sets of markdown syntax extensions; `commonmark` enables none. */
+export type ExtensionProfile = 'default' | 'github' | 'commonmark' | 'full';
+
+/** Settings the document was rendered with, after applying the nearest `.mdviewer.toml`. */
+export interface DocumentConfig {
+  render: {
+    extensionProfile: ExtensionProfile;
+    extensions: Record<string, boolean>;
+  };
+  projectConfigPath: string | null;
+  customCss: string | null;
+}
+
 export interface MarkdownDocument {
   path: string;
   title: string;
@@ -18,9 +38,29 @@ export interface MarkdownDocument {
   toc: TocEntry[];
   wordCount: number;
   readingTimeMinutes: number;
+  origin?: DocumentOrigin;
+  config?: DocumentConfig;
+}
+
+export interface TextReplacementRule {
+  pattern: string;
+  replacement: string;
+  linkTemplate?: string;
 }
 
 export interface RenderPreferences {
   performanceMode: boolean;
   wordCountRules: WordCountRules;
+  textReplacements?: TextReplacementRule[];
+  allowRawHtml?: boolean;
+  htmlAllowlist?: { tags?: string[]; attributes?: string[]; urlSchemes?: string[] } | null;
+  imageEmbedding?: 'assetUrl' | 'dataUri';
+  csvRowLimit?: number | null;
+  numberHeadings?: { startLevel?: number } | null;
+  wordsPerMinute?: number | null;
+  hanCharactersPerMinute?: number | null;
+  kanaCharactersPerMinute?: number | null;
+  blockExternalLinks?: boolean;
+  extensionProfile?: ExtensionProfile;
+  extensions?: Record<string, boolean>;
 }
@@ -131,6 +131,14 @@ class E2eMarkdownGateway implements MarkdownGateway {
     return this.state.pickMarkdownPath;
   }
 
+  async isViewableDocument(path: string): Promise<boolean> {
+    const normalizedPath = normalizePath(path);
+    return (
+      this.state.documentsByPath.has(normalizedPath) ||
+      this.state.loadFailuresByPath.has(normalizedPath)
+    );
+  }
+
   async loadMarkdownFile(path: string, preferences: RenderPreferences): Promise<MarkdownDocument> {
     const normalizedPath = normalizePath(path);
     this.state.calls.gateway.loadRequests.push({
@@ -17,10 +17,19 @@ export class TauriMarkdownGateway implements MarkdownGateway {
     return invoke<string | null>('pick_markdown_file');
   }
 
-  async loadMarkdownFile(path: string, preferences: RenderPreferences): Promise<MarkdownDocument> {
+  async isViewableDocument(path: string): Promise<boolean> {
+    return invoke<boolean>('is_viewable_document', { path });
+  }
+
+  async loadMarkdownFile(
+    path: string,
+    preferences: RenderPreferences,
+    asMarkdown = false,
+  ): Promise<MarkdownDocument> {
     return invoke<MarkdownDocument>('load_markdown_file', {
       path,
       preferences,
+      asMarkdown,
     });
   }
 
@@ -0,0 +1,183 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+import type { ViewerSettingsStore } from '../application/ports';
+import {
+  mergeViewerSettings,
+  toRenderPreferences,
+  type ViewerSettings,
+} from '../application/settings';
+import type { RenderPreferences } from '../domain';
+
+const SETTINGS_UPDATED_EVENT = 'settings://updated';
+/** Where settings were kept before they moved to `settings.toml`. */
+const LEGACY_SETTINGS_STORAGE_KEY = 'markdown-viewer:v1:settings';
+/** Collapsed outline entries are remembered per webview, like the sidebar layout. */
+const TOC_COLLAPSED_STORAGE_KEY = 'markdown-viewer:v1:toc-collapsed';
+
+interface SettingsDto {
+  render: RenderPreferences;
+  safeMode: boolean;
+  theme: ViewerSettings['theme'];
+  fontScale: number;
+  lineHeight: number;
+  measureWidth: number;
+  tocAutoExpand: boolean;
+}
+
+/**
+ * Keeps the settings in `settings.toml` through the `get_settings`/`update_settings` commands,
+ * so every window and the command line tool share them. `load` and `save` work on the copy read
+ * by {@link TauriViewerSettingsStore.open}; saves are written in the background.
+ */
+export class TauriViewerSettingsStore implements ViewerSettingsStore {
+  /** `null` when the settings file could not be read; it is then never overwritten. */
+  private current: ViewerSettings | null;
+  private tocCollapsed: Record<string, boolean>;
+  /** Settings this window wrote whose `settings://updated` echo has not arrived yet. */
+  private readonly pendingEchoes = new Set<string>();
+
+  private constructor(current: ViewerSettings | null, tocCollapsed: Record<string, boolean>) {
+    this.current = current;
+    this.tocCollapsed = tocCollapsed;
+  }
+
+  /** Reads the settings file, first moving settings older versions kept in browser storage. */
+  static async open(): Promise<TauriViewerSettingsStore> {
+    let stored: SettingsDto;
+    try {
+      stored = await invoke<SettingsDto>('get_settings');
+    } catch {
+      // An invalid file is left for the user to fix; the defaults apply meanwhile.
+      return new TauriViewerSettingsStore(null, loadTocCollapsed());
+    }
+
+    const legacy = loadLegacySettings();
+    if (!legacy) {
+      return new TauriViewerSettingsStore(fromSettingsDto(stored), loadTocCollapsed());
+    }
+    const migrated = {
+      ...legacy,
+      renderOptions: fromSettingsDto(stored).renderOptions,
+    };
+    try {
+      stored = await invoke<SettingsDto>('update_settings', {
+        settings: toSettingsDto(migrated),
+      });
+      saveTocCollapsed(legacy.tocCollapsed);
+      removeLegacySettings();
+    } catch {
+      // Browser storage is kept, so the move is retried on the next start.
+      return new TauriViewerSettingsStore(migrated, legacy.tocCollapsed);
+    }
+    return new TauriViewerSettingsStore(fromSettingsDto(stored), legacy.tocCollapsed);
+  }
+
+  load(): ViewerSettings {
+    const settings = this.current ?? mergeViewerSettings(undefined);
+    return {
+      ...settings,
+      wordCountRules: { ...settings.wordCountRules },
+      renderOptions: { ...settings.renderOptions },
+      tocCollapsed: { ...this.tocCollapsed },
+    };
+  }
+
+  save(next: ViewerSettings): void {
+    this.tocCollapsed = { ...next.tocCollapsed };
+    saveTocCollapsed(this.tocCollapsed);
+    if (!this.current) {
+      return;
+    }
+
+    const settings = toSettingsDto(next);
+    const key = JSON.stringify(settings);
+    if (key === JSON.stringify(toSettingsDto(this.current))) {
+      return;
+    }
+    this.current = fromSettingsDto(settings);
+    this.pendingEchoes.add(key);
+    void invoke('update_settings', { settings }).catch(() => {
+      // The file keeps its previous contents; the next change writes it again.
+      this.pendingEchoes.delete(key);
+    });
+  }
+
+  async onSettingsChanged(handler: (next: ViewerSettings) => void): Promise<() => void> {
+    return listen<SettingsDto>(SETTINGS_UPDATED_EVENT, (event) => {
+      const next = fromSettingsDto(event.payload);
+      const key = JSON.stringify(toSettingsDto(next));
+      if (this.pendingEchoes.delete(key)) {
+        return;
+      }
+      if (this.current && key === JSON.stringify(toSettingsDto(this.current))) {
+        return;
+      }
+      this.current = next;
+      handler(this.load());
+    });
+  }
+}
+
+function fromSettingsDto(dto: SettingsDto): ViewerSettings {
+  const { performanceMode, wordCountRules, ...renderOptions } = dto.render;
+  return mergeViewerSettings({
+    performanceMode,
+    wordCountRules,
+    safeMode: dto.safeMode,
+    theme: dto.theme,
+    fontScale: dto.fontScale,
+    lineHeight: dto.lineHeight,
+    measureWidth: dto.measureWidth,
+    tocAutoExpand: dto.tocAutoExpand,
+    renderOptions,
+  });
+}
+
+function toSettingsDto(settings: ViewerSettings): SettingsDto {
+  return {
+    render: toRenderPreferences(settings),
+    safeMode: settings.safeMode,
+    theme: settings.theme,
+    fontScale: settings.fontScale,
+    lineHeight: settings.lineHeight,
+    measureWidth: settings.measureWidth,
+    tocAutoExpand: settings.tocAutoExpand,
+  };
+}
+
+function loadLegacySettings(): ViewerSettings | null {
+  try {
+    const raw = localStorage.getItem(LEGACY_SETTINGS_STORAGE_KEY);
+    return raw ? mergeViewerSettings(JSON.parse(raw) as Partial<ViewerSettings>) : null;
+  } catch {
+    return null;
+  }
+}
+
+function removeLegacySettings(): void {
+  try {
+    localStorage.removeItem(LEGACY_SETTINGS_STORAGE_KEY);
+  } catch {
+    // Ignore storage write failures (private mode/quota/blocked storage).
+  }
+}
+
+function loadTocCollapsed(): Record<string, boolean> {
+  try {
+    const raw = localStorage.getItem(TOC_COLLAPSED_STORAGE_KEY);
+    return mergeViewerSettings({
+      tocCollapsed: raw ? (JSON.parse(raw) as Record<string, boolean>) : {},
+    }).tocCollapsed;
+  } catch {
+    return {};
+  }
+}
+
+function saveTocCollapsed(next: Record<string, boolean>): void {
+  try {
+    localStorage.setItem(TOC_COLLAPSED_STORAGE_KEY, JSON.stringify(next));
+  } catch {
+    // Ignore storage write failures (private mode/quota/blocked storage).
+  }
+}
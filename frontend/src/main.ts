@@ -8,6 +8,7 @@ import type {
   ExternalUrlOpener,
   MarkdownGateway,
   UpdateService,
+  ViewerSettingsStore,
 } from './application/ports';
 import {
   LocalStorageDocumentTabSessionStore,
@@ -28,6 +29,7 @@ interface RuntimeServices {
   updateService: UpdateService;
   appVersionProvider: AppVersionProvider;
   diagnosticsReportWriter: DiagnosticsReportWriter;
+  settingsStore: ViewerSettingsStore;
 }
 
 function initialDocumentPathFromQuery(): string | null {
@@ -59,6 +61,7 @@ async function runtimeServicesForMode(mode: RuntimeMode): Promise<RuntimeService
       updateService: adapters.updateService,
       appVersionProvider: adapters.appVersionProvider,
       diagnosticsReportWriter: adapters.diagnosticsReportWriter,
+      settingsStore: new LocalStorageViewerSettingsStore(),
     };
   }
 
@@ -67,11 +70,13 @@ async function runtimeServicesForMode(mode: RuntimeMode): Promise<RuntimeService
     { TauriExternalUrlOpener },
     { TauriUpdaterService },
     { TauriAppVersionProvider },
+    { TauriViewerSettingsStore },
   ] = await Promise.all([
     import('./infrastructure/tauri-markdown-gateway'),
     import('./infrastructure/tauri-external-url-opener'),
     import('./infrastructure/tauri-updater-service'),
     import('./infrastructure/tauri-app-version-provider'),
+    import('./infrastructure/tauri-settings-store'),
   ]);
 
   return {
@@ -80,6 +85,7 @@ async function runtimeServicesForMode(mode: RuntimeMode): Promise<RuntimeService
     updateService: new TauriUpdaterService(),
     appVersionProvider: new TauriAppVersionProvider(),
     diagnosticsReportWriter: new BrowserDiagnosticsReportWriter(),
+    settingsStore: await TauriViewerSettingsStore.open(),
   };
 }
 
@@ -96,7 +102,7 @@ async function bootstrap(): Promise<void> {
     appVersionProvider: runtimeServices.appVersionProvider,
     diagnosticsReportWriter: runtimeServices.diagnosticsReportWriter,
     initialDocumentPath: initialDocumentPathFromQuery(),
-    settingsStore: new LocalStorageViewerSettingsStore(),
+    settingsStore: runtimeServices.settingsStore,
     layoutStateStore: new LocalStorageViewerLayoutStateStore(),
     scrollMemoryStore: new LocalStorageScrollMemoryStore(),
     tabSessionStore: new LocalStorageDocumentTabSessionStore(),
@@ -1,9 +1,11 @@
-import { isMarkdownPath } from '../application/path-utils';
 import type {
+  DocumentTypeChecker,
   DragDropEvents,
   MarkdownFileUpdateEvents,
   OpenPathRequestEvents,
+  ViewerSettingsStore,
 } from '../application/ports';
+import type { ViewerSettings } from '../application/settings';
 import { errorToMessage } from './error-utils';
 import type { ViewerUi } from './ui';
 
@@ -11,11 +13,14 @@ interface AppRuntimeListenerControllerDeps {
   markdownFileUpdates: MarkdownFileUpdateEvents;
   dragDropEvents: DragDropEvents;
   openPathRequests: OpenPathRequestEvents;
+  documentTypes: DocumentTypeChecker;
+  settingsStore: Pick<ViewerSettingsStore, 'onSettingsChanged'>;
   ui: Pick<ViewerUi, 'dropOverlay'>;
   isLifecycleActive: (lifecycleToken: number) => boolean;
   onFileUpdated: (path: string) => void;
   onDroppedMarkdownPath: (path: string) => void;
   onOpenPathRequested: (path: string) => void;
+  onSettingsChanged: (settings: ViewerSettings) => void;
   onError: (message: string) => void;
 }
 
@@ -24,6 +29,7 @@ export class AppRuntimeListenerController {
   private fileUpdateUnlisten: (() => void) | null = null;
   private dragDropUnlisten: (() => void) | null = null;
   private openPathUnlisten: (() => void) | null = null;
+  private settingsUnlisten: (() => void) | null = null;
 
   constructor(deps: AppRuntimeListenerControllerDeps) {
     this.deps = deps;
@@ -61,10 +67,11 @@ export class AppRuntimeListenerController {
         }
 
         this.deps.ui.dropOverlay.classList.remove('visible');
-        const path = event.paths.find((candidate) => isMarkdownPath(candidate));
-        if (path) {
-          this.deps.onDroppedMarkdownPath(path);
-        }
+        void this.firstViewablePath(event.paths).then((path) => {
+          if (path && this.deps.isLifecycleActive(lifecycleToken)) {
+            this.deps.onDroppedMarkdownPath(path);
+          }
+        });
       });
 
       if (!this.deps.isLifecycleActive(lifecycleToken)) {
@@ -77,9 +84,11 @@ export class AppRuntimeListenerController {
         if (!this.deps.isLifecycleActive(lifecycleToken)) {
           return;
         }
-        if (isMarkdownPath(path)) {
-          this.deps.onOpenPathRequested(path);
-        }
+        void this.firstViewablePath([path]).then((viewablePath) => {
+          if (viewablePath && this.deps.isLifecycleActive(lifecycleToken)) {
+            this.deps.onOpenPathRequested(viewablePath);
+          }
+        });
       });
 
       if (!this.deps.isLifecycleActive(lifecycleToken)) {
@@ -88,17 +97,43 @@ export class AppRuntimeListenerController {
       }
       this.openPathUnlisten = openPathUnlisten;
 
+      const settingsUnlisten = await this.deps.settingsStore.onSettingsChanged?.((settings) => {
+        if (!this.deps.isLifecycleActive(lifecycleToken)) {
+          return;
+        }
+        this.deps.onSettingsChanged(settings);
+      });
+
+      if (settingsUnlisten && !this.deps.isLifecycleActive(lifecycleToken)) {
+        settingsUnlisten();
+        return;
+      }
+      this.settingsUnlisten = settingsUnlisten ?? null;
+
       const launchPath = await this.deps.openPathRequests.consumeLaunchOpenPath();
-      if (
-        this.deps.isLifecycleActive(lifecycleToken) &&
-        typeof launchPath === 'string' &&
-        isMarkdownPath(launchPath)
-      ) {
-        this.deps.onOpenPathRequested(launchPath);
+      if (typeof launchPath !== 'string' || !this.deps.isLifecycleActive(lifecycleToken)) {
+        return;
+      }
+      const viewableLaunchPath = await this.firstViewablePath([launchPath]);
+      if (viewableLaunchPath && this.deps.isLifecycleActive(lifecycleToken)) {
+        this.deps.onOpenPathRequested(viewableLaunchPath);
+      }
+    } catch (error) {
+      this.deps.onError(errorToMessage(error));
+    }
+  }
+
+  private async firstViewablePath(paths: string[]): Promise<string | null> {
+    try {
+      for (const path of paths) {
+        if (await this.deps.documentTypes.isViewableDocument(path)) {
+          return path;
+        }
       }
     } catch (error) {
       this.deps.onError(errorToMessage(error));
     }
+    return null;
   }
 
   dispose(): void {
@@ -111,6 +146,9 @@ export class AppRuntimeListenerController {
     this.openPathUnlisten?.();
     this.openPathUnlisten = null;
 
+    this.settingsUnlisten?.();
+    this.settingsUnlisten = null;
+
     this.deps.ui.dropOverlay.classList.remove('visible');
   }
 }
@@ -93,6 +93,7 @@ export function appShell(): string {
           <section id="error-banner" class="error-banner" role="alert">
             <p id="error-message"></p>
             <div class="actions">
+              <button id="open-as-markdown" class="btn" hidden>Open as Markdown</button>
               <button id="recover-view" class="btn danger">Exit Safe Mode</button>
               <button id="dismiss-error" class="btn ghost">Dismiss</button>
             </div>
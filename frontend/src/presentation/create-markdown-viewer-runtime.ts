@@ -25,6 +25,7 @@ import {
   buildDiagnosticsReport,
   diagnosticsReportFileName,
 } from '../application/diagnostics';
+import type { ViewerSettings } from '../application/settings';
 import { buildWorkspaceQuickOpenItems } from '../application/workspace-quick-open';
 import {
   createWorkspaceQuickOpenCommands,
@@ -149,6 +150,7 @@ export function createMarkdownViewerRuntime(
     ui: {
       markdownContent: deps.ui.markdownContent,
     },
+    documentTypes: deps.gateway,
     openExternalUrl: async (url: string) => linkDispatcher.openExternalUrl(url),
     openMarkdownFile: async (path: string) => linkDispatcher.openMarkdownFile(path),
     openLocalFile: async (path: string, sourceDocumentPath: string) =>
@@ -169,6 +171,7 @@ export function createMarkdownViewerRuntime(
       reloadButton: deps.ui.reloadButton,
       recentDocumentsList: deps.ui.recentDocumentsList,
       errorBanner: deps.ui.errorBanner,
+      openAsMarkdownButton: deps.ui.openAsMarkdownButton,
     },
     markdownLoader: deps.gateway,
     markdownWatch: deps.gateway,
@@ -293,6 +296,8 @@ export function createMarkdownViewerRuntime(
     markdownFileUpdates: deps.gateway,
     dragDropEvents: deps.gateway,
     openPathRequests: deps.gateway,
+    documentTypes: deps.gateway,
+    settingsStore: deps.settingsStore,
     ui: {
       dropOverlay: deps.ui.dropOverlay,
     },
@@ -306,6 +311,11 @@ export function createMarkdownViewerRuntime(
     onOpenPathRequested: (path: string) => {
       void workspaceController.openDroppedMarkdownPath(path);
     },
+    onSettingsChanged: (settings: ViewerSettings) => {
+      if (preferencesController.applyExternalSettings(settings)) {
+        void workspaceController.reloadCurrentDocument();
+      }
+    },
     onError: deps.onErrorBanner,
   });
 
@@ -5,7 +5,7 @@ import { describe, expect, it, vi } from 'vitest';
 import { DocumentLinkController } from './document-link-controller';
 
 describe('document-link-controller (presentation)', () => {
-  it('routes anchor, external, markdown, and local file links to the correct handlers', () => {
+  it('routes anchor, external, markdown, and local file links to the correct handlers', async () => {
     const markdownContent = document.createElement('article');
     markdownContent.innerHTML = [
       '<h2><a id="mdv-inline-html" aria-hidden="true"></a>Inline HTML</h2>',
@@ -22,8 +22,10 @@ describe('document-link-controller (presentation)', () => {
     const openMarkdownFile = vi.fn();
     const openLocalFile = vi.fn();
     const onBlockedLink = vi.fn();
+    const isViewableDocument = vi.fn(async (path: string) => path.endsWith('.md'));
     const controller = new DocumentLinkController({
       ui: { markdownContent },
+      documentTypes: { isViewableDocument },
       openExternalUrl,
       openMarkdownFile,
       openLocalFile,
@@ -59,14 +61,21 @@ describe('document-link-controller (presentation)', () => {
     const markdownEvent = new MouseEvent('click', { bubbles: true, cancelable: true });
     markdownContent.querySelector<HTMLElement>('#markdown-link')!.dispatchEvent(markdownEvent);
     expect(markdownEvent.defaultPrevented).toBe(true);
-    expect(openMarkdownFile).toHaveBeenCalledWith('/work/markdown-viewer/test-fixtures/link-behavior/next.md');
+    await vi.waitFor(() => {
+      expect(openMarkdownFile).toHaveBeenCalledWith('/work/markdown-viewer/test-fixtures/link-behavior/next.md');
+    });
 
     const localEvent = new MouseEvent('click', { bubbles: true, cancelable: true });
     markdownContent.querySelector<HTMLElement>('#local-link')!.dispatchEvent(localEvent);
     expect(localEvent.defaultPrevented).toBe(true);
-    expect(openLocalFile).toHaveBeenCalledWith(
-      '/work/markdown-viewer/test-fixtures/link-behavior/assets/sample-image.svg',
-      sourcePath
+    await vi.waitFor(() => {
+      expect(openLocalFile).toHaveBeenCalledWith(
+        '/work/markdown-viewer/test-fixtures/link-behavior/assets/sample-image.svg',
+        sourcePath
+      );
+    });
+    expect(isViewableDocument).toHaveBeenCalledWith(
+      '/work/markdown-viewer/test-fixtures/link-behavior/assets/sample-image.svg'
     );
 
     const normalizedImageSrc = markdownContent
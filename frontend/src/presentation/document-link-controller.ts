@@ -1,4 +1,5 @@
 import { resolveDocumentLinkIntent } from '../application/link-navigation';
+import type { DocumentTypeChecker } from '../application/ports';
 import {
   baseDirectoryFileUrl,
   hasUriScheme,
@@ -7,6 +8,7 @@ import type { ViewerUi } from './ui';
 
 interface DocumentLinkControllerDeps {
   ui: Pick<ViewerUi, 'markdownContent'>;
+  documentTypes: DocumentTypeChecker;
   openExternalUrl: (url: string) => Promise<void> | void;
   openMarkdownFile: (path: string) => Promise<void> | void;
   openLocalFile: (path: string, sourceDocumentPath: string) => Promise<void> | void;
@@ -94,18 +96,27 @@ export class DocumentLinkController {
         return;
       }
 
-      if (intent.type === 'open-markdown-file') {
-        void this.deps.openMarkdownFile(intent.path);
-        return;
-      }
-
-      void this.deps.openLocalFile(intent.path, documentPath);
+      void this.openFile(intent.path, documentPath);
     };
 
     ui.markdownContent.addEventListener('click', onClick);
     this.clickDisposer = () => ui.markdownContent.removeEventListener('click', onClick);
   }
 
+  private async openFile(path: string, documentPath: string): Promise<void> {
+    let viewable = false;
+    try {
+      viewable = await this.deps.documentTypes.isViewableDocument(path);
+    } catch {
+      // Hand files the backend cannot classify to the system instead.
+    }
+    if (viewable) {
+      await this.deps.openMarkdownFile(path);
+      return;
+    }
+    await this.deps.openLocalFile(path, documentPath);
+  }
+
   private resolveHeadingTarget(fragment: string, anchorLabel?: string): HTMLElement | null {
     const decoded = this.decodeUriComponent(fragment).trim();
     if (!decoded) {
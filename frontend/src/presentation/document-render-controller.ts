@@ -1,6 +1,7 @@
 import type { ViewerSettings } from '../application/settings';
 import type { MarkdownDocument } from '../domain';
 import type { DocumentLinkController } from './document-link-controller';
+import { compareTableCellText } from './document-view-utils';
 import { errorToMessage } from './error-utils';
 import { FindController } from './find-controller';
 import type { MarkdownFormattingEngine } from '../application/ports';
@@ -11,6 +12,8 @@ import {
 import { TocController } from './toc-controller';
 import type { ViewerUi } from './ui';
 
+const PROJECT_STYLE_ELEMENT_ID = 'mdv-project-css';
+
 interface DocumentRenderControllerDeps {
   ui: Pick<
     ViewerUi,
@@ -59,6 +62,7 @@ export class DocumentRenderController {
     ui.stats.textContent = `${documentDto.wordCount.toLocaleString()} words • ${documentDto.readingTimeMinutes} min read`;
 
     this.cleanupObservers();
+    this.applyProjectStyle(documentDto.config?.customCss ?? null);
     this.deps.tocController.render(documentDto.toc);
 
     if (settings.safeMode) {
@@ -79,6 +83,7 @@ export class DocumentRenderController {
     }
     this.deps.documentLinkController.applyNormalizedResourceUrls(documentDto.path);
     this.deps.documentLinkController.bind(documentDto.path);
+    this.applyTableSorting();
     if (!(await this.applyMathEnhancement(documentDto.source, renderToken))) {
       return;
     }
@@ -101,6 +106,7 @@ export class DocumentRenderController {
     ui.subtitle.textContent = 'Drop a .md file or click Open';
     ui.stats.textContent = '0 words • 0 min read';
     ui.path.textContent = 'No file loaded';
+    this.applyProjectStyle(null);
     this.deps.tocController.render([]);
     ui.safeContent.hidden = true;
     ui.markdownContent.hidden = false;
@@ -299,6 +305,63 @@ export class DocumentRenderController {
     return true;
   }
 
+  private applyProjectStyle(css: string | null): void {
+    const existing = document.getElementById(PROJECT_STYLE_ELEMENT_ID);
+    if (css === null) {
+      existing?.remove();
+      return;
+    }
+    const style = existing ?? document.head.appendChild(document.createElement('style'));
+    style.id = PROJECT_STYLE_ELEMENT_ID;
+    style.textContent = css;
+  }
+
+  private applyTableSorting(): void {
+    const tables = this.deps.ui.markdownContent.querySelectorAll<HTMLTableElement>(
+      'table[data-sortable="true"]'
+    );
+    for (const table of tables) {
+      const headerCells = Array.from(table.tHead?.rows[0]?.cells ?? []);
+      headerCells.forEach((cell, columnIndex) => {
+        const sort = () => this.sortTableByColumn(table, headerCells, columnIndex);
+        cell.tabIndex = 0;
+        cell.setAttribute('aria-sort', 'none');
+        cell.addEventListener('click', sort);
+        cell.addEventListener('keydown', (event) => {
+          if (event.key === 'Enter' || event.key === ' ') {
+            event.preventDefault();
+            sort();
+          }
+        });
+      });
+    }
+  }
+
+  private sortTableByColumn(
+    table: HTMLTableElement,
+    headerCells: HTMLTableCellElement[],
+    columnIndex: number
+  ): void {
+    const body = table.tBodies[0];
+    if (!body) {
+      return;
+    }
+
+    const direction =
+      headerCells[columnIndex].getAttribute('aria-sort') === 'ascending' ? 'descending' : 'ascending';
+    for (const cell of headerCells) {
+      cell.setAttribute('aria-sort', 'none');
+    }
+    headerCells[columnIndex].setAttribute('aria-sort', direction);
+
+    const cellText = (row: HTMLTableRowElement) => row.cells[columnIndex]?.textContent ?? '';
+    const rows = Array.from(body.rows).sort((left, right) => {
+      const order = compareTableCellText(cellText(left), cellText(right));
+      return direction === 'ascending' ? order : -order;
+    });
+    body.append(...rows);
+  }
+
   private codeLanguage(block: HTMLElement): string | null {
     for (const className of block.classList) {
       if (!className.startsWith('language-')) {
@@ -4,6 +4,7 @@ import {
   baseDirectoryFileUrl,
   buildParentMap,
   buildTocTree,
+  compareTableCellText,
   escapeHtml,
   filePathToFileUrl,
   hasUriScheme,
@@ -40,6 +41,14 @@ describe('document-view-utils', () => {
     );
   });
 
+  it('compares table cells numerically when both are numbers', () => {
+    expect(compareTableCellText('$1,200', '950')).toBeGreaterThan(0);
+    expect(compareTableCellText('12%', '9%')).toBeGreaterThan(0);
+    expect(compareTableCellText('item 10', 'item 9')).toBeGreaterThan(0);
+    expect(compareTableCellText('apple', 'Banana')).toBeLessThan(0);
+    expect(compareTableCellText('', '3')).toBeLessThan(0);
+  });
+
   it('builds toc tree and parent map for nested headings', () => {
     const tree = buildTocTree([
       { id: 'h1', text: 'H1', level: 1 },
@@ -63,3 +63,26 @@ export function buildParentMap(nodes: TocNode[]): Map<string, string | null> {
   walk(nodes, null);
   return map;
 }
+
+/**
+ * Orders two table cells for column sorting: numerically when both look like numbers
+ * (ignoring thousands separators, currency signs and a trailing percent), otherwise as text
+ * with embedded numbers compared by value.
+ */
+export function compareTableCellText(left: string, right: string): number {
+  const leftNumber = parseTableNumber(left);
+  const rightNumber = parseTableNumber(right);
+  if (leftNumber !== null && rightNumber !== null) {
+    return leftNumber - rightNumber;
+  }
+  return left.trim().localeCompare(right.trim(), undefined, { numeric: true, sensitivity: 'base' });
+}
+
+function parseTableNumber(value: string): number | null {
+  const clean = value.trim().replace(/^[$€£¥]/, '').replace(/%$/, '').replaceAll(/[,_]/g, '');
+  if (clean.length === 0) {
+    return null;
+  }
+  const parsed = Number(clean);
+  return Number.isFinite(parsed) ? parsed : null;
+}
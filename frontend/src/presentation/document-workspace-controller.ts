@@ -76,6 +76,10 @@ export class DocumentWorkspaceController {
     });
   }
 
+  async openRejectedDocumentAsMarkdown(): Promise<void> {
+    await this.deps.runtime.openRejectedDocumentAsMarkdown();
+  }
+
   async reloadCurrentDocument(): Promise<void> {
     await this.deps.runtime.reloadCurrentDocument();
   }
@@ -38,6 +38,8 @@ export interface DocumentWorkspaceRuntime {
   tabsUseCase: DocumentWorkspaceTabsUseCase;
   loadDocument: (path: string, options: WorkspaceLoadOptions) => Promise<DocumentLoadResult>;
   reloadCurrentDocument: () => Promise<void>;
+  /** Reopens the file last rejected as not markdown, rendering it as markdown anyway. */
+  openRejectedDocumentAsMarkdown: () => Promise<void>;
 }
 
 interface CreateDocumentWorkspaceRuntimeDeps {
@@ -54,6 +56,7 @@ interface CreateDocumentWorkspaceRuntimeDeps {
     | 'reloadButton'
     | 'recentDocumentsList'
     | 'errorBanner'
+    | 'openAsMarkdownButton'
   >;
   markdownLoader: MarkdownFileLoader;
   markdownWatch: MarkdownWatchController;
@@ -73,6 +76,7 @@ export function createDocumentWorkspaceRuntime(
   deps: CreateDocumentWorkspaceRuntimeDeps
 ): DocumentWorkspaceRuntime {
   const state = new DocumentWorkspaceState();
+  let rejectedMarkdownPath: string | null = null;
 
   const sessionController = new DocumentWorkspaceSessionController({
     ui: {
@@ -129,8 +133,13 @@ export function createDocumentWorkspaceRuntime(
       showError: (message: string) => {
         deps.onErrorBanner(message);
       },
+      offerOpenAsMarkdown: (path: string) => {
+        rejectedMarkdownPath = path;
+        deps.ui.openAsMarkdownButton.hidden = false;
+      },
       clearError: () => {
         deps.ui.errorBanner.classList.remove('visible');
+        deps.ui.openAsMarkdownButton.hidden = true;
       },
       didLoadDocument: (requestedPath: string, document: MarkdownDocument) => {
         sessionController.applyLoadedDocument(requestedPath, document.path, document.title);
@@ -171,6 +180,22 @@ export function createDocumentWorkspaceRuntime(
     });
   };
 
+  const openRejectedDocumentAsMarkdown = async (): Promise<void> => {
+    const path = rejectedMarkdownPath;
+    if (!path) {
+      return;
+    }
+
+    rejectedMarkdownPath = null;
+    deps.ui.openAsMarkdownButton.hidden = true;
+    loadController.openAsMarkdown(path);
+    await tabsUseCase.openInTab(path, {
+      activateTab: true,
+      restartWatch: true,
+      restoreScroll: true,
+    });
+  };
+
   const reloadController = new DocumentReloadController({
     currentDocumentPath: () => state.currentDocumentPath(),
     reloadCurrentDocument,
@@ -196,5 +221,6 @@ export function createDocumentWorkspaceRuntime(
     tabsUseCase,
     loadDocument,
     reloadCurrentDocument,
+    openRejectedDocumentAsMarkdown,
   };
 }
@@ -129,6 +129,7 @@ class FakeGateway implements MarkdownGateway {
   startWatchCalls: string[] = [];
   stopWatchCalls = 0;
   launchPath: string | null = null;
+  nonViewablePaths = new Set<string>();
   protected fileUpdatedHandler: ((event: FileUpdatedEvent) => void) | null = null;
   protected dragDropHandler: ((event: DragDropEventPayload) => void) | null = null;
   protected openPathHandler: ((path: string) => void) | null = null;
@@ -137,6 +138,10 @@ class FakeGateway implements MarkdownGateway {
     return this.pickResult;
   }
 
+  async isViewableDocument(path: string): Promise<boolean> {
+    return !this.nonViewablePaths.has(path);
+  }
+
   async loadMarkdownFile(
     path: string,
     preferences: RenderPreferences
@@ -220,6 +225,28 @@ class PathSelectiveFailureGateway extends FakeGateway {
   }
 }
 
+class NotMarkdownGateway extends FakeGateway {
+  notMarkdownPaths = new Set<string>();
+  asMarkdownLoads: string[] = [];
+
+  override async loadMarkdownFile(
+    path: string,
+    preferences: RenderPreferences,
+    asMarkdown = false
+  ): Promise<MarkdownDocument> {
+    this.loadCalls.push({ path, preferences });
+    if (asMarkdown) {
+      this.asMarkdownLoads.push(path);
+    } else if (this.notMarkdownPaths.has(path)) {
+      throw new Error(`not a markdown file: ${path}`);
+    }
+    return {
+      ...this.nextDocument,
+      path,
+    };
+  }
+}
+
 class MemorySettingsStore implements ViewerSettingsStore {
   private settings: ViewerSettings;
 
@@ -682,6 +709,7 @@ describe('MarkdownViewerApp', () => {
     const context = setupApp();
 
     await flushMicrotasks();
+    context.gateway.nonViewablePaths.add('/tmp/notes.txt');
     context.gateway.emitOpenPath('/tmp/notes.txt');
     await flushMicrotasks();
     expect(context.gateway.loadCalls).toHaveLength(0);
@@ -695,6 +723,28 @@ describe('MarkdownViewerApp', () => {
     await context.app.dispose();
   });
 
+  it('offers to open files rejected as not markdown as markdown anyway', async () => {
+    const gateway = new NotMarkdownGateway();
+    gateway.notMarkdownPaths.add('/tmp/build.log');
+    const context = setupApp({ gateway });
+
+    await flushMicrotasks();
+    gateway.emitOpenPath('/tmp/build.log');
+    await vi.waitFor(() => {
+      expect(context.ui.errorText.textContent).toContain('not a markdown file');
+    });
+    expect(context.ui.openAsMarkdownButton.hidden).toBe(false);
+
+    context.ui.openAsMarkdownButton.click();
+    await vi.waitFor(() => {
+      expect(context.ui.path.textContent).toBe('/tmp/build.log');
+    });
+    expect(gateway.asMarkdownLoads).toEqual(['/tmp/build.log']);
+    expect(context.ui.openAsMarkdownButton.hidden).toBe(true);
+
+    await context.app.dispose();
+  });
+
   it('opens multiple markdown files as tabs and switches active tab on click', async () => {
     const context = setupApp();
 
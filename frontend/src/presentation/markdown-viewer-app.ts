@@ -159,6 +159,7 @@ export class MarkdownViewerApp {
 
   private showErrorBanner(message: string): void {
     this.deps.ui.errorText.textContent = message;
+    this.deps.ui.openAsMarkdownButton.hidden = true;
     this.deps.ui.errorBanner.classList.add('visible');
   }
 }
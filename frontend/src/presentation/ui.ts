@@ -38,6 +38,7 @@ export interface ViewerUi {
   viewerScroll: HTMLElement;
   errorBanner: HTMLElement;
   errorText: HTMLElement;
+  openAsMarkdownButton: HTMLButtonElement;
   recoverButton: HTMLButtonElement;
   dismissErrorButton: HTMLButtonElement;
   dropOverlay: HTMLElement;
@@ -101,6 +102,7 @@ export function createViewerUi(): ViewerUi {
     viewerScroll: mustGet<HTMLElement>('#viewer-scroll'),
     errorBanner: mustGet<HTMLElement>('#error-banner'),
     errorText: mustGet<HTMLElement>('#error-message'),
+    openAsMarkdownButton: mustGet<HTMLButtonElement>('#open-as-markdown'),
     recoverButton: mustGet<HTMLButtonElement>('#recover-view'),
     dismissErrorButton: mustGet<HTMLButtonElement>('#dismiss-error'),
     dropOverlay: mustGet<HTMLElement>('#drop-overlay'),
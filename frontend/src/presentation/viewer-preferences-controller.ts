@@ -5,7 +5,7 @@ import {
   measureWidthCssValue,
   reconcileMeasureWidthOnMaxChange,
 } from '../application/reader-layout';
-import type { ViewerSettings } from '../application/settings';
+import { toRenderPreferences, type ViewerSettings } from '../application/settings';
 import type {
   ViewerLayoutState,
   ViewerLayoutStateStore,
@@ -60,6 +60,21 @@ export class ViewerPreferencesController {
     return this.settings;
   }
 
+  /**
+   * Shows settings saved by another window or a hand edit of the settings file, keeping this
+   * window's collapsed outline entries. Returns whether the document must render again.
+   */
+  applyExternalSettings(next: ViewerSettings): boolean {
+    const renderChanged =
+      JSON.stringify(toRenderPreferences(next)) !==
+      JSON.stringify(toRenderPreferences(this.settings));
+    this.settings = { ...next, tocCollapsed: this.settings.tocCollapsed };
+    this.refreshMeasureWidthControl({ keepAtMax: true });
+    this.applySettingsToControls();
+    this.applySettingsToDocument();
+    return renderChanged;
+  }
+
   isSafeModeEnabled(): boolean {
     return this.settings.safeMode;
   }
@@ -230,9 +230,13 @@ export class ViewerUiActionController {
 
   private errorAndScrollHandlers(): Pick<
     ViewerUiBindingHandlers,
-    'onRecoverFromError' | 'onDismissError' | 'onViewerScroll'
+    'onOpenAsMarkdown' | 'onRecoverFromError' | 'onDismissError' | 'onViewerScroll'
   > {
     return {
+      onOpenAsMarkdown: () => {
+        this.deps.ui.errorBanner.classList.remove('visible');
+        void this.deps.workspaceController.openRejectedDocumentAsMarkdown();
+      },
       onRecoverFromError: () => {
         this.deps.preferencesController.clearSafeModeForRecovery();
         this.deps.ui.errorBanner.classList.remove('visible');
@@ -44,6 +44,7 @@ export interface ViewerUiBindingHandlers {
   onShowShortcutsHelp: () => void;
   onShortcutsClose: () => void;
   onShortcutsDismiss: () => void;
+  onOpenAsMarkdown: () => void;
   onRecoverFromError: () => void;
   onDismissError: () => void;
   onViewerScroll: () => void;
@@ -243,6 +244,7 @@ export class ViewerUiBinder {
   }
 
   private bindErrorBannerControls(ui: ViewerUi, handlers: ViewerUiBindingHandlers): void {
+    this.bindListener(ui.openAsMarkdownButton, 'click', handlers.onOpenAsMarkdown);
     this.bindListener(ui.recoverButton, 'click', handlers.onRecoverFromError);
     this.bindListener(ui.dismissErrorButton, 'click', handlers.onDismissError);
   }
@@ -8,8 +8,24 @@ pub enum MarkdownViewerError {
     FileNotFound(PathBuf),
     #[error("not a markdown file: {0}")]
     NotMarkdown(PathBuf),
+    #[error("not a folder: {0}")]
+    NotAFolder(PathBuf),
     #[error("failed to read file {path}: {reason}")]
     ReadFile { path: PathBuf, reason: String },
+    #[error("failed to write file {path}: {reason}")]
+    WriteFile { path: PathBuf, reason: String },
+    #[error("file changed since it was loaded, reload it and try again: {0}")]
+    DocumentChanged(PathBuf),
+    #[error("document is read-only: {0}")]
+    ReadOnlyDocument(PathBuf),
+    #[error("failed to fetch {url}: {reason}")]
+    Fetch { url: String, reason: String },
+    #[error("not a zip archive: {0}")]
+    NotAnArchive(PathBuf),
+    #[error("failed to read archive {path}: {reason}")]
+    Archive { path: PathBuf, reason: String },
+    #[error("no task list item on line {line} of {path}")]
+    TaskItemNotFound { path: PathBuf, line: usize },
     #[error("file watcher error for {path}: {reason}")]
     Watch { path: PathBuf, reason: String },
     #[error("invalid source document path: {0}")]
@@ -23,4 +39,24 @@ pub enum MarkdownViewerError {
     },
     #[error("failed to open linked file {path}: {reason}")]
     OpenLinkedFile { path: PathBuf, reason: String },
+    #[error("invalid text replacement pattern `{pattern}`: {reason}")]
+    InvalidTextReplacement { pattern: String, reason: String },
+    #[error("invalid project configuration {path}: {reason}")]
+    ProjectConfig { path: PathBuf, reason: String },
+    #[error("invalid lint configuration {path}: {reason}")]
+    LintConfig { path: PathBuf, reason: String },
+    #[error("failed to read word list {path}: {reason}")]
+    WordList { path: PathBuf, reason: String },
+    #[error("file is not in a git repository: {0}")]
+    NotVersioned(PathBuf),
+    #[error("unknown revision `{revision}`: {reason}")]
+    UnknownRevision { revision: String, reason: String },
+    #[error("{path} does not exist at revision `{revision}`")]
+    FileNotInRevision { path: PathBuf, revision: String },
+    #[error("failed to read git history for {path}: {reason}")]
+    VersionControl { path: PathBuf, reason: String },
+    #[error("failed to load render plugin {path}: {reason}")]
+    Plugin { path: PathBuf, reason: String },
+    #[error("invalid settings file {path}: {reason}")]
+    Settings { path: PathBuf, reason: String },
 }
@@ -1,14 +1,29 @@
 use std::sync::Arc;
 
 use crate::error::MarkdownViewerError;
-use crate::models::{MarkdownDocumentOutput, RenderPreferencesInput};
-use crate::use_cases::{LoadMarkdownFileUseCase, OpenLinkedFileUseCase, WatchMarkdownFileUseCase};
+use crate::models::{
+    ArchiveListingOutput, DiffBaseInput, DocumentDiffOutput, FileHistoryOutput, LintReportOutput,
+    MarkdownDocumentOutput, RenderPreferencesInput, SpellCheckOutput, ToggleTaskItemOutput,
+    ViewerSettings, WorkspaceTasksOutput,
+};
+use crate::use_cases::{
+    CheckSpellingUseCase, DiffMarkdownFileUseCase, GetSettingsUseCase, LintMarkdownFileUseCase,
+    ListArchiveEntriesUseCase, ListFileHistoryUseCase, ListWorkspaceTasksUseCase,
+    LoadMarkdownFileUseCase, LoadMarkdownRevisionUseCase, OpenLinkedFileUseCase,
+    ToggleTaskItemUseCase, UpdateSettingsUseCase, WatchMarkdownFileUseCase, WatchSettingsUseCase,
+};
 
 pub trait LoadMarkdownFileInputPort: Send + Sync {
     fn execute(
         &self,
         path_input: &str,
-        preferences: RenderPreferencesInput,
+        preferences: Option<RenderPreferencesInput>,
+    ) -> Result<MarkdownDocumentOutput, MarkdownViewerError>;
+
+    fn execute_as_markdown(
+        &self,
+        path_input: &str,
+        preferences: Option<RenderPreferencesInput>,
     ) -> Result<MarkdownDocumentOutput, MarkdownViewerError>;
 }
 
@@ -16,10 +31,18 @@ impl LoadMarkdownFileInputPort for LoadMarkdownFileUseCase {
     fn execute(
         &self,
         path_input: &str,
-        preferences: RenderPreferencesInput,
+        preferences: Option<RenderPreferencesInput>,
     ) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
         LoadMarkdownFileUseCase::execute(self, path_input, preferences)
     }
+
+    fn execute_as_markdown(
+        &self,
+        path_input: &str,
+        preferences: Option<RenderPreferencesInput>,
+    ) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
+        LoadMarkdownFileUseCase::execute_as_markdown(self, path_input, preferences)
+    }
 }
 
 pub trait WatchMarkdownFileInputPort: Send + Sync {
@@ -63,3 +86,163 @@ impl OpenLinkedFileInputPort for OpenLinkedFileUseCase {
         OpenLinkedFileUseCase::execute(self, linked_path_input, source_document_path_input)
     }
 }
+
+pub trait ListWorkspaceTasksInputPort: Send + Sync {
+    fn execute(
+        &self,
+        folder_input: &str,
+        preferences: RenderPreferencesInput,
+    ) -> Result<WorkspaceTasksOutput, MarkdownViewerError>;
+}
+
+impl ListWorkspaceTasksInputPort for ListWorkspaceTasksUseCase {
+    fn execute(
+        &self,
+        folder_input: &str,
+        preferences: RenderPreferencesInput,
+    ) -> Result<WorkspaceTasksOutput, MarkdownViewerError> {
+        ListWorkspaceTasksUseCase::execute(self, folder_input, preferences)
+    }
+}
+
+pub trait ToggleTaskItemInputPort: Send + Sync {
+    fn execute(
+        &self,
+        path_input: &str,
+        line: usize,
+        expected_content_hash: &str,
+    ) -> Result<ToggleTaskItemOutput, MarkdownViewerError>;
+}
+
+impl ToggleTaskItemInputPort for ToggleTaskItemUseCase {
+    fn execute(
+        &self,
+        path_input: &str,
+        line: usize,
+        expected_content_hash: &str,
+    ) -> Result<ToggleTaskItemOutput, MarkdownViewerError> {
+        ToggleTaskItemUseCase::execute(self, path_input, line, expected_content_hash)
+    }
+}
+
+pub trait LintMarkdownFileInputPort: Send + Sync {
+    fn execute(&self, path_input: &str) -> Result<LintReportOutput, MarkdownViewerError>;
+}
+
+impl LintMarkdownFileInputPort for LintMarkdownFileUseCase {
+    fn execute(&self, path_input: &str) -> Result<LintReportOutput, MarkdownViewerError> {
+        LintMarkdownFileUseCase::execute(self, path_input)
+    }
+}
+
+pub trait CheckSpellingInputPort: Send + Sync {
+    fn execute(&self, path_input: &str) -> Result<SpellCheckOutput, MarkdownViewerError>;
+}
+
+impl CheckSpellingInputPort for CheckSpellingUseCase {
+    fn execute(&self, path_input: &str) -> Result<SpellCheckOutput, MarkdownViewerError> {
+        CheckSpellingUseCase::execute(self, path_input)
+    }
+}
+
+pub trait LoadMarkdownRevisionInputPort: Send + Sync {
+    fn execute(
+        &self,
+        path_input: &str,
+        revision: &str,
+        preferences: RenderPreferencesInput,
+    ) -> Result<MarkdownDocumentOutput, MarkdownViewerError>;
+}
+
+impl LoadMarkdownRevisionInputPort for LoadMarkdownRevisionUseCase {
+    fn execute(
+        &self,
+        path_input: &str,
+        revision: &str,
+        preferences: RenderPreferencesInput,
+    ) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
+        LoadMarkdownRevisionUseCase::execute(self, path_input, revision, preferences)
+    }
+}
+
+pub trait ListFileHistoryInputPort: Send + Sync {
+    fn execute(&self, path_input: &str) -> Result<FileHistoryOutput, MarkdownViewerError>;
+}
+
+impl ListFileHistoryInputPort for ListFileHistoryUseCase {
+    fn execute(&self, path_input: &str) -> Result<FileHistoryOutput, MarkdownViewerError> {
+        ListFileHistoryUseCase::execute(self, path_input)
+    }
+}
+
+pub trait DiffMarkdownFileInputPort: Send + Sync {
+    fn execute(
+        &self,
+        path_input: &str,
+        base: DiffBaseInput,
+        preferences: RenderPreferencesInput,
+    ) -> Result<DocumentDiffOutput, MarkdownViewerError>;
+}
+
+impl DiffMarkdownFileInputPort for DiffMarkdownFileUseCase {
+    fn execute(
+        &self,
+        path_input: &str,
+        base: DiffBaseInput,
+        preferences: RenderPreferencesInput,
+    ) -> Result<DocumentDiffOutput, MarkdownViewerError> {
+        DiffMarkdownFileUseCase::execute(self, path_input, base, preferences)
+    }
+}
+
+pub trait ListArchiveEntriesInputPort: Send + Sync {
+    fn execute(&self, path_input: &str) -> Result<ArchiveListingOutput, MarkdownViewerError>;
+}
+
+impl ListArchiveEntriesInputPort for ListArchiveEntriesUseCase {
+    fn execute(&self, path_input: &str) -> Result<ArchiveListingOutput, MarkdownViewerError> {
+        ListArchiveEntriesUseCase::execute(self, path_input)
+    }
+}
+
+pub trait GetSettingsInputPort: Send + Sync {
+    fn execute(&self) -> Result<ViewerSettings, MarkdownViewerError>;
+}
+
+impl GetSettingsInputPort for GetSettingsUseCase {
+    fn execute(&self) -> Result<ViewerSettings, MarkdownViewerError> {
+        GetSettingsUseCase::execute(self)
+    }
+}
+
+pub trait UpdateSettingsInputPort: Send + Sync {
+    fn execute(&self, settings: ViewerSettings) -> Result<ViewerSettings, MarkdownViewerError>;
+}
+
+impl UpdateSettingsInputPort for UpdateSettingsUseCase {
+    fn execute(&self, settings: ViewerSettings) -> Result<ViewerSettings, MarkdownViewerError> {
+        UpdateSettingsUseCase::execute(self, settings)
+    }
+}
+
+pub trait WatchSettingsInputPort: Send + Sync {
+    fn start(
+        &self,
+        on_changed: Arc<dyn Fn(Result<ViewerSettings, MarkdownViewerError>) + Send + Sync>,
+    ) -> Result<(), MarkdownViewerError>;
+
+    fn stop(&self);
+}
+
+impl WatchSettingsInputPort for WatchSettingsUseCase {
+    fn start(
+        &self,
+        on_changed: Arc<dyn Fn(Result<ViewerSettings, MarkdownViewerError>) + Send + Sync>,
+    ) -> Result<(), MarkdownViewerError> {
+        WatchSettingsUseCase::start(self, on_changed)
+    }
+
+    fn stop(&self) {
+        WatchSettingsUseCase::stop(self);
+    }
+}
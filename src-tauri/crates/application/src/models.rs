@@ -1,6 +1,23 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use markdown_viewer_domain::archive::ArchiveEntry as DomainArchiveEntry;
 use markdown_viewer_domain::document::{
-    RenderPreferences as DomainRenderPreferences, WordCountRules as DomainWordCountRules,
+    DocumentStatistics as DomainDocumentStatistics, DocumentWarning as DomainDocumentWarning,
+    DocumentWarningKind as DomainDocumentWarningKind, HeadingNumbering as DomainHeadingNumbering,
+    HtmlAllowlist as DomainHtmlAllowlist, ImageEmbedding as DomainImageEmbedding,
+    RenderPreferences as DomainRenderPreferences, TaskListItem as DomainTaskListItem,
+    TextReplacementRule as DomainTextReplacementRule, WordCountRules as DomainWordCountRules,
+};
+use markdown_viewer_domain::document_source::DocumentOrigin as DomainDocumentOrigin;
+use markdown_viewer_domain::extensions::{
+    ExtensionProfile as DomainExtensionProfile, MarkdownExtension,
+};
+use markdown_viewer_domain::lint::{
+    LintDiagnostic as DomainLintDiagnostic, LintSeverity as DomainLintSeverity,
 };
+use markdown_viewer_domain::revision::FileRevision as DomainFileRevision;
+use markdown_viewer_domain::spelling::Misspelling as DomainMisspelling;
 
 #[derive(Debug, Clone)]
 pub struct TocEntryOutput {
@@ -9,6 +26,124 @@ pub struct TocEntryOutput {
     pub text: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentWarningKindOutput {
+    MissingImage,
+    DiagramError,
+    CsvError,
+    PluginError,
+    /// A project configuration setting that was ignored.
+    ProjectConfig,
+}
+
+/// Where a loaded document came from; only file documents are watched and editable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentOriginOutput {
+    File,
+    Stdin,
+    Remote,
+    Archive,
+}
+
+impl From<DomainDocumentOrigin> for DocumentOriginOutput {
+    fn from(value: DomainDocumentOrigin) -> Self {
+        match value {
+            DomainDocumentOrigin::File => Self::File,
+            DomainDocumentOrigin::Stdin => Self::Stdin,
+            DomainDocumentOrigin::Remote => Self::Remote,
+            DomainDocumentOrigin::Archive => Self::Archive,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentWarningOutput {
+    pub kind: DocumentWarningKindOutput,
+    pub message: String,
+    pub line: Option<usize>,
+}
+
+impl From<DomainDocumentWarning> for DocumentWarningOutput {
+    fn from(value: DomainDocumentWarning) -> Self {
+        Self {
+            kind: match value.kind {
+                DomainDocumentWarningKind::MissingImage => DocumentWarningKindOutput::MissingImage,
+                DomainDocumentWarningKind::DiagramError => DocumentWarningKindOutput::DiagramError,
+                DomainDocumentWarningKind::CsvError => DocumentWarningKindOutput::CsvError,
+                DomainDocumentWarningKind::PluginError => DocumentWarningKindOutput::PluginError,
+            },
+            message: value.message,
+            line: value.line,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskCompletionOutput {
+    pub checked: usize,
+    pub unchecked: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadabilityOutput {
+    pub flesch_reading_ease: f64,
+    pub flesch_kincaid_grade: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentStatisticsOutput {
+    pub section_word_counts: BTreeMap<String, usize>,
+    pub code_blocks: usize,
+    pub images: usize,
+    pub tables: usize,
+    pub links: usize,
+    pub footnotes: usize,
+    pub tasks: TaskCompletionOutput,
+    pub readability: Option<ReadabilityOutput>,
+}
+
+impl From<DomainDocumentStatistics> for DocumentStatisticsOutput {
+    fn from(value: DomainDocumentStatistics) -> Self {
+        Self {
+            section_word_counts: value.section_word_counts,
+            code_blocks: value.code_blocks,
+            images: value.images,
+            tables: value.tables,
+            links: value.links,
+            footnotes: value.footnotes,
+            tasks: TaskCompletionOutput {
+                checked: value.tasks.checked,
+                unchecked: value.tasks.unchecked,
+            },
+            readability: value.readability.map(|readability| ReadabilityOutput {
+                flesch_reading_ease: readability.flesch_reading_ease,
+                flesch_kincaid_grade: readability.flesch_kincaid_grade,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskListItemOutput {
+    pub text: String,
+    pub checked: bool,
+    pub depth: usize,
+    pub heading_id: Option<String>,
+    pub line: usize,
+}
+
+impl From<DomainTaskListItem> for TaskListItemOutput {
+    fn from(value: DomainTaskListItem) -> Self {
+        Self {
+            text: value.text,
+            checked: value.checked,
+            depth: value.depth,
+            heading_id: value.heading_id,
+            line: value.line,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MarkdownDocumentOutput {
     pub path: String,
@@ -18,9 +153,210 @@ pub struct MarkdownDocumentOutput {
     pub toc: Vec<TocEntryOutput>,
     pub word_count: usize,
     pub reading_time_minutes: u16,
+    pub statistics: DocumentStatisticsOutput,
+    pub tasks: Vec<TaskListItemOutput>,
+    pub warnings: Vec<DocumentWarningOutput>,
+    pub local_images: Vec<PathBuf>,
+    /// Fingerprint of `source`; pass it back when editing the file to detect concurrent changes.
+    pub content_hash: String,
+    pub origin: DocumentOriginOutput,
+    pub config: EffectiveConfig,
+}
+
+/// The configuration a document was rendered with: the caller's render preferences with the
+/// nearest project configuration file, if any, applied over them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EffectiveConfig {
+    pub render: RenderPreferencesInput,
+    /// The `.mdviewer.toml` that applied.
+    pub project_config_path: Option<String>,
+    /// Stylesheet named by the project configuration, to apply on top of the viewer's own.
+    pub custom_css: Option<String>,
+    /// Why project settings were ignored, reported as warnings of the document.
+    pub ignored_settings: Vec<String>,
+}
+
+impl From<RenderPreferencesInput> for EffectiveConfig {
+    fn from(value: RenderPreferencesInput) -> Self {
+        Self {
+            render: value,
+            project_config_path: None,
+            custom_css: None,
+            ignored_settings: Vec::new(),
+        }
+    }
+}
+
+/// The version a diff compares the current file against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffBaseInput {
+    /// Markdown source held by the caller, such as the version shown before a reload.
+    Source(String),
+    /// The file as committed at a revision of its version control repository.
+    Revision(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentDiffOutput {
+    pub path: String,
+    pub html: String,
+    pub inserted_blocks: usize,
+    pub deleted_blocks: usize,
+    pub modified_blocks: usize,
+    pub local_images: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToggleTaskItemOutput {
+    pub path: String,
+    /// State of the task after the toggle.
+    pub checked: bool,
+    /// Fingerprint of the file as written, for a follow-up edit.
+    pub content_hash: String,
+}
+
+/// An open task found while scanning a folder, with the document it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceTaskOutput {
+    pub path: String,
+    pub document_title: String,
+    pub task: TaskListItemOutput,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceTasksOutput {
+    pub folder: String,
+    /// Unchecked tasks ordered by document path, then by position in the document.
+    pub tasks: Vec<WorkspaceTaskOutput>,
+    /// Markdown files that were found but could not be read or rendered.
+    pub unreadable_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintSeverityOutput {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnosticOutput {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub severity: LintSeverityOutput,
+    pub message: String,
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+impl From<DomainLintDiagnostic> for LintDiagnosticOutput {
+    fn from(value: DomainLintDiagnostic) -> Self {
+        Self {
+            rule_id: value.rule_id,
+            rule_name: value.rule_name,
+            severity: match value.severity {
+                DomainLintSeverity::Warning => LintSeverityOutput::Warning,
+                DomainLintSeverity::Error => LintSeverityOutput::Error,
+            },
+            message: value.message,
+            line: value.line,
+            column: value.column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintReportOutput {
+    pub path: String,
+    pub diagnostics: Vec<LintDiagnosticOutput>,
+    /// The `.markdownlint.json` that configured the rules; `None` when the defaults applied.
+    pub config_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisspellingOutput {
+    pub word: String,
+    pub line: usize,
+    pub start_column: usize,
+    pub end_column: usize,
+    pub suggestions: Vec<String>,
+}
+
+impl From<DomainMisspelling> for MisspellingOutput {
+    fn from(value: DomainMisspelling) -> Self {
+        Self {
+            word: value.word,
+            line: value.line,
+            start_column: value.start_column,
+            end_column: value.end_column,
+            suggestions: value.suggestions,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellCheckOutput {
+    pub path: String,
+    pub misspellings: Vec<MisspellingOutput>,
+    /// The workspace word list that was applied; `None` when there is none.
+    pub word_list_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRevisionOutput {
+    pub commit_id: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: i64,
+    pub summary: String,
+    pub message: String,
+}
+
+impl From<DomainFileRevision> for FileRevisionOutput {
+    fn from(value: DomainFileRevision) -> Self {
+        Self {
+            commit_id: value.commit_id,
+            author_name: value.author_name,
+            author_email: value.author_email,
+            authored_at: value.authored_at,
+            summary: value.summary,
+            message: value.message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHistoryOutput {
+    pub path: String,
+    /// Commits that changed the file, newest first.
+    pub revisions: Vec<FileRevisionOutput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntryOutput {
+    pub name: String,
+    /// Path input that opens the entry.
+    pub path: String,
+    pub size: u64,
+}
+
+impl From<DomainArchiveEntry> for ArchiveEntryOutput {
+    fn from(value: DomainArchiveEntry) -> Self {
+        Self {
+            name: value.name,
+            path: value.path.to_string_lossy().into_owned(),
+            size: value.size,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveListingOutput {
+    pub path: String,
+    /// Markdown entries sorted by name.
+    pub entries: Vec<ArchiveEntryOutput>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WordCountRulesInput {
     pub include_links: bool,
     pub include_code: bool,
@@ -37,10 +373,134 @@ impl Default for WordCountRulesInput {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextReplacementRuleInput {
+    pub pattern: String,
+    pub replacement: String,
+    pub link_template: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlAllowlistInput {
+    pub tags: Vec<String>,
+    pub attributes: Vec<String>,
+    pub url_schemes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadingNumberingInput {
+    pub start_level: u8,
+}
+
+impl Default for HeadingNumberingInput {
+    fn default() -> Self {
+        Self { start_level: 1 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImageEmbeddingInput {
+    #[default]
+    AssetUrl,
+    DataUri,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExtensionProfileInput {
+    #[default]
+    Default,
+    Github,
+    CommonMark,
+    Full,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct RenderPreferencesInput {
     pub performance_mode: bool,
     pub word_count_rules: WordCountRulesInput,
+    pub text_replacements: Vec<TextReplacementRuleInput>,
+    pub allow_raw_html: bool,
+    /// `None` keeps the domain's default allowlist.
+    pub html_allowlist: Option<HtmlAllowlistInput>,
+    pub image_embedding: ImageEmbeddingInput,
+    pub csv_row_limit: Option<usize>,
+    pub number_headings: Option<HeadingNumberingInput>,
+    pub words_per_minute: Option<usize>,
+    pub han_characters_per_minute: Option<usize>,
+    pub kana_characters_per_minute: Option<usize>,
+    pub block_external_links: bool,
+    pub extension_profile: ExtensionProfileInput,
+    /// Extensions turned on or off regardless of the profile, keyed by their snake_case name
+    /// (`spoiler`, `math_dollars`, ...); unknown names are ignored.
+    pub extension_overrides: BTreeMap<String, bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThemePreset {
+    #[default]
+    Paper,
+    Slate,
+    ContrastLight,
+    ContrastDark,
+}
+
+/// Everything the user configures, kept in the settings file so the command line tool and every
+/// window see the same values: how documents render, plus how the viewer presents them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewerSettings {
+    pub render: RenderPreferencesInput,
+    /// Asks before following links that leave the document.
+    pub safe_mode: bool,
+    pub theme: ThemePreset,
+    pub font_scale: f64,
+    pub line_height: f64,
+    /// Widest text column, in characters.
+    pub measure_width: u32,
+    pub toc_auto_expand: bool,
+}
+
+impl Default for ViewerSettings {
+    fn default() -> Self {
+        Self {
+            render: RenderPreferencesInput::default(),
+            safe_mode: false,
+            theme: ThemePreset::default(),
+            font_scale: 1.0,
+            line_height: 1.62,
+            measure_width: 76,
+            toc_auto_expand: true,
+        }
+    }
+}
+
+impl ViewerSettings {
+    pub const FONT_SCALE_RANGE: (f64, f64) = (0.85, 1.3);
+    pub const LINE_HEIGHT_RANGE: (f64, f64) = (1.35, 2.0);
+    pub const MIN_MEASURE_WIDTH: u32 = 58;
+
+    /// The settings with values the viewer cannot apply moved into range, the same way the
+    /// frontend treats its stored settings.
+    pub fn clamped(self) -> Self {
+        let defaults = Self::default();
+        Self {
+            font_scale: clamp_finite(self.font_scale, Self::FONT_SCALE_RANGE, defaults.font_scale),
+            line_height: clamp_finite(
+                self.line_height,
+                Self::LINE_HEIGHT_RANGE,
+                defaults.line_height,
+            ),
+            measure_width: self.measure_width.max(Self::MIN_MEASURE_WIDTH),
+            ..self
+        }
+    }
+}
+
+fn clamp_finite(value: f64, (min, max): (f64, f64), fallback: f64) -> f64 {
+    if value.is_finite() {
+        value.clamp(min, max)
+    } else {
+        fallback
+    }
 }
 
 impl From<WordCountRulesInput> for DomainWordCountRules {
@@ -53,11 +513,82 @@ impl From<WordCountRulesInput> for DomainWordCountRules {
     }
 }
 
+impl From<TextReplacementRuleInput> for DomainTextReplacementRule {
+    fn from(value: TextReplacementRuleInput) -> Self {
+        Self {
+            pattern: value.pattern,
+            replacement: value.replacement,
+            link_template: value.link_template,
+        }
+    }
+}
+
+impl From<HtmlAllowlistInput> for DomainHtmlAllowlist {
+    fn from(value: HtmlAllowlistInput) -> Self {
+        Self {
+            tags: value.tags,
+            attributes: value.attributes,
+            url_schemes: value.url_schemes,
+        }
+    }
+}
+
+impl From<HeadingNumberingInput> for DomainHeadingNumbering {
+    fn from(value: HeadingNumberingInput) -> Self {
+        Self {
+            start_level: value.start_level,
+        }
+    }
+}
+
+impl From<ImageEmbeddingInput> for DomainImageEmbedding {
+    fn from(value: ImageEmbeddingInput) -> Self {
+        match value {
+            ImageEmbeddingInput::AssetUrl => Self::AssetUrl,
+            ImageEmbeddingInput::DataUri => Self::DataUri,
+        }
+    }
+}
+
+impl From<ExtensionProfileInput> for DomainExtensionProfile {
+    fn from(value: ExtensionProfileInput) -> Self {
+        match value {
+            ExtensionProfileInput::Default => Self::Default,
+            ExtensionProfileInput::Github => Self::Github,
+            ExtensionProfileInput::CommonMark => Self::CommonMark,
+            ExtensionProfileInput::Full => Self::Full,
+        }
+    }
+}
+
 impl From<RenderPreferencesInput> for DomainRenderPreferences {
     fn from(value: RenderPreferencesInput) -> Self {
         Self {
             performance_mode: value.performance_mode,
             word_count_rules: value.word_count_rules.into(),
+            text_replacements: value
+                .text_replacements
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            allow_raw_html: value.allow_raw_html,
+            html_allowlist: value.html_allowlist.map(Into::into).unwrap_or_default(),
+            image_embedding: value.image_embedding.into(),
+            csv_row_limit: value.csv_row_limit,
+            number_headings: value.number_headings.map(Into::into),
+            words_per_minute: value.words_per_minute,
+            han_characters_per_minute: value.han_characters_per_minute,
+            kana_characters_per_minute: value.kana_characters_per_minute,
+            block_external_links: value.block_external_links,
+            extension_profile: value.extension_profile.into(),
+            extension_overrides: value
+                .extension_overrides
+                .into_iter()
+                .filter_map(|(name, enabled)| {
+                    MarkdownExtension::from_name(&name).map(|extension| (extension, enabled))
+                })
+                .collect(),
+            base_url: None,
         }
     }
 }
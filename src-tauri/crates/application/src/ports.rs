@@ -1,22 +1,122 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use markdown_viewer_domain::archive::ArchiveEntry;
+use markdown_viewer_domain::diff::DocumentDiff;
 use markdown_viewer_domain::document::{RenderPreferences, RenderedMarkdown};
+use markdown_viewer_domain::document_source::SourceDocument;
+use markdown_viewer_domain::lint::LintReport;
+use markdown_viewer_domain::revision::FileRevision;
+use markdown_viewer_domain::spelling::SpellCheckReport;
 
 use crate::error::MarkdownViewerError;
+use crate::models::{EffectiveConfig, RenderPreferencesInput, ViewerSettings};
 
+/// Source of the documents the viewer opens, usually files on disk but also in-memory documents
+/// such as markdown piped into standard input.
 pub trait MarkdownFileRepository: Send + Sync {
-    fn read(&self, path_input: &str) -> Result<(PathBuf, String), MarkdownViewerError>;
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError>;
+
+    /// Reads the document as markdown even when its file type is not recognised as markdown,
+    /// for when the user asks to open it anyway.
+    fn read_as_markdown(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        self.read(path_input)
+    }
+}
+
+/// Reads markdown files as they were committed to the version control repository containing
+/// them.
+pub trait MarkdownRevisionRepository: Send + Sync {
+    /// Resolves `path_input` to a file and reads it as of `revision`, a commit ID, branch or
+    /// tag name, or any other revision expression.
+    fn read_at(
+        &self,
+        path_input: &str,
+        revision: &str,
+    ) -> Result<(PathBuf, String), MarkdownViewerError>;
+
+    /// Resolves `path_input` to a file and lists the commits that changed it, newest first.
+    fn history(
+        &self,
+        path_input: &str,
+    ) -> Result<(PathBuf, Vec<FileRevision>), MarkdownViewerError>;
+}
+
+/// Looks inside archives the viewer can open documents from.
+pub trait MarkdownArchiveBrowser: Send + Sync {
+    /// Resolves `path_input` to an archive and lists the markdown files inside it, by entry name.
+    fn list_markdown_entries(
+        &self,
+        path_input: &str,
+    ) -> Result<(PathBuf, Vec<ArchiveEntry>), MarkdownViewerError>;
+}
+
+/// Writes edited documents back to where [`MarkdownFileRepository`] read them from.
+pub trait MarkdownFileWriter: Send + Sync {
+    /// Replaces the contents of `path` so that readers see either the old or the new contents,
+    /// never a partial write.
+    fn write_atomically(&self, path: &Path, contents: &str) -> Result<(), MarkdownViewerError>;
+}
+
+pub trait MarkdownWorkspaceScanner: Send + Sync {
+    /// Resolves `folder_input` to a folder and lists the markdown files below it, recursively and
+    /// sorted by path.
+    fn list_markdown_files(
+        &self,
+        folder_input: &str,
+    ) -> Result<(PathBuf, Vec<PathBuf>), MarkdownViewerError>;
 }
 
 pub trait MarkdownRenderer: Send + Sync {
+    /// Renders `markdown` read from `document_path`, which is used to resolve relative
+    /// references such as local images.
     fn render(
         &self,
         markdown: &str,
+        document_path: &Path,
         preferences: RenderPreferences,
     ) -> Result<RenderedMarkdown, MarkdownViewerError>;
 }
 
+pub trait MarkdownDiffer: Send + Sync {
+    /// Compares the `old` and `new` sources of the document at `document_path` block by block
+    /// and renders both into one document with the changes marked.
+    fn diff(
+        &self,
+        old: &str,
+        new: &str,
+        document_path: &Path,
+        preferences: RenderPreferences,
+    ) -> Result<DocumentDiff, MarkdownViewerError>;
+}
+
+pub trait ProjectConfigResolver: Send + Sync {
+    /// Applies the project configuration file nearest to `document_path` over `preferences`.
+    /// Without one, the preferences are returned unchanged.
+    fn resolve(
+        &self,
+        document_path: &Path,
+        preferences: RenderPreferencesInput,
+    ) -> Result<EffectiveConfig, MarkdownViewerError>;
+}
+
+pub trait MarkdownLinter: Send + Sync {
+    /// Checks `markdown` read from `document_path` against the rule configuration that applies
+    /// to that document.
+    fn lint(&self, markdown: &str, document_path: &Path)
+        -> Result<LintReport, MarkdownViewerError>;
+}
+
+pub trait SpellChecker: Send + Sync {
+    /// Checks the prose of `markdown` read from `document_path`, accepting the words of the
+    /// workspace word list that applies to that document.
+    fn check(
+        &self,
+        markdown: &str,
+        document_path: &Path,
+    ) -> Result<SpellCheckReport, MarkdownViewerError>;
+}
+
 pub trait MarkdownWatchService: Send + Sync {
     fn start(
         &self,
@@ -34,3 +134,19 @@ pub trait PathCanonicalizer: Send + Sync {
 pub trait LinkedFileOpener: Send + Sync {
     fn open_detached(&self, path: &Path) -> Result<(), MarkdownViewerError>;
 }
+
+/// Keeps the viewer settings in a file outside the webview, shared by every window and the
+/// command line tool.
+pub trait SettingsStore: Send + Sync {
+    /// The settings file.
+    fn path(&self) -> PathBuf;
+
+    /// Whether the settings file has been written.
+    fn exists(&self) -> bool;
+
+    /// Reads the settings, or the defaults when the file is missing. Files written by older
+    /// versions are upgraded in place.
+    fn load(&self) -> Result<ViewerSettings, MarkdownViewerError>;
+
+    fn save(&self, settings: &ViewerSettings) -> Result<(), MarkdownViewerError>;
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use markdown_viewer_domain::document_source::SourceDocument;
+
+use crate::error::MarkdownViewerError;
+use crate::models::SpellCheckOutput;
+use crate::ports::{MarkdownFileRepository, SpellChecker};
+
+#[derive(Clone)]
+pub struct CheckSpellingUseCase {
+    repository: Arc<dyn MarkdownFileRepository>,
+    spell_checker: Arc<dyn SpellChecker>,
+}
+
+impl CheckSpellingUseCase {
+    pub fn new(
+        repository: Arc<dyn MarkdownFileRepository>,
+        spell_checker: Arc<dyn SpellChecker>,
+    ) -> Self {
+        Self {
+            repository,
+            spell_checker,
+        }
+    }
+
+    pub fn execute(&self, path_input: &str) -> Result<SpellCheckOutput, MarkdownViewerError> {
+        let SourceDocument { path, source, .. } = self.repository.read(path_input)?;
+        let report = self.spell_checker.check(&source, &path)?;
+
+        Ok(SpellCheckOutput {
+            path: path.to_string_lossy().into_owned(),
+            misspellings: report.misspellings.into_iter().map(Into::into).collect(),
+            word_list_path: report
+                .word_list_path
+                .map(|word_list_path| word_list_path.to_string_lossy().into_owned()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::spelling::{Misspelling, SpellCheckReport};
+
+    use crate::error::MarkdownViewerError;
+    use crate::use_cases::check_spelling::CheckSpellingUseCase;
+    use crate::use_cases::test_support::{StubRepository, StubSpellChecker};
+
+    #[test]
+    fn checks_the_file_source_and_reports_the_word_list_in_use() {
+        let repository = Arc::new(StubRepository::ok(
+            PathBuf::from("/docs/guide.md"),
+            "Teh guide\n",
+        ));
+        let spell_checker = Arc::new(StubSpellChecker::ok(SpellCheckReport {
+            misspellings: vec![Misspelling {
+                word: "Teh".to_string(),
+                line: 1,
+                start_column: 1,
+                end_column: 4,
+                suggestions: vec!["The".to_string()],
+            }],
+            word_list_path: Some(PathBuf::from("/docs/.mdviewer-words.txt")),
+        }));
+        let use_case = CheckSpellingUseCase::new(repository, Arc::clone(&spell_checker) as Arc<_>);
+
+        let output = use_case
+            .execute("/docs/guide.md")
+            .expect("spell check should succeed");
+
+        assert_eq!(output.path, "/docs/guide.md");
+        assert_eq!(
+            output.word_list_path.as_deref(),
+            Some("/docs/.mdviewer-words.txt")
+        );
+        assert_eq!(output.misspellings.len(), 1);
+        assert_eq!(output.misspellings[0].word, "Teh");
+        assert_eq!(output.misspellings[0].suggestions, ["The"]);
+        assert_eq!(
+            spell_checker
+                .last_call
+                .lock()
+                .expect("spell checker call state should be lockable")
+                .clone(),
+            Some(("Teh guide\n".to_string(), PathBuf::from("/docs/guide.md")))
+        );
+    }
+
+    #[test]
+    fn propagates_word_list_errors() {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/docs/a.md"), "text"));
+        let spell_checker = Arc::new(StubSpellChecker::fail(MarkdownViewerError::WordList {
+            path: PathBuf::from("/docs/.mdviewer-words.txt"),
+            reason: "permission denied".to_string(),
+        }));
+        let use_case = CheckSpellingUseCase::new(repository, spell_checker);
+
+        let error = use_case
+            .execute("/docs/a.md")
+            .expect_err("spell check should fail");
+
+        assert!(matches!(error, MarkdownViewerError::WordList { .. }));
+    }
+}
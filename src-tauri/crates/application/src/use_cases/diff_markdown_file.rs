@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use markdown_viewer_domain::document_source::SourceDocument;
+
+use crate::error::MarkdownViewerError;
+use crate::models::{DiffBaseInput, DocumentDiffOutput, RenderPreferencesInput};
+use crate::ports::{MarkdownDiffer, MarkdownFileRepository, MarkdownRevisionRepository};
+use crate::use_cases::load_markdown_file::document_preferences;
+
+#[derive(Clone)]
+pub struct DiffMarkdownFileUseCase {
+    repository: Arc<dyn MarkdownFileRepository>,
+    revisions: Arc<dyn MarkdownRevisionRepository>,
+    differ: Arc<dyn MarkdownDiffer>,
+}
+
+impl DiffMarkdownFileUseCase {
+    pub fn new(
+        repository: Arc<dyn MarkdownFileRepository>,
+        revisions: Arc<dyn MarkdownRevisionRepository>,
+        differ: Arc<dyn MarkdownDiffer>,
+    ) -> Self {
+        Self {
+            repository,
+            revisions,
+            differ,
+        }
+    }
+
+    /// Renders the current contents of the file with the changes since `base` marked.
+    pub fn execute(
+        &self,
+        path_input: &str,
+        base: DiffBaseInput,
+        preferences: RenderPreferencesInput,
+    ) -> Result<DocumentDiffOutput, MarkdownViewerError> {
+        let document = self.repository.read(path_input)?;
+        let preferences = document_preferences(&document, preferences);
+        let SourceDocument { path, source, .. } = document;
+        let base_source = match base {
+            DiffBaseInput::Source(source) => source,
+            DiffBaseInput::Revision(revision) => {
+                self.revisions
+                    .read_at(&path.to_string_lossy(), &revision)?
+                    .1
+            }
+        };
+        let diff = self
+            .differ
+            .diff(&base_source, &source, &path, preferences)?;
+
+        Ok(DocumentDiffOutput {
+            path: path.to_string_lossy().into_owned(),
+            html: diff.html,
+            inserted_blocks: diff.inserted_blocks,
+            deleted_blocks: diff.deleted_blocks,
+            modified_blocks: diff.modified_blocks,
+            local_images: diff.local_images,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::diff::DocumentDiff;
+
+    use crate::error::MarkdownViewerError;
+    use crate::models::DiffBaseInput;
+    use crate::use_cases::diff_markdown_file::DiffMarkdownFileUseCase;
+    use crate::use_cases::test_support::{
+        sample_preferences, StubDiffer, StubRepository, StubRevisionRepository,
+    };
+
+    fn sample_diff() -> DocumentDiff {
+        DocumentDiff {
+            html: "<div class=\"mdv-diff-modified\"><p>new</p></div>".to_string(),
+            inserted_blocks: 0,
+            deleted_blocks: 0,
+            modified_blocks: 1,
+            local_images: vec![PathBuf::from("/docs/chart.png")],
+        }
+    }
+
+    #[test]
+    fn compares_the_current_file_against_a_caller_provided_source() {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/docs/guide.md"), "new"));
+        let revisions = Arc::new(StubRevisionRepository::fail(
+            MarkdownViewerError::NotVersioned(PathBuf::from("/docs/guide.md")),
+        ));
+        let differ = Arc::new(StubDiffer::ok(sample_diff()));
+        let use_case =
+            DiffMarkdownFileUseCase::new(repository, revisions, Arc::clone(&differ) as Arc<_>);
+
+        let output = use_case
+            .execute(
+                "guide.md",
+                DiffBaseInput::Source("old".to_string()),
+                sample_preferences(),
+            )
+            .expect("diff should succeed");
+
+        assert_eq!(output.path, "/docs/guide.md");
+        assert_eq!(output.modified_blocks, 1);
+        assert_eq!(output.local_images, [PathBuf::from("/docs/chart.png")]);
+        assert_eq!(
+            differ
+                .last_call
+                .lock()
+                .expect("differ call state should be lockable")
+                .clone(),
+            Some((
+                "old".to_string(),
+                "new".to_string(),
+                PathBuf::from("/docs/guide.md")
+            ))
+        );
+    }
+
+    #[test]
+    fn reads_the_base_from_the_resolved_path_at_the_revision() {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/repo/guide.md"), "new"));
+        let revisions = Arc::new(StubRevisionRepository::ok(
+            PathBuf::from("/repo/guide.md"),
+            "committed",
+            Vec::new(),
+        ));
+        let differ = Arc::new(StubDiffer::ok(sample_diff()));
+        let use_case = DiffMarkdownFileUseCase::new(
+            repository,
+            Arc::clone(&revisions) as Arc<_>,
+            Arc::clone(&differ) as Arc<_>,
+        );
+
+        use_case
+            .execute(
+                "guide.md",
+                DiffBaseInput::Revision("main".to_string()),
+                sample_preferences(),
+            )
+            .expect("diff should succeed");
+
+        assert_eq!(
+            revisions
+                .last_read
+                .lock()
+                .expect("revision repository call state should be lockable")
+                .clone(),
+            Some(("/repo/guide.md".to_string(), "main".to_string()))
+        );
+        assert_eq!(
+            differ
+                .last_call
+                .lock()
+                .expect("differ call state should be lockable")
+                .as_ref()
+                .map(|(old, _, _)| old.clone()),
+            Some("committed".to_string())
+        );
+    }
+
+    #[test]
+    fn propagates_revision_errors() {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/repo/new.md"), "new"));
+        let revisions = Arc::new(StubRevisionRepository::fail(
+            MarkdownViewerError::FileNotInRevision {
+                path: PathBuf::from("/repo/new.md"),
+                revision: "HEAD".to_string(),
+            },
+        ));
+        let differ = Arc::new(StubDiffer::ok(sample_diff()));
+        let use_case =
+            DiffMarkdownFileUseCase::new(repository, revisions, Arc::clone(&differ) as Arc<_>);
+
+        let error = use_case
+            .execute(
+                "/repo/new.md",
+                DiffBaseInput::Revision("HEAD".to_string()),
+                sample_preferences(),
+            )
+            .expect_err("diff should fail");
+
+        assert!(matches!(
+            error,
+            MarkdownViewerError::FileNotInRevision { .. }
+        ));
+        assert!(differ
+            .last_call
+            .lock()
+            .expect("differ call state should be lockable")
+            .is_none());
+    }
+}
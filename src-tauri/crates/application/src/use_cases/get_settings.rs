@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::error::MarkdownViewerError;
+use crate::models::ViewerSettings;
+use crate::ports::SettingsStore;
+
+#[derive(Clone)]
+pub struct GetSettingsUseCase {
+    store: Arc<dyn SettingsStore>,
+}
+
+impl GetSettingsUseCase {
+    pub fn new(store: Arc<dyn SettingsStore>) -> Self {
+        Self { store }
+    }
+
+    pub fn execute(&self) -> Result<ViewerSettings, MarkdownViewerError> {
+        self.store.load().map(ViewerSettings::clamped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use crate::error::MarkdownViewerError;
+    use crate::models::ViewerSettings;
+    use crate::use_cases::get_settings::GetSettingsUseCase;
+    use crate::use_cases::test_support::StubSettingsStore;
+
+    #[test]
+    fn returns_stored_settings_moved_into_range() {
+        let use_case = GetSettingsUseCase::new(Arc::new(StubSettingsStore::ok(ViewerSettings {
+            font_scale: 4.0,
+            line_height: f64::NAN,
+            measure_width: 10,
+            safe_mode: true,
+            ..ViewerSettings::default()
+        })));
+
+        let settings = use_case.execute().expect("settings should load");
+
+        assert_eq!(settings.font_scale, 1.3);
+        assert_eq!(settings.line_height, ViewerSettings::default().line_height);
+        assert_eq!(settings.measure_width, ViewerSettings::MIN_MEASURE_WIDTH);
+        assert!(settings.safe_mode);
+    }
+
+    #[test]
+    fn propagates_invalid_settings_files() {
+        let use_case = GetSettingsUseCase::new(Arc::new(StubSettingsStore::with_loads(vec![Err(
+            MarkdownViewerError::Settings {
+                path: PathBuf::from("/config/settings.toml"),
+                reason: "expected a table".to_string(),
+            },
+        )])));
+
+        let error = use_case.execute().expect_err("loading should fail");
+
+        assert!(matches!(error, MarkdownViewerError::Settings { .. }));
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use markdown_viewer_domain::document_source::SourceDocument;
+
+use crate::error::MarkdownViewerError;
+use crate::models::LintReportOutput;
+use crate::ports::{MarkdownFileRepository, MarkdownLinter};
+
+#[derive(Clone)]
+pub struct LintMarkdownFileUseCase {
+    repository: Arc<dyn MarkdownFileRepository>,
+    linter: Arc<dyn MarkdownLinter>,
+}
+
+impl LintMarkdownFileUseCase {
+    pub fn new(
+        repository: Arc<dyn MarkdownFileRepository>,
+        linter: Arc<dyn MarkdownLinter>,
+    ) -> Self {
+        Self { repository, linter }
+    }
+
+    pub fn execute(&self, path_input: &str) -> Result<LintReportOutput, MarkdownViewerError> {
+        let SourceDocument { path, source, .. } = self.repository.read(path_input)?;
+        let report = self.linter.lint(&source, &path)?;
+
+        Ok(LintReportOutput {
+            path: path.to_string_lossy().into_owned(),
+            diagnostics: report.diagnostics.into_iter().map(Into::into).collect(),
+            config_path: report
+                .config_path
+                .map(|config_path| config_path.to_string_lossy().into_owned()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::lint::{LintDiagnostic, LintReport, LintSeverity};
+
+    use crate::error::MarkdownViewerError;
+    use crate::models::LintSeverityOutput;
+    use crate::use_cases::lint_markdown_file::LintMarkdownFileUseCase;
+    use crate::use_cases::test_support::{StubLinter, StubRepository};
+
+    #[test]
+    fn lints_the_file_source_and_reports_the_config_in_use() {
+        let repository = Arc::new(StubRepository::ok(
+            PathBuf::from("/docs/guide.md"),
+            "# Guide\n\n### Setup\n",
+        ));
+        let linter = Arc::new(StubLinter::ok(LintReport {
+            diagnostics: vec![LintDiagnostic {
+                rule_id: "MD001".to_string(),
+                rule_name: "heading-increment".to_string(),
+                severity: LintSeverity::Error,
+                message: "Heading levels should only increment by one level at a time".to_string(),
+                line: 3,
+                column: None,
+            }],
+            config_path: Some(PathBuf::from("/docs/.markdownlint.json")),
+        }));
+        let use_case = LintMarkdownFileUseCase::new(repository, Arc::clone(&linter) as Arc<_>);
+
+        let report = use_case
+            .execute("/docs/guide.md")
+            .expect("lint should succeed");
+
+        assert_eq!(report.path, "/docs/guide.md");
+        assert_eq!(
+            report.config_path.as_deref(),
+            Some("/docs/.markdownlint.json")
+        );
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].rule_id, "MD001");
+        assert_eq!(report.diagnostics[0].severity, LintSeverityOutput::Error);
+        assert_eq!(report.diagnostics[0].line, 3);
+        assert_eq!(
+            linter
+                .last_call
+                .lock()
+                .expect("linter call state should be lockable")
+                .clone(),
+            Some((
+                "# Guide\n\n### Setup\n".to_string(),
+                PathBuf::from("/docs/guide.md")
+            ))
+        );
+    }
+
+    #[test]
+    fn propagates_config_errors() {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/docs/a.md"), "text"));
+        let linter = Arc::new(StubLinter::fail(MarkdownViewerError::LintConfig {
+            path: PathBuf::from("/docs/.markdownlint.json"),
+            reason: "expected value at line 1 column 1".to_string(),
+        }));
+        let use_case = LintMarkdownFileUseCase::new(repository, linter);
+
+        let error = use_case
+            .execute("/docs/a.md")
+            .expect_err("lint should fail");
+
+        assert!(matches!(error, MarkdownViewerError::LintConfig { .. }));
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::error::MarkdownViewerError;
+use crate::models::ArchiveListingOutput;
+use crate::ports::MarkdownArchiveBrowser;
+
+#[derive(Clone)]
+pub struct ListArchiveEntriesUseCase {
+    archives: Arc<dyn MarkdownArchiveBrowser>,
+}
+
+impl ListArchiveEntriesUseCase {
+    pub fn new(archives: Arc<dyn MarkdownArchiveBrowser>) -> Self {
+        Self { archives }
+    }
+
+    pub fn execute(&self, path_input: &str) -> Result<ArchiveListingOutput, MarkdownViewerError> {
+        let (path, entries) = self.archives.list_markdown_entries(path_input)?;
+
+        Ok(ArchiveListingOutput {
+            path: path.to_string_lossy().into_owned(),
+            entries: entries.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::archive::ArchiveEntry;
+
+    use crate::error::MarkdownViewerError;
+    use crate::use_cases::list_archive_entries::ListArchiveEntriesUseCase;
+    use crate::use_cases::test_support::StubArchiveBrowser;
+
+    #[test]
+    fn lists_the_markdown_entries_of_the_resolved_archive() {
+        let use_case = ListArchiveEntriesUseCase::new(Arc::new(StubArchiveBrowser::ok(
+            PathBuf::from("/exports/docs.zip"),
+            vec![ArchiveEntry {
+                name: "guide/intro.md".to_string(),
+                path: PathBuf::from("/exports/docs.zip!/guide/intro.md"),
+                size: 120,
+            }],
+        )));
+
+        let output = use_case
+            .execute("docs.zip")
+            .expect("entries should be listed");
+
+        assert_eq!(output.path, "/exports/docs.zip");
+        assert_eq!(output.entries.len(), 1);
+        assert_eq!(output.entries[0].name, "guide/intro.md");
+        assert_eq!(output.entries[0].path, "/exports/docs.zip!/guide/intro.md");
+        assert_eq!(output.entries[0].size, 120);
+    }
+
+    #[test]
+    fn propagates_unreadable_archives() {
+        let use_case = ListArchiveEntriesUseCase::new(Arc::new(StubArchiveBrowser::fail(
+            MarkdownViewerError::NotAnArchive(PathBuf::from("/tmp/notes.md")),
+        )));
+
+        let error = use_case
+            .execute("/tmp/notes.md")
+            .expect_err("listing should fail");
+
+        assert!(matches!(error, MarkdownViewerError::NotAnArchive(_)));
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use crate::error::MarkdownViewerError;
+use crate::models::FileHistoryOutput;
+use crate::ports::MarkdownRevisionRepository;
+
+#[derive(Clone)]
+pub struct ListFileHistoryUseCase {
+    revisions: Arc<dyn MarkdownRevisionRepository>,
+}
+
+impl ListFileHistoryUseCase {
+    pub fn new(revisions: Arc<dyn MarkdownRevisionRepository>) -> Self {
+        Self { revisions }
+    }
+
+    pub fn execute(&self, path_input: &str) -> Result<FileHistoryOutput, MarkdownViewerError> {
+        let (path, revisions) = self.revisions.history(path_input)?;
+
+        Ok(FileHistoryOutput {
+            path: path.to_string_lossy().into_owned(),
+            revisions: revisions.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::revision::FileRevision;
+
+    use crate::error::MarkdownViewerError;
+    use crate::use_cases::list_file_history::ListFileHistoryUseCase;
+    use crate::use_cases::test_support::StubRevisionRepository;
+
+    #[test]
+    fn lists_the_revisions_of_the_resolved_file() {
+        let revisions = Arc::new(StubRevisionRepository::ok(
+            PathBuf::from("/repo/guide.md"),
+            "",
+            vec![FileRevision {
+                commit_id: "0123456789abcdef0123456789abcdef01234567".to_string(),
+                author_name: "Ada".to_string(),
+                author_email: "ada@example.com".to_string(),
+                authored_at: 1_700_000_000,
+                summary: "Rewrite the guide".to_string(),
+                message: "Rewrite the guide\n\nShorter sections.".to_string(),
+            }],
+        ));
+        let use_case = ListFileHistoryUseCase::new(revisions);
+
+        let output = use_case
+            .execute("guide.md")
+            .expect("history should be listed");
+
+        assert_eq!(output.path, "/repo/guide.md");
+        assert_eq!(output.revisions.len(), 1);
+        assert_eq!(output.revisions[0].author_name, "Ada");
+        assert_eq!(output.revisions[0].authored_at, 1_700_000_000);
+        assert_eq!(output.revisions[0].summary, "Rewrite the guide");
+    }
+
+    #[test]
+    fn propagates_files_outside_a_repository() {
+        let use_case = ListFileHistoryUseCase::new(Arc::new(StubRevisionRepository::fail(
+            MarkdownViewerError::NotVersioned(PathBuf::from("/tmp/notes.md")),
+        )));
+
+        let error = use_case
+            .execute("/tmp/notes.md")
+            .expect_err("history should fail");
+
+        assert!(matches!(error, MarkdownViewerError::NotVersioned(_)));
+    }
+}
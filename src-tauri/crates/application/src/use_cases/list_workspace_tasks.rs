@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use markdown_viewer_domain::document::RenderedMarkdown;
+use markdown_viewer_domain::document_source::SourceDocument;
+
+use crate::error::MarkdownViewerError;
+use crate::models::{RenderPreferencesInput, WorkspaceTaskOutput, WorkspaceTasksOutput};
+use crate::ports::{MarkdownFileRepository, MarkdownRenderer, MarkdownWorkspaceScanner};
+use crate::use_cases::load_markdown_file::document_title;
+
+#[derive(Clone)]
+pub struct ListWorkspaceTasksUseCase {
+    scanner: Arc<dyn MarkdownWorkspaceScanner>,
+    repository: Arc<dyn MarkdownFileRepository>,
+    renderer: Arc<dyn MarkdownRenderer>,
+}
+
+impl ListWorkspaceTasksUseCase {
+    pub fn new(
+        scanner: Arc<dyn MarkdownWorkspaceScanner>,
+        repository: Arc<dyn MarkdownFileRepository>,
+        renderer: Arc<dyn MarkdownRenderer>,
+    ) -> Self {
+        Self {
+            scanner,
+            repository,
+            renderer,
+        }
+    }
+
+    /// Collects the unchecked tasks of every markdown file in `folder_input`. A file that cannot
+    /// be read or rendered is listed in `unreadable_files` instead of failing the whole scan.
+    pub fn execute(
+        &self,
+        folder_input: &str,
+        preferences: RenderPreferencesInput,
+    ) -> Result<WorkspaceTasksOutput, MarkdownViewerError> {
+        let (folder, files) = self.scanner.list_markdown_files(folder_input)?;
+        let mut output = WorkspaceTasksOutput {
+            folder: folder.to_string_lossy().into_owned(),
+            ..WorkspaceTasksOutput::default()
+        };
+
+        for file in files {
+            let file_input = file.to_string_lossy().into_owned();
+            let Ok((path, rendered)) = self.render_file(&file_input, &preferences) else {
+                output.unreadable_files.push(file_input);
+                continue;
+            };
+
+            let document_title = document_title(&rendered, &path);
+            let path = path.to_string_lossy().into_owned();
+            let open_tasks = rendered.tasks.into_iter().filter(|task| !task.checked);
+            output
+                .tasks
+                .extend(open_tasks.map(|task| WorkspaceTaskOutput {
+                    path: path.clone(),
+                    document_title: document_title.clone(),
+                    task: task.into(),
+                }));
+        }
+
+        Ok(output)
+    }
+
+    fn render_file(
+        &self,
+        file_input: &str,
+        preferences: &RenderPreferencesInput,
+    ) -> Result<(PathBuf, RenderedMarkdown), MarkdownViewerError> {
+        let SourceDocument { path, source, .. } = self.repository.read(file_input)?;
+        let rendered = self
+            .renderer
+            .render(&source, &path, preferences.clone().into())?;
+        Ok((path, rendered))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::document::{
+        DocumentStatistics, RenderedMarkdown, TaskListItem, TocEntry,
+    };
+
+    use crate::error::MarkdownViewerError;
+    use crate::models::RenderPreferencesInput;
+    use crate::use_cases::list_workspace_tasks::ListWorkspaceTasksUseCase;
+    use crate::use_cases::test_support::{FolderRepository, StubRenderer, StubWorkspaceScanner};
+
+    fn task(text: &str, checked: bool, line: usize) -> TaskListItem {
+        TaskListItem {
+            text: text.to_string(),
+            checked,
+            depth: 0,
+            heading_id: Some("mdv-plan".to_string()),
+            line,
+        }
+    }
+
+    fn rendered_with_tasks(tasks: Vec<TaskListItem>) -> RenderedMarkdown {
+        RenderedMarkdown {
+            html: String::new(),
+            toc: vec![TocEntry {
+                level: 1,
+                id: "mdv-plan".to_string(),
+                text: "Plan".to_string(),
+                plain_text: "Plan".to_string(),
+            }],
+            word_count: 0,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks,
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn aggregates_open_tasks_across_files_and_reports_unreadable_ones() {
+        let scanner = Arc::new(StubWorkspaceScanner::ok(
+            PathBuf::from("/notes"),
+            vec![
+                PathBuf::from("/notes/a.md"),
+                PathBuf::from("/notes/broken.md"),
+                PathBuf::from("/notes/sub/b.md"),
+            ],
+        ));
+        let repository = Arc::new(FolderRepository::with_files(&[
+            ("/notes/a.md", "- [ ] write"),
+            ("/notes/sub/b.md", "- [ ] write"),
+        ]));
+        let renderer = Arc::new(StubRenderer::ok(rendered_with_tasks(vec![
+            task("ship", true, 3),
+            task("write", false, 4),
+        ])));
+        let use_case = ListWorkspaceTasksUseCase::new(scanner, repository, renderer);
+
+        let output = use_case
+            .execute("/notes", RenderPreferencesInput::default())
+            .expect("scan should succeed");
+
+        assert_eq!(output.folder, "/notes");
+        assert_eq!(output.unreadable_files, ["/notes/broken.md"]);
+        let locations: Vec<(&str, &str, usize)> = output
+            .tasks
+            .iter()
+            .map(|entry| {
+                (
+                    entry.path.as_str(),
+                    entry.task.text.as_str(),
+                    entry.task.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            locations,
+            [("/notes/a.md", "write", 4), ("/notes/sub/b.md", "write", 4)]
+        );
+        assert!(output
+            .tasks
+            .iter()
+            .all(|entry| entry.document_title == "Plan"));
+        assert_eq!(output.tasks[0].task.heading_id.as_deref(), Some("mdv-plan"));
+    }
+
+    #[test]
+    fn propagates_scanner_error() {
+        let scanner = Arc::new(StubWorkspaceScanner::fail(MarkdownViewerError::NotAFolder(
+            PathBuf::from("/notes/a.md"),
+        )));
+        let use_case = ListWorkspaceTasksUseCase::new(
+            scanner,
+            Arc::new(FolderRepository::with_files(&[])),
+            Arc::new(StubRenderer::ok(rendered_with_tasks(Vec::new()))),
+        );
+
+        let error = use_case
+            .execute("/notes/a.md", RenderPreferencesInput::default())
+            .expect_err("a file is not a folder");
+
+        assert!(matches!(error, MarkdownViewerError::NotAFolder(_)));
+    }
+}
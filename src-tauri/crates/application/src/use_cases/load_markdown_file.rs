@@ -1,14 +1,28 @@
 use std::path::Path;
 use std::sync::Arc;
 
+use markdown_viewer_domain::document::{RenderPreferences, RenderedMarkdown};
+use markdown_viewer_domain::document_source::{DocumentOrigin, SourceDocument};
+use markdown_viewer_domain::markdown_source::content_hash;
+
 use crate::error::MarkdownViewerError;
-use crate::models::{MarkdownDocumentOutput, RenderPreferencesInput, TocEntryOutput};
-use crate::ports::{MarkdownFileRepository, MarkdownRenderer};
+use crate::models::{
+    DocumentWarningKindOutput, DocumentWarningOutput, EffectiveConfig, MarkdownDocumentOutput,
+    RenderPreferencesInput, TocEntryOutput,
+};
+use crate::ports::{
+    MarkdownFileRepository, MarkdownRenderer, ProjectConfigResolver, SettingsStore,
+};
+
+/// Title of piped documents without a heading, whose synthetic path has no useful file name.
+const STDIN_TITLE: &str = "Standard input";
 
 #[derive(Clone)]
 pub struct LoadMarkdownFileUseCase {
     repository: Arc<dyn MarkdownFileRepository>,
     renderer: Arc<dyn MarkdownRenderer>,
+    project_config: Option<Arc<dyn ProjectConfigResolver>>,
+    settings: Option<Arc<dyn SettingsStore>>,
 }
 
 impl LoadMarkdownFileUseCase {
@@ -19,42 +33,155 @@ impl LoadMarkdownFileUseCase {
         Self {
             repository,
             renderer,
+            project_config: None,
+            settings: None,
         }
     }
 
+    /// Applies the project configuration of the folder each file is in over the caller's
+    /// preferences.
+    pub fn with_project_config(mut self, resolver: Arc<dyn ProjectConfigResolver>) -> Self {
+        self.project_config = Some(resolver);
+        self
+    }
+
+    /// Renders with the render preferences kept in `store` when the caller passes none.
+    pub fn with_settings(mut self, store: Arc<dyn SettingsStore>) -> Self {
+        self.settings = Some(store);
+        self
+    }
+
     pub fn execute(
         &self,
         path_input: &str,
-        preferences: RenderPreferencesInput,
+        preferences: Option<RenderPreferencesInput>,
+    ) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
+        let document = self.repository.read(path_input)?;
+        self.render(document, preferences)
+    }
+
+    /// Like [`Self::execute`], but renders files that are not recognised as markdown instead of
+    /// failing with [`MarkdownViewerError::NotMarkdown`].
+    pub fn execute_as_markdown(
+        &self,
+        path_input: &str,
+        preferences: Option<RenderPreferencesInput>,
+    ) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
+        let document = self.repository.read_as_markdown(path_input)?;
+        self.render(document, preferences)
+    }
+
+    fn render(
+        &self,
+        document: SourceDocument,
+        preferences: Option<RenderPreferencesInput>,
     ) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
-        let (path, source) = self.repository.read(path_input)?;
-        let rendered = self.renderer.render(&source, preferences.into())?;
-        let title = rendered
+        let preferences = match (preferences, &self.settings) {
+            (Some(preferences), _) => preferences,
+            (None, Some(store)) => store.load()?.render,
+            (None, None) => RenderPreferencesInput::default(),
+        };
+        // Piped, fetched and archived documents are not in a project folder.
+        let config = match &self.project_config {
+            Some(resolver) if document.origin == DocumentOrigin::File => {
+                resolver.resolve(&document.path, preferences)?
+            }
+            _ => preferences.into(),
+        };
+        render_document(self.renderer.as_ref(), document, config)
+    }
+}
+
+/// Renders a document read from a source into the document shown by the viewer.
+pub(super) fn render_document(
+    renderer: &dyn MarkdownRenderer,
+    document: SourceDocument,
+    config: EffectiveConfig,
+) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
+    let preferences = document_preferences(&document, config.render.clone());
+    let SourceDocument {
+        path,
+        source,
+        origin,
+    } = document;
+    let rendered = renderer.render(&source, &path, preferences)?;
+    let title = match origin {
+        DocumentOrigin::File | DocumentOrigin::Remote => document_title(&rendered, &path),
+        // `notes.md.gz` is titled `notes`, not `notes.md`; zip entries keep their own name.
+        DocumentOrigin::Archive
+            if path
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("gz")) =>
+        {
+            document_title(&rendered, &path.with_extension(""))
+        }
+        DocumentOrigin::Archive => document_title(&rendered, &path),
+        DocumentOrigin::Stdin => {
+            first_heading(&rendered).unwrap_or_else(|| STDIN_TITLE.to_string())
+        }
+    };
+
+    Ok(MarkdownDocumentOutput {
+        path: path.to_string_lossy().into_owned(),
+        title,
+        content_hash: content_hash(&source),
+        source,
+        html: rendered.html,
+        toc: rendered
             .toc
-            .first()
-            .map(|entry| entry.text.clone())
-            .unwrap_or_else(|| title_from_path(&path));
-
-        Ok(MarkdownDocumentOutput {
-            path: path.to_string_lossy().into_owned(),
-            title,
-            source,
-            html: rendered.html,
-            toc: rendered
-                .toc
-                .into_iter()
-                .map(|entry| TocEntryOutput {
-                    level: entry.level,
-                    id: entry.id,
-                    text: entry.text,
-                })
-                .collect(),
-            word_count: rendered.word_count,
-            reading_time_minutes: rendered.reading_time_minutes,
-        })
+            .into_iter()
+            .map(|entry| TocEntryOutput {
+                level: entry.level,
+                id: entry.id,
+                text: entry.text,
+            })
+            .collect(),
+        word_count: rendered.word_count,
+        reading_time_minutes: rendered.reading_time_minutes,
+        statistics: rendered.statistics.into(),
+        tasks: rendered.tasks.into_iter().map(Into::into).collect(),
+        warnings: rendered
+            .warnings
+            .into_iter()
+            .map(Into::into)
+            .chain(
+                config
+                    .ignored_settings
+                    .iter()
+                    .map(|message| DocumentWarningOutput {
+                        kind: DocumentWarningKindOutput::ProjectConfig,
+                        message: message.clone(),
+                        line: None,
+                    }),
+            )
+            .collect(),
+        local_images: rendered.local_images,
+        origin: origin.into(),
+        config,
+    })
+}
+
+/// Render preferences for `document`, resolving relative references against its URL when it was
+/// fetched from the web.
+pub(super) fn document_preferences(
+    document: &SourceDocument,
+    preferences: RenderPreferencesInput,
+) -> RenderPreferences {
+    RenderPreferences {
+        base_url: document.base_url(),
+        ..preferences.into()
     }
 }
 
+/// The first heading of the document, or its file name when it has none.
+pub(super) fn document_title(rendered: &RenderedMarkdown, path: &Path) -> String {
+    first_heading(rendered).unwrap_or_else(|| title_from_path(path))
+}
+
+fn first_heading(rendered: &RenderedMarkdown) -> Option<String> {
+    rendered.toc.first().map(|entry| entry.plain_text.clone())
+}
+
 fn title_from_path(path: &Path) -> String {
     path.file_stem()
         .and_then(|stem| stem.to_str())
@@ -68,12 +195,23 @@ mod tests {
     use std::sync::atomic::Ordering;
     use std::sync::Arc;
 
-    use markdown_viewer_domain::document::{RenderedMarkdown, TocEntry};
+    use markdown_viewer_domain::document::{
+        DocumentStatistics, DocumentWarning, DocumentWarningKind, ImageEmbedding, RenderedMarkdown,
+        TocEntry,
+    };
+    use markdown_viewer_domain::document_source::DocumentOrigin;
+    use markdown_viewer_domain::extensions::{ExtensionProfile, MarkdownExtension};
+    use markdown_viewer_domain::markdown_source::content_hash;
 
     use crate::error::MarkdownViewerError;
-    use crate::models::RenderPreferencesInput;
+    use crate::models::{
+        DocumentOriginOutput, DocumentWarningKindOutput, RenderPreferencesInput, ViewerSettings,
+    };
     use crate::use_cases::load_markdown_file::LoadMarkdownFileUseCase;
-    use crate::use_cases::test_support::{sample_preferences, StubRenderer, StubRepository};
+    use crate::use_cases::test_support::{
+        sample_preferences, StubProjectConfigResolver, StubRenderer, StubRepository,
+        StubSettingsStore,
+    };
 
     #[test]
     fn load_use_case_prefers_first_toc_heading_for_title() {
@@ -87,19 +225,29 @@ mod tests {
                 level: 1,
                 id: "mdv-overview".to_string(),
                 text: "Overview".to_string(),
+                plain_text: "Overview".to_string(),
             }],
             word_count: 3,
             reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: vec![DocumentWarning {
+                kind: DocumentWarningKind::MissingImage,
+                message: "Image not found: ./missing.png".to_string(),
+                line: Some(3),
+            }],
+            local_images: Vec::new(),
         }));
         let use_case = LoadMarkdownFileUseCase::new(repository, Arc::clone(&renderer) as Arc<_>);
 
         let document = use_case
-            .execute("/tmp/notes.md", sample_preferences())
+            .execute("/tmp/notes.md", Some(sample_preferences()))
             .expect("load should succeed");
 
         assert_eq!(document.title, "Overview");
         assert_eq!(document.path, "/tmp/notes.md");
         assert_eq!(document.source, "# intro markdown");
+        assert_eq!(document.content_hash, content_hash("# intro markdown"));
         assert!(renderer.called.load(Ordering::Relaxed));
         assert_eq!(
             renderer
@@ -109,6 +257,51 @@ mod tests {
                 .as_deref(),
             Some("# intro markdown")
         );
+        assert_eq!(
+            renderer
+                .last_document_path
+                .lock()
+                .expect("renderer document path state should be lockable")
+                .as_deref(),
+            Some(PathBuf::from("/tmp/notes.md").as_path())
+        );
+        assert_eq!(document.warnings.len(), 1);
+        assert_eq!(
+            document.warnings[0].kind,
+            DocumentWarningKindOutput::MissingImage
+        );
+        assert_eq!(document.warnings[0].line, Some(3));
+    }
+
+    #[test]
+    fn load_use_case_titles_numbered_documents_without_the_section_number() {
+        let repository = Arc::new(StubRepository::ok(
+            PathBuf::from("/tmp/guide.md"),
+            "# Introduction",
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<h1>1 Introduction</h1>".to_string(),
+            toc: vec![TocEntry {
+                level: 1,
+                id: "mdv-introduction".to_string(),
+                text: "1 Introduction".to_string(),
+                plain_text: "Introduction".to_string(),
+            }],
+            word_count: 1,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository, renderer);
+
+        let document = use_case
+            .execute("/tmp/guide.md", Some(sample_preferences()))
+            .expect("load should succeed");
+
+        assert_eq!(document.title, "Introduction");
+        assert_eq!(document.toc[0].text, "1 Introduction");
     }
 
     #[test]
@@ -122,16 +315,197 @@ mod tests {
             toc: Vec::new(),
             word_count: 2,
             reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
         }));
         let use_case = LoadMarkdownFileUseCase::new(repository, renderer);
 
         let document = use_case
-            .execute("/tmp/engineering-notes_v2.md", sample_preferences())
+            .execute("/tmp/engineering-notes_v2.md", Some(sample_preferences()))
             .expect("load should succeed");
 
         assert_eq!(document.title, "engineering notes v2");
     }
 
+    #[test]
+    fn load_use_case_titles_piped_documents_without_headings_as_standard_input() {
+        let repository = Arc::new(StubRepository::with_origin(
+            PathBuf::from("/work/-"),
+            "no headings here",
+            DocumentOrigin::Stdin,
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<p>no headings</p>".to_string(),
+            toc: Vec::new(),
+            word_count: 3,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository, renderer);
+
+        let document = use_case
+            .execute("-", Some(sample_preferences()))
+            .expect("load should succeed");
+
+        assert_eq!(document.title, "Standard input");
+        assert_eq!(document.path, "/work/-");
+        assert_eq!(document.origin, DocumentOriginOutput::Stdin);
+    }
+
+    #[test]
+    fn load_use_case_titles_compressed_files_without_the_markdown_extension() {
+        let repository = Arc::new(StubRepository::with_origin(
+            PathBuf::from("/tmp/release_notes.md.gz"),
+            "no headings here",
+            DocumentOrigin::Archive,
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<p>no headings</p>".to_string(),
+            toc: Vec::new(),
+            word_count: 3,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository, renderer);
+
+        let document = use_case
+            .execute("/tmp/release_notes.md.gz", Some(sample_preferences()))
+            .expect("load should succeed");
+
+        assert_eq!(document.title, "release notes");
+        assert_eq!(document.origin, DocumentOriginOutput::Archive);
+    }
+
+    #[test]
+    fn load_use_case_titles_dotted_zip_entries_by_their_full_name() {
+        let repository = Arc::new(StubRepository::with_origin(
+            PathBuf::from("/tmp/docs.zip/notes/release-1.2.md"),
+            "no headings here",
+            DocumentOrigin::Archive,
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<p>no headings</p>".to_string(),
+            toc: Vec::new(),
+            word_count: 3,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository, renderer);
+
+        let document = use_case
+            .execute(
+                "/tmp/docs.zip/notes/release-1.2.md",
+                Some(sample_preferences()),
+            )
+            .expect("load should succeed");
+
+        assert_eq!(document.title, "release 1.2");
+    }
+
+    #[test]
+    fn load_use_case_resolves_remote_documents_against_their_url() {
+        let repository = Arc::new(StubRepository::with_origin(
+            PathBuf::from("https://example.com/docs/guide.md"),
+            "see [setup](setup.md)",
+            DocumentOrigin::Remote,
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<p>see setup</p>".to_string(),
+            toc: Vec::new(),
+            word_count: 2,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository, Arc::clone(&renderer) as Arc<_>);
+
+        let document = use_case
+            .execute(
+                "https://example.com/docs/guide.md",
+                Some(sample_preferences()),
+            )
+            .expect("load should succeed");
+
+        let preferences = renderer
+            .last_preferences
+            .lock()
+            .expect("renderer preferences state should be lockable")
+            .clone()
+            .expect("renderer should receive preferences");
+        assert_eq!(
+            preferences.base_url.as_deref(),
+            Some("https://example.com/docs/guide.md")
+        );
+        assert_eq!(document.title, "guide");
+        assert_eq!(document.origin, DocumentOriginOutput::Remote);
+    }
+
+    #[test]
+    fn load_use_case_forwards_render_preferences_to_renderer() {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/tmp/ok.md"), "ABC-1"));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<p>ABC-1</p>".to_string(),
+            toc: Vec::new(),
+            word_count: 1,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository, Arc::clone(&renderer) as Arc<_>);
+
+        use_case
+            .execute("/tmp/ok.md", Some(sample_preferences()))
+            .expect("load should succeed");
+
+        let preferences = renderer
+            .last_preferences
+            .lock()
+            .expect("renderer preferences state should be lockable")
+            .clone()
+            .expect("renderer should receive preferences");
+        assert!(preferences.performance_mode);
+        assert!(!preferences.word_count_rules.include_links);
+        assert_eq!(preferences.text_replacements.len(), 1);
+        assert_eq!(
+            preferences.text_replacements[0].link_template.as_deref(),
+            Some("https://tracker.example.com/browse/$0")
+        );
+        assert!(preferences.allow_raw_html);
+        assert_eq!(preferences.html_allowlist.tags, ["kbd"]);
+        assert_eq!(preferences.image_embedding, ImageEmbedding::DataUri);
+        assert_eq!(preferences.csv_row_limit, Some(25));
+        assert_eq!(
+            preferences
+                .number_headings
+                .map(|numbering| numbering.start_level),
+            Some(2)
+        );
+        assert_eq!(preferences.words_per_minute, Some(180));
+        assert_eq!(preferences.han_characters_per_minute, Some(260));
+        assert_eq!(preferences.kana_characters_per_minute, Some(450));
+        assert_eq!(preferences.extension_profile, ExtensionProfile::Github);
+        assert_eq!(
+            preferences.extension_overrides,
+            [(MarkdownExtension::Highlight, true)].into_iter().collect()
+        );
+        assert_eq!(preferences.base_url, None);
+    }
+
     #[test]
     fn load_use_case_returns_repository_error_without_calling_renderer() {
         let repo_error = MarkdownViewerError::FileNotFound(PathBuf::from("/tmp/missing.md"));
@@ -141,11 +515,15 @@ mod tests {
             toc: Vec::new(),
             word_count: 0,
             reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
         }));
         let use_case = LoadMarkdownFileUseCase::new(repository, Arc::clone(&renderer) as Arc<_>);
 
         let error = use_case
-            .execute("/tmp/missing.md", RenderPreferencesInput::default())
+            .execute("/tmp/missing.md", Some(RenderPreferencesInput::default()))
             .expect_err("load should fail");
 
         match error {
@@ -168,7 +546,7 @@ mod tests {
         let use_case = LoadMarkdownFileUseCase::new(repository, renderer);
 
         let error = use_case
-            .execute("/tmp/ok.md", RenderPreferencesInput::default())
+            .execute("/tmp/ok.md", Some(RenderPreferencesInput::default()))
             .expect_err("load should fail");
 
         match error {
@@ -179,4 +557,146 @@ mod tests {
             other => panic!("unexpected error variant: {other:?}"),
         }
     }
+
+    #[test]
+    fn load_as_markdown_reads_past_the_file_type_check() {
+        let repository = Arc::new(StubRepository::ok(
+            PathBuf::from("/tmp/notes.txt"),
+            "# Notes",
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<h1>Notes</h1>".to_string(),
+            toc: Vec::new(),
+            word_count: 1,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository.clone(), renderer);
+
+        let document = use_case
+            .execute_as_markdown("/tmp/notes.txt", Some(sample_preferences()))
+            .expect("load should succeed");
+
+        assert_eq!(document.title, "notes");
+        assert!(repository.read_as_markdown_called.load(Ordering::Relaxed));
+    }
+
+    fn rendered_notes() -> RenderedMarkdown {
+        RenderedMarkdown {
+            html: "<p>notes</p>".to_string(),
+            toc: Vec::new(),
+            word_count: 1,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn load_use_case_renders_with_the_stored_settings_unless_given_preferences() {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/tmp/notes.md"), "notes"));
+        let renderer = Arc::new(StubRenderer::ok(rendered_notes()));
+        let store = Arc::new(StubSettingsStore::ok(ViewerSettings {
+            render: sample_preferences(),
+            ..ViewerSettings::default()
+        }));
+        let use_case = LoadMarkdownFileUseCase::new(repository, Arc::clone(&renderer) as Arc<_>)
+            .with_settings(store);
+        let rendered_with = || {
+            renderer
+                .last_preferences
+                .lock()
+                .expect("renderer preferences state should be lockable")
+                .clone()
+                .expect("renderer should be called")
+        };
+
+        let stored = use_case
+            .execute("/tmp/notes.md", None)
+            .expect("load should succeed");
+        assert!(rendered_with().performance_mode);
+        assert_eq!(rendered_with().csv_row_limit, Some(25));
+        assert_eq!(stored.config.render, sample_preferences());
+
+        let given = use_case
+            .execute("/tmp/notes.md", Some(RenderPreferencesInput::default()))
+            .expect("load should succeed");
+        assert!(!rendered_with().performance_mode);
+        assert_eq!(given.config.render, RenderPreferencesInput::default());
+    }
+
+    #[test]
+    fn load_use_case_applies_the_project_config_of_files() {
+        let repository = Arc::new(StubRepository::ok(
+            PathBuf::from("/project/docs/notes.md"),
+            "notes",
+        ));
+        let renderer = Arc::new(StubRenderer::ok(rendered_notes()));
+        let resolver = Arc::new(StubProjectConfigResolver::new());
+        let use_case = LoadMarkdownFileUseCase::new(repository, Arc::clone(&renderer) as Arc<_>)
+            .with_project_config(resolver.clone());
+
+        let document = use_case
+            .execute("/project/docs/notes.md", Some(sample_preferences()))
+            .expect("load should succeed");
+
+        assert_eq!(
+            resolver
+                .resolved_path
+                .lock()
+                .expect("resolver state should be lockable")
+                .as_deref(),
+            Some(PathBuf::from("/project/docs/notes.md").as_path())
+        );
+        let rendered_with = renderer
+            .last_preferences
+            .lock()
+            .expect("renderer preferences state should be lockable")
+            .clone()
+            .expect("renderer should be called");
+        assert!(rendered_with.block_external_links);
+        assert!(document.config.render.block_external_links);
+        assert_eq!(
+            document.config.project_config_path.as_deref(),
+            Some("/project/.mdviewer.toml")
+        );
+        assert_eq!(
+            document.config.custom_css.as_deref(),
+            Some("body { color: teal; }")
+        );
+        assert_eq!(
+            document.warnings.last().map(|warning| warning.kind),
+            Some(DocumentWarningKindOutput::ProjectConfig)
+        );
+    }
+
+    #[test]
+    fn load_use_case_reports_caller_preferences_for_documents_outside_projects() {
+        let repository = Arc::new(StubRepository::with_origin(
+            PathBuf::from("/work/-"),
+            "notes",
+            DocumentOrigin::Stdin,
+        ));
+        let resolver = Arc::new(StubProjectConfigResolver::new());
+        let use_case =
+            LoadMarkdownFileUseCase::new(repository, Arc::new(StubRenderer::ok(rendered_notes())))
+                .with_project_config(resolver.clone());
+
+        let document = use_case
+            .execute("-", Some(sample_preferences()))
+            .expect("load should succeed");
+
+        assert!(resolver
+            .resolved_path
+            .lock()
+            .expect("resolver state should be lockable")
+            .is_none());
+        assert_eq!(document.config.render, sample_preferences());
+        assert_eq!(document.config.project_config_path, None);
+    }
 }
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use markdown_viewer_domain::document_source::SourceDocument;
+
+use crate::error::MarkdownViewerError;
+use crate::models::{MarkdownDocumentOutput, RenderPreferencesInput};
+use crate::ports::{MarkdownRenderer, MarkdownRevisionRepository};
+use crate::use_cases::load_markdown_file::render_document;
+
+#[derive(Clone)]
+pub struct LoadMarkdownRevisionUseCase {
+    revisions: Arc<dyn MarkdownRevisionRepository>,
+    renderer: Arc<dyn MarkdownRenderer>,
+}
+
+impl LoadMarkdownRevisionUseCase {
+    pub fn new(
+        revisions: Arc<dyn MarkdownRevisionRepository>,
+        renderer: Arc<dyn MarkdownRenderer>,
+    ) -> Self {
+        Self {
+            revisions,
+            renderer,
+        }
+    }
+
+    /// Renders the file as of `revision`. The document keeps the file's current path, so
+    /// relative links and images resolve against the working tree.
+    pub fn execute(
+        &self,
+        path_input: &str,
+        revision: &str,
+        preferences: RenderPreferencesInput,
+    ) -> Result<MarkdownDocumentOutput, MarkdownViewerError> {
+        let (path, source) = self.revisions.read_at(path_input, revision)?;
+        render_document(
+            self.renderer.as_ref(),
+            SourceDocument::file(path, source),
+            preferences.into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::document::{DocumentStatistics, RenderedMarkdown, TocEntry};
+    use markdown_viewer_domain::markdown_source::content_hash;
+
+    use crate::error::MarkdownViewerError;
+    use crate::use_cases::load_markdown_revision::LoadMarkdownRevisionUseCase;
+    use crate::use_cases::test_support::{
+        sample_preferences, StubRenderer, StubRevisionRepository,
+    };
+
+    #[test]
+    fn renders_the_file_contents_at_the_requested_revision() {
+        let revisions = Arc::new(StubRevisionRepository::ok(
+            PathBuf::from("/repo/guide.md"),
+            "# Old guide\n",
+            Vec::new(),
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: "<h1 id=\"mdv-old-guide\">Old guide</h1>".to_string(),
+            toc: vec![TocEntry {
+                level: 1,
+                id: "mdv-old-guide".to_string(),
+                text: "Old guide".to_string(),
+                plain_text: "Old guide".to_string(),
+            }],
+            word_count: 2,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownRevisionUseCase::new(
+            Arc::clone(&revisions) as Arc<_>,
+            Arc::clone(&renderer) as Arc<_>,
+        );
+
+        let output = use_case
+            .execute("/repo/guide.md", "v1.0", sample_preferences())
+            .expect("revision should load");
+
+        assert_eq!(output.path, "/repo/guide.md");
+        assert_eq!(output.title, "Old guide");
+        assert_eq!(output.source, "# Old guide\n");
+        assert_eq!(output.content_hash, content_hash("# Old guide\n"));
+        assert!(renderer.called.load(Ordering::SeqCst));
+        assert_eq!(
+            revisions
+                .last_read
+                .lock()
+                .expect("revision repository call state should be lockable")
+                .clone(),
+            Some(("/repo/guide.md".to_string(), "v1.0".to_string()))
+        );
+    }
+
+    #[test]
+    fn propagates_unknown_revisions_without_rendering() {
+        let revisions = Arc::new(StubRevisionRepository::fail(
+            MarkdownViewerError::UnknownRevision {
+                revision: "nope".to_string(),
+                reason: "not found".to_string(),
+            },
+        ));
+        let renderer = Arc::new(StubRenderer::ok(RenderedMarkdown {
+            html: String::new(),
+            toc: Vec::new(),
+            word_count: 0,
+            reading_time_minutes: 0,
+            statistics: DocumentStatistics::default(),
+            tasks: Vec::new(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }));
+        let use_case = LoadMarkdownRevisionUseCase::new(revisions, Arc::clone(&renderer) as Arc<_>);
+
+        let error = use_case
+            .execute("/repo/guide.md", "nope", sample_preferences())
+            .expect_err("unknown revision should fail");
+
+        assert!(matches!(error, MarkdownViewerError::UnknownRevision { .. }));
+        assert!(!renderer.called.load(Ordering::SeqCst));
+    }
+}
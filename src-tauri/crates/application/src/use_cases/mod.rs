@@ -1,10 +1,32 @@
+mod check_spelling;
+mod diff_markdown_file;
+mod get_settings;
+mod lint_markdown_file;
+mod list_archive_entries;
+mod list_file_history;
+mod list_workspace_tasks;
 mod load_markdown_file;
+mod load_markdown_revision;
 mod open_linked_file;
+mod toggle_task_item;
+mod update_settings;
 mod watch_markdown_file;
+mod watch_settings;
 
+pub use check_spelling::CheckSpellingUseCase;
+pub use diff_markdown_file::DiffMarkdownFileUseCase;
+pub use get_settings::GetSettingsUseCase;
+pub use lint_markdown_file::LintMarkdownFileUseCase;
+pub use list_archive_entries::ListArchiveEntriesUseCase;
+pub use list_file_history::ListFileHistoryUseCase;
+pub use list_workspace_tasks::ListWorkspaceTasksUseCase;
 pub use load_markdown_file::LoadMarkdownFileUseCase;
+pub use load_markdown_revision::LoadMarkdownRevisionUseCase;
 pub use open_linked_file::OpenLinkedFileUseCase;
+pub use toggle_task_item::ToggleTaskItemUseCase;
+pub use update_settings::UpdateSettingsUseCase;
 pub use watch_markdown_file::WatchMarkdownFileUseCase;
+pub use watch_settings::WatchSettingsUseCase;
 
 #[cfg(test)]
 mod test_support;
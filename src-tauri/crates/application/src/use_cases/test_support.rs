@@ -1,26 +1,65 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
+use markdown_viewer_domain::archive::ArchiveEntry;
+use markdown_viewer_domain::diff::DocumentDiff;
 use markdown_viewer_domain::document::{
-    RenderPreferences as DomainRenderPreferences, RenderedMarkdown,
+    DocumentStatistics, RenderPreferences as DomainRenderPreferences, RenderedMarkdown,
 };
+use markdown_viewer_domain::document_source::{DocumentOrigin, SourceDocument};
+use markdown_viewer_domain::lint::LintReport;
+use markdown_viewer_domain::revision::FileRevision;
+use markdown_viewer_domain::spelling::SpellCheckReport;
 
 use crate::error::MarkdownViewerError;
-use crate::models::{RenderPreferencesInput, WordCountRulesInput};
+use crate::models::{
+    EffectiveConfig, ExtensionProfileInput, HeadingNumberingInput, HtmlAllowlistInput,
+    ImageEmbeddingInput, RenderPreferencesInput, TextReplacementRuleInput, ViewerSettings,
+    WordCountRulesInput,
+};
 use crate::ports::{
-    LinkedFileOpener, MarkdownFileRepository, MarkdownRenderer, MarkdownWatchService,
-    PathCanonicalizer,
+    LinkedFileOpener, MarkdownArchiveBrowser, MarkdownDiffer, MarkdownFileRepository,
+    MarkdownFileWriter, MarkdownLinter, MarkdownRenderer, MarkdownRevisionRepository,
+    MarkdownWatchService, MarkdownWorkspaceScanner, PathCanonicalizer, ProjectConfigResolver,
+    SettingsStore, SpellChecker,
 };
 
 pub(super) fn clone_error(error: &MarkdownViewerError) -> MarkdownViewerError {
     match error {
         MarkdownViewerError::FileNotFound(path) => MarkdownViewerError::FileNotFound(path.clone()),
         MarkdownViewerError::NotMarkdown(path) => MarkdownViewerError::NotMarkdown(path.clone()),
+        MarkdownViewerError::NotAFolder(path) => MarkdownViewerError::NotAFolder(path.clone()),
         MarkdownViewerError::ReadFile { path, reason } => MarkdownViewerError::ReadFile {
             path: path.clone(),
             reason: reason.clone(),
         },
+        MarkdownViewerError::WriteFile { path, reason } => MarkdownViewerError::WriteFile {
+            path: path.clone(),
+            reason: reason.clone(),
+        },
+        MarkdownViewerError::DocumentChanged(path) => {
+            MarkdownViewerError::DocumentChanged(path.clone())
+        }
+        MarkdownViewerError::ReadOnlyDocument(path) => {
+            MarkdownViewerError::ReadOnlyDocument(path.clone())
+        }
+        MarkdownViewerError::Fetch { url, reason } => MarkdownViewerError::Fetch {
+            url: url.clone(),
+            reason: reason.clone(),
+        },
+        MarkdownViewerError::NotAnArchive(path) => MarkdownViewerError::NotAnArchive(path.clone()),
+        MarkdownViewerError::Archive { path, reason } => MarkdownViewerError::Archive {
+            path: path.clone(),
+            reason: reason.clone(),
+        },
+        MarkdownViewerError::TaskItemNotFound { path, line } => {
+            MarkdownViewerError::TaskItemNotFound {
+                path: path.clone(),
+                line: *line,
+            }
+        }
         MarkdownViewerError::Watch { path, reason } => MarkdownViewerError::Watch {
             path: path.clone(),
             reason: reason.clone(),
@@ -45,14 +84,61 @@ pub(super) fn clone_error(error: &MarkdownViewerError) -> MarkdownViewerError {
                 reason: reason.clone(),
             }
         }
+        MarkdownViewerError::InvalidTextReplacement { pattern, reason } => {
+            MarkdownViewerError::InvalidTextReplacement {
+                pattern: pattern.clone(),
+                reason: reason.clone(),
+            }
+        }
+        MarkdownViewerError::ProjectConfig { path, reason } => MarkdownViewerError::ProjectConfig {
+            path: path.clone(),
+            reason: reason.clone(),
+        },
+        MarkdownViewerError::LintConfig { path, reason } => MarkdownViewerError::LintConfig {
+            path: path.clone(),
+            reason: reason.clone(),
+        },
+        MarkdownViewerError::WordList { path, reason } => MarkdownViewerError::WordList {
+            path: path.clone(),
+            reason: reason.clone(),
+        },
+        MarkdownViewerError::NotVersioned(path) => MarkdownViewerError::NotVersioned(path.clone()),
+        MarkdownViewerError::UnknownRevision { revision, reason } => {
+            MarkdownViewerError::UnknownRevision {
+                revision: revision.clone(),
+                reason: reason.clone(),
+            }
+        }
+        MarkdownViewerError::FileNotInRevision { path, revision } => {
+            MarkdownViewerError::FileNotInRevision {
+                path: path.clone(),
+                revision: revision.clone(),
+            }
+        }
+        MarkdownViewerError::VersionControl { path, reason } => {
+            MarkdownViewerError::VersionControl {
+                path: path.clone(),
+                reason: reason.clone(),
+            }
+        }
+        MarkdownViewerError::Plugin { path, reason } => MarkdownViewerError::Plugin {
+            path: path.clone(),
+            reason: reason.clone(),
+        },
+        MarkdownViewerError::Settings { path, reason } => MarkdownViewerError::Settings {
+            path: path.clone(),
+            reason: reason.clone(),
+        },
     }
 }
 
 pub(super) struct StubRepository {
     path: PathBuf,
     source: String,
+    origin: DocumentOrigin,
     error: Option<MarkdownViewerError>,
     pub(super) last_input: Mutex<Option<String>>,
+    pub(super) read_as_markdown_called: AtomicBool,
 }
 
 impl StubRepository {
@@ -60,8 +146,21 @@ impl StubRepository {
         Self {
             path,
             source: source.into(),
+            origin: DocumentOrigin::File,
             error: None,
             last_input: Mutex::new(None),
+            read_as_markdown_called: AtomicBool::new(false),
+        }
+    }
+
+    pub(super) fn with_origin(
+        path: PathBuf,
+        source: impl Into<String>,
+        origin: DocumentOrigin,
+    ) -> Self {
+        Self {
+            origin,
+            ..Self::ok(path, source)
         }
     }
 
@@ -69,14 +168,16 @@ impl StubRepository {
         Self {
             path: PathBuf::new(),
             source: String::new(),
+            origin: DocumentOrigin::File,
             error: Some(error),
             last_input: Mutex::new(None),
+            read_as_markdown_called: AtomicBool::new(false),
         }
     }
 }
 
 impl MarkdownFileRepository for StubRepository {
-    fn read(&self, path_input: &str) -> Result<(PathBuf, String), MarkdownViewerError> {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
         self.last_input
             .lock()
             .expect("repository call state should be lockable")
@@ -84,8 +185,252 @@ impl MarkdownFileRepository for StubRepository {
         if let Some(error) = &self.error {
             return Err(clone_error(error));
         }
+        Ok(SourceDocument {
+            path: self.path.clone(),
+            source: self.source.clone(),
+            origin: self.origin,
+        })
+    }
+
+    fn read_as_markdown(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        self.read_as_markdown_called.store(true, Ordering::Relaxed);
+        self.read(path_input)
+    }
+}
+
+pub(super) struct StubRevisionRepository {
+    path: PathBuf,
+    source: String,
+    revisions: Vec<FileRevision>,
+    error: Option<MarkdownViewerError>,
+    pub(super) last_read: Mutex<Option<(String, String)>>,
+}
+
+impl StubRevisionRepository {
+    pub(super) fn ok(
+        path: PathBuf,
+        source: impl Into<String>,
+        revisions: Vec<FileRevision>,
+    ) -> Self {
+        Self {
+            path,
+            source: source.into(),
+            revisions,
+            error: None,
+            last_read: Mutex::new(None),
+        }
+    }
+
+    pub(super) fn fail(error: MarkdownViewerError) -> Self {
+        Self {
+            path: PathBuf::new(),
+            source: String::new(),
+            revisions: Vec::new(),
+            error: Some(error),
+            last_read: Mutex::new(None),
+        }
+    }
+}
+
+impl MarkdownRevisionRepository for StubRevisionRepository {
+    fn read_at(
+        &self,
+        path_input: &str,
+        revision: &str,
+    ) -> Result<(PathBuf, String), MarkdownViewerError> {
+        self.last_read
+            .lock()
+            .expect("revision repository call state should be lockable")
+            .replace((path_input.to_string(), revision.to_string()));
+        if let Some(error) = &self.error {
+            return Err(clone_error(error));
+        }
         Ok((self.path.clone(), self.source.clone()))
     }
+
+    fn history(
+        &self,
+        _path_input: &str,
+    ) -> Result<(PathBuf, Vec<FileRevision>), MarkdownViewerError> {
+        if let Some(error) = &self.error {
+            return Err(clone_error(error));
+        }
+        Ok((self.path.clone(), self.revisions.clone()))
+    }
+}
+
+/// A repository over a fixed set of files; other paths are reported as not found.
+pub(super) struct StubArchiveBrowser {
+    path: PathBuf,
+    entries: Vec<ArchiveEntry>,
+    error: Option<MarkdownViewerError>,
+}
+
+impl StubArchiveBrowser {
+    pub(super) fn ok(path: PathBuf, entries: Vec<ArchiveEntry>) -> Self {
+        Self {
+            path,
+            entries,
+            error: None,
+        }
+    }
+
+    pub(super) fn fail(error: MarkdownViewerError) -> Self {
+        Self {
+            path: PathBuf::new(),
+            entries: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+impl MarkdownArchiveBrowser for StubArchiveBrowser {
+    fn list_markdown_entries(
+        &self,
+        _path_input: &str,
+    ) -> Result<(PathBuf, Vec<ArchiveEntry>), MarkdownViewerError> {
+        if let Some(error) = &self.error {
+            return Err(clone_error(error));
+        }
+        Ok((self.path.clone(), self.entries.clone()))
+    }
+}
+
+pub(super) struct FolderRepository {
+    files: HashMap<String, String>,
+}
+
+impl FolderRepository {
+    pub(super) fn with_files(files: &[(&str, &str)]) -> Self {
+        Self {
+            files: files
+                .iter()
+                .map(|(path, source)| (path.to_string(), source.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl MarkdownFileRepository for FolderRepository {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        self.files
+            .get(path_input)
+            .map(|source| SourceDocument::file(PathBuf::from(path_input), source.clone()))
+            .ok_or_else(|| MarkdownViewerError::FileNotFound(PathBuf::from(path_input)))
+    }
+}
+
+pub(super) struct StubFileWriter {
+    written: Mutex<Vec<(PathBuf, String)>>,
+    error: Option<MarkdownViewerError>,
+}
+
+impl StubFileWriter {
+    pub(super) fn ok() -> Self {
+        Self {
+            written: Mutex::new(Vec::new()),
+            error: None,
+        }
+    }
+
+    pub(super) fn fail(error: MarkdownViewerError) -> Self {
+        Self {
+            written: Mutex::new(Vec::new()),
+            error: Some(error),
+        }
+    }
+
+    pub(super) fn written(&self) -> Vec<(PathBuf, String)> {
+        self.written
+            .lock()
+            .expect("writer state should be lockable")
+            .clone()
+    }
+}
+
+impl MarkdownFileWriter for StubFileWriter {
+    fn write_atomically(&self, path: &Path, contents: &str) -> Result<(), MarkdownViewerError> {
+        if let Some(error) = &self.error {
+            return Err(clone_error(error));
+        }
+        self.written
+            .lock()
+            .expect("writer state should be lockable")
+            .push((path.to_path_buf(), contents.to_string()));
+        Ok(())
+    }
+}
+
+pub(super) struct StubWorkspaceScanner {
+    folder: PathBuf,
+    files: Vec<PathBuf>,
+    error: Option<MarkdownViewerError>,
+}
+
+impl StubWorkspaceScanner {
+    pub(super) fn ok(folder: PathBuf, files: Vec<PathBuf>) -> Self {
+        Self {
+            folder,
+            files,
+            error: None,
+        }
+    }
+
+    pub(super) fn fail(error: MarkdownViewerError) -> Self {
+        Self {
+            folder: PathBuf::new(),
+            files: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+impl MarkdownWorkspaceScanner for StubWorkspaceScanner {
+    fn list_markdown_files(
+        &self,
+        _folder_input: &str,
+    ) -> Result<(PathBuf, Vec<PathBuf>), MarkdownViewerError> {
+        if let Some(error) = &self.error {
+            return Err(clone_error(error));
+        }
+        Ok((self.folder.clone(), self.files.clone()))
+    }
+}
+
+/// A project configuration at `/project/.mdviewer.toml` that blocks external links and adds a
+/// stylesheet.
+pub(super) struct StubProjectConfigResolver {
+    pub(super) resolved_path: Mutex<Option<PathBuf>>,
+}
+
+impl StubProjectConfigResolver {
+    pub(super) fn new() -> Self {
+        Self {
+            resolved_path: Mutex::new(None),
+        }
+    }
+}
+
+impl ProjectConfigResolver for StubProjectConfigResolver {
+    fn resolve(
+        &self,
+        document_path: &Path,
+        preferences: RenderPreferencesInput,
+    ) -> Result<EffectiveConfig, MarkdownViewerError> {
+        self.resolved_path
+            .lock()
+            .expect("resolver state should be lockable")
+            .replace(document_path.to_path_buf());
+        Ok(EffectiveConfig {
+            render: RenderPreferencesInput {
+                block_external_links: true,
+                ..preferences
+            },
+            project_config_path: Some("/project/.mdviewer.toml".to_string()),
+            custom_css: Some("body { color: teal; }".to_string()),
+            ignored_settings: vec!["`render.allow_raw_html` was ignored".to_string()],
+        })
+    }
 }
 
 pub(super) struct StubRenderer {
@@ -93,6 +438,7 @@ pub(super) struct StubRenderer {
     error: Option<MarkdownViewerError>,
     pub(super) called: AtomicBool,
     pub(super) last_markdown: Mutex<Option<String>>,
+    pub(super) last_document_path: Mutex<Option<PathBuf>>,
     pub(super) last_preferences: Mutex<Option<DomainRenderPreferences>>,
 }
 
@@ -103,6 +449,7 @@ impl StubRenderer {
             error: None,
             called: AtomicBool::new(false),
             last_markdown: Mutex::new(None),
+            last_document_path: Mutex::new(None),
             last_preferences: Mutex::new(None),
         }
     }
@@ -114,10 +461,15 @@ impl StubRenderer {
                 toc: Vec::new(),
                 word_count: 0,
                 reading_time_minutes: 0,
+                statistics: DocumentStatistics::default(),
+                tasks: Vec::new(),
+                warnings: Vec::new(),
+                local_images: Vec::new(),
             },
             error: Some(error),
             called: AtomicBool::new(false),
             last_markdown: Mutex::new(None),
+            last_document_path: Mutex::new(None),
             last_preferences: Mutex::new(None),
         }
     }
@@ -127,6 +479,7 @@ impl MarkdownRenderer for StubRenderer {
     fn render(
         &self,
         markdown: &str,
+        document_path: &Path,
         preferences: DomainRenderPreferences,
     ) -> Result<RenderedMarkdown, MarkdownViewerError> {
         self.called.store(true, Ordering::Relaxed);
@@ -134,6 +487,10 @@ impl MarkdownRenderer for StubRenderer {
             .lock()
             .expect("renderer markdown state should be lockable")
             .replace(markdown.to_string());
+        self.last_document_path
+            .lock()
+            .expect("renderer document path state should be lockable")
+            .replace(document_path.to_path_buf());
         self.last_preferences
             .lock()
             .expect("renderer preferences state should be lockable")
@@ -145,6 +502,122 @@ impl MarkdownRenderer for StubRenderer {
     }
 }
 
+pub(super) struct StubLinter {
+    report: LintReport,
+    error: Option<MarkdownViewerError>,
+    pub(super) last_call: Mutex<Option<(String, PathBuf)>>,
+}
+
+impl StubLinter {
+    pub(super) fn ok(report: LintReport) -> Self {
+        Self {
+            report,
+            error: None,
+            last_call: Mutex::new(None),
+        }
+    }
+
+    pub(super) fn fail(error: MarkdownViewerError) -> Self {
+        Self {
+            report: LintReport::default(),
+            error: Some(error),
+            last_call: Mutex::new(None),
+        }
+    }
+}
+
+impl MarkdownLinter for StubLinter {
+    fn lint(
+        &self,
+        markdown: &str,
+        document_path: &Path,
+    ) -> Result<LintReport, MarkdownViewerError> {
+        self.last_call
+            .lock()
+            .expect("linter call state should be lockable")
+            .replace((markdown.to_string(), document_path.to_path_buf()));
+        if let Some(error) = &self.error {
+            return Err(clone_error(error));
+        }
+        Ok(self.report.clone())
+    }
+}
+
+pub(super) struct StubSpellChecker {
+    report: SpellCheckReport,
+    error: Option<MarkdownViewerError>,
+    pub(super) last_call: Mutex<Option<(String, PathBuf)>>,
+}
+
+impl StubSpellChecker {
+    pub(super) fn ok(report: SpellCheckReport) -> Self {
+        Self {
+            report,
+            error: None,
+            last_call: Mutex::new(None),
+        }
+    }
+
+    pub(super) fn fail(error: MarkdownViewerError) -> Self {
+        Self {
+            report: SpellCheckReport::default(),
+            error: Some(error),
+            last_call: Mutex::new(None),
+        }
+    }
+}
+
+impl SpellChecker for StubSpellChecker {
+    fn check(
+        &self,
+        markdown: &str,
+        document_path: &Path,
+    ) -> Result<SpellCheckReport, MarkdownViewerError> {
+        self.last_call
+            .lock()
+            .expect("spell checker call state should be lockable")
+            .replace((markdown.to_string(), document_path.to_path_buf()));
+        if let Some(error) = &self.error {
+            return Err(clone_error(error));
+        }
+        Ok(self.report.clone())
+    }
+}
+
+pub(super) struct StubDiffer {
+    diff: DocumentDiff,
+    pub(super) last_call: Mutex<Option<(String, String, PathBuf)>>,
+}
+
+impl StubDiffer {
+    pub(super) fn ok(diff: DocumentDiff) -> Self {
+        Self {
+            diff,
+            last_call: Mutex::new(None),
+        }
+    }
+}
+
+impl MarkdownDiffer for StubDiffer {
+    fn diff(
+        &self,
+        old: &str,
+        new: &str,
+        document_path: &Path,
+        _preferences: DomainRenderPreferences,
+    ) -> Result<DocumentDiff, MarkdownViewerError> {
+        self.last_call
+            .lock()
+            .expect("differ call state should be lockable")
+            .replace((
+                old.to_string(),
+                new.to_string(),
+                document_path.to_path_buf(),
+            ));
+        Ok(self.diff.clone())
+    }
+}
+
 pub(super) struct StubWatchService {
     should_fail: bool,
     pub(super) started_path: Mutex<Option<String>>,
@@ -186,6 +659,73 @@ impl MarkdownWatchService for StubWatchService {
     }
 }
 
+/// Serves the queued load results in order, repeating the last one, and records saves.
+pub(super) struct StubSettingsStore {
+    loads: Mutex<Vec<Result<ViewerSettings, MarkdownViewerError>>>,
+    file_exists: bool,
+    pub(super) saved: Mutex<Option<ViewerSettings>>,
+}
+
+impl StubSettingsStore {
+    pub(super) fn ok(settings: ViewerSettings) -> Self {
+        Self::with_loads(vec![Ok(settings)])
+    }
+
+    pub(super) fn with_loads(loads: Vec<Result<ViewerSettings, MarkdownViewerError>>) -> Self {
+        Self {
+            loads: Mutex::new(loads),
+            file_exists: true,
+            saved: Mutex::new(None),
+        }
+    }
+
+    /// A store whose file has not been written yet.
+    pub(super) fn missing() -> Self {
+        Self {
+            file_exists: false,
+            ..Self::with_loads(Vec::new())
+        }
+    }
+}
+
+impl SettingsStore for StubSettingsStore {
+    fn path(&self) -> PathBuf {
+        PathBuf::from("/config/settings.toml")
+    }
+
+    fn exists(&self) -> bool {
+        self.file_exists
+            || self
+                .saved
+                .lock()
+                .expect("saved settings should be lockable")
+                .is_some()
+    }
+
+    fn load(&self) -> Result<ViewerSettings, MarkdownViewerError> {
+        let mut loads = self
+            .loads
+            .lock()
+            .expect("settings loads should be lockable");
+        if loads.len() > 1 {
+            return loads.remove(0);
+        }
+        match loads.first() {
+            Some(Ok(settings)) => Ok(settings.clone()),
+            Some(Err(error)) => Err(clone_error(error)),
+            None => Ok(ViewerSettings::default()),
+        }
+    }
+
+    fn save(&self, settings: &ViewerSettings) -> Result<(), MarkdownViewerError> {
+        self.saved
+            .lock()
+            .expect("saved settings should be lockable")
+            .replace(settings.clone());
+        Ok(())
+    }
+}
+
 pub(super) enum CanonicalizeResponse {
     Success(PathBuf),
     Fail(MarkdownViewerError),
@@ -257,5 +797,27 @@ pub(super) fn sample_preferences() -> RenderPreferencesInput {
             include_code: true,
             include_front_matter: true,
         },
+        text_replacements: vec![TextReplacementRuleInput {
+            pattern: r"\b[A-Z]+-\d+\b".to_string(),
+            replacement: "$0".to_string(),
+            link_template: Some("https://tracker.example.com/browse/$0".to_string()),
+        }],
+        allow_raw_html: true,
+        html_allowlist: Some(HtmlAllowlistInput {
+            tags: vec!["kbd".to_string()],
+            attributes: Vec::new(),
+            url_schemes: vec!["https".to_string()],
+        }),
+        image_embedding: ImageEmbeddingInput::DataUri,
+        csv_row_limit: Some(25),
+        words_per_minute: Some(180),
+        han_characters_per_minute: Some(260),
+        kana_characters_per_minute: Some(450),
+        number_headings: Some(HeadingNumberingInput { start_level: 2 }),
+        block_external_links: false,
+        extension_profile: ExtensionProfileInput::Github,
+        extension_overrides: [("highlight".to_string(), true), ("typo".to_string(), true)]
+            .into_iter()
+            .collect(),
     }
 }
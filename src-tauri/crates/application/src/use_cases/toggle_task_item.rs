@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use markdown_viewer_domain::document::RenderPreferences;
+use markdown_viewer_domain::document_source::SourceDocument;
+use markdown_viewer_domain::markdown_source::{content_hash, toggle_task_marker};
+
+use crate::error::MarkdownViewerError;
+use crate::models::ToggleTaskItemOutput;
+use crate::ports::{MarkdownFileRepository, MarkdownFileWriter, MarkdownRenderer};
+
+#[derive(Clone)]
+pub struct ToggleTaskItemUseCase {
+    repository: Arc<dyn MarkdownFileRepository>,
+    renderer: Arc<dyn MarkdownRenderer>,
+    writer: Arc<dyn MarkdownFileWriter>,
+}
+
+impl ToggleTaskItemUseCase {
+    pub fn new(
+        repository: Arc<dyn MarkdownFileRepository>,
+        renderer: Arc<dyn MarkdownRenderer>,
+        writer: Arc<dyn MarkdownFileWriter>,
+    ) -> Self {
+        Self {
+            repository,
+            renderer,
+            writer,
+        }
+    }
+
+    /// Checks or unchecks the task list item starting on 1-based `line` and writes the file back.
+    ///
+    /// `expected_content_hash` is the `content_hash` of the document the caller is showing; if the
+    /// file no longer matches it, nothing is written. The line must hold a task item in the parsed
+    /// document, so `[ ]` inside code blocks or HTML is never edited. The view is refreshed by the
+    /// file watcher like for any other change on disk.
+    pub fn execute(
+        &self,
+        path_input: &str,
+        line: usize,
+        expected_content_hash: &str,
+    ) -> Result<ToggleTaskItemOutput, MarkdownViewerError> {
+        let SourceDocument {
+            path,
+            source,
+            origin,
+        } = self.repository.read(path_input)?;
+        if !origin.is_file() {
+            return Err(MarkdownViewerError::ReadOnlyDocument(path));
+        }
+        if content_hash(&source) != expected_content_hash {
+            return Err(MarkdownViewerError::DocumentChanged(path));
+        }
+
+        let rendered = self
+            .renderer
+            .render(&source, &path, RenderPreferences::default())?;
+        let edit = rendered
+            .tasks
+            .iter()
+            .any(|task| task.line == line)
+            .then(|| toggle_task_marker(&source, line))
+            .flatten();
+        let Some((edited, checked)) = edit else {
+            return Err(MarkdownViewerError::TaskItemNotFound { path, line });
+        };
+        self.writer.write_atomically(&path, &edited)?;
+
+        Ok(ToggleTaskItemOutput {
+            path: path.to_string_lossy().into_owned(),
+            checked,
+            content_hash: content_hash(&edited),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use markdown_viewer_domain::document::{DocumentStatistics, RenderedMarkdown, TaskListItem};
+    use markdown_viewer_domain::document_source::DocumentOrigin;
+    use markdown_viewer_domain::markdown_source::content_hash;
+
+    use crate::error::MarkdownViewerError;
+    use crate::use_cases::test_support::{StubFileWriter, StubRenderer, StubRepository};
+    use crate::use_cases::toggle_task_item::ToggleTaskItemUseCase;
+
+    const SOURCE: &str = "# Plan\n\n- [ ] write\n- [x] ship\n";
+
+    fn rendered_with_task_lines(lines: &[usize]) -> RenderedMarkdown {
+        RenderedMarkdown {
+            html: String::new(),
+            toc: Vec::new(),
+            word_count: 0,
+            reading_time_minutes: 1,
+            statistics: DocumentStatistics::default(),
+            tasks: lines
+                .iter()
+                .map(|&line| TaskListItem {
+                    text: String::new(),
+                    checked: false,
+                    depth: 0,
+                    heading_id: None,
+                    line,
+                })
+                .collect(),
+            warnings: Vec::new(),
+            local_images: Vec::new(),
+        }
+    }
+
+    fn use_case(writer: Arc<StubFileWriter>) -> ToggleTaskItemUseCase {
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/docs/plan.md"), SOURCE));
+        let renderer = Arc::new(StubRenderer::ok(rendered_with_task_lines(&[3, 4])));
+        ToggleTaskItemUseCase::new(repository, renderer, writer)
+    }
+
+    #[test]
+    fn flips_the_task_and_writes_the_file() {
+        let writer = Arc::new(StubFileWriter::ok());
+
+        let output = use_case(writer.clone())
+            .execute("/docs/plan.md", 3, &content_hash(SOURCE))
+            .expect("toggle should succeed");
+
+        let expected = "# Plan\n\n- [x] write\n- [x] ship\n";
+        assert!(output.checked);
+        assert_eq!(output.path, "/docs/plan.md");
+        assert_eq!(output.content_hash, content_hash(expected));
+        assert_eq!(
+            writer.written(),
+            [(PathBuf::from("/docs/plan.md"), expected.to_string())]
+        );
+    }
+
+    #[test]
+    fn refuses_to_write_when_the_file_changed() {
+        let writer = Arc::new(StubFileWriter::ok());
+
+        let error = use_case(writer.clone())
+            .execute("/docs/plan.md", 3, &content_hash("# Plan\n"))
+            .expect_err("stale hash should be rejected");
+
+        assert!(matches!(error, MarkdownViewerError::DocumentChanged(_)));
+        assert!(writer.written().is_empty());
+    }
+
+    #[test]
+    fn refuses_to_edit_piped_documents() {
+        let repository = Arc::new(StubRepository::with_origin(
+            PathBuf::from("/work/-"),
+            SOURCE,
+            DocumentOrigin::Stdin,
+        ));
+        let writer = Arc::new(StubFileWriter::ok());
+        let renderer = Arc::new(StubRenderer::ok(rendered_with_task_lines(&[3, 4])));
+        let use_case = ToggleTaskItemUseCase::new(repository, renderer, writer.clone());
+
+        let error = use_case
+            .execute("-", 3, &content_hash(SOURCE))
+            .expect_err("stdin documents are read-only");
+
+        assert!(matches!(error, MarkdownViewerError::ReadOnlyDocument(_)));
+        assert!(writer.written().is_empty());
+    }
+
+    #[test]
+    fn reports_lines_without_a_task() {
+        let writer = Arc::new(StubFileWriter::ok());
+
+        let error = use_case(writer.clone())
+            .execute("/docs/plan.md", 1, &content_hash(SOURCE))
+            .expect_err("a heading is not a task");
+
+        assert!(matches!(
+            error,
+            MarkdownViewerError::TaskItemNotFound { line: 1, .. }
+        ));
+        assert!(writer.written().is_empty());
+    }
+
+    #[test]
+    fn leaves_task_markers_outside_parsed_task_items_alone() {
+        let source = "```\n- [ ] sample\n```\n";
+        let repository = Arc::new(StubRepository::ok(PathBuf::from("/docs/plan.md"), source));
+        let renderer = Arc::new(StubRenderer::ok(rendered_with_task_lines(&[])));
+        let writer = Arc::new(StubFileWriter::ok());
+        let use_case = ToggleTaskItemUseCase::new(repository, renderer.clone(), writer.clone());
+
+        let error = use_case
+            .execute("/docs/plan.md", 2, &content_hash(source))
+            .expect_err("a code block line is not a task");
+
+        assert!(matches!(
+            error,
+            MarkdownViewerError::TaskItemNotFound { line: 2, .. }
+        ));
+        assert_eq!(
+            renderer
+                .last_markdown
+                .lock()
+                .expect("renderer markdown state should be lockable")
+                .as_deref(),
+            Some(source)
+        );
+        assert!(writer.written().is_empty());
+    }
+
+    #[test]
+    fn propagates_writer_error() {
+        let writer = Arc::new(StubFileWriter::fail(MarkdownViewerError::WriteFile {
+            path: PathBuf::from("/docs/plan.md"),
+            reason: "read-only file system".to_string(),
+        }));
+
+        let error = use_case(writer)
+            .execute("/docs/plan.md", 4, &content_hash(SOURCE))
+            .expect_err("write failure should surface");
+
+        assert!(matches!(error, MarkdownViewerError::WriteFile { .. }));
+    }
+}
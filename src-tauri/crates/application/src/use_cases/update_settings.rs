@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::error::MarkdownViewerError;
+use crate::models::ViewerSettings;
+use crate::ports::SettingsStore;
+
+#[derive(Clone)]
+pub struct UpdateSettingsUseCase {
+    store: Arc<dyn SettingsStore>,
+}
+
+impl UpdateSettingsUseCase {
+    pub fn new(store: Arc<dyn SettingsStore>) -> Self {
+        Self { store }
+    }
+
+    /// Saves `settings`, moved into range, and returns what was saved.
+    pub fn execute(&self, settings: ViewerSettings) -> Result<ViewerSettings, MarkdownViewerError> {
+        let settings = settings.clamped();
+        self.store.save(&settings)?;
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::models::{ThemePreset, ViewerSettings};
+    use crate::use_cases::test_support::StubSettingsStore;
+    use crate::use_cases::update_settings::UpdateSettingsUseCase;
+
+    #[test]
+    fn saves_and_returns_the_clamped_settings() {
+        let store = Arc::new(StubSettingsStore::ok(ViewerSettings::default()));
+        let use_case = UpdateSettingsUseCase::new(store.clone());
+
+        let saved = use_case
+            .execute(ViewerSettings {
+                theme: ThemePreset::Slate,
+                line_height: 0.5,
+                ..ViewerSettings::default()
+            })
+            .expect("settings should save");
+
+        assert_eq!(saved.theme, ThemePreset::Slate);
+        assert_eq!(saved.line_height, 1.35);
+        assert_eq!(
+            store
+                .saved
+                .lock()
+                .expect("saved settings should be lockable")
+                .as_ref(),
+            Some(&saved)
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::sync::{Arc, Mutex};
+
+use crate::error::MarkdownViewerError;
+use crate::models::ViewerSettings;
+use crate::ports::{MarkdownWatchService, SettingsStore};
+
+/// Reloads the settings whenever the settings file changes, for example when it is edited by
+/// hand or saved from another window.
+#[derive(Clone)]
+pub struct WatchSettingsUseCase {
+    store: Arc<dyn SettingsStore>,
+    watch_service: Arc<dyn MarkdownWatchService>,
+}
+
+impl WatchSettingsUseCase {
+    pub fn new(
+        store: Arc<dyn SettingsStore>,
+        watch_service: Arc<dyn MarkdownWatchService>,
+    ) -> Self {
+        Self {
+            store,
+            watch_service,
+        }
+    }
+
+    /// Calls `on_changed` with the reloaded settings when they differ from the ones last seen,
+    /// or with the error when the edited file cannot be used.
+    pub fn start(
+        &self,
+        on_changed: Arc<dyn Fn(Result<ViewerSettings, MarkdownViewerError>) + Send + Sync>,
+    ) -> Result<(), MarkdownViewerError> {
+        let settings = self.store.load()?;
+        // The watcher follows an existing file, so a missing one is written with the defaults.
+        if !self.store.exists() {
+            self.store.save(&settings)?;
+        }
+        let last_seen = Mutex::new(settings.clamped());
+        let store = Arc::clone(&self.store);
+        let path = self.store.path();
+
+        self.watch_service.start(
+            &path.to_string_lossy(),
+            Arc::new(move |_| match store.load().map(ViewerSettings::clamped) {
+                Ok(settings) => {
+                    let mut last_seen =
+                        last_seen.lock().expect("settings state should be lockable");
+                    if *last_seen != settings {
+                        *last_seen = settings.clone();
+                        on_changed(Ok(settings));
+                    }
+                }
+                Err(error) => on_changed(Err(error)),
+            }),
+        )
+    }
+
+    pub fn stop(&self) {
+        self.watch_service.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::models::{ThemePreset, ViewerSettings};
+    use crate::use_cases::test_support::{StubSettingsStore, StubWatchService};
+    use crate::use_cases::watch_settings::WatchSettingsUseCase;
+
+    fn watch(store: StubSettingsStore) -> (Arc<StubWatchService>, Vec<ViewerSettings>) {
+        let watch_service = Arc::new(StubWatchService::new(false));
+        let use_case = WatchSettingsUseCase::new(Arc::new(store), watch_service.clone());
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let changes_for_callback = Arc::clone(&changes);
+
+        use_case
+            .start(Arc::new(move |result| {
+                changes_for_callback
+                    .lock()
+                    .expect("changes should be lockable")
+                    .push(result.expect("settings should reload"));
+            }))
+            .expect("watch should start");
+
+        let changes = changes.lock().expect("changes should be lockable").clone();
+        (watch_service, changes)
+    }
+
+    #[test]
+    fn reports_reloaded_settings_that_changed() {
+        let edited = ViewerSettings {
+            theme: ThemePreset::ContrastDark,
+            ..ViewerSettings::default()
+        };
+        let store =
+            StubSettingsStore::with_loads(vec![Ok(ViewerSettings::default()), Ok(edited.clone())]);
+
+        let (watch_service, changes) = watch(store);
+
+        assert_eq!(
+            watch_service
+                .started_path
+                .lock()
+                .expect("watch state should be lockable")
+                .as_deref(),
+            Some("/config/settings.toml")
+        );
+        assert_eq!(changes, vec![edited]);
+    }
+
+    #[test]
+    fn ignores_writes_that_leave_the_settings_unchanged() {
+        let (_, changes) = watch(StubSettingsStore::ok(ViewerSettings::default()));
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn writes_the_defaults_before_watching_a_missing_file() {
+        let store = Arc::new(StubSettingsStore::missing());
+        let use_case =
+            WatchSettingsUseCase::new(store.clone(), Arc::new(StubWatchService::new(false)));
+
+        use_case
+            .start(Arc::new(|_| {}))
+            .expect("watch should start");
+
+        assert_eq!(
+            *store
+                .saved
+                .lock()
+                .expect("saved settings should be lockable"),
+            Some(ViewerSettings::default())
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use markdown_viewer_application::models::{LintReportOutput, LintSeverityOutput};
+use markdown_viewer_application::ports::MarkdownWorkspaceScanner;
+use markdown_viewer_application::use_cases::LintMarkdownFileUseCase;
+use markdown_viewer_infrastructure::file_repository::LocalMarkdownFileRepository;
+use markdown_viewer_infrastructure::markdown_lint::ComrakMarkdownLinter;
+use markdown_viewer_infrastructure::workspace_scanner::LocalWorkspaceScanner;
+use markdown_viewer_presentation::dto::LintReportDto;
+
+use crate::{EXIT_FAILURE, USAGE};
+
+/// At least one error-severity rule was violated.
+const EXIT_VIOLATIONS: u8 = 1;
+
+struct LintArgs<'a> {
+    json: bool,
+    paths: Vec<&'a str>,
+}
+
+fn parse_args(args: &[String]) -> Result<LintArgs<'_>, String> {
+    let mut parsed = LintArgs {
+        json: false,
+        paths: Vec::new(),
+    };
+    for arg in args {
+        match arg.as_str() {
+            "--json" => parsed.json = true,
+            option if option.starts_with("--") => {
+                return Err(format!("unknown option `{option}`"));
+            }
+            path => parsed.paths.push(path),
+        }
+    }
+    if parsed.paths.is_empty() {
+        return Err("lint needs at least one file or folder".to_string());
+    }
+    Ok(parsed)
+}
+
+/// Lints every file named in `args`, expanding folders to the markdown files below them, and
+/// writes the findings to `out` and unreadable inputs to `err`.
+pub(crate) fn run(args: &[String], out: &mut dyn Write, err: &mut dyn Write) -> io::Result<u8> {
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(message) => {
+            writeln!(err, "{message}\n\n{USAGE}")?;
+            return Ok(EXIT_FAILURE);
+        }
+    };
+    let use_case = LintMarkdownFileUseCase::new(
+        Arc::new(LocalMarkdownFileRepository::new()),
+        Arc::new(ComrakMarkdownLinter::new()),
+    );
+    let scanner = LocalWorkspaceScanner::new();
+
+    let mut reports = Vec::new();
+    let mut failed = false;
+    for path in args.paths {
+        let files = if Path::new(path).is_dir() {
+            match scanner.list_markdown_files(path) {
+                Ok((_, files)) => files
+                    .into_iter()
+                    .map(|file| file.to_string_lossy().into_owned())
+                    .collect(),
+                Err(error) => {
+                    writeln!(err, "{path}: {error}")?;
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            vec![path.to_string()]
+        };
+
+        for file in files {
+            match use_case.execute(&file) {
+                Ok(report) => reports.push(report),
+                Err(error) => {
+                    writeln!(err, "{file}: {error}")?;
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    let violations = reports.iter().any(|report| {
+        report
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == LintSeverityOutput::Error)
+    });
+    if args.json {
+        let reports: Vec<LintReportDto> = reports.into_iter().map(Into::into).collect();
+        serde_json::to_writer_pretty(&mut *out, &reports)?;
+        writeln!(out)?;
+    } else {
+        for report in &reports {
+            write_report(report, out)?;
+        }
+    }
+
+    Ok(if failed {
+        EXIT_FAILURE
+    } else if violations {
+        EXIT_VIOLATIONS
+    } else {
+        0
+    })
+}
+
+/// One line per finding, in the `path:line[:column] severity RULE/alias message` shape that
+/// editors and CI annotations already understand from markdownlint.
+fn write_report(report: &LintReportOutput, out: &mut dyn Write) -> io::Result<()> {
+    for diagnostic in &report.diagnostics {
+        let location = match diagnostic.column {
+            Some(column) => format!("{}:{}:{column}", report.path, diagnostic.line),
+            None => format!("{}:{}", report.path, diagnostic.line),
+        };
+        let severity = match diagnostic.severity {
+            LintSeverityOutput::Warning => "warning",
+            LintSeverityOutput::Error => "error",
+        };
+        writeln!(
+            out,
+            "{location} {severity} {}/{} {}",
+            diagnostic.rule_id, diagnostic.rule_name, diagnostic.message
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::{run, EXIT_VIOLATIONS};
+    use crate::EXIT_FAILURE;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after the epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("mdv-cli-{name}-{unique}"));
+        fs::create_dir_all(&dir).expect("temp dir should be creatable");
+        dir.canonicalize().expect("temp dir should resolve")
+    }
+
+    fn lint(args: &[&str]) -> (u8, String, String) {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let code = run(&args, &mut out, &mut err).expect("writing to a buffer cannot fail");
+        (
+            code,
+            String::from_utf8(out).expect("stdout should be UTF-8"),
+            String::from_utf8(err).expect("stderr should be UTF-8"),
+        )
+    }
+
+    #[test]
+    fn lints_folders_and_fails_only_on_error_severity() {
+        let dir = temp_dir("folder");
+        fs::create_dir_all(dir.join("guide")).expect("nested dir should be creatable");
+        fs::write(dir.join("a.md"), "# A\n\n### Deep\n").expect("file should be writable");
+        fs::write(dir.join("guide").join("b.md"), "# B \n").expect("file should be writable");
+        let (code, out, err) = lint(&[&dir.to_string_lossy()]);
+
+        assert_eq!(code, 0, "{err}");
+        let a = dir.join("a.md");
+        let b = dir.join("guide").join("b.md");
+        assert_eq!(
+            out,
+            format!(
+                "{}:3 warning MD001/heading-increment Heading levels should only increment by one level at a time [Expected: h2; Actual: h3]\n\
+                 {}:1:4 warning MD009/no-trailing-spaces Trailing spaces [Expected: 0 or 2; Actual: 1]\n",
+                a.display(),
+                b.display()
+            )
+        );
+
+        fs::write(dir.join(".markdownlint.json"), r#"{ "MD001": "error" }"#)
+            .expect("config should be writable");
+        let (code, out, _) = lint(&["--json", &a.to_string_lossy()]);
+
+        assert_eq!(code, EXIT_VIOLATIONS);
+        let json: serde_json::Value = serde_json::from_str(&out).expect("output should be JSON");
+        assert_eq!(json[0]["diagnostics"][0]["severity"], "error");
+        assert_eq!(
+            json[0]["configPath"],
+            dir.join(".markdownlint.json").to_string_lossy().as_ref()
+        );
+
+        fs::remove_dir_all(dir).expect("temp dir should be removable");
+    }
+
+    #[test]
+    fn reports_unreadable_inputs_and_invalid_arguments() {
+        let dir = temp_dir("missing");
+        let missing = dir.join("missing.md").to_string_lossy().into_owned();
+
+        let (code, _, err) = lint(&[&missing]);
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(
+            err.starts_with(&format!("{missing}: file does not exist")),
+            "{err}"
+        );
+
+        let (code, _, err) = lint(&["--fix"]);
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(err.starts_with("unknown option `--fix`"));
+        assert_eq!(lint(&[]).0, EXIT_FAILURE);
+
+        fs::remove_dir_all(dir).expect("temp dir should be removable");
+    }
+}
@@ -0,0 +1,71 @@
+//! Command line entry point for the viewer's headless features.
+
+use std::env;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+mod lint;
+mod settings;
+
+const USAGE: &str = "\
+Usage: markdown-viewer-cli <command> [options]
+
+Commands:
+  lint [--json] <path>...  Check markdown files, or every markdown file in a folder, against
+                           the lint rules configured by the nearest .markdownlint.json
+  settings [--file <path>] Print the desktop app's settings, or those in the given settings
+                           file, as JSON
+
+Exit status: 0 when no error-severity rule is violated, 1 when one is, 2 when a file or
+configuration could not be read or the command line is invalid.";
+
+/// The command line was invalid or an input could not be processed.
+const EXIT_FAILURE: u8 = 2;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let code = run(&args, &mut io::stdout().lock(), &mut io::stderr().lock());
+    ExitCode::from(code)
+}
+
+fn run(args: &[String], out: &mut dyn Write, err: &mut dyn Write) -> u8 {
+    let result = match args.split_first() {
+        Some((command, rest)) if command == "lint" => lint::run(rest, out, err),
+        Some((command, rest)) if command == "settings" => settings::run(rest, out, err),
+        Some((flag, _)) if flag == "-h" || flag == "--help" => writeln!(out, "{USAGE}").map(|()| 0),
+        Some((command, _)) => {
+            writeln!(err, "unknown command `{command}`\n\n{USAGE}").map(|()| EXIT_FAILURE)
+        }
+        None => writeln!(err, "{USAGE}").map(|()| EXIT_FAILURE),
+    };
+    result.unwrap_or(EXIT_FAILURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, EXIT_FAILURE};
+
+    fn run_with(args: &[&str]) -> (u8, String, String) {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let code = run(&args, &mut out, &mut err);
+        (
+            code,
+            String::from_utf8(out).expect("stdout should be UTF-8"),
+            String::from_utf8(err).expect("stderr should be UTF-8"),
+        )
+    }
+
+    #[test]
+    fn prints_usage_for_help_and_rejects_unknown_commands() {
+        let (code, out, _) = run_with(&["--help"]);
+        assert_eq!(code, 0);
+        assert!(out.starts_with("Usage: markdown-viewer-cli"));
+
+        let (code, _, err) = run_with(&["render"]);
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(err.starts_with("unknown command `render`"));
+
+        assert_eq!(run_with(&[]).0, EXIT_FAILURE);
+    }
+}
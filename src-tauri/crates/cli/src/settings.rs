@@ -0,0 +1,113 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use markdown_viewer_application::use_cases::GetSettingsUseCase;
+use markdown_viewer_infrastructure::settings_store::{default_settings_path, TomlSettingsStore};
+use markdown_viewer_presentation::dto::SettingsDto;
+
+use crate::{EXIT_FAILURE, USAGE};
+
+fn parse_args(args: &[String]) -> Result<Option<PathBuf>, String> {
+    match args {
+        [] => Ok(None),
+        [flag, path] if flag == "--file" => Ok(Some(PathBuf::from(path))),
+        [flag] if flag == "--file" => Err("`--file` needs a path".to_string()),
+        [other, ..] => Err(format!("unknown option `{other}`")),
+    }
+}
+
+/// Prints the settings the desktop app uses, or those in the file named by `--file`, as JSON.
+pub(crate) fn run(args: &[String], out: &mut dyn Write, err: &mut dyn Write) -> io::Result<u8> {
+    let path = match parse_args(args) {
+        Ok(Some(path)) => path,
+        Ok(None) => match default_settings_path() {
+            Some(path) => path,
+            None => {
+                writeln!(
+                    err,
+                    "cannot locate the settings file: no home folder is set"
+                )?;
+                return Ok(EXIT_FAILURE);
+            }
+        },
+        Err(message) => {
+            writeln!(err, "{message}\n\n{USAGE}")?;
+            return Ok(EXIT_FAILURE);
+        }
+    };
+
+    let use_case = GetSettingsUseCase::new(Arc::new(TomlSettingsStore::new(path)));
+    match use_case.execute() {
+        Ok(settings) => {
+            let json = serde_json::to_string_pretty(&SettingsDto::from(settings))
+                .expect("settings always serialize");
+            writeln!(out, "{json}")?;
+            Ok(0)
+        }
+        Err(error) => {
+            writeln!(err, "{error}")?;
+            Ok(EXIT_FAILURE)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::run;
+    use crate::EXIT_FAILURE;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after the epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("mdv-cli-{name}-{unique}"));
+        fs::create_dir_all(&dir).expect("temp dir should be creatable");
+        dir
+    }
+
+    fn settings(args: &[&str]) -> (u8, String, String) {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let code = run(&args, &mut out, &mut err).expect("writing to a buffer cannot fail");
+        (
+            code,
+            String::from_utf8(out).expect("stdout should be UTF-8"),
+            String::from_utf8(err).expect("stderr should be UTF-8"),
+        )
+    }
+
+    #[test]
+    fn prints_the_settings_file_as_json() {
+        let path = temp_dir("settings").join("settings.toml");
+        fs::write(&path, "version = 1\n\n[appearance]\ntheme = \"slate\"\n")
+            .expect("settings should be writable");
+
+        let (code, out, err) = settings(&["--file", &path.to_string_lossy()]);
+
+        assert_eq!(code, 0, "{err}");
+        let json: serde_json::Value = serde_json::from_str(&out).expect("output should be JSON");
+        assert_eq!(json["theme"], "slate");
+        assert_eq!(json["render"]["wordCountRules"]["includeLinks"], true);
+    }
+
+    #[test]
+    fn reports_invalid_settings_and_arguments() {
+        let path = temp_dir("settings-invalid").join("settings.toml");
+        fs::write(&path, "version = 1\n\n[appearance]\nfont_scale = \"big\"\n")
+            .expect("settings should be writable");
+
+        let (code, _, err) = settings(&["--file", &path.to_string_lossy()]);
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(err.contains("`appearance.font_scale`"), "{err}");
+
+        let (code, _, err) = settings(&["--json"]);
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(err.starts_with("unknown option `--json`"), "{err}");
+    }
+}
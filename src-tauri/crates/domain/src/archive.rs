@@ -0,0 +1,12 @@
+use std::path::PathBuf;
+
+/// A markdown file stored inside an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Name of the entry inside the archive, such as `guide/intro.md`.
+    pub name: String,
+    /// Path that opens the entry as a document.
+    pub path: PathBuf,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+}
@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+/// The newer version of a document rendered with the blocks that changed since an older version
+/// marked up.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentDiff {
+    /// Every block of both versions in document order: unchanged blocks as rendered, added and
+    /// removed blocks wrapped in `mdv-diff-inserted`/`mdv-diff-deleted` containers, and edited
+    /// blocks wrapped in `mdv-diff-modified` with `<ins>`/`<del>` around the changed words.
+    pub html: String,
+    pub inserted_blocks: usize,
+    pub deleted_blocks: usize,
+    pub modified_blocks: usize,
+    /// Local image files the HTML embeds, from either version.
+    pub local_images: Vec<PathBuf>,
+}
@@ -1,8 +1,79 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::extensions::{ExtensionProfile, MarkdownExtension};
+
 #[derive(Debug, Clone)]
 pub struct TocEntry {
     pub level: u8,
     pub id: String,
+    /// The heading as listed in the table of contents, including its section number.
+    pub text: String,
+    /// The heading's own text, without a section number.
+    pub plain_text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentWarningKind {
+    MissingImage,
+    DiagramError,
+    CsvError,
+    PluginError,
+}
+
+/// A problem found while rendering that did not stop the document from being shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentWarning {
+    pub kind: DocumentWarningKind,
+    pub message: String,
+    /// 1-based source line the warning refers to, when known.
+    pub line: Option<usize>,
+}
+
+/// Task list items by state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskCompletion {
+    pub checked: usize,
+    pub unchecked: usize,
+}
+
+/// Flesch readability scores of the document's prose. The formulas are calibrated for English.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Readability {
+    /// 0-100, higher is easier to read.
+    pub flesch_reading_ease: f64,
+    /// Approximate US school grade needed to follow the text.
+    pub flesch_kincaid_grade: f64,
+}
+
+/// Structure and prose metrics reported alongside the word count.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentStatistics {
+    /// Words from each heading up to the next heading, keyed by the heading's TOC id. Text
+    /// before the first heading is not part of any section.
+    pub section_word_counts: BTreeMap<String, usize>,
+    pub code_blocks: usize,
+    pub images: usize,
+    pub tables: usize,
+    pub links: usize,
+    pub footnotes: usize,
+    pub tasks: TaskCompletion,
+    /// `None` when the document has no prose paragraphs to score.
+    pub readability: Option<Readability>,
+}
+
+/// A `- [ ]` / `- [x]` item from a task list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskListItem {
+    /// The item's own text, without the text of nested items.
     pub text: String,
+    pub checked: bool,
+    /// Number of list items enclosing this one; top-level items are 0.
+    pub depth: usize,
+    /// TOC id of the closest heading above the item, if any.
+    pub heading_id: Option<String>,
+    /// 1-based source line of the item.
+    pub line: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -11,6 +82,11 @@ pub struct RenderedMarkdown {
     pub toc: Vec<TocEntry>,
     pub word_count: usize,
     pub reading_time_minutes: u16,
+    pub statistics: DocumentStatistics,
+    pub tasks: Vec<TaskListItem>,
+    pub warnings: Vec<DocumentWarning>,
+    /// Local image files embedded by the HTML, so the shell can grant the webview access to them.
+    pub local_images: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -30,15 +106,163 @@ impl Default for WordCountRules {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+/// Rewrites prose matching `pattern` (a regular expression) with `replacement`.
+///
+/// `replacement` may reference capture groups (`$0`, `$1`, `${name}`). When `link_template`
+/// is set the replaced text becomes a link whose URL is expanded the same way, which is how
+/// ticket IDs such as `ABC-123` are turned into tracker links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextReplacementRule {
+    pub pattern: String,
+    pub replacement: String,
+    pub link_template: Option<String>,
+}
+
+/// Raw HTML that may pass through when [`RenderPreferences::allow_raw_html`] is enabled.
+///
+/// This extends, and cannot remove, the markup the renderer itself produces. `attributes` are
+/// allowed on every permitted tag, and `url_schemes` restricts `href`/`src` values (relative URLs
+/// are always allowed). Scripts, styles and event handler attributes are never allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlAllowlist {
+    pub tags: Vec<String>,
+    pub attributes: Vec<String>,
+    pub url_schemes: Vec<String>,
+}
+
+impl Default for HtmlAllowlist {
+    fn default() -> Self {
+        let owned = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        Self {
+            tags: owned(&[
+                "a",
+                "abbr",
+                "b",
+                "blockquote",
+                "br",
+                "code",
+                "dd",
+                "del",
+                "details",
+                "div",
+                "dl",
+                "dt",
+                "em",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "hr",
+                "i",
+                "img",
+                "ins",
+                "kbd",
+                "li",
+                "mark",
+                "ol",
+                "p",
+                "picture",
+                "pre",
+                "q",
+                "s",
+                "samp",
+                "source",
+                "span",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "table",
+                "tbody",
+                "td",
+                "tfoot",
+                "th",
+                "thead",
+                "tr",
+                "u",
+                "ul",
+                "var",
+            ]),
+            attributes: owned(&[
+                "align", "alt", "colspan", "dir", "height", "href", "lang", "media", "open",
+                "rowspan", "src", "srcset", "start", "title", "width",
+            ]),
+            url_schemes: owned(&["http", "https", "mailto"]),
+        }
+    }
+}
+
+/// How local images referenced by a document are embedded in the rendered HTML.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImageEmbedding {
+    /// Link to the file through the app's asset protocol, for display in the viewer.
+    #[default]
+    AssetUrl,
+    /// Inline the file as a `data:` URI so the HTML is self-contained, for export.
+    DataUri,
+}
+
+/// Hierarchical section numbers ("3.2.1") prefixed to headings at `start_level` and deeper.
+///
+/// Numbers are shown in the HTML and the table of contents only; heading IDs are derived from
+/// the unnumbered text so links keep working when numbering is toggled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadingNumbering {
+    pub start_level: u8,
+}
+
+impl Default for HeadingNumbering {
+    fn default() -> Self {
+        Self { start_level: 1 }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct RenderPreferences {
     pub performance_mode: bool,
     pub word_count_rules: WordCountRules,
+    pub text_replacements: Vec<TextReplacementRule>,
+    pub allow_raw_html: bool,
+    pub html_allowlist: HtmlAllowlist,
+    pub image_embedding: ImageEmbedding,
+    /// Maximum data rows shown for CSV/TSV tables; `None` uses the renderer's default.
+    pub csv_row_limit: Option<usize>,
+    /// `None` leaves headings unnumbered.
+    pub number_headings: Option<HeadingNumbering>,
+    /// Reading speed used for the reading time; `None` uses the renderer's default.
+    pub words_per_minute: Option<usize>,
+    /// Reading speed for Chinese characters (Han ideographs), which are counted one word each.
+    pub han_characters_per_minute: Option<usize>,
+    /// Reading speed for Japanese kana, which are counted one word each.
+    pub kana_characters_per_minute: Option<usize>,
+    /// Renders links to web pages and other non-file URLs as their text only.
+    pub block_external_links: bool,
+    /// Markdown extensions to parse, before `extension_overrides` are applied.
+    pub extension_profile: ExtensionProfile,
+    /// Extensions turned on or off regardless of the profile.
+    pub extension_overrides: BTreeMap<MarkdownExtension, bool>,
+    /// Absolute URL that relative links and images resolve against instead of the document's
+    /// folder; set for documents fetched from the web, which must not reference local files.
+    pub base_url: Option<String>,
+}
+
+impl RenderPreferences {
+    pub fn extension_enabled(&self, extension: MarkdownExtension) -> bool {
+        self.extension_overrides
+            .get(&extension)
+            .copied()
+            .unwrap_or_else(|| self.extension_profile.enables(extension))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{RenderPreferences, WordCountRules};
+    use super::{
+        HeadingNumbering, HtmlAllowlist, ImageEmbedding, RenderPreferences, WordCountRules,
+    };
+    use crate::extensions::{ExtensionProfile, MarkdownExtension};
 
     #[test]
     fn word_count_rules_default_matches_reader_expectations() {
@@ -55,5 +279,56 @@ mod tests {
         assert!(preferences.word_count_rules.include_links);
         assert!(!preferences.word_count_rules.include_code);
         assert!(!preferences.word_count_rules.include_front_matter);
+        assert!(preferences.text_replacements.is_empty());
+        assert!(!preferences.allow_raw_html);
+        assert_eq!(preferences.image_embedding, ImageEmbedding::AssetUrl);
+        assert_eq!(preferences.csv_row_limit, None);
+        assert_eq!(preferences.number_headings, None);
+        assert_eq!(preferences.words_per_minute, None);
+        assert_eq!(preferences.han_characters_per_minute, None);
+        assert_eq!(preferences.kana_characters_per_minute, None);
+        assert!(!preferences.block_external_links);
+        assert_eq!(preferences.extension_profile, ExtensionProfile::Default);
+        assert!(preferences.extension_overrides.is_empty());
+        assert_eq!(HeadingNumbering::default().start_level, 1);
+    }
+
+    #[test]
+    fn extension_overrides_take_precedence_over_the_profile() {
+        let preferences = RenderPreferences {
+            extension_profile: ExtensionProfile::Github,
+            extension_overrides: [
+                (MarkdownExtension::Table, false),
+                (MarkdownExtension::Highlight, true),
+            ]
+            .into_iter()
+            .collect(),
+            ..RenderPreferences::default()
+        };
+
+        assert!(!preferences.extension_enabled(MarkdownExtension::Table));
+        assert!(preferences.extension_enabled(MarkdownExtension::Highlight));
+        assert!(preferences.extension_enabled(MarkdownExtension::Tasklist));
+        assert!(!preferences.extension_enabled(MarkdownExtension::Spoiler));
+    }
+
+    #[test]
+    fn html_allowlist_default_covers_common_readme_markup_without_scripts() {
+        let allowlist = HtmlAllowlist::default();
+        for tag in ["details", "summary", "kbd", "sub", "img"] {
+            assert!(allowlist.tags.iter().any(|candidate| candidate == tag));
+        }
+        assert!(allowlist
+            .attributes
+            .iter()
+            .any(|attribute| attribute == "align"));
+        assert!(!allowlist
+            .tags
+            .iter()
+            .any(|tag| tag == "script" || tag == "style"));
+        assert!(!allowlist
+            .url_schemes
+            .iter()
+            .any(|scheme| scheme == "javascript"));
     }
 }
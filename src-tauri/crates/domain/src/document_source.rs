@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+/// Where a document's markdown came from, which decides what the viewer can do with it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DocumentOrigin {
+    /// A file on disk: watched for changes and editable in place.
+    #[default]
+    File,
+    /// Markdown piped into the viewer's standard input, read once and held in memory.
+    Stdin,
+    /// Markdown fetched over HTTP(S); its path is the URL, which relative references resolve
+    /// against.
+    Remote,
+    /// Markdown decompressed from a zip archive entry or a gzip file, read-only.
+    Archive,
+}
+
+impl DocumentOrigin {
+    /// Whether the document lives in a file that can be watched and written back.
+    pub fn is_file(self) -> bool {
+        self == Self::File
+    }
+}
+
+/// Markdown read from a document source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceDocument {
+    /// Identifies the document when it is reloaded; relative links and images resolve against
+    /// its parent folder. Documents that are not files get a synthetic path.
+    pub path: PathBuf,
+    pub source: String,
+    pub origin: DocumentOrigin,
+}
+
+impl SourceDocument {
+    /// A document read from the file at `path`.
+    pub fn file(path: PathBuf, source: String) -> Self {
+        Self {
+            path,
+            source,
+            origin: DocumentOrigin::File,
+        }
+    }
+
+    /// The URL relative links and images resolve against, for documents that are not local.
+    pub fn base_url(&self) -> Option<String> {
+        match self.origin {
+            DocumentOrigin::Remote => Some(self.path.to_string_lossy().into_owned()),
+            DocumentOrigin::File | DocumentOrigin::Stdin | DocumentOrigin::Archive => None,
+        }
+    }
+}
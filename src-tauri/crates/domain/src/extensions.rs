@@ -0,0 +1,231 @@
+/// A markdown syntax extension beyond CommonMark that the renderer can turn on or off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MarkdownExtension {
+    /// `~~deleted~~`
+    Strikethrough,
+    /// Escapes raw HTML tags GitHub disallows, such as `<script>` and `<iframe>`.
+    Tagfilter,
+    /// Pipe tables.
+    Table,
+    /// Bare `www.` and `https://` URLs and email addresses become links.
+    Autolink,
+    /// `- [ ]` and `- [x]` list items.
+    Tasklist,
+    /// `e = mc^2^`
+    Superscript,
+    /// `[^1]` references with `[^1]: ...` definitions.
+    Footnotes,
+    /// `^[inline footnote]`
+    InlineFootnotes,
+    /// Terms followed by `: definition` lines.
+    DescriptionLists,
+    /// A leading `---` block that is kept out of the rendered document.
+    FrontMatter,
+    /// Block quotes fenced by `>>>` lines.
+    MultilineBlockQuotes,
+    /// `> [!NOTE]` callouts.
+    Alerts,
+    /// `$inline$` and `$$display$$` math.
+    MathDollars,
+    /// ``$`inline`$`` and ` ```math ` blocks.
+    MathCode,
+    /// `:rocket:` emoji shortcodes.
+    Shortcodes,
+    /// `[[page|title]]` wiki links.
+    WikilinksTitleAfterPipe,
+    /// `[[title|page]]` wiki links.
+    WikilinksTitleBeforePipe,
+    /// `__underlined__` instead of strong emphasis.
+    Underline,
+    /// `H~2~O`
+    Subscript,
+    /// `||hidden until clicked||`
+    Spoiler,
+    /// `> greentext` lines that are not block quotes.
+    Greentext,
+    /// Emphasis next to CJK punctuation without surrounding spaces.
+    CjkFriendlyEmphasis,
+    /// `-# small print` lines.
+    Subtext,
+    /// `==marked==`
+    Highlight,
+}
+
+impl MarkdownExtension {
+    pub const ALL: [Self; 24] = [
+        Self::Strikethrough,
+        Self::Tagfilter,
+        Self::Table,
+        Self::Autolink,
+        Self::Tasklist,
+        Self::Superscript,
+        Self::Footnotes,
+        Self::InlineFootnotes,
+        Self::DescriptionLists,
+        Self::FrontMatter,
+        Self::MultilineBlockQuotes,
+        Self::Alerts,
+        Self::MathDollars,
+        Self::MathCode,
+        Self::Shortcodes,
+        Self::WikilinksTitleAfterPipe,
+        Self::WikilinksTitleBeforePipe,
+        Self::Underline,
+        Self::Subscript,
+        Self::Spoiler,
+        Self::Greentext,
+        Self::CjkFriendlyEmphasis,
+        Self::Subtext,
+        Self::Highlight,
+    ];
+
+    /// The snake_case name used in settings files and render preferences.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Strikethrough => "strikethrough",
+            Self::Tagfilter => "tagfilter",
+            Self::Table => "table",
+            Self::Autolink => "autolink",
+            Self::Tasklist => "tasklist",
+            Self::Superscript => "superscript",
+            Self::Footnotes => "footnotes",
+            Self::InlineFootnotes => "inline_footnotes",
+            Self::DescriptionLists => "description_lists",
+            Self::FrontMatter => "front_matter",
+            Self::MultilineBlockQuotes => "multiline_block_quotes",
+            Self::Alerts => "alerts",
+            Self::MathDollars => "math_dollars",
+            Self::MathCode => "math_code",
+            Self::Shortcodes => "shortcodes",
+            Self::WikilinksTitleAfterPipe => "wikilinks_title_after_pipe",
+            Self::WikilinksTitleBeforePipe => "wikilinks_title_before_pipe",
+            Self::Underline => "underline",
+            Self::Subscript => "subscript",
+            Self::Spoiler => "spoiler",
+            Self::Greentext => "greentext",
+            Self::CjkFriendlyEmphasis => "cjk_friendly_emphasis",
+            Self::Subtext => "subtext",
+            Self::Highlight => "highlight",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|extension| extension.name() == name)
+    }
+}
+
+/// A named set of enabled [`MarkdownExtension`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExtensionProfile {
+    /// The viewer's long-standing set: GitHub Flavored Markdown plus footnotes, math, alerts and
+    /// the inline extensions (`^sup^`, `~sub~`, `__underline__`, `||spoiler||`).
+    #[default]
+    Default,
+    /// What GitHub renders: tables, task lists, strikethrough, autolinks, footnotes, alerts, math,
+    /// emoji shortcodes and front matter.
+    Github,
+    /// Plain CommonMark with no extensions.
+    CommonMark,
+    /// Every extension, with wiki links written `[[page|title]]`.
+    Full,
+}
+
+impl ExtensionProfile {
+    pub const ALL: [Self; 4] = [Self::Default, Self::Github, Self::CommonMark, Self::Full];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Github => "github",
+            Self::CommonMark => "commonmark",
+            Self::Full => "full",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.name() == name)
+    }
+
+    pub fn enables(self, extension: MarkdownExtension) -> bool {
+        use MarkdownExtension::*;
+
+        match self {
+            Self::Default => matches!(
+                extension,
+                Strikethrough
+                    | Tagfilter
+                    | Table
+                    | Autolink
+                    | Tasklist
+                    | Superscript
+                    | Footnotes
+                    | InlineFootnotes
+                    | DescriptionLists
+                    | FrontMatter
+                    | MultilineBlockQuotes
+                    | Alerts
+                    | MathDollars
+                    | MathCode
+                    | Shortcodes
+                    | Underline
+                    | Subscript
+                    | Spoiler
+            ),
+            Self::Github => matches!(
+                extension,
+                Strikethrough
+                    | Tagfilter
+                    | Table
+                    | Autolink
+                    | Tasklist
+                    | Footnotes
+                    | FrontMatter
+                    | Alerts
+                    | MathDollars
+                    | MathCode
+                    | Shortcodes
+            ),
+            Self::CommonMark => false,
+            Self::Full => extension != WikilinksTitleBeforePipe,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtensionProfile, MarkdownExtension};
+
+    #[test]
+    fn names_round_trip() {
+        for extension in MarkdownExtension::ALL {
+            assert_eq!(
+                MarkdownExtension::from_name(extension.name()),
+                Some(extension)
+            );
+        }
+        for profile in ExtensionProfile::ALL {
+            assert_eq!(ExtensionProfile::from_name(profile.name()), Some(profile));
+        }
+        assert_eq!(MarkdownExtension::from_name("Spoiler"), None);
+        assert_eq!(ExtensionProfile::from_name("gfm"), None);
+    }
+
+    #[test]
+    fn github_profile_leaves_out_the_inline_extensions_that_misread_shell_snippets() {
+        for extension in [
+            MarkdownExtension::Spoiler,
+            MarkdownExtension::Subscript,
+            MarkdownExtension::Underline,
+            MarkdownExtension::Superscript,
+        ] {
+            assert!(ExtensionProfile::Default.enables(extension));
+            assert!(!ExtensionProfile::Github.enables(extension));
+        }
+        assert!(ExtensionProfile::Github.enables(MarkdownExtension::Table));
+        assert!(MarkdownExtension::ALL
+            .into_iter()
+            .all(|extension| !ExtensionProfile::CommonMark.enables(extension)));
+    }
+}
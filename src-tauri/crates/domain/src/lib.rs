@@ -1 +1,9 @@
+pub mod archive;
+pub mod diff;
 pub mod document;
+pub mod document_source;
+pub mod extensions;
+pub mod lint;
+pub mod markdown_source;
+pub mod revision;
+pub mod spelling;
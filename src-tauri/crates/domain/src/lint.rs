@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+/// One rule violation, located in the document source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    /// markdownlint rule ID such as `MD009`.
+    pub rule_id: String,
+    /// markdownlint rule alias such as `no-trailing-spaces`.
+    pub rule_name: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// 1-based source line.
+    pub line: usize,
+    /// 1-based column in characters, when the rule points at a position within the line.
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintReport {
+    /// Diagnostics ordered by line, then column, then rule ID.
+    pub diagnostics: Vec<LintDiagnostic>,
+    /// The rule configuration file that applied, if one was found.
+    pub config_path: Option<PathBuf>,
+}
@@ -0,0 +1,151 @@
+/// Fingerprint of a document's source, used to detect that a file changed between reading it and
+/// writing an edit back. FNV-1a over the UTF-8 bytes, as 16 hex digits; stable across runs and
+/// platforms, but not a cryptographic hash.
+pub fn content_hash(source: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = source.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/// Flips the task list checkbox that starts on 1-based `line` of `source`.
+///
+/// Returns the edited source and whether the task is now checked, or `None` when the line does
+/// not start a task list item. Everything but the character between the brackets is preserved,
+/// including line endings.
+pub fn toggle_task_marker(source: &str, line: usize) -> Option<(String, bool)> {
+    let mut edited = String::with_capacity(source.len());
+    let mut checked = None;
+
+    for (index, text) in source.split_inclusive('\n').enumerate() {
+        if index + 1 != line {
+            edited.push_str(text);
+            continue;
+        }
+        let marker = task_marker_offset(text)?;
+        let now_checked = text.as_bytes()[marker] == b' ';
+        edited.push_str(&text[..marker]);
+        edited.push(if now_checked { 'x' } else { ' ' });
+        edited.push_str(&text[marker + 1..]);
+        checked = Some(now_checked);
+    }
+
+    checked.map(|checked| (edited, checked))
+}
+
+/// Byte offset of the character inside `[ ]`/`[x]` when `line` starts a task list item, after
+/// any indentation, block quote markers and the bullet or ordered list marker.
+fn task_marker_offset(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut position = 0;
+    let skip_blanks = |mut position: usize| {
+        while matches!(bytes.get(position), Some(b' ' | b'\t')) {
+            position += 1;
+        }
+        position
+    };
+
+    position = skip_blanks(position);
+    while bytes.get(position) == Some(&b'>') {
+        position = skip_blanks(position + 1);
+    }
+
+    match bytes.get(position)? {
+        b'-' | b'*' | b'+' => position += 1,
+        b'0'..=b'9' => {
+            let digits = bytes[position..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+            if digits > 9 || !matches!(bytes.get(position + digits), Some(b'.' | b')')) {
+                return None;
+            }
+            position += digits + 1;
+        }
+        _ => return None,
+    }
+
+    let after_marker = skip_blanks(position);
+    if after_marker == position {
+        return None;
+    }
+    match bytes.get(after_marker..after_marker + 3)? {
+        [b'[', b' ' | b'x' | b'X', b']'] => {}
+        _ => return None,
+    }
+    match bytes.get(after_marker + 3) {
+        None | Some(b' ' | b'\t' | b'\r' | b'\n') => Some(after_marker + 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_hash, toggle_task_marker};
+
+    #[test]
+    fn content_hash_is_stable_and_sensitive_to_edits() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_eq!(content_hash("- [ ] a"), content_hash("- [ ] a"));
+        assert_ne!(content_hash("- [ ] a"), content_hash("- [x] a"));
+    }
+
+    #[test]
+    fn toggles_only_the_requested_line() {
+        let source = "# Plan\r\n\r\n- [ ] write\r\n- [x] ship\r\n";
+
+        assert_eq!(
+            toggle_task_marker(source, 3),
+            Some((
+                "# Plan\r\n\r\n- [x] write\r\n- [x] ship\r\n".to_string(),
+                true
+            ))
+        );
+        assert_eq!(
+            toggle_task_marker(source, 4),
+            Some((
+                "# Plan\r\n\r\n- [ ] write\r\n- [ ] ship\r\n".to_string(),
+                false
+            ))
+        );
+    }
+
+    #[test]
+    fn recognizes_nested_ordered_and_quoted_items() {
+        assert_eq!(
+            toggle_task_marker("    * [X] nested", 1).map(|(_, checked)| checked),
+            Some(false)
+        );
+        assert_eq!(
+            toggle_task_marker("12) [ ]\tordered", 1).map(|(edited, _)| edited),
+            Some("12) [x]\tordered".to_string())
+        );
+        assert_eq!(
+            toggle_task_marker("> - [ ] quoted", 1).map(|(edited, _)| edited),
+            Some("> - [x] quoted".to_string())
+        );
+        assert_eq!(
+            toggle_task_marker("- [ ]", 1).map(|(edited, _)| edited),
+            Some("- [x]".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_lines_that_are_not_task_items() {
+        for line in [
+            "[ ] no list marker",
+            "-[ ] no space after marker",
+            "- [ ]no space after box",
+            "- [y] unknown state",
+            "- plain item",
+            "1234567890. [ ] too many digits",
+        ] {
+            assert_eq!(toggle_task_marker(line, 1), None, "{line}");
+        }
+        assert_eq!(toggle_task_marker("- [ ] a\n", 2), None);
+        assert_eq!(toggle_task_marker("- [ ] a", 0), None);
+    }
+}
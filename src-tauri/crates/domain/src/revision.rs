@@ -0,0 +1,14 @@
+/// A commit that changed a document, as listed in the document's history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRevision {
+    /// Full hexadecimal commit ID, usable as a revision.
+    pub commit_id: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Author date in seconds since the Unix epoch.
+    pub authored_at: i64,
+    /// First line of the commit message.
+    pub summary: String,
+    /// Complete commit message, without trailing whitespace.
+    pub message: String,
+}
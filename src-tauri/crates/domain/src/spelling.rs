@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+/// A prose word the dictionary does not know, located in the document source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misspelling {
+    pub word: String,
+    /// 1-based source line.
+    pub line: usize,
+    /// 1-based character column of the word's first character.
+    pub start_column: usize,
+    /// 1-based character column just past the word's last character.
+    pub end_column: usize,
+    /// Dictionary suggestions, best first; empty when none were found.
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpellCheckReport {
+    /// Misspellings in source order.
+    pub misspellings: Vec<Misspelling>,
+    /// The workspace word list that was applied, if one was found.
+    pub word_list_path: Option<PathBuf>,
+}
@@ -0,0 +1,414 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use flate2::read::MultiGzDecoder;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::{MarkdownArchiveBrowser, MarkdownFileRepository};
+use markdown_viewer_domain::archive::ArchiveEntry;
+use markdown_viewer_domain::document_source::{DocumentOrigin, SourceDocument};
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use crate::document_sources::DocumentSource;
+use crate::file_repository::{is_markdown_file, resolve_path_input};
+
+/// Separates a zip archive from the entry inside it, as in `docs.zip!/guide/intro.md`.
+pub const ARCHIVE_ENTRY_SEPARATOR: &str = "!/";
+/// Decompressed documents larger than this are refused, which also stops decompression bombs.
+const MAX_DOCUMENT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Reads markdown stored in zip archives (`docs.zip!/guide/intro.md`) and gzip-compressed
+/// markdown files (`notes.md.gz`).
+///
+/// Documents inside an archive are addressed by the archive's path, the separator and the entry
+/// name, so relative links between entries resolve like links between files in a folder.
+/// Archived documents are read-only and never watched.
+pub struct ArchiveDocumentSource;
+
+impl ArchiveDocumentSource {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ArchiveDocumentSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocumentSource for ArchiveDocumentSource {
+    fn handles(&self, path_input: &str) -> bool {
+        split_archive_input(path_input).is_some()
+            || is_compressed_markdown_file(Path::new(path_input))
+    }
+}
+
+impl MarkdownFileRepository for ArchiveDocumentSource {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        let (path, source) = match split_archive_input(path_input) {
+            Some((archive_input, entry_name)) => read_zip_entry(archive_input, entry_name)?,
+            None => read_gzip_file(path_input)?,
+        };
+        Ok(SourceDocument {
+            path,
+            source,
+            origin: DocumentOrigin::Archive,
+        })
+    }
+}
+
+impl MarkdownArchiveBrowser for ArchiveDocumentSource {
+    fn list_markdown_entries(
+        &self,
+        path_input: &str,
+    ) -> Result<(PathBuf, Vec<ArchiveEntry>), MarkdownViewerError> {
+        let archive_path = resolve_path_input(path_input)?;
+        if !is_zip_file(&archive_path) {
+            return Err(MarkdownViewerError::NotAnArchive(archive_path));
+        }
+        let mut archive = open_zip(&archive_path)?;
+
+        let mut entries = Vec::new();
+        for index in 0..archive.len() {
+            let entry = archive
+                .by_index_raw(index)
+                .map_err(|error| archive_error(&archive_path, error))?;
+            let Some(name) = normalize_entry_name(entry.name()) else {
+                continue;
+            };
+            if entry.is_file() && is_markdown_file(Path::new(&name)) {
+                entries.push(ArchiveEntry {
+                    path: entry_path(&archive_path, &name),
+                    name,
+                    size: entry.size(),
+                });
+            }
+        }
+        entries.sort_by(|left, right| left.name.cmp(&right.name));
+
+        Ok((archive_path, entries))
+    }
+}
+
+/// Whether `path` names gzip-compressed markdown, such as `notes.md.gz`.
+pub fn is_compressed_markdown_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"))
+        && is_markdown_file(&path.with_extension(""))
+}
+
+fn is_zip_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+/// Splits `docs.zip!/guide/intro.md` into the archive and the entry name. Backslashes are
+/// accepted after the `!` for paths built on Windows.
+fn split_archive_input(path_input: &str) -> Option<(&str, &str)> {
+    path_input.match_indices('!').find_map(|(index, _)| {
+        let (archive, rest) = path_input.split_at(index);
+        let entry_name = rest[1..].strip_prefix(['/', '\\'])?;
+        is_zip_file(Path::new(archive)).then_some((archive, entry_name))
+    })
+}
+
+/// The zip archive and the normalized entry name of a document inside one, such as
+/// `docs.zip!/guide/intro.md`.
+pub(crate) fn split_archive_path(path: &Path) -> Option<(PathBuf, String)> {
+    let (archive, entry_name) = split_archive_input(path.to_str()?)?;
+    Some((PathBuf::from(archive), normalize_entry_name(entry_name)?))
+}
+
+/// The entry name with `.` and `..` segments applied and `/` separators, or `None` when it
+/// leaves the archive root or names nothing.
+pub(crate) fn normalize_entry_name(name: &str) -> Option<String> {
+    let mut segments = Vec::new();
+    for segment in name.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    (!segments.is_empty()).then(|| segments.join("/"))
+}
+
+fn entry_path(archive_path: &Path, entry_name: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}{ARCHIVE_ENTRY_SEPARATOR}{entry_name}",
+        archive_path.to_string_lossy()
+    ))
+}
+
+fn read_zip_entry(
+    archive_input: &str,
+    entry_name: &str,
+) -> Result<(PathBuf, String), MarkdownViewerError> {
+    let archive_path = resolve_path_input(archive_input)?;
+    let Some(name) = normalize_entry_name(entry_name) else {
+        return Err(MarkdownViewerError::FileNotFound(entry_path(
+            &archive_path,
+            entry_name,
+        )));
+    };
+    let path = entry_path(&archive_path, &name);
+    if !is_markdown_file(Path::new(&name)) {
+        return Err(MarkdownViewerError::NotMarkdown(path));
+    }
+
+    let mut archive = open_zip(&archive_path)?;
+    let entry = match archive.by_name(&name) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Err(MarkdownViewerError::FileNotFound(path)),
+        Err(error) => return Err(archive_error(&archive_path, error)),
+    };
+    let source = read_limited(entry).map_err(|reason| MarkdownViewerError::Archive {
+        path: archive_path.clone(),
+        reason,
+    })?;
+
+    Ok((path, source))
+}
+
+/// The contents of the file entry `name`, or `None` when the archive has no such entry.
+pub(crate) fn read_archive_file(
+    archive_path: &Path,
+    name: &str,
+) -> Result<Option<Vec<u8>>, MarkdownViewerError> {
+    let mut archive = open_zip(archive_path)?;
+    let entry = match archive.by_name(name) {
+        Ok(entry) if entry.is_file() => entry,
+        Ok(_) | Err(ZipError::FileNotFound) => return Ok(None),
+        Err(error) => return Err(archive_error(archive_path, error)),
+    };
+    read_limited_bytes(entry)
+        .map(Some)
+        .map_err(|reason| MarkdownViewerError::Archive {
+            path: archive_path.to_path_buf(),
+            reason,
+        })
+}
+
+fn read_gzip_file(path_input: &str) -> Result<(PathBuf, String), MarkdownViewerError> {
+    let path = resolve_path_input(path_input)?;
+    if !is_compressed_markdown_file(&path) {
+        return Err(MarkdownViewerError::NotMarkdown(path));
+    }
+    let file = File::open(&path).map_err(|error| MarkdownViewerError::ReadFile {
+        path: path.clone(),
+        reason: error.to_string(),
+    })?;
+    let source =
+        read_limited(MultiGzDecoder::new(file)).map_err(|reason| MarkdownViewerError::Archive {
+            path: path.clone(),
+            reason,
+        })?;
+
+    Ok((path, source))
+}
+
+fn open_zip(archive_path: &Path) -> Result<ZipArchive<File>, MarkdownViewerError> {
+    let file = File::open(archive_path).map_err(|error| MarkdownViewerError::ReadFile {
+        path: archive_path.to_path_buf(),
+        reason: error.to_string(),
+    })?;
+    ZipArchive::new(file).map_err(|error| archive_error(archive_path, error))
+}
+
+fn archive_error(archive_path: &Path, error: ZipError) -> MarkdownViewerError {
+    MarkdownViewerError::Archive {
+        path: archive_path.to_path_buf(),
+        reason: error.to_string(),
+    }
+}
+
+fn read_limited(reader: impl Read) -> Result<String, String> {
+    read_limited_bytes(reader).map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn read_limited_bytes(reader: impl Read) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_DOCUMENT_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|error| error.to_string())?;
+    if bytes.len() as u64 > MAX_DOCUMENT_BYTES {
+        return Err(format!(
+            "document is larger than {} MiB",
+            MAX_DOCUMENT_BYTES / 1024 / 1024
+        ));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::{MarkdownArchiveBrowser, MarkdownFileRepository};
+    use markdown_viewer_domain::document_source::DocumentOrigin;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    use super::{normalize_entry_name, split_archive_input, ArchiveDocumentSource};
+    use crate::document_sources::DocumentSource;
+
+    fn temp_directory() -> PathBuf {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after unix epoch")
+            .as_nanos();
+        let directory = std::env::temp_dir().join(format!("mdv-archive-{suffix}"));
+        fs::create_dir_all(&directory).expect("temp directory should be creatable");
+        directory
+            .canonicalize()
+            .expect("temp directory should canonicalize")
+    }
+
+    fn write_docs_zip(directory: &Path) -> PathBuf {
+        let path = directory.join("docs.zip");
+        let mut writer = ZipWriter::new(File::create(&path).expect("archive should be creatable"));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        writer
+            .add_directory("guide/", options)
+            .expect("directory entry should be writable");
+        for (name, contents) in [
+            ("guide/intro.md", "# Intro\n\nSee [setup](setup.md).\n"),
+            ("guide/setup.md", "# Setup\n"),
+            ("README.markdown", "# Docs\n"),
+            ("logo.png", "not markdown"),
+        ] {
+            writer
+                .start_file(name, options)
+                .expect("entry should be writable");
+            writer
+                .write_all(contents.as_bytes())
+                .expect("entry contents should be writable");
+        }
+        writer.finish().expect("archive should be finished");
+        path
+    }
+
+    #[test]
+    fn reads_zip_entries_as_read_only_documents() {
+        let directory = temp_directory();
+        let archive = write_docs_zip(&directory);
+        let source = ArchiveDocumentSource::new();
+        let intro_input = format!("{}!/guide/intro.md", archive.display());
+
+        let intro = source.read(&intro_input).expect("entry should be readable");
+        // Relative links from the frontend arrive already joined to the entry's folder.
+        let setup = source
+            .read(&format!("{}!/guide/./../guide/setup.md", archive.display()))
+            .expect("sibling entry should be readable");
+
+        assert_eq!(intro.path, PathBuf::from(&intro_input));
+        assert_eq!(intro.source, "# Intro\n\nSee [setup](setup.md).\n");
+        assert_eq!(intro.origin, DocumentOrigin::Archive);
+        assert_eq!(
+            setup.path,
+            PathBuf::from(format!("{}!/guide/setup.md", archive.display()))
+        );
+        assert_eq!(setup.source, "# Setup\n");
+        assert!(matches!(
+            source.read(&format!("{}!/guide/missing.md", archive.display())),
+            Err(MarkdownViewerError::FileNotFound(_))
+        ));
+        assert!(matches!(
+            source.read(&format!("{}!/logo.png", archive.display())),
+            Err(MarkdownViewerError::NotMarkdown(_))
+        ));
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn lists_markdown_entries_by_name() {
+        let directory = temp_directory();
+        let archive = write_docs_zip(&directory);
+
+        let (path, entries) = ArchiveDocumentSource::new()
+            .list_markdown_entries(&archive.to_string_lossy())
+            .expect("archive should be listed");
+
+        assert_eq!(path, archive);
+        let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["README.markdown", "guide/intro.md", "guide/setup.md"]
+        );
+        assert_eq!(
+            entries[1].path,
+            PathBuf::from(format!("{}!/guide/intro.md", archive.display()))
+        );
+        assert_eq!(entries[2].size, "# Setup\n".len() as u64);
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn reads_gzip_compressed_markdown_files() {
+        let directory = temp_directory();
+        let path = directory.join("notes.md.gz");
+        let mut encoder = GzEncoder::new(
+            File::create(&path).expect("file should be creatable"),
+            Compression::default(),
+        );
+        encoder
+            .write_all(b"# Notes\n")
+            .expect("contents should be writable");
+        encoder.finish().expect("gzip stream should be finished");
+        let source = ArchiveDocumentSource::new();
+
+        assert!(source.handles(&path.to_string_lossy()));
+        let document = source
+            .read(&path.to_string_lossy())
+            .expect("compressed file should be readable");
+
+        assert_eq!(document.path, path);
+        assert_eq!(document.source, "# Notes\n");
+        assert_eq!(document.origin, DocumentOrigin::Archive);
+
+        fs::write(&path, "not gzip").expect("file should be writable");
+        assert!(matches!(
+            source.read(&path.to_string_lossy()),
+            Err(MarkdownViewerError::Archive { .. })
+        ));
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn rejects_non_archives_and_paths_leaving_the_archive() {
+        let source = ArchiveDocumentSource::new();
+
+        assert!(matches!(
+            source.list_markdown_entries(env!("CARGO_MANIFEST_PATH")),
+            Err(MarkdownViewerError::NotAnArchive(_))
+        ));
+        assert!(!source.handles("/docs/guide.md"));
+        assert!(!source.handles("/docs/data.csv.gz"));
+        assert!(!source.handles("/docs/wow!/guide.md"));
+        assert_eq!(
+            split_archive_input(r"C:\exports\Docs.ZIP!\guide\intro.md"),
+            Some((r"C:\exports\Docs.ZIP", r"guide\intro.md"))
+        );
+        assert_eq!(
+            normalize_entry_name(r"guide\.\intro.md").as_deref(),
+            Some("guide/intro.md")
+        );
+        assert_eq!(normalize_entry_name("../secrets.md"), None);
+    }
+}
@@ -1,21 +1,54 @@
-use std::cmp::max;
+use std::path::Path;
 
 use comrak::nodes::{AstNode, NodeValue};
-use comrak::{markdown_to_html, parse_document, Anchorizer, Arena, Options};
+use comrak::{format_html, parse_document, Anchorizer, Arena, Options};
 use markdown_viewer_application::error::MarkdownViewerError;
 use markdown_viewer_application::ports::MarkdownRenderer;
 use markdown_viewer_domain::document::{
-    RenderPreferences, RenderedMarkdown, TocEntry, WordCountRules,
+    HeadingNumbering, RenderPreferences, RenderedMarkdown, TocEntry,
 };
+use markdown_viewer_domain::extensions::MarkdownExtension;
+
+use crate::csv_tables::CsvTableTransform;
+use crate::diagrams::DiagramTransform;
+use crate::document_statistics::{count_elements, count_words, readability, reading_time_minutes};
+use crate::external_links::ExternalLinkTransform;
+use crate::html_sanitizer::HtmlSanitizer;
+use crate::image_resolution::ImageResolutionTransform;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+use crate::mdx::render_source;
+use crate::remote_links::RemoteLinkTransform;
+use crate::task_list::extract_tasks;
+use crate::text_replacements::TextReplacementTransform;
+use crate::toc_placeholder::expand_toc_placeholders;
 
 const HEADING_ID_PREFIX: &str = "mdv-";
-const WORDS_PER_MINUTE: usize = 225;
 
-pub struct ComrakMarkdownRenderer;
+pub struct ComrakMarkdownRenderer {
+    transforms: Vec<Box<dyn MarkdownTransform>>,
+}
 
 impl ComrakMarkdownRenderer {
     pub fn new() -> Self {
-        Self
+        Self::with_transforms(Self::default_transforms())
+    }
+
+    /// Builds a renderer that runs exactly `transforms`, in order, on every document.
+    pub fn with_transforms(transforms: Vec<Box<dyn MarkdownTransform>>) -> Self {
+        Self { transforms }
+    }
+
+    /// The transforms used by [`ComrakMarkdownRenderer::new`], for callers that want to extend
+    /// rather than replace the built-in pipeline.
+    pub fn default_transforms() -> Vec<Box<dyn MarkdownTransform>> {
+        vec![
+            Box::new(TextReplacementTransform::new()),
+            Box::new(RemoteLinkTransform::new()),
+            Box::new(ExternalLinkTransform::new()),
+            Box::new(ImageResolutionTransform::new()),
+            Box::new(DiagramTransform::new()),
+            Box::new(CsvTableTransform::new()),
+        ]
     }
 }
 
@@ -29,59 +62,153 @@ impl MarkdownRenderer for ComrakMarkdownRenderer {
     fn render(
         &self,
         markdown: &str,
+        document_path: &Path,
         preferences: RenderPreferences,
     ) -> Result<RenderedMarkdown, MarkdownViewerError> {
-        let options = markdown_options(preferences);
-        let html = markdown_to_html(markdown, &options);
+        let options = markdown_options(&preferences);
 
+        let markdown = render_source(markdown, document_path);
         let arena = Arena::new();
-        let root = parse_document(&arena, markdown, &options);
-        let toc = build_toc(root);
-        let word_count = count_words(root, preferences.word_count_rules);
-        let reading_time_minutes = max(1, word_count.div_ceil(WORDS_PER_MINUTE) as u16);
+        let root = parse_document(&arena, &markdown, &options);
+        let mut statistics = count_elements(root);
+        let context = TransformContext::new(&preferences, document_path);
+        for transform in &self.transforms {
+            transform.transform(&arena, root, &context)?;
+        }
+        let (warnings, local_images) = context.into_findings();
+        let heading_numbers = preferences
+            .number_headings
+            .map(|numbering| number_headings(&arena, root, numbering))
+            .unwrap_or_default();
+        let toc = build_toc(root, &heading_numbers);
+        expand_toc_placeholders(root, &toc);
+
+        let mut html = String::new();
+        format_html(root, &options, &mut html).expect("writing HTML into a String cannot fail");
+        if preferences.allow_raw_html {
+            html = HtmlSanitizer::with_allowlist(&preferences.html_allowlist).sanitize(&html);
+        }
+
+        let word_counts = count_words(root, &toc, preferences.word_count_rules);
+        let reading_time_minutes = reading_time_minutes(word_counts.total, &preferences);
+        statistics.section_word_counts = word_counts.sections;
+        statistics.readability = readability(root);
+        let tasks = extract_tasks(root, &toc);
 
         Ok(RenderedMarkdown {
             html,
             toc,
-            word_count,
+            word_count: word_counts.total.total(),
             reading_time_minutes,
+            statistics,
+            tasks,
+            warnings,
+            local_images,
         })
     }
 }
 
-fn markdown_options(preferences: RenderPreferences) -> Options<'static> {
+pub(crate) fn markdown_options(preferences: &RenderPreferences) -> Options<'static> {
     let mut options = Options::default();
+    let enabled = |extension| preferences.extension_enabled(extension);
 
-    options.extension.strikethrough = true;
-    options.extension.tagfilter = true;
-    options.extension.table = true;
-    options.extension.autolink = true;
-    options.extension.tasklist = true;
-    options.extension.superscript = true;
+    // Heading IDs are not syntax: the table of contents and in-document links rely on them.
     options.extension.header_ids = Some(HEADING_ID_PREFIX.to_string());
-    options.extension.footnotes = true;
-    options.extension.inline_footnotes = true;
-    options.extension.description_lists = true;
-    options.extension.front_matter_delimiter = Some("---".to_string());
-    options.extension.multiline_block_quotes = true;
-    options.extension.alerts = true;
-    options.extension.math_dollars = true;
-    options.extension.math_code = true;
-    options.extension.underline = true;
-    options.extension.subscript = true;
-    options.extension.spoiler = true;
+    options.extension.strikethrough = enabled(MarkdownExtension::Strikethrough);
+    options.extension.tagfilter = enabled(MarkdownExtension::Tagfilter);
+    options.extension.table = enabled(MarkdownExtension::Table);
+    options.extension.autolink = enabled(MarkdownExtension::Autolink);
+    options.extension.tasklist = enabled(MarkdownExtension::Tasklist);
+    options.extension.superscript = enabled(MarkdownExtension::Superscript);
+    options.extension.footnotes = enabled(MarkdownExtension::Footnotes);
+    options.extension.inline_footnotes = enabled(MarkdownExtension::InlineFootnotes);
+    options.extension.description_lists = enabled(MarkdownExtension::DescriptionLists);
+    options.extension.front_matter_delimiter =
+        enabled(MarkdownExtension::FrontMatter).then(|| "---".to_string());
+    options.extension.multiline_block_quotes = enabled(MarkdownExtension::MultilineBlockQuotes);
+    options.extension.alerts = enabled(MarkdownExtension::Alerts);
+    options.extension.math_dollars = enabled(MarkdownExtension::MathDollars);
+    options.extension.math_code = enabled(MarkdownExtension::MathCode);
+    options.extension.shortcodes = enabled(MarkdownExtension::Shortcodes);
+    options.extension.wikilinks_title_after_pipe =
+        enabled(MarkdownExtension::WikilinksTitleAfterPipe);
+    options.extension.wikilinks_title_before_pipe =
+        enabled(MarkdownExtension::WikilinksTitleBeforePipe);
+    options.extension.underline = enabled(MarkdownExtension::Underline);
+    options.extension.subscript = enabled(MarkdownExtension::Subscript);
+    options.extension.spoiler = enabled(MarkdownExtension::Spoiler);
+    options.extension.greentext = enabled(MarkdownExtension::Greentext);
+    options.extension.cjk_friendly_emphasis = enabled(MarkdownExtension::CjkFriendlyEmphasis);
+    options.extension.subtext = enabled(MarkdownExtension::Subtext);
+    options.extension.highlight = enabled(MarkdownExtension::Highlight);
 
     // Performance mode keeps syntax support but turns off smart punctuation transforms.
     options.parse.smart = !preferences.performance_mode;
-    options.render.escape = true;
-    options.render.r#unsafe = false;
+    // Raw HTML is only passed through when the reader opted in; the rendered output is then
+    // sanitized against the allowlist before it leaves the renderer.
+    options.render.escape = !preferences.allow_raw_html;
+    options.render.r#unsafe = preferences.allow_raw_html;
 
     options
 }
 
-fn build_toc<'a>(root: &'a AstNode<'a>) -> Vec<TocEntry> {
+/// Prefixes every heading at `numbering.start_level` or deeper with its hierarchical number and
+/// returns the numbers in document order (`None` for headings above the start level).
+///
+/// The number is inserted as raw HTML, which comrak leaves out of the text it derives heading
+/// IDs from, so IDs are the same with and without numbering.
+fn number_headings<'a>(
+    arena: &'a Arena<'a>,
+    root: &'a AstNode<'a>,
+    numbering: HeadingNumbering,
+) -> Vec<Option<String>> {
+    let start_level = numbering.start_level.clamp(1, 6);
+    let mut counters = [0_usize; 6];
+    let mut numbers = Vec::new();
+
+    let headings: Vec<&'a AstNode<'a>> = root
+        .descendants()
+        .filter(|node| matches!(node.data.borrow().value, NodeValue::Heading(..)))
+        .collect();
+    for node in headings {
+        let level = match &node.data.borrow().value {
+            NodeValue::Heading(heading) => heading.level.clamp(1, 6),
+            _ => continue,
+        };
+        if level < start_level {
+            // A heading above the numbered range starts the numbering over.
+            counters = [0; 6];
+            numbers.push(None);
+            continue;
+        }
+
+        let depth = usize::from(level - start_level);
+        for counter in &mut counters[..depth] {
+            // Skipped levels (an H4 directly under an H2) count as their first section.
+            *counter = (*counter).max(1);
+        }
+        counters[depth] += 1;
+        counters[depth + 1..].fill(0);
+
+        let number = counters[..=depth]
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(".");
+        let prefix = arena.alloc(AstNode::from(NodeValue::Raw(format!(
+            "<span class=\"mdv-heading-number\">{number}</span> "
+        ))));
+        node.prepend(prefix);
+        numbers.push(Some(number));
+    }
+
+    numbers
+}
+
+fn build_toc<'a>(root: &'a AstNode<'a>, heading_numbers: &[Option<String>]) -> Vec<TocEntry> {
     let mut anchorizer = Anchorizer::new();
     let mut toc = Vec::new();
+    let mut heading_index = 0;
 
     for node in root.descendants() {
         let level = {
@@ -91,20 +218,32 @@ fn build_toc<'a>(root: &'a AstNode<'a>) -> Vec<TocEntry> {
                 _ => continue,
             }
         };
+        let number = heading_numbers.get(heading_index).cloned().flatten();
+        heading_index += 1;
 
-        let text = heading_text(node);
+        let text = inline_text(node);
         if text.is_empty() {
             continue;
         }
 
         let id = format!("{}{}", HEADING_ID_PREFIX, anchorizer.anchorize(&text));
-        toc.push(TocEntry { level, id, text });
+        let numbered_text = match number {
+            Some(number) => format!("{number} {text}"),
+            None => text.clone(),
+        };
+        toc.push(TocEntry {
+            level,
+            id,
+            text: numbered_text,
+            plain_text: text,
+        });
     }
 
     toc
 }
 
-fn heading_text<'a>(node: &'a AstNode<'a>) -> String {
+/// The readable text inside `node` (text, inline code and math) with whitespace collapsed.
+pub(crate) fn inline_text<'a>(node: &'a AstNode<'a>) -> String {
     let mut text = String::new();
 
     for descendant in node.descendants().skip(1) {
@@ -131,57 +270,74 @@ fn heading_text<'a>(node: &'a AstNode<'a>) -> String {
     text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
-fn count_words<'a>(root: &'a AstNode<'a>, rules: WordCountRules) -> usize {
-    let mut count = 0_usize;
-
-    for node in root.descendants() {
-        let data = node.data.borrow();
-        match &data.value {
-            NodeValue::Text(value) => {
-                if !rules.include_links && has_link_ancestor(node) {
-                    continue;
-                }
-                count += word_len(value);
-            }
-            NodeValue::Code(code) if rules.include_code => {
-                if !rules.include_links && has_link_ancestor(node) {
-                    continue;
-                }
-                count += word_len(&code.literal);
-            }
-            NodeValue::CodeBlock(code_block) if rules.include_code => {
-                count += word_len(&code_block.literal);
-            }
-            NodeValue::Math(math) if rules.include_code => {
-                count += word_len(&math.literal);
-            }
-            NodeValue::FrontMatter(front_matter) if rules.include_front_matter => {
-                count += word_len(front_matter);
-            }
-            _ => {}
-        }
-    }
-
-    count
-}
-
-fn has_link_ancestor<'a>(node: &'a AstNode<'a>) -> bool {
+pub(crate) fn has_link_ancestor<'a>(node: &'a AstNode<'a>) -> bool {
     node.ancestors().skip(1).any(|ancestor| {
         let data = ancestor.data.borrow();
-        matches!(data.value, NodeValue::Link(..) | NodeValue::Image(..))
+        matches!(
+            data.value,
+            NodeValue::Link(..) | NodeValue::Image(..) | NodeValue::WikiLink(..)
+        )
     })
 }
 
-fn word_len(content: &str) -> usize {
-    content.split_whitespace().count()
-}
-
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    use comrak::nodes::{AstNode, NodeValue};
+    use comrak::Arena;
+    use markdown_viewer_application::error::MarkdownViewerError;
     use markdown_viewer_application::ports::MarkdownRenderer;
-    use markdown_viewer_domain::document::{RenderPreferences, WordCountRules};
+    use markdown_viewer_domain::document::{
+        HeadingNumbering, RenderPreferences, TextReplacementRule, WordCountRules,
+    };
+    use markdown_viewer_domain::extensions::{ExtensionProfile, MarkdownExtension};
 
     use crate::comrak_renderer::ComrakMarkdownRenderer;
+    use crate::markdown_transform::{MarkdownTransform, TransformContext};
+
+    struct SuffixLinkUrls(&'static str);
+
+    impl MarkdownTransform for SuffixLinkUrls {
+        fn transform<'a>(
+            &self,
+            _arena: &'a Arena<'a>,
+            root: &'a AstNode<'a>,
+            _context: &TransformContext<'_>,
+        ) -> Result<(), MarkdownViewerError> {
+            for node in root.descendants() {
+                if let NodeValue::Link(link) = &mut node.data.borrow_mut().value {
+                    link.url.push_str(self.0);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    struct UppercaseHeadings;
+
+    impl MarkdownTransform for UppercaseHeadings {
+        fn transform<'a>(
+            &self,
+            _arena: &'a Arena<'a>,
+            root: &'a AstNode<'a>,
+            _context: &TransformContext<'_>,
+        ) -> Result<(), MarkdownViewerError> {
+            for node in root.descendants() {
+                let in_heading = node
+                    .ancestors()
+                    .any(|ancestor| matches!(ancestor.data.borrow().value, NodeValue::Heading(..)));
+                if !in_heading {
+                    continue;
+                }
+                if let NodeValue::Text(text) = &mut node.data.borrow_mut().value {
+                    *text = text.to_uppercase().into();
+                }
+            }
+            Ok(())
+        }
+    }
 
     #[test]
     fn generates_unique_ids_for_duplicate_headings() {
@@ -189,6 +345,7 @@ mod tests {
         let rendered = renderer
             .render(
                 "# Title\n\n## Title\n\n### Title\n",
+                Path::new("notes.md"),
                 RenderPreferences::default(),
             )
             .expect("renderer should work");
@@ -203,7 +360,11 @@ mod tests {
     fn reading_time_is_at_least_one_minute() {
         let renderer = ComrakMarkdownRenderer::new();
         let rendered = renderer
-            .render("small file", RenderPreferences::default())
+            .render(
+                "small file",
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
             .expect("renderer should work");
         assert_eq!(rendered.reading_time_minutes, 1);
     }
@@ -215,6 +376,7 @@ mod tests {
         let rendered = renderer
             .render(
                 markdown,
+                Path::new("notes.md"),
                 RenderPreferences {
                     performance_mode: false,
                     word_count_rules: WordCountRules {
@@ -222,6 +384,7 @@ mod tests {
                         include_code: false,
                         include_front_matter: false,
                     },
+                    ..RenderPreferences::default()
                 },
             )
             .expect("renderer should work");
@@ -235,7 +398,11 @@ mod tests {
         let markdown =
             "1. Parent\n   - [ ] child task\n\n      ```rust\n      let x = 1;\n      ```";
         let rendered = renderer
-            .render(markdown, RenderPreferences::default())
+            .render(
+                markdown,
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
             .expect("renderer should work");
 
         assert!(rendered.html.contains("<ol>"));
@@ -248,11 +415,362 @@ mod tests {
         let renderer = ComrakMarkdownRenderer::new();
         let markdown = "One[^a][^b]\n\n[^a]: A\n[^b]: B";
         let rendered = renderer
-            .render(markdown, RenderPreferences::default())
+            .render(
+                markdown,
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
             .expect("renderer should work");
 
         assert!(rendered.html.contains("footnote-ref"));
         assert!(rendered.html.contains("fn-a"));
         assert!(rendered.html.contains("fn-b"));
     }
+
+    #[test]
+    fn renders_emoji_shortcodes() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "Shipped :rocket:",
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert!(rendered.html.contains("Shipped \u{1F680}"));
+        assert!(!rendered.html.contains(":rocket:"));
+    }
+
+    #[test]
+    fn text_replacements_turn_matches_into_links() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "Fixed in ABC-123 and ABC-7.",
+                Path::new("notes.md"),
+                RenderPreferences {
+                    text_replacements: vec![TextReplacementRule {
+                        pattern: r"\b[A-Z]+-\d+\b".to_string(),
+                        replacement: "$0".to_string(),
+                        link_template: Some("https://tracker.example.com/browse/$0".to_string()),
+                    }],
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        assert!(rendered
+            .html
+            .contains("<a href=\"https://tracker.example.com/browse/ABC-123\">ABC-123</a>"));
+        assert!(rendered
+            .html
+            .contains("<a href=\"https://tracker.example.com/browse/ABC-7\">ABC-7</a>."));
+    }
+
+    #[test]
+    fn text_replacements_drop_links_to_other_url_schemes() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "see ref:javascript:alert(1) and ref:docs/a.md",
+                Path::new("notes.md"),
+                RenderPreferences {
+                    text_replacements: vec![TextReplacementRule {
+                        pattern: r"ref:(\S+)".to_string(),
+                        replacement: "$1".to_string(),
+                        link_template: Some("$1".to_string()),
+                    }],
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        assert!(!rendered.html.contains("href=\"javascript:"));
+        assert!(rendered.html.contains("see javascript:alert(1) and "));
+        assert!(rendered
+            .html
+            .contains("<a href=\"docs/a.md\">docs/a.md</a>"));
+    }
+
+    #[test]
+    fn text_replacements_skip_code_and_existing_links() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "teh `teh` [teh](https://example.com)",
+                Path::new("notes.md"),
+                RenderPreferences {
+                    text_replacements: vec![TextReplacementRule {
+                        pattern: r"\bteh\b".to_string(),
+                        replacement: "the".to_string(),
+                        link_template: None,
+                    }],
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        assert!(rendered.html.starts_with("<p>the <code>teh</code>"));
+        assert!(rendered.html.contains(">teh</a>"));
+    }
+
+    #[test]
+    fn invalid_text_replacement_pattern_is_reported() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let error = renderer
+            .render(
+                "text",
+                Path::new("notes.md"),
+                RenderPreferences {
+                    text_replacements: vec![TextReplacementRule {
+                        pattern: "(unclosed".to_string(),
+                        replacement: String::new(),
+                        link_template: None,
+                    }],
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect_err("invalid pattern should fail");
+
+        match error {
+            MarkdownViewerError::InvalidTextReplacement { pattern, reason } => {
+                assert_eq!(pattern, "(unclosed");
+                assert!(!reason.is_empty());
+            }
+            other => panic!("unexpected error variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn runs_transforms_in_order_before_formatting_html() {
+        let renderer = ComrakMarkdownRenderer::with_transforms(vec![
+            Box::new(SuffixLinkUrls("?a")),
+            Box::new(SuffixLinkUrls("&b")),
+        ]);
+        let rendered = renderer
+            .render(
+                "[docs](https://example.com/docs)",
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert!(rendered
+            .html
+            .contains("<a href=\"https://example.com/docs?a&amp;b\">docs</a>"));
+    }
+
+    #[test]
+    fn toc_reflects_transformed_headings() {
+        let renderer = ComrakMarkdownRenderer::with_transforms(vec![Box::new(UppercaseHeadings)]);
+        let rendered = renderer
+            .render(
+                "# Overview\n\nbody text",
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert_eq!(rendered.toc[0].text, "OVERVIEW");
+        assert_eq!(rendered.toc[0].id, "mdv-overview");
+        assert!(rendered.html.contains("OVERVIEW</h1>"));
+        assert!(rendered.html.contains("body text"));
+    }
+
+    #[test]
+    fn renderer_without_transforms_leaves_text_untouched() {
+        let renderer = ComrakMarkdownRenderer::with_transforms(Vec::new());
+        let rendered = renderer
+            .render(
+                "ABC-1",
+                Path::new("notes.md"),
+                RenderPreferences {
+                    text_replacements: vec![TextReplacementRule {
+                        pattern: "ABC-1".to_string(),
+                        replacement: "XYZ-9".to_string(),
+                        link_template: None,
+                    }],
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        assert_eq!(rendered.html, "<p>ABC-1</p>\n");
+    }
+
+    #[test]
+    fn raw_html_is_escaped_by_default() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "<details><summary>More</summary>hidden</details>\n\nPress <kbd>K</kbd>",
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert!(!rendered.html.contains("<details>"));
+        assert!(rendered.html.contains("&lt;kbd&gt;"));
+    }
+
+    #[test]
+    fn raw_html_is_sanitized_when_allowed() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let markdown = "# Title\n\n\
+<details><summary>More</summary>\n\nhidden\n\n</details>\n\n\
+Press <kbd>K</kbd> <img src=x onerror=\"alert(1)\">\n\n\
+<script>alert(1)</script>\n\n\
+<a href=\"javascript:alert(1)\">bad</a>\n\n\
+- [x] done\n";
+        let rendered = renderer
+            .render(
+                markdown,
+                Path::new("notes.md"),
+                RenderPreferences {
+                    allow_raw_html: true,
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        assert!(rendered.html.contains("<details><summary>More</summary>"));
+        assert!(rendered.html.contains("<kbd>K</kbd>"));
+        assert!(rendered.html.contains("id=\"mdv-title\""));
+        assert!(rendered.html.contains("type=\"checkbox\""));
+        assert!(!rendered.html.contains("onerror"));
+        assert!(!rendered.html.contains("<script"));
+        assert!(!rendered.html.contains("javascript:"));
+        assert_eq!(rendered.toc[0].id, "mdv-title");
+    }
+
+    #[test]
+    fn numbers_headings_hierarchically_in_html_and_toc() {
+        let markdown =
+            "# Spec\n\n## Scope\n\n## Design\n\n### Errors\n\n#### Retries\n\n## Testing\n";
+        let rendered = ComrakMarkdownRenderer::new()
+            .render(
+                markdown,
+                Path::new("notes.md"),
+                RenderPreferences {
+                    number_headings: Some(HeadingNumbering { start_level: 2 }),
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        let texts: Vec<&str> = rendered
+            .toc
+            .iter()
+            .map(|entry| entry.text.as_str())
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "Spec",
+                "1 Scope",
+                "2 Design",
+                "2.1 Errors",
+                "2.1.1 Retries",
+                "3 Testing"
+            ]
+        );
+        assert!(rendered
+            .html
+            .contains("<span class=\"mdv-heading-number\">2.1</span> Errors</h3>"));
+        assert!(!rendered.html.contains(">1</span> Spec"));
+    }
+
+    #[test]
+    fn heading_ids_do_not_change_when_numbering_is_toggled() {
+        let markdown = "# Intro\n\n## Error handling\n\n#### Skipped level\n";
+        let render = |number_headings| {
+            ComrakMarkdownRenderer::new()
+                .render(
+                    markdown,
+                    Path::new("notes.md"),
+                    RenderPreferences {
+                        number_headings,
+                        ..RenderPreferences::default()
+                    },
+                )
+                .expect("renderer should work")
+        };
+
+        let plain = render(None);
+        let numbered = render(Some(HeadingNumbering::default()));
+        let ids = |rendered: &markdown_viewer_domain::document::RenderedMarkdown| {
+            rendered
+                .toc
+                .iter()
+                .map(|entry| entry.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(&plain), ids(&numbered));
+        assert!(numbered.html.contains("id=\"mdv-error-handling\""));
+        assert_eq!(numbered.toc[1].text, "1.1 Error handling");
+        assert_eq!(numbered.toc[2].text, "1.1.1.1 Skipped level");
+        assert_eq!(plain.toc[1].text, "Error handling");
+        assert_eq!(numbered.toc[1].plain_text, "Error handling");
+        assert_eq!(numbered.word_count, plain.word_count);
+    }
+
+    #[test]
+    fn extension_profile_decides_which_syntax_is_parsed() {
+        let markdown = "Run `a || b` or a ||b|| c, H~2~O, ==marked== and ~~gone~~\n";
+        let render = |extension_profile, extension_overrides| {
+            ComrakMarkdownRenderer::new()
+                .render(
+                    markdown,
+                    Path::new("notes.md"),
+                    RenderPreferences {
+                        extension_profile,
+                        extension_overrides,
+                        ..RenderPreferences::default()
+                    },
+                )
+                .expect("renderer should work")
+                .html
+        };
+
+        let default = render(ExtensionProfile::Default, BTreeMap::new());
+        assert!(default.contains("<span class=\"spoiler\">b</span>"));
+        assert!(default.contains("H<sub>2</sub>O"));
+
+        let github = render(ExtensionProfile::Github, BTreeMap::new());
+        assert!(github.contains("a ||b|| c"));
+        assert!(!github.contains("<sub>"));
+        assert!(github.contains("<del>gone</del>"));
+        assert!(github.contains("==marked=="));
+
+        let strict = render(ExtensionProfile::CommonMark, BTreeMap::new());
+        assert!(strict.contains("~~gone~~"));
+
+        let overridden = render(
+            ExtensionProfile::Github,
+            [(MarkdownExtension::Highlight, true)].into_iter().collect(),
+        );
+        assert!(overridden.contains("<mark>marked</mark>"));
+    }
+
+    #[test]
+    fn sanitizing_raw_html_keeps_highlight_markup() {
+        let rendered = ComrakMarkdownRenderer::new()
+            .render(
+                "Keep ==this== <big>raw</big>",
+                Path::new("notes.md"),
+                RenderPreferences {
+                    allow_raw_html: true,
+                    extension_overrides: [(MarkdownExtension::Highlight, true)]
+                        .into_iter()
+                        .collect(),
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        assert!(rendered.html.contains("<mark>this</mark>"));
+        assert!(!rendered.html.contains("<big>"));
+    }
 }
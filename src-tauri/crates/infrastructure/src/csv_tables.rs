@@ -0,0 +1,524 @@
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_domain::document::{DocumentWarning, DocumentWarningKind};
+use regex::Regex;
+
+use crate::html_sanitizer::escape_html;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+use crate::path_confinement::confined_relative_path;
+
+/// Data rows shown when the preferences do not set a limit.
+pub const DEFAULT_CSV_ROW_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CsvDelimiter {
+    Comma,
+    Tab,
+}
+
+impl CsvDelimiter {
+    /// The delimiter implied by a fenced block language or file extension (`csv`, `tsv`).
+    fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("csv") {
+            Some(Self::Comma)
+        } else if name.eq_ignore_ascii_case("tsv") {
+            Some(Self::Tab)
+        } else {
+            None
+        }
+    }
+
+    fn byte(self) -> u8 {
+        match self {
+            Self::Comma => b',',
+            Self::Tab => b'\t',
+        }
+    }
+}
+
+/// Per-table overrides written after the block language or embed path, such as
+/// ` ```csv header=false limit=20 `.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TableOptions {
+    /// `None` detects the header row from the data.
+    header: Option<bool>,
+    limit: Option<usize>,
+}
+
+impl TableOptions {
+    fn parse<'t>(tokens: impl Iterator<Item = &'t str>) -> Result<Self, String> {
+        let mut options = Self::default();
+        for token in tokens {
+            match token.split_once('=') {
+                Some(("header", value)) => {
+                    options.header =
+                        Some(value.parse().map_err(|_| {
+                            format!("`header` must be true or false, got `{value}`")
+                        })?);
+                }
+                Some(("limit", value)) => {
+                    options.limit =
+                        Some(value.parse().map_err(|_| {
+                            format!("`limit` must be a number of rows, got `{value}`")
+                        })?);
+                }
+                _ => return Err(format!("unknown table option `{token}`")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Renders CSV and TSV data as tables.
+///
+/// Handles fenced ` ```csv ` / ` ```tsv ` blocks and paragraphs consisting only of a
+/// `{{csv: ./data.csv}}` embed, whose path resolves next to the document. Rows beyond the
+/// preferences' [`csv_row_limit`](markdown_viewer_domain::document::RenderPreferences) are cut
+/// off with a note, and tables with a detected header row are marked sortable for the viewer.
+/// Data that cannot be read is left as written and reported as a
+/// [`DocumentWarningKind::CsvError`] warning.
+pub struct CsvTableTransform;
+
+impl CsvTableTransform {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CsvTableTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownTransform for CsvTableTransform {
+    fn transform<'a>(
+        &self,
+        _arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError> {
+        let default_limit = context
+            .preferences
+            .csv_row_limit
+            .unwrap_or(DEFAULT_CSV_ROW_LIMIT);
+        let candidates: Vec<&'a AstNode<'a>> = root
+            .descendants()
+            .filter(|node| {
+                matches!(
+                    &node.data.borrow().value,
+                    NodeValue::CodeBlock(block) if block.fenced
+                ) || matches!(node.data.borrow().value, NodeValue::Paragraph)
+            })
+            .collect();
+
+        for node in candidates {
+            let line = node.data.borrow().sourcepos.start.line;
+            let rendered = match &node.data.borrow().value {
+                NodeValue::CodeBlock(block) => {
+                    let mut words = block.info.split_whitespace();
+                    let Some(delimiter) = words.next().and_then(CsvDelimiter::from_name) else {
+                        continue;
+                    };
+                    TableOptions::parse(words).and_then(|options| {
+                        csv_table_html(&block.literal, delimiter, options, default_limit)
+                    })
+                }
+                NodeValue::Paragraph => {
+                    let Some(embed) = csv_embed(node) else {
+                        continue;
+                    };
+                    if context.preferences.base_url.is_some() {
+                        Err("files are not embedded into remote documents".to_string())
+                    } else {
+                        render_embed(&embed, context.base_directory(), default_limit)
+                    }
+                }
+                _ => continue,
+            };
+
+            match rendered {
+                Ok(html) => {
+                    while let Some(child) = node.first_child() {
+                        child.detach();
+                    }
+                    node.data.borrow_mut().value = NodeValue::Raw(html);
+                }
+                Err(reason) => context.warn(DocumentWarning {
+                    kind: DocumentWarningKind::CsvError,
+                    message: format!("Table on line {line} could not be rendered: {reason}"),
+                    line: Some(line),
+                }),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The text of a paragraph that consists only of a `{{csv: ...}}` embed.
+fn csv_embed<'a>(paragraph: &'a AstNode<'a>) -> Option<String> {
+    let mut text = String::new();
+    for child in paragraph.children() {
+        match &child.data.borrow().value {
+            NodeValue::Text(value) => text.push_str(value),
+            _ => return None,
+        }
+    }
+
+    let inner = text.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    let (keyword, _) = inner.split_once(':')?;
+    CsvDelimiter::from_name(keyword.trim())?;
+    Some(inner.to_string())
+}
+
+fn render_embed(
+    embed: &str,
+    base_directory: &Path,
+    default_limit: usize,
+) -> Result<String, String> {
+    static OPTION: OnceLock<Regex> = OnceLock::new();
+    let option =
+        OPTION.get_or_init(|| Regex::new(r"\s+(header|limit)=\S+\s*$").expect("valid regex"));
+
+    let (keyword, argument) = embed
+        .split_once(':')
+        .ok_or_else(|| "embed is missing a file path".to_string())?;
+    let mut path = argument.trim();
+    let mut option_tokens = Vec::new();
+    while let Some(found) = option.find(path) {
+        option_tokens.push(found.as_str().trim());
+        path = path[..found.start()].trim_end();
+    }
+    if path.is_empty() {
+        return Err("embed is missing a file path".to_string());
+    }
+
+    let options = TableOptions::parse(option_tokens.into_iter().rev())?;
+    let file = base_directory.join(
+        confined_relative_path(path)
+            .ok_or_else(|| format!("CSV file must be inside the document's folder: {path}"))?,
+    );
+    let delimiter = file
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(CsvDelimiter::from_name)
+        .or_else(|| CsvDelimiter::from_name(keyword.trim()))
+        .unwrap_or(CsvDelimiter::Comma);
+    let data = fs::read_to_string(&file).map_err(|error| match error.kind() {
+        std::io::ErrorKind::NotFound => format!("CSV file not found: {path}"),
+        _ => format!("could not read {path}: {error}"),
+    })?;
+    csv_table_html(&data, delimiter, options, default_limit)
+}
+
+/// Renders delimited text as an HTML table using the document's regular table markup.
+fn csv_table_html(
+    data: &str,
+    delimiter: CsvDelimiter,
+    options: TableOptions,
+    default_limit: usize,
+) -> Result<String, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter.byte())
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(data.as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|error| error.to_string())?;
+        rows.push(record.iter().map(str::to_string).collect::<Vec<_>>());
+    }
+    if rows.is_empty() {
+        return Err("no rows".to_string());
+    }
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let has_header = options.header.unwrap_or_else(|| looks_like_header(&rows));
+    let body = if has_header { &rows[1..] } else { &rows[..] };
+    let numeric_columns: Vec<bool> = (0..columns)
+        .map(|column| {
+            let mut values = body
+                .iter()
+                .filter_map(|row| row.get(column))
+                .filter(|value| !value.trim().is_empty())
+                .peekable();
+            values.peek().is_some() && values.all(|value| is_numeric(value))
+        })
+        .collect();
+    let align = |column: usize| {
+        if numeric_columns[column] {
+            " align=\"right\""
+        } else {
+            ""
+        }
+    };
+
+    let sortable = if has_header {
+        " data-sortable=\"true\""
+    } else {
+        ""
+    };
+    let mut html = format!("<div class=\"mdv-csv-table\">\n<table{sortable}>\n");
+    if has_header {
+        html.push_str("<thead>\n<tr>\n");
+        for column in 0..columns {
+            let cell = rows[0].get(column).map(String::as_str).unwrap_or_default();
+            html.push_str(&format!(
+                "<th{}>{}</th>\n",
+                align(column),
+                escape_html(cell)
+            ));
+        }
+        html.push_str("</tr>\n</thead>\n");
+    }
+
+    let limit = options.limit.unwrap_or(default_limit);
+    html.push_str("<tbody>\n");
+    for row in body.iter().take(limit) {
+        html.push_str("<tr>\n");
+        for column in 0..columns {
+            let cell = row.get(column).map(String::as_str).unwrap_or_default();
+            html.push_str(&format!(
+                "<td{}>{}</td>\n",
+                align(column),
+                escape_html(cell)
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+    if body.len() > limit {
+        html.push_str(&format!(
+            "<p class=\"mdv-csv-truncated\">Showing the first {limit} of {} rows.</p>\n",
+            body.len()
+        ));
+    }
+    html.push_str("</div>\n");
+    Ok(html)
+}
+
+/// Treats the first row as a header when its cells are distinct, non-empty and not numbers.
+/// Tables whose first row is data that happens to look like that can set `header=false`.
+fn looks_like_header(rows: &[Vec<String>]) -> bool {
+    let first = &rows[0];
+    let is_label = |value: &String| !value.trim().is_empty() && !is_numeric(value);
+    if !first.iter().all(is_label) {
+        return false;
+    }
+    let mut seen: Vec<&str> = first.iter().map(|value| value.trim()).collect();
+    seen.sort_unstable();
+    seen.dedup();
+    seen.len() == first.len()
+}
+
+fn is_numeric(value: &str) -> bool {
+    let trimmed = value
+        .trim()
+        .trim_start_matches(['$', '€', '£', '¥'])
+        .trim_end_matches('%')
+        .replace([',', '_'], "");
+    !trimmed.is_empty() && trimmed.parse::<f64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::{
+        DocumentWarningKind, RenderPreferences, RenderedMarkdown,
+    };
+
+    use super::{csv_table_html, CsvDelimiter, TableOptions};
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    fn render_at(
+        markdown: &str,
+        document_path: &Path,
+        csv_row_limit: Option<usize>,
+    ) -> RenderedMarkdown {
+        ComrakMarkdownRenderer::new()
+            .render(
+                markdown,
+                document_path,
+                RenderPreferences {
+                    csv_row_limit,
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("render should succeed")
+    }
+
+    fn temp_directory() -> PathBuf {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic after epoch")
+            .as_nanos();
+        let directory = std::env::temp_dir().join(format!("mdv-csv-{suffix}"));
+        fs::create_dir_all(&directory).expect("temp directory should be creatable");
+        directory
+    }
+
+    #[test]
+    fn fenced_csv_blocks_render_as_sortable_tables() {
+        let rendered = render_at(
+            "```csv\nName,Price\n\"Widget, large\",\"$1,200\"\n<b>Gadget</b>,5\n```\n",
+            Path::new("notes.md"),
+            None,
+        );
+
+        assert!(rendered.html.contains("<table data-sortable=\"true\">"));
+        assert!(rendered.html.contains("<th>Name</th>"));
+        assert!(rendered.html.contains("<th align=\"right\">Price</th>"));
+        assert!(rendered.html.contains("<td>Widget, large</td>"));
+        assert!(rendered.html.contains("<td align=\"right\">$1,200</td>"));
+        assert!(rendered.html.contains("&lt;b&gt;Gadget&lt;/b&gt;"));
+        assert!(!rendered.html.contains("<pre"));
+    }
+
+    #[test]
+    fn header_detection_and_overrides() {
+        let numeric = csv_table_html(
+            "1,2\n3,4\n",
+            CsvDelimiter::Comma,
+            TableOptions::default(),
+            10,
+        )
+        .expect("table should render");
+        assert!(!numeric.contains("<thead>"));
+        assert!(!numeric.contains("data-sortable"));
+
+        let tsv = csv_table_html(
+            "a\tb\nc\td\n",
+            CsvDelimiter::Tab,
+            TableOptions::default(),
+            10,
+        )
+        .expect("table should render");
+        assert!(tsv.contains("<th>a</th>"));
+
+        let forced = csv_table_html(
+            "a,b\nc,d\n",
+            CsvDelimiter::Comma,
+            TableOptions {
+                header: Some(false),
+                limit: None,
+            },
+            10,
+        )
+        .expect("table should render");
+        assert!(!forced.contains("<thead>"));
+        assert!(forced.contains("<td>a</td>"));
+    }
+
+    #[test]
+    fn row_limits_truncate_with_a_note() {
+        let rendered = render_at(
+            "```csv\nn\n1\n2\n3\n```\n\n```csv limit=1\nn\n1\n2\n```\n",
+            Path::new("notes.md"),
+            Some(2),
+        );
+
+        assert!(rendered.html.contains("Showing the first 2 of 3 rows."));
+        assert!(rendered.html.contains("Showing the first 1 of 2 rows."));
+        assert!(!rendered.html.contains("<td align=\"right\">3</td>"));
+    }
+
+    #[test]
+    fn embeds_resolve_next_to_the_document() {
+        let directory = temp_directory();
+        fs::write(
+            directory.join("data.tsv"),
+            "City\tPopulation\nOslo\t709000\n",
+        )
+        .expect("fixture should be writable");
+
+        let rendered = render_at(
+            "Intro\n\n{{csv: ./data.tsv header=true}}\n\n{{csv: missing.csv}}\n",
+            &directory.join("notes.md"),
+            None,
+        );
+
+        assert!(rendered.html.contains("<th>City</th>"));
+        assert!(rendered.html.contains("<td align=\"right\">709000</td>"));
+        assert!(rendered.html.contains("{{csv: missing.csv}}"));
+        assert_eq!(rendered.warnings.len(), 1);
+        assert_eq!(rendered.warnings[0].kind, DocumentWarningKind::CsvError);
+        assert!(rendered.warnings[0]
+            .message
+            .contains("CSV file not found: missing.csv"));
+        assert_eq!(rendered.warnings[0].line, Some(5));
+
+        fs::remove_dir_all(directory).expect("temp directory should be removable");
+    }
+
+    #[test]
+    fn embeds_cannot_leave_the_document_folder() {
+        let directory = temp_directory();
+        let documents = directory.join("docs");
+        fs::create_dir_all(documents.join("data")).expect("fixture should be writable");
+        fs::write(directory.join("secrets.csv"), "key,value\ntoken,42\n")
+            .expect("fixture should be writable");
+        fs::write(documents.join("data").join("ok.csv"), "a,b\n1,2\n")
+            .expect("fixture should be writable");
+        let markdown = format!(
+            "{{{{csv: ../secrets.csv}}}}\n\n{{{{csv: {}}}}}\n\n{{{{csv: data/../data/ok.csv}}}}\n",
+            directory.join("secrets.csv").display()
+        );
+
+        let rendered = render_at(&markdown, &documents.join("notes.md"), None);
+
+        assert!(!rendered.html.contains("token"));
+        assert!(rendered.html.contains("<td align=\"right\">2</td>"));
+        assert_eq!(rendered.warnings.len(), 2);
+        assert!(rendered
+            .warnings
+            .iter()
+            .all(|warning| warning.message.contains("inside the document's folder")));
+
+        fs::remove_dir_all(directory).expect("temp directory should be removable");
+    }
+
+    #[test]
+    fn remote_documents_do_not_embed_local_files() {
+        let directory = temp_directory();
+        fs::write(directory.join("data.csv"), "a,b\n1,2\n").expect("fixture should be writable");
+        let embed = format!("{{{{csv: {}}}}}", directory.join("data.csv").display());
+
+        let rendered = ComrakMarkdownRenderer::new()
+            .render(
+                &embed,
+                Path::new("https://example.com/notes.md"),
+                RenderPreferences {
+                    base_url: Some("https://example.com/notes.md".to_string()),
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("render should succeed");
+
+        assert!(!rendered.html.contains("<table"));
+        assert_eq!(rendered.warnings.len(), 1);
+        assert_eq!(rendered.warnings[0].kind, DocumentWarningKind::CsvError);
+
+        fs::remove_dir_all(directory).expect("temp directory should be removable");
+    }
+
+    #[test]
+    fn invalid_options_keep_the_code_block() {
+        let rendered = render_at("```csv limit=all\na,b\n```\n", Path::new("notes.md"), None);
+
+        assert!(rendered.html.contains("<pre"));
+        assert_eq!(rendered.warnings.len(), 1);
+        assert!(rendered.warnings[0]
+            .message
+            .contains("`limit` must be a number"));
+    }
+}
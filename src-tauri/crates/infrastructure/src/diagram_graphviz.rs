@@ -0,0 +1,49 @@
+use layout::backends::svg::SVGWriter;
+use layout::gv::{DotParser, GraphBuilder};
+
+/// Lays out a Graphviz DOT graph and returns it as a standalone SVG document.
+///
+/// Subgraphs, HTML labels and other features the layout engine does not support are ignored
+/// rather than rejected; syntax errors are returned with the parser's message.
+pub(crate) fn render_dot(source: &str) -> Result<String, String> {
+    let mut parser = DotParser::new(source);
+    let graph = parser.process()?;
+
+    let mut builder = GraphBuilder::new();
+    builder.visit_graph(&graph);
+    let mut visual_graph = builder.get();
+    if visual_graph.num_nodes() == 0 {
+        return Err("graph has no nodes".to_string());
+    }
+
+    let mut writer = SVGWriter::new();
+    visual_graph.do_it(false, false, false, &mut writer);
+    Ok(writer.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_dot;
+
+    #[test]
+    fn renders_nodes_edges_and_labels() {
+        let svg = render_dot("digraph { api -> db [label=\"reads\"]; api -> cache; }")
+            .expect("valid DOT should render");
+
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains(">api<"));
+        assert!(svg.contains(">db<"));
+        assert!(svg.contains("reads"));
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        let error = render_dot("digraph { a -> ; }").expect_err("invalid DOT should fail");
+        assert!(!error.is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_graphs() {
+        assert!(render_dot("digraph { }").is_err());
+    }
+}
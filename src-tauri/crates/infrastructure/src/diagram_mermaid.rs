@@ -0,0 +1,861 @@
+//! A subset of Mermaid rendered without a JavaScript runtime.
+//!
+//! Flowcharts (`flowchart`/`graph`) support node declarations with rectangle, rounded, stadium
+//! and circle shapes (other shapes are drawn as rectangles), `&` node groups, solid, dotted and
+//! thick links with `|label|` or inline `-- label -->` text, and `TD`/`TB`/`BT`/`LR`/`RL`
+//! directions. They are laid out by the same engine as Graphviz diagrams. Sequence diagrams
+//! support participants, actors, aliases, `->>`/`-->>`/`->`/`-->`/`-x`/`-)` messages, notes and
+//! `autonumber`. Styling statements, subgraphs and sequence blocks (`loop`, `alt`, ...) are
+//! accepted and ignored so diagrams still render, just without those decorations.
+
+use std::sync::OnceLock;
+
+use layout::backends::svg::SVGWriter;
+use layout::core::base::Orientation;
+use layout::core::color::Color;
+use layout::core::style::{LineStyleKind, StyleAttr};
+use layout::std_shapes::render::get_shape_size;
+use layout::std_shapes::shapes::{Arrow, Element, LineEndKind, ShapeKind};
+use layout::topo::layout::VisualGraph;
+use regex::Regex;
+
+use crate::html_sanitizer::escape_html;
+
+const FONT_SIZE: usize = 14;
+const LINE_COLOR: u32 = 0x333333ff;
+const NODE_FILL: u32 = 0xf4f6faff;
+
+/// Flowchart statements that only affect styling or grouping.
+const IGNORED_FLOWCHART_KEYWORDS: &[&str] = &[
+    "subgraph",
+    "end",
+    "classDef",
+    "class",
+    "style",
+    "linkStyle",
+    "click",
+    "direction",
+];
+/// Sequence diagram statements that only group or decorate messages.
+const IGNORED_SEQUENCE_KEYWORDS: &[&str] = &[
+    "activate",
+    "deactivate",
+    "loop",
+    "alt",
+    "else",
+    "opt",
+    "par",
+    "and",
+    "critical",
+    "option",
+    "break",
+    "rect",
+    "box",
+    "end",
+    "title",
+];
+
+/// Renders a Mermaid diagram as a standalone SVG document.
+pub(crate) fn render_mermaid(source: &str) -> Result<String, String> {
+    let lines: Vec<&str> = source
+        .lines()
+        .map(|line| line.split("%%").next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let Some((header, body)) = lines.split_first() else {
+        return Err("diagram is empty".to_string());
+    };
+
+    let keyword = header
+        .split(|character: char| character.is_whitespace() || character == ';')
+        .next()
+        .unwrap_or_default();
+    match keyword {
+        "flowchart" | "graph" => render_flowchart(&parse_flowchart(header, body)?),
+        "sequenceDiagram" => Ok(render_sequence(&parse_sequence(body)?)),
+        other => Err(format!(
+            "unsupported Mermaid diagram type `{other}`; only flowchart and sequenceDiagram are supported"
+        )),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlowDirection {
+    TopDown,
+    BottomUp,
+    LeftRight,
+    RightLeft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeShape {
+    Rectangle,
+    Rounded,
+    Circle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinkStroke {
+    Solid,
+    Dotted,
+    Thick,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlowNode {
+    id: String,
+    label: String,
+    shape: NodeShape,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Link {
+    label: String,
+    stroke: LinkStroke,
+    arrow_at_start: bool,
+    arrow_at_end: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlowEdge {
+    from: String,
+    to: String,
+    link: Link,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Flowchart {
+    direction: FlowDirection,
+    nodes: Vec<FlowNode>,
+    edges: Vec<FlowEdge>,
+}
+
+impl Flowchart {
+    fn declare_node(&mut self, id: &str, declared: Option<(String, NodeShape)>) {
+        match (self.nodes.iter_mut().find(|node| node.id == id), declared) {
+            (Some(node), Some((label, shape))) => {
+                node.label = label;
+                node.shape = shape;
+            }
+            (Some(_), None) => {}
+            (None, declared) => {
+                let (label, shape) =
+                    declared.unwrap_or_else(|| (id.to_string(), NodeShape::Rectangle));
+                self.nodes.push(FlowNode {
+                    id: id.to_string(),
+                    label,
+                    shape,
+                });
+            }
+        }
+    }
+}
+
+fn parse_flowchart(header: &str, body: &[&str]) -> Result<Flowchart, String> {
+    let mut header_statements = header.split(';');
+    let declaration = header_statements.next().unwrap_or_default();
+    let direction = match declaration.split_whitespace().nth(1) {
+        None | Some("TD") | Some("TB") => FlowDirection::TopDown,
+        Some("BT") => FlowDirection::BottomUp,
+        Some("LR") => FlowDirection::LeftRight,
+        Some("RL") => FlowDirection::RightLeft,
+        Some(other) => return Err(format!("unknown flowchart direction `{other}`")),
+    };
+
+    let mut chart = Flowchart {
+        direction,
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    let statements = header_statements
+        .chain(body.iter().flat_map(|line| line.split(';')))
+        .map(str::trim)
+        .filter(|statement| !statement.is_empty());
+    for statement in statements {
+        let first_word = statement.split_whitespace().next().unwrap_or_default();
+        if IGNORED_FLOWCHART_KEYWORDS.contains(&first_word) {
+            continue;
+        }
+        parse_flowchart_statement(statement, &mut chart)?;
+    }
+
+    if chart.nodes.is_empty() {
+        return Err("flowchart has no nodes".to_string());
+    }
+    Ok(chart)
+}
+
+/// Parses `A --> B & C -- text --> D` style chains of node groups joined by links.
+fn parse_flowchart_statement(statement: &str, chart: &mut Flowchart) -> Result<(), String> {
+    let mut rest = statement;
+    let mut previous = parse_node_group(&mut rest, chart)?;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(());
+        }
+        let link = parse_link(&mut rest)?;
+        let next = parse_node_group(&mut rest, chart)?;
+        for from in &previous {
+            for to in &next {
+                chart.edges.push(FlowEdge {
+                    from: from.clone(),
+                    to: to.clone(),
+                    link: link.clone(),
+                });
+            }
+        }
+        previous = next;
+    }
+}
+
+fn parse_node_group(rest: &mut &str, chart: &mut Flowchart) -> Result<Vec<String>, String> {
+    let mut ids = vec![parse_node(rest, chart)?];
+    while let Some(after_ampersand) = rest.trim_start().strip_prefix('&') {
+        *rest = after_ampersand;
+        ids.push(parse_node(rest, chart)?);
+    }
+    Ok(ids)
+}
+
+fn parse_node(rest: &mut &str, chart: &mut Flowchart) -> Result<String, String> {
+    static NODE_ID: OnceLock<Regex> = OnceLock::new();
+    let node_id = NODE_ID.get_or_init(|| Regex::new(r"^[\p{L}\p{N}_]+").expect("valid regex"));
+
+    let trimmed = rest.trim_start();
+    let Some(id) = node_id.find(trimmed) else {
+        return Err(format!("expected a node id at `{}`", snippet(trimmed)));
+    };
+    let id = id.as_str().to_string();
+    let after_id = &trimmed[id.len()..];
+
+    // Longer openers first so `((` is not read as `(`.
+    const SHAPES: &[(&str, &str, NodeShape)] = &[
+        ("(((", ")))", NodeShape::Circle),
+        ("((", "))", NodeShape::Circle),
+        ("([", "])", NodeShape::Rounded),
+        ("[[", "]]", NodeShape::Rectangle),
+        ("[(", ")]", NodeShape::Rectangle),
+        ("{{", "}}", NodeShape::Rectangle),
+        ("[", "]", NodeShape::Rectangle),
+        ("(", ")", NodeShape::Rounded),
+        ("{", "}", NodeShape::Rectangle),
+        (">", "]", NodeShape::Rectangle),
+    ];
+    let shape = SHAPES
+        .iter()
+        .find(|(open, _, _)| after_id.starts_with(open));
+    let Some((open, close, shape)) = shape else {
+        chart.declare_node(&id, None);
+        *rest = after_id;
+        return Ok(id);
+    };
+
+    let label_start = &after_id[open.len()..];
+    let Some(label_end) = label_start.find(close) else {
+        return Err(format!("missing `{close}` after node `{id}`"));
+    };
+    let label = clean_label(&label_start[..label_end]);
+    chart.declare_node(&id, Some((label, *shape)));
+    *rest = &label_start[label_end + close.len()..];
+    Ok(id)
+}
+
+fn parse_link(rest: &mut &str) -> Result<Link, String> {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| {
+        Regex::new(
+            r"^(<)?(?:(?:--|==|-\.)\s+([^|]+?)\s+)?(-+\.+-+>?|\.+-+>?|-{2,}>|-{3,}|={2,}>|={3,}|-{2,}[ox]|={2,}[ox])(?:\|([^|]*)\|)?",
+        )
+        .expect("valid regex")
+    });
+
+    let trimmed = rest.trim_start();
+    let Some(captures) = link.captures(trimmed) else {
+        return Err(format!(
+            "expected a link such as `-->` at `{}`",
+            snippet(trimmed)
+        ));
+    };
+    let body = &captures[3];
+    let label = captures
+        .get(4)
+        .or_else(|| captures.get(2))
+        .map(|label| clean_label(label.as_str()))
+        .unwrap_or_default();
+    let stroke = if body.contains('.') {
+        LinkStroke::Dotted
+    } else if body.contains('=') {
+        LinkStroke::Thick
+    } else {
+        LinkStroke::Solid
+    };
+
+    *rest = &trimmed[captures[0].len()..];
+    Ok(Link {
+        label,
+        stroke,
+        arrow_at_start: captures.get(1).is_some(),
+        arrow_at_end: body.ends_with(['>', 'o', 'x']),
+    })
+}
+
+fn clean_label(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let unquoted = trimmed
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .unwrap_or(trimmed);
+    unquoted
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("<br>", "\n")
+}
+
+fn snippet(text: &str) -> String {
+    text.chars().take(24).collect()
+}
+
+fn render_flowchart(chart: &Flowchart) -> Result<String, String> {
+    let orientation = match chart.direction {
+        FlowDirection::TopDown | FlowDirection::BottomUp => Orientation::TopToBottom,
+        FlowDirection::LeftRight | FlowDirection::RightLeft => Orientation::LeftToRight,
+    };
+    let reversed = matches!(
+        chart.direction,
+        FlowDirection::BottomUp | FlowDirection::RightLeft
+    );
+
+    let mut graph = VisualGraph::new(orientation);
+    let handles: Vec<_> = chart
+        .nodes
+        .iter()
+        .map(|node| {
+            let (shape, make_xy_same) = match node.shape {
+                NodeShape::Circle => (ShapeKind::new_circle(&node.label), true),
+                NodeShape::Rectangle | NodeShape::Rounded => {
+                    (ShapeKind::new_box(&node.label), false)
+                }
+            };
+            let rounded = if node.shape == NodeShape::Rounded {
+                12
+            } else {
+                0
+            };
+            let look = StyleAttr::new(
+                Color::new(LINE_COLOR),
+                1,
+                Some(Color::new(NODE_FILL)),
+                rounded,
+                FONT_SIZE,
+            );
+            let size = get_shape_size(orientation, &shape, FONT_SIZE, make_xy_same);
+            graph.add_node(Element::create(shape, look, orientation, size))
+        })
+        .collect();
+    let handle_of = |id: &str| {
+        chart
+            .nodes
+            .iter()
+            .position(|node| node.id == id)
+            .map(|index| handles[index])
+            .ok_or_else(|| format!("unknown node `{id}`"))
+    };
+
+    for edge in &chart.edges {
+        let line_width = if edge.link.stroke == LinkStroke::Thick {
+            3
+        } else {
+            1
+        };
+        let look = StyleAttr::new(Color::new(LINE_COLOR), line_width, None, 0, FONT_SIZE);
+        let line_style = match edge.link.stroke {
+            LinkStroke::Dotted => LineStyleKind::Dashed,
+            LinkStroke::Solid | LinkStroke::Thick => LineStyleKind::Normal,
+        };
+        let end_kind = |has_arrow: bool| {
+            if has_arrow {
+                LineEndKind::Arrow
+            } else {
+                LineEndKind::None
+            }
+        };
+
+        // Bottom-up and right-to-left charts are laid out top-down/left-to-right with every
+        // edge flipped, which puts the sources at the far end.
+        let (from, to, start, end) = if reversed {
+            (
+                &edge.to,
+                &edge.from,
+                edge.link.arrow_at_end,
+                edge.link.arrow_at_start,
+            )
+        } else {
+            (
+                &edge.from,
+                &edge.to,
+                edge.link.arrow_at_start,
+                edge.link.arrow_at_end,
+            )
+        };
+        let arrow = Arrow::new(
+            end_kind(start),
+            end_kind(end),
+            line_style,
+            &edge.link.label,
+            &look,
+            &None,
+            &None,
+        );
+        graph.add_edge(arrow, handle_of(from)?, handle_of(to)?);
+    }
+
+    let mut writer = SVGWriter::new();
+    graph.do_it(false, false, false, &mut writer);
+    Ok(writer.finalize())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Participant {
+    id: String,
+    label: String,
+    actor: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MessageHead {
+    Filled,
+    None,
+    Cross,
+    Open,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotePlacement {
+    LeftOf,
+    RightOf,
+    Over,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SequenceEvent {
+    Message {
+        from: usize,
+        to: usize,
+        text: String,
+        dashed: bool,
+        head: MessageHead,
+    },
+    Note {
+        first: usize,
+        last: usize,
+        placement: NotePlacement,
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SequenceDiagram {
+    participants: Vec<Participant>,
+    events: Vec<SequenceEvent>,
+    autonumber: bool,
+}
+
+impl SequenceDiagram {
+    fn participant(&mut self, id: &str) -> usize {
+        if let Some(index) = self.participants.iter().position(|p| p.id == id) {
+            return index;
+        }
+        self.participants.push(Participant {
+            id: id.to_string(),
+            label: id.to_string(),
+            actor: false,
+        });
+        self.participants.len() - 1
+    }
+}
+
+fn parse_sequence(body: &[&str]) -> Result<SequenceDiagram, String> {
+    static MESSAGE: OnceLock<Regex> = OnceLock::new();
+    static NOTE: OnceLock<Regex> = OnceLock::new();
+    let message = MESSAGE.get_or_init(|| {
+        Regex::new(
+            r"^([\p{L}\p{N}_]+)\s*(--?>>|--?>|--?x|--?\))\s*[+-]?\s*([\p{L}\p{N}_]+)\s*(?::(.*))?$",
+        )
+        .expect("valid regex")
+    });
+    let note = NOTE.get_or_init(|| {
+        Regex::new(r"(?i)^note\s+(left of|right of|over)\s+([\p{L}\p{N}_]+)(?:\s*,\s*([\p{L}\p{N}_]+))?\s*:(.*)$")
+            .expect("valid regex")
+    });
+
+    let mut diagram = SequenceDiagram {
+        participants: Vec::new(),
+        events: Vec::new(),
+        autonumber: false,
+    };
+
+    for line in body {
+        let first_word = line.split_whitespace().next().unwrap_or_default();
+        match first_word {
+            "participant" | "actor" => {
+                let declaration = line[first_word.len()..].trim();
+                let (id, label) = match declaration.split_once(" as ") {
+                    Some((id, label)) => (id.trim(), label.trim()),
+                    None => (declaration, declaration),
+                };
+                if id.is_empty() {
+                    return Err(format!("`{first_word}` needs a name"));
+                }
+                let index = diagram.participant(id);
+                diagram.participants[index].label = label.to_string();
+                diagram.participants[index].actor = first_word == "actor";
+            }
+            "autonumber" => diagram.autonumber = true,
+            _ if IGNORED_SEQUENCE_KEYWORDS.contains(&first_word) => {}
+            _ => {
+                if let Some(captures) = note.captures(line) {
+                    let placement = match captures[1].to_ascii_lowercase().as_str() {
+                        "left of" => NotePlacement::LeftOf,
+                        "right of" => NotePlacement::RightOf,
+                        _ => NotePlacement::Over,
+                    };
+                    let first = diagram.participant(&captures[2]);
+                    let last = captures
+                        .get(3)
+                        .map(|id| diagram.participant(id.as_str()))
+                        .unwrap_or(first);
+                    diagram.events.push(SequenceEvent::Note {
+                        first: first.min(last),
+                        last: first.max(last),
+                        placement,
+                        text: clean_label(&captures[4]),
+                    });
+                } else if let Some(captures) = message.captures(line) {
+                    let arrow = &captures[2];
+                    let head = if arrow.ends_with(">>") {
+                        MessageHead::Filled
+                    } else if arrow.ends_with('x') {
+                        MessageHead::Cross
+                    } else if arrow.ends_with(')') {
+                        MessageHead::Open
+                    } else {
+                        MessageHead::None
+                    };
+                    let from = diagram.participant(&captures[1]);
+                    let to = diagram.participant(&captures[3]);
+                    diagram.events.push(SequenceEvent::Message {
+                        from,
+                        to,
+                        text: captures
+                            .get(4)
+                            .map(|text| clean_label(text.as_str()))
+                            .unwrap_or_default(),
+                        dashed: arrow.starts_with("--"),
+                        head,
+                    });
+                } else {
+                    return Err(format!(
+                        "unsupported sequence diagram statement `{}`",
+                        snippet(line)
+                    ));
+                }
+            }
+        }
+    }
+
+    if diagram.participants.is_empty() {
+        return Err("sequence diagram has no participants".to_string());
+    }
+    Ok(diagram)
+}
+
+const SEQUENCE_FONT_SIZE: usize = 13;
+const CHARACTER_WIDTH: usize = 8;
+const PARTICIPANT_HEIGHT: usize = 36;
+const MARGIN: usize = 20;
+const ROW_HEIGHT: usize = 44;
+
+fn text_width(text: &str) -> usize {
+    text.lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        * CHARACTER_WIDTH
+}
+
+fn render_sequence(diagram: &SequenceDiagram) -> String {
+    let box_width = diagram
+        .participants
+        .iter()
+        .map(|participant| text_width(&participant.label) + 24)
+        .max()
+        .unwrap_or(0)
+        .max(100);
+    let widest_message = diagram
+        .events
+        .iter()
+        .map(|event| match event {
+            SequenceEvent::Message { text, .. } => text_width(text) + 40,
+            SequenceEvent::Note { .. } => 0,
+        })
+        .max()
+        .unwrap_or(0);
+    let column_spacing = (box_width + 40).max(widest_message.min(480));
+    let center = |index: usize| MARGIN + box_width / 2 + index * column_spacing;
+
+    let width = 2 * MARGIN + box_width + column_spacing * (diagram.participants.len() - 1) + 120;
+    let lifeline_top = MARGIN + PARTICIPANT_HEIGHT;
+    let lifeline_bottom = lifeline_top + ROW_HEIGHT * (diagram.events.len() + 1);
+    let height = lifeline_bottom + PARTICIPANT_HEIGHT + MARGIN;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" font-size=\"{SEQUENCE_FONT_SIZE}\">\n\
+         <defs>\
+         <marker id=\"filled\" markerWidth=\"10\" markerHeight=\"7\" refX=\"10\" refY=\"3.5\" orient=\"auto\">\
+         <polygon points=\"0 0, 10 3.5, 0 7\" fill=\"#333333\" /></marker>\
+         <marker id=\"open\" markerWidth=\"10\" markerHeight=\"7\" refX=\"10\" refY=\"3.5\" orient=\"auto\">\
+         <polyline points=\"0 0, 10 3.5, 0 7\" fill=\"none\" stroke=\"#333333\" /></marker>\
+         <marker id=\"cross\" markerWidth=\"10\" markerHeight=\"10\" refX=\"5\" refY=\"5\" orient=\"auto\">\
+         <path d=\"M 0 0 L 10 10 M 10 0 L 0 10\" stroke=\"#333333\" /></marker>\
+         </defs>\n"
+    );
+
+    for (index, participant) in diagram.participants.iter().enumerate() {
+        let x = center(index);
+        svg.push_str(&format!(
+            "<line x1=\"{x}\" y1=\"{lifeline_top}\" x2=\"{x}\" y2=\"{lifeline_bottom}\" \
+             stroke=\"#999999\" stroke-dasharray=\"4,4\" />\n"
+        ));
+        for top in [MARGIN, lifeline_bottom] {
+            svg.push_str(&participant_box(participant, x, top, box_width));
+        }
+    }
+
+    let mut number = 0;
+    for (row, event) in diagram.events.iter().enumerate() {
+        let y = lifeline_top + ROW_HEIGHT * (row + 1);
+        match event {
+            SequenceEvent::Message {
+                from,
+                to,
+                text,
+                dashed,
+                head,
+            } => {
+                number += 1;
+                let label = if diagram.autonumber {
+                    format!("{number}. {text}")
+                } else {
+                    text.clone()
+                };
+                svg.push_str(&message_svg(
+                    center(*from),
+                    center(*to),
+                    y,
+                    &label,
+                    *dashed,
+                    *head,
+                ));
+            }
+            SequenceEvent::Note {
+                first,
+                last,
+                placement,
+                text,
+            } => {
+                let note_width = (text_width(text) + 20).max(80);
+                let (left, right) = match placement {
+                    NotePlacement::LeftOf => {
+                        (center(*first) - 10 - note_width, center(*first) - 10)
+                    }
+                    NotePlacement::RightOf => (center(*last) + 10, center(*last) + 10 + note_width),
+                    NotePlacement::Over if first == last => (
+                        center(*first) - note_width / 2,
+                        center(*first) + note_width / 2,
+                    ),
+                    NotePlacement::Over => (center(*first) - 40, center(*last) + 40),
+                };
+                let left = left.max(2);
+                svg.push_str(&format!(
+                    "<rect x=\"{left}\" y=\"{}\" width=\"{}\" height=\"30\" fill=\"#fff5ad\" \
+                     stroke=\"#aaaa33\" />\n<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                    y - 20,
+                    right.saturating_sub(left),
+                    (left + right) / 2,
+                    y,
+                    escape_html(text)
+                ));
+            }
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn participant_box(participant: &Participant, x: usize, top: usize, box_width: usize) -> String {
+    let rounded = if participant.actor { 18 } else { 3 };
+    format!(
+        "<rect x=\"{}\" y=\"{top}\" width=\"{box_width}\" height=\"{PARTICIPANT_HEIGHT}\" rx=\"{rounded}\" \
+         fill=\"#f4f6fa\" stroke=\"#333333\" />\n<text x=\"{x}\" y=\"{}\" text-anchor=\"middle\" \
+         dominant-baseline=\"middle\">{}</text>\n",
+        x - box_width / 2,
+        top + PARTICIPANT_HEIGHT / 2,
+        escape_html(&participant.label)
+    )
+}
+
+fn message_svg(
+    from_x: usize,
+    to_x: usize,
+    y: usize,
+    label: &str,
+    dashed: bool,
+    head: MessageHead,
+) -> String {
+    let dash = if dashed {
+        " stroke-dasharray=\"5,4\""
+    } else {
+        ""
+    };
+    let marker = match head {
+        MessageHead::Filled => " marker-end=\"url(#filled)\"",
+        MessageHead::Open => " marker-end=\"url(#open)\"",
+        MessageHead::Cross => " marker-end=\"url(#cross)\"",
+        MessageHead::None => "",
+    };
+    let text = escape_html(label);
+
+    if from_x == to_x {
+        return format!(
+            "<path d=\"M {from_x} {} h 40 v 24 h -40\" fill=\"none\" stroke=\"#333333\"{dash}{marker} />\n\
+             <text x=\"{}\" y=\"{}\">{text}</text>\n",
+            y - 12,
+            from_x + 46,
+            y + 4
+        );
+    }
+    format!(
+        "<line x1=\"{from_x}\" y1=\"{y}\" x2=\"{to_x}\" y2=\"{y}\" stroke=\"#333333\"{dash}{marker} />\n\
+         <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{text}</text>\n",
+        (from_x + to_x) / 2,
+        y - 6
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_flowchart, parse_sequence, render_mermaid, FlowDirection, LinkStroke, NodeShape,
+        SequenceEvent,
+    };
+
+    #[test]
+    fn parses_flowchart_nodes_links_and_labels() {
+        let chart = parse_flowchart(
+            "flowchart LR",
+            &[
+                "A[Start] --> B{Is it?}",
+                "B -- Yes --> C((Done))",
+                "B -.->|No| D(Retry) & E",
+                "D ==> A",
+                "classDef hot fill:#f00",
+            ],
+        )
+        .expect("flowchart should parse");
+
+        assert_eq!(chart.direction, FlowDirection::LeftRight);
+        let ids: Vec<&str> = chart.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(ids, ["A", "B", "C", "D", "E"]);
+        assert_eq!(chart.nodes[0].label, "Start");
+        assert_eq!(chart.nodes[2].shape, NodeShape::Circle);
+        assert_eq!(chart.nodes[3].shape, NodeShape::Rounded);
+        assert_eq!(chart.edges.len(), 5);
+        assert_eq!(chart.edges[1].link.label, "Yes");
+        assert_eq!(chart.edges[2].link.label, "No");
+        assert_eq!(chart.edges[2].link.stroke, LinkStroke::Dotted);
+        assert_eq!(chart.edges[3].to, "E");
+        assert_eq!(chart.edges[4].link.stroke, LinkStroke::Thick);
+        assert!(chart.edges.iter().all(|edge| edge.link.arrow_at_end));
+    }
+
+    #[test]
+    fn flowchart_header_may_carry_statements() {
+        let chart = parse_flowchart("graph TD; a --- b", &[]).expect("flowchart should parse");
+        assert_eq!(chart.edges.len(), 1);
+        assert!(!chart.edges[0].link.arrow_at_end);
+    }
+
+    #[test]
+    fn renders_flowchart_svg() {
+        let svg = render_mermaid("flowchart TD\n  A[Write docs] --> B[Ship]\n")
+            .expect("flowchart should render");
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("Write docs"));
+        assert!(svg.contains("Ship"));
+    }
+
+    #[test]
+    fn reports_flowchart_syntax_errors() {
+        let error = render_mermaid("flowchart TD\n  A[unclosed --> B").expect_err("should fail");
+        assert!(error.contains("missing `]`"));
+        let error = render_mermaid("flowchart TD\n  A ~~ B").expect_err("should fail");
+        assert!(error.contains("expected a link"));
+    }
+
+    #[test]
+    fn parses_sequence_messages_and_notes() {
+        let diagram = parse_sequence(&[
+            "participant C as Client",
+            "actor U",
+            "C->>S: request",
+            "loop every minute",
+            "S-->>C: response",
+            "end",
+            "Note over C,S: TLS",
+            "S-xU: gone",
+        ])
+        .expect("sequence should parse");
+
+        let labels: Vec<&str> = diagram
+            .participants
+            .iter()
+            .map(|participant| participant.label.as_str())
+            .collect();
+        assert_eq!(labels, ["Client", "U", "S"]);
+        assert!(diagram.participants[1].actor);
+        assert_eq!(diagram.events.len(), 4);
+        assert!(matches!(
+            &diagram.events[1],
+            SequenceEvent::Message { dashed: true, text, .. } if text == "response"
+        ));
+        assert!(matches!(
+            &diagram.events[2],
+            SequenceEvent::Note {
+                first: 0,
+                last: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn renders_sequence_svg_with_escaped_text() {
+        let svg =
+            render_mermaid("sequenceDiagram\nautonumber\nA->>B: <b>hi</b> & bye\nB->>B: think")
+                .expect("sequence should render");
+        assert!(svg.contains("1. &lt;b&gt;hi&lt;/b&gt; &amp; bye"));
+        assert!(svg.contains("2. think"));
+        assert!(!svg.contains("<b>"));
+    }
+
+    #[test]
+    fn rejects_unsupported_diagram_types() {
+        let error = render_mermaid("pie title Pets\n\"Dogs\" : 3").expect_err("should fail");
+        assert!(error.contains("unsupported Mermaid diagram type `pie`"));
+        assert!(render_mermaid("%% only a comment").is_err());
+    }
+}
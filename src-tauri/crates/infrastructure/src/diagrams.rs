@@ -0,0 +1,379 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, OnceLock};
+
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_domain::document::{DocumentWarning, DocumentWarningKind};
+use regex::Regex;
+
+use crate::diagram_graphviz::render_dot;
+use crate::diagram_mermaid::render_mermaid;
+use crate::html_sanitizer::escape_html;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+
+/// Rendered diagrams kept per transform; the cache is cleared once it grows past this.
+const MAX_CACHED_DIAGRAMS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DiagramKind {
+    Graphviz,
+    Mermaid,
+}
+
+impl DiagramKind {
+    fn from_info(info: &str) -> Option<Self> {
+        match info
+            .split_whitespace()
+            .next()?
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "dot" | "graphviz" => Some(Self::Graphviz),
+            "mermaid" => Some(Self::Mermaid),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Graphviz => "Graphviz",
+            Self::Mermaid => "Mermaid",
+        }
+    }
+
+    fn class_suffix(self) -> &'static str {
+        match self {
+            Self::Graphviz => "graphviz",
+            Self::Mermaid => "mermaid",
+        }
+    }
+
+    fn render(self, source: &str) -> Result<String, String> {
+        match self {
+            Self::Graphviz => render_dot(source),
+            Self::Mermaid => render_mermaid(source),
+        }
+    }
+}
+
+/// Replaces fenced ` ```mermaid `, ` ```dot ` and ` ```graphviz ` blocks with inline SVG.
+///
+/// Diagrams are rendered in-process, so no JavaScript runs in the webview. A block that cannot
+/// be rendered stays as a code block with an error notice above it and is reported as a
+/// [`DocumentWarningKind::DiagramError`] warning. Results are cached by block content, so
+/// re-rendering a document after an edit only lays out the diagrams that changed.
+pub struct DiagramTransform {
+    cache: Mutex<HashMap<u64, Result<String, String>>>,
+}
+
+impl DiagramTransform {
+    pub fn new() -> Self {
+        Self {
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Number of diagrams currently held in the render cache.
+    pub fn cached_diagram_count(&self) -> usize {
+        self.cache.lock().map(|cache| cache.len()).unwrap_or(0)
+    }
+
+    fn render_cached(&self, kind: DiagramKind, source: &str, key: u64) -> Result<String, String> {
+        if let Some(cached) = self
+            .cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&key).cloned())
+        {
+            return cached;
+        }
+
+        // The layout engine asserts on some malformed graphs; treat a panic as a render error
+        // rather than taking the whole document down with it.
+        let rendered = panic::catch_unwind(AssertUnwindSafe(|| kind.render(source)))
+            .unwrap_or_else(|_| Err("the layout engine could not place this diagram".to_string()))
+            .map(|svg| normalize_svg(&svg, &cached_id_prefix(key)));
+
+        if let Ok(mut cache) = self.cache.lock() {
+            if cache.len() >= MAX_CACHED_DIAGRAMS {
+                cache.clear();
+            }
+            cache.insert(key, rendered.clone());
+        }
+        rendered
+    }
+}
+
+impl Default for DiagramTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownTransform for DiagramTransform {
+    fn transform<'a>(
+        &self,
+        arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError> {
+        let code_blocks: Vec<&'a AstNode<'a>> = root
+            .descendants()
+            .filter(|node| {
+                matches!(&node.data.borrow().value, NodeValue::CodeBlock(block) if block.fenced)
+            })
+            .collect();
+        let mut occurrences: HashMap<u64, usize> = HashMap::new();
+
+        for node in code_blocks {
+            let (kind, literal, line) = {
+                let data = node.data.borrow();
+                let NodeValue::CodeBlock(block) = &data.value else {
+                    continue;
+                };
+                let Some(kind) = DiagramKind::from_info(&block.info) else {
+                    continue;
+                };
+                (kind, block.literal.clone(), data.sourcepos.start.line)
+            };
+
+            let key = diagram_key(kind, &literal);
+            match self.render_cached(kind, &literal, key) {
+                Ok(svg) => {
+                    // The same diagram can appear more than once; number each copy's ids so
+                    // `url(#…)` references stay within their own SVG.
+                    let occurrence = occurrences.entry(key).or_default();
+                    let svg = svg.replace(
+                        &cached_id_prefix(key),
+                        &format!("mdv-diagram-{key:x}-{occurrence}-"),
+                    );
+                    *occurrence += 1;
+                    node.data.borrow_mut().value = NodeValue::Raw(format!(
+                        "<div class=\"mdv-diagram mdv-diagram-{}\">{svg}</div>",
+                        kind.class_suffix()
+                    ));
+                }
+                Err(reason) => {
+                    let notice = format!(
+                        "<div class=\"mdv-diagram-error\" role=\"alert\">{} diagram error: {}</div>",
+                        kind.label(),
+                        escape_html(&reason)
+                    );
+                    let notice = arena.alloc(AstNode::from(NodeValue::Raw(notice)));
+                    notice.data.borrow_mut().sourcepos = node.data.borrow().sourcepos;
+                    node.insert_before(notice);
+                    context.warn(DocumentWarning {
+                        kind: DocumentWarningKind::DiagramError,
+                        message: format!(
+                            "{} diagram on line {line} could not be rendered: {reason}",
+                            kind.label()
+                        ),
+                        line: Some(line),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn diagram_key(kind: DiagramKind, source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    kind.hash(&mut hasher);
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Id prefix of a cached diagram, replaced per occurrence when the diagram is placed.
+fn cached_id_prefix(key: u64) -> String {
+    format!("mdv-diagram-{key:x}-")
+}
+
+/// Makes a standalone SVG document safe to inline next to other diagrams.
+///
+/// Drops the XML prologue, replaces the layout engine's `<style>` font classes with plain
+/// attributes (the sanitizer removes `<style>`), and prefixes element ids and their references
+/// so markers and clip paths from two diagrams on the same page do not collide.
+fn normalize_svg(svg: &str, id_prefix: &str) -> String {
+    static PROLOGUE: OnceLock<Regex> = OnceLock::new();
+    static STYLE: OnceLock<Regex> = OnceLock::new();
+    static FONT_RULE: OnceLock<Regex> = OnceLock::new();
+    static CLASS: OnceLock<Regex> = OnceLock::new();
+    static ID_REFERENCE: OnceLock<Regex> = OnceLock::new();
+    let prologue = PROLOGUE.get_or_init(|| Regex::new(r"<\?xml[^>]*\?>\s*").expect("valid regex"));
+    let style =
+        STYLE.get_or_init(|| Regex::new(r"(?s)<style[^>]*>.*?</style>").expect("valid regex"));
+    let font_rule = FONT_RULE
+        .get_or_init(|| Regex::new(r"\.(\w+)\s*\{\s*font-size:\s*(\d+)px").expect("valid regex"));
+    let class = CLASS.get_or_init(|| Regex::new(r#"class="(\w+)""#).expect("valid regex"));
+    let id_reference = ID_REFERENCE
+        .get_or_init(|| Regex::new(r##"(\bid=")|(url\(#)|(href="#)"##).expect("valid regex"));
+
+    let svg = prologue.replace(svg, "");
+    let font_sizes: HashMap<String, String> = style
+        .find(&svg)
+        .map(|block| {
+            font_rule
+                .captures_iter(block.as_str())
+                .map(|rule| (rule[1].to_string(), rule[2].to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let svg = style.replace_all(&svg, "");
+    let svg = class.replace_all(&svg, |captures: &regex::Captures<'_>| {
+        match font_sizes.get(&captures[1]) {
+            Some(size) => format!("font-size=\"{size}\""),
+            None => captures[0].to_string(),
+        }
+    });
+    let svg = id_reference.replace_all(&svg, |captures: &regex::Captures<'_>| {
+        format!("{}{id_prefix}", &captures[0])
+    });
+    svg.replacen("<svg", "<svg role=\"img\"", 1)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use comrak::{parse_document, Arena, Options};
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::{
+        DocumentWarningKind, RenderPreferences, RenderedMarkdown,
+    };
+
+    use super::{normalize_svg, DiagramTransform};
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+    use crate::markdown_transform::{MarkdownTransform, TransformContext};
+
+    fn render(markdown: &str, preferences: RenderPreferences) -> RenderedMarkdown {
+        ComrakMarkdownRenderer::new()
+            .render(markdown, Path::new("notes.md"), preferences)
+            .expect("render should succeed")
+    }
+
+    #[test]
+    fn renders_dot_and_mermaid_blocks_as_inline_svg() {
+        let rendered = render(
+            "```dot\ndigraph { a -> b }\n```\n\n```mermaid\nflowchart LR\n  A[Draft] --> B[Review]\n```\n",
+            RenderPreferences::default(),
+        );
+
+        assert!(rendered
+            .html
+            .contains("<div class=\"mdv-diagram mdv-diagram-graphviz\"><svg role=\"img\""));
+        assert!(rendered
+            .html
+            .contains("<div class=\"mdv-diagram mdv-diagram-mermaid\"><svg role=\"img\""));
+        assert!(rendered.html.contains("Review"));
+        assert!(!rendered.html.contains("<?xml"));
+        assert!(!rendered.html.contains("<pre"));
+        assert!(rendered.warnings.is_empty());
+    }
+
+    #[test]
+    fn invalid_diagrams_keep_the_code_block_and_warn() {
+        let rendered = render(
+            "# Title\n\n```mermaid\npie title Pets\n```\n",
+            RenderPreferences::default(),
+        );
+
+        assert!(rendered.html.contains("<div class=\"mdv-diagram-error\" role=\"alert\">Mermaid diagram error: unsupported Mermaid diagram type `pie`"));
+        assert!(rendered.html.contains("<pre"));
+        assert_eq!(rendered.warnings.len(), 1);
+        assert_eq!(rendered.warnings[0].kind, DocumentWarningKind::DiagramError);
+        assert_eq!(rendered.warnings[0].line, Some(3));
+    }
+
+    #[test]
+    fn diagrams_survive_raw_html_sanitization() {
+        let rendered = render(
+            "```dot\ndigraph { a -> b }\n```\n",
+            RenderPreferences {
+                allow_raw_html: true,
+                ..RenderPreferences::default()
+            },
+        );
+
+        assert!(rendered.html.contains("<svg"));
+        assert!(rendered.html.contains("viewBox="));
+        assert!(rendered.html.contains("<path"));
+        assert!(rendered.html.contains("marker-end=\"url(#mdv-diagram-"));
+    }
+
+    #[test]
+    fn repeated_diagrams_get_their_own_ids() {
+        let rendered = render(
+            "```dot\ndigraph { a -> b }\n```\n\n```dot\ndigraph { a -> b }\n```\n",
+            RenderPreferences::default(),
+        );
+
+        let ids: Vec<&str> = rendered
+            .html
+            .match_indices(" id=\"")
+            .map(|(start, _)| {
+                let value = &rendered.html[start + 5..];
+                &value[..value.find('"').expect("id should be closed")]
+            })
+            .collect();
+        let unique: std::collections::HashSet<&str> = ids.iter().copied().collect();
+        assert!(ids.len() >= 2);
+        assert_eq!(unique.len(), ids.len());
+
+        let (first, second) = rendered
+            .html
+            .split_once("</svg>")
+            .expect("two diagrams should render");
+        let marker = |svg: &str| {
+            let start = svg.find("url(#").expect("edge should reference its marker") + 5;
+            svg[start..start + svg[start..].find(')').expect("url should close")].to_string()
+        };
+        assert!(first.contains(&format!("id=\"{}\"", marker(first))));
+        assert!(second.contains(&format!("id=\"{}\"", marker(second))));
+        assert_ne!(marker(first), marker(second));
+    }
+
+    #[test]
+    fn caches_diagrams_by_content() {
+        let transform = DiagramTransform::new();
+        let preferences = RenderPreferences::default();
+        let context = TransformContext::new(&preferences, Path::new("notes.md"));
+        let arena = Arena::new();
+        let transform_markdown = |markdown: &str| {
+            let root = parse_document(&arena, markdown, &Options::default());
+            transform
+                .transform(&arena, root, &context)
+                .expect("transform should succeed");
+        };
+
+        transform_markdown("```dot\ndigraph { a -> b }\n```\n\n```dot\ndigraph { a -> b }\n```\n");
+        transform_markdown("```dot\ndigraph { a -> b }\n```\n");
+        assert_eq!(transform.cached_diagram_count(), 1);
+
+        transform_markdown("```mermaid\ngraph TD\n  x --> y\n```\n");
+        assert_eq!(transform.cached_diagram_count(), 2);
+    }
+
+    #[test]
+    fn normalize_svg_inlines_font_classes_and_prefixes_ids() {
+        let svg =
+            "<?xml version=\"1.0\"?>\n<svg width=\"1\"><style>.a0 { font-size: 14px; }</style>\
+                   <defs><marker id=\"endarrow\"/></defs><text class=\"a0\">x</text>\
+                   <path marker-end=\"url(#endarrow)\"/><textPath href=\"#arrow1\"/></svg>";
+        let normalized = normalize_svg(svg, "p-");
+
+        assert!(normalized.starts_with("<svg role=\"img\" width=\"1\">"));
+        assert!(normalized.contains("<text font-size=\"14\">"));
+        assert!(normalized.contains("id=\"p-endarrow\""));
+        assert!(normalized.contains("url(#p-endarrow)"));
+        assert!(normalized.contains("href=\"#p-arrow1\""));
+        assert!(!normalized.contains("<style"));
+    }
+}
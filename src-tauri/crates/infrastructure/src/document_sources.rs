@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::MarkdownFileRepository;
+use markdown_viewer_domain::document_source::SourceDocument;
+
+/// A source that owns some of the inputs the viewer is asked to open, such as standard input.
+pub trait DocumentSource: MarkdownFileRepository {
+    /// Whether `path_input` names a document of this source rather than a file on disk.
+    fn handles(&self, path_input: &str) -> bool;
+}
+
+/// Reads each document from the first source that handles its input, and from files on disk
+/// otherwise.
+pub struct DocumentSources {
+    sources: Vec<Arc<dyn DocumentSource>>,
+    files: Arc<dyn MarkdownFileRepository>,
+}
+
+impl DocumentSources {
+    pub fn new(files: Arc<dyn MarkdownFileRepository>) -> Self {
+        Self {
+            sources: Vec::new(),
+            files,
+        }
+    }
+
+    /// Adds a source, consulted after the ones added before it.
+    pub fn with_source(mut self, source: Arc<dyn DocumentSource>) -> Self {
+        self.sources.push(source);
+        self
+    }
+}
+
+impl DocumentSources {
+    fn source_for(&self, path_input: &str) -> Option<&Arc<dyn DocumentSource>> {
+        self.sources
+            .iter()
+            .find(|source| source.handles(path_input))
+    }
+}
+
+impl MarkdownFileRepository for DocumentSources {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        match self.source_for(path_input) {
+            Some(source) => source.read(path_input),
+            None => self.files.read(path_input),
+        }
+    }
+
+    fn read_as_markdown(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        match self.source_for(path_input) {
+            Some(source) => source.read_as_markdown(path_input),
+            None => self.files.read_as_markdown(path_input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::MarkdownFileRepository;
+    use markdown_viewer_domain::document_source::{DocumentOrigin, SourceDocument};
+
+    use super::DocumentSources;
+    use crate::stdin_source::StdinDocumentSource;
+
+    struct FixedFile;
+
+    impl MarkdownFileRepository for FixedFile {
+        fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+            if path_input != "/docs/guide.md" {
+                return Err(MarkdownViewerError::FileNotFound(PathBuf::from(path_input)));
+            }
+            Ok(SourceDocument::file(
+                PathBuf::from(path_input),
+                "# Guide\n".to_string(),
+            ))
+        }
+    }
+
+    #[test]
+    fn routes_inputs_to_the_source_that_handles_them() {
+        let stdin = StdinDocumentSource::new("# Piped\n".to_string(), Path::new("/work"));
+        let sources = DocumentSources::new(Arc::new(FixedFile)).with_source(Arc::new(stdin));
+
+        let piped = sources.read("-").expect("stdin should be readable");
+        assert_eq!(piped.origin, DocumentOrigin::Stdin);
+        assert_eq!(piped.source, "# Piped\n");
+
+        let file = sources
+            .read("/docs/guide.md")
+            .expect("files should be readable");
+        assert_eq!(file.origin, DocumentOrigin::File);
+        assert_eq!(file.source, "# Guide\n");
+
+        assert!(matches!(
+            sources.read("/docs/missing.md"),
+            Err(MarkdownViewerError::FileNotFound(_))
+        ));
+    }
+}
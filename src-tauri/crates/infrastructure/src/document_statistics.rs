@@ -0,0 +1,500 @@
+use std::cmp::max;
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+use comrak::nodes::{AstNode, NodeValue};
+use markdown_viewer_domain::document::{
+    DocumentStatistics, Readability, RenderPreferences, TocEntry, WordCountRules,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::comrak_renderer::{has_link_ancestor, inline_text};
+
+/// Counts the structural elements the author wrote: code blocks, images, tables, links,
+/// footnotes and task items.
+///
+/// Run this on the parsed tree before any transform, so blocks that a transform replaces with
+/// rendered HTML (diagrams, CSV tables) and links that text replacements add are counted as
+/// they appear in the source. Word counts and readability are left empty.
+pub(crate) fn count_elements<'a>(root: &'a AstNode<'a>) -> DocumentStatistics {
+    let mut statistics = DocumentStatistics::default();
+
+    for node in root.descendants() {
+        match &node.data.borrow().value {
+            NodeValue::CodeBlock(..) => statistics.code_blocks += 1,
+            NodeValue::Image(..) => statistics.images += 1,
+            NodeValue::Table(..) => statistics.tables += 1,
+            NodeValue::Link(..) | NodeValue::WikiLink(..) => statistics.links += 1,
+            NodeValue::FootnoteDefinition(..) => statistics.footnotes += 1,
+            NodeValue::TaskItem(task) if task.symbol.is_some() => statistics.tasks.checked += 1,
+            NodeValue::TaskItem(..) => statistics.tasks.unchecked += 1,
+            _ => {}
+        }
+    }
+
+    statistics
+}
+
+const DEFAULT_WORDS_PER_MINUTE: usize = 225;
+/// Silent reading speeds for Chinese and Japanese text measured in characters, which is how those
+/// scripts are counted.
+const DEFAULT_HAN_CHARACTERS_PER_MINUTE: usize = 260;
+const DEFAULT_KANA_CHARACTERS_PER_MINUTE: usize = 360;
+
+/// Words counted by how they are read. Scripts written without spaces between words (Han
+/// ideographs and Japanese kana) are counted one word per character, everything else one word
+/// per whitespace-separated token.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct WordTally {
+    pub words: usize,
+    pub han_characters: usize,
+    pub kana_characters: usize,
+}
+
+impl WordTally {
+    pub fn total(&self) -> usize {
+        self.words + self.han_characters + self.kana_characters
+    }
+}
+
+impl AddAssign for WordTally {
+    fn add_assign(&mut self, other: Self) {
+        self.words += other.words;
+        self.han_characters += other.han_characters;
+        self.kana_characters += other.kana_characters;
+    }
+}
+
+/// Word counts of a rendered document.
+pub(crate) struct WordCounts {
+    pub total: WordTally,
+    /// Words per section keyed by TOC id; see [`DocumentStatistics::section_word_counts`].
+    pub sections: BTreeMap<String, usize>,
+}
+
+/// Counts the words `rules` include, in total and per section.
+///
+/// `toc` must be the table of contents built from the same tree: headings with text are matched
+/// to its entries in order, and words under an empty heading stay in the preceding section.
+pub(crate) fn count_words<'a>(
+    root: &'a AstNode<'a>,
+    toc: &[TocEntry],
+    rules: WordCountRules,
+) -> WordCounts {
+    let mut total = WordTally::default();
+    let mut sections = BTreeMap::new();
+    let mut toc_entries = toc.iter();
+    let mut section: Option<&str> = None;
+
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        let words = match &data.value {
+            NodeValue::Heading(..) => {
+                if !inline_text(node).is_empty() {
+                    section = toc_entries.next().map(|entry| entry.id.as_str());
+                    if let Some(id) = section {
+                        sections.entry(id.to_string()).or_insert(0);
+                    }
+                }
+                continue;
+            }
+            NodeValue::Text(value) => {
+                if !rules.include_links && has_link_ancestor(node) {
+                    continue;
+                }
+                word_tally(value)
+            }
+            NodeValue::Code(code) if rules.include_code => {
+                if !rules.include_links && has_link_ancestor(node) {
+                    continue;
+                }
+                word_tally(&code.literal)
+            }
+            NodeValue::CodeBlock(code_block) if rules.include_code => {
+                word_tally(&code_block.literal)
+            }
+            NodeValue::Math(math) if rules.include_code => word_tally(&math.literal),
+            NodeValue::FrontMatter(front_matter) if rules.include_front_matter => {
+                word_tally(front_matter)
+            }
+            _ => continue,
+        };
+
+        total += words;
+        if let Some(id) = section {
+            *sections.entry(id.to_string()).or_insert(0) += words.total();
+        }
+    }
+
+    WordCounts { total, sections }
+}
+
+/// Scores the prose in paragraphs (headings, code and tables are left out) with the Flesch
+/// reading-ease and Flesch-Kincaid grade formulas, rounded to one decimal.
+pub(crate) fn readability<'a>(root: &'a AstNode<'a>) -> Option<Readability> {
+    let mut words = 0_usize;
+    let mut sentences = 0_usize;
+    let mut syllables = 0_usize;
+
+    for node in root.descendants() {
+        if !matches!(node.data.borrow().value, NodeValue::Paragraph) {
+            continue;
+        }
+        let text = paragraph_text(node);
+        let paragraph_words: Vec<&str> = text
+            .split_whitespace()
+            // The Flesch formulas only apply to alphabetic scripts; CJK text is left out.
+            .filter(|word| {
+                word.chars().any(char::is_alphanumeric)
+                    && !word
+                        .chars()
+                        .any(|character| cjk_script(character).is_some())
+            })
+            .collect();
+        if paragraph_words.is_empty() {
+            continue;
+        }
+
+        words += paragraph_words.len();
+        syllables += paragraph_words
+            .iter()
+            .map(|word| syllable_count(word))
+            .sum::<usize>();
+        // A paragraph without terminal punctuation (a list item, a caption) is one sentence.
+        sentences += sentence_count(&text).max(1);
+    }
+
+    if words == 0 {
+        return None;
+    }
+
+    let words_per_sentence = words as f64 / sentences as f64;
+    let syllables_per_word = syllables as f64 / words as f64;
+    Some(Readability {
+        flesch_reading_ease: round_to_tenth(
+            206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word,
+        ),
+        flesch_kincaid_grade: round_to_tenth(
+            0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59,
+        ),
+    })
+}
+
+/// Minutes needed to read `tally` at the speeds in `preferences`, at least one.
+pub(crate) fn reading_time_minutes(tally: WordTally, preferences: &RenderPreferences) -> u16 {
+    let minutes = |count: usize, speed: Option<usize>, default_speed: usize| {
+        count as f64 / speed.unwrap_or(default_speed).max(1) as f64
+    };
+    let total = minutes(
+        tally.words,
+        preferences.words_per_minute,
+        DEFAULT_WORDS_PER_MINUTE,
+    ) + minutes(
+        tally.han_characters,
+        preferences.han_characters_per_minute,
+        DEFAULT_HAN_CHARACTERS_PER_MINUTE,
+    ) + minutes(
+        tally.kana_characters,
+        preferences.kana_characters_per_minute,
+        DEFAULT_KANA_CHARACTERS_PER_MINUTE,
+    );
+    max(1, total.ceil() as u16)
+}
+
+pub(crate) fn word_tally(content: &str) -> WordTally {
+    let mut tally = WordTally::default();
+
+    for token in content.split_whitespace() {
+        if !token
+            .chars()
+            .any(|character| cjk_script(character).is_some())
+        {
+            tally.words += 1;
+            continue;
+        }
+        // Split tokens such as `用Rust写的` at Unicode word boundaries so embedded Latin words
+        // still count once while every CJK character counts on its own.
+        for segment in token.unicode_words() {
+            let mut has_cjk = false;
+            for character in segment.chars() {
+                match cjk_script(character) {
+                    Some(CjkScript::Han) => tally.han_characters += 1,
+                    Some(CjkScript::Kana) => tally.kana_characters += 1,
+                    None => continue,
+                }
+                has_cjk = true;
+            }
+            if !has_cjk {
+                tally.words += 1;
+            }
+        }
+    }
+
+    tally
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CjkScript {
+    Han,
+    Kana,
+}
+
+/// The space-less script `character` belongs to. Hangul is not included: Korean separates words
+/// with spaces.
+fn cjk_script(character: char) -> Option<CjkScript> {
+    match character {
+        '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}'
+        | '\u{30000}'..='\u{323AF}' => Some(CjkScript::Han),
+        '\u{3041}'..='\u{309F}'
+        | '\u{30A0}'..='\u{30FA}'
+        | '\u{30FC}'..='\u{30FF}'
+        | '\u{31F0}'..='\u{31FF}'
+        | '\u{FF66}'..='\u{FF9D}' => Some(CjkScript::Kana),
+        _ => None,
+    }
+}
+
+/// The text a paragraph reads as; inline code is read as a word, other markup is skipped.
+fn paragraph_text<'a>(paragraph: &'a AstNode<'a>) -> String {
+    let mut text = String::new();
+    for descendant in paragraph.descendants().skip(1) {
+        match &descendant.data.borrow().value {
+            NodeValue::Text(value) => text.push_str(value),
+            NodeValue::Code(code) => text.push_str(&code.literal),
+            NodeValue::LineBreak | NodeValue::SoftBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Runs of `.`, `!` or `?` that end a word, so decimals and abbreviations inside a word such as
+/// `3.5` or `e.g` are not sentence breaks.
+fn sentence_count(text: &str) -> usize {
+    let mut count = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(current) = chars.next() {
+        if !matches!(current, '.' | '!' | '?') {
+            continue;
+        }
+        while chars
+            .next_if(|next| matches!(next, '.' | '!' | '?'))
+            .is_some()
+        {}
+        if !matches!(chars.peek(), Some(next) if !next.is_whitespace()) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Estimates syllables as groups of vowels, dropping a silent final `e`. Words without vowels
+/// (numbers, acronyms) count as one syllable.
+fn syllable_count(word: &str) -> usize {
+    let letters: Vec<char> = word
+        .chars()
+        .filter(|character| character.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let is_vowel = |character: char| matches!(character, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+
+    let mut groups = 0;
+    let mut previous_vowel = false;
+    for &character in &letters {
+        let vowel = is_vowel(character);
+        if vowel && !previous_vowel {
+            groups += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    let silent_e = letters.len() > 2
+        && letters.ends_with(&['e'])
+        && !letters.ends_with(&['l', 'e'])
+        && !is_vowel(letters[letters.len() - 2]);
+    if silent_e && groups > 1 {
+        groups -= 1;
+    }
+
+    groups.max(1)
+}
+
+fn round_to_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::{RenderPreferences, RenderedMarkdown, TaskCompletion};
+
+    use super::{sentence_count, syllable_count, word_tally, WordTally};
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    fn render(markdown: &str) -> RenderedMarkdown {
+        ComrakMarkdownRenderer::new()
+            .render(
+                markdown,
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("render should succeed")
+    }
+
+    #[test]
+    fn counts_words_per_section_by_toc_id() {
+        let rendered = render(
+            "Intro words here\n\n# Setup\n\nOne two three\n\n## Linux\n\nFour five\n\n# Setup\n\nSix\n",
+        );
+        let sections = &rendered.statistics.section_word_counts;
+
+        assert_eq!(sections.get("mdv-setup"), Some(&4));
+        assert_eq!(sections.get("mdv-linux"), Some(&3));
+        assert_eq!(sections.get("mdv-setup-1"), Some(&2));
+        assert_eq!(sections.len(), 3);
+        assert_eq!(rendered.word_count, 3 + 4 + 3 + 2);
+    }
+
+    #[test]
+    fn counts_structural_elements_and_tasks() {
+        let rendered = render(
+            "# Tasks\n\n\
+             - [x] done\n- [ ] open\n- [ ] also open\n\n\
+             See [docs](https://example.com) and ![logo](logo.png).[^note]\n\n\
+             | a | b |\n|---|---|\n| 1 | 2 |\n\n\
+             ```rust\nfn main() {}\n```\n\n\
+             ```mermaid\ngraph TD; A-->B\n```\n\n\
+             [^note]: A footnote.\n",
+        );
+        let statistics = &rendered.statistics;
+
+        assert_eq!(
+            statistics.tasks,
+            TaskCompletion {
+                checked: 1,
+                unchecked: 2
+            }
+        );
+        assert_eq!(statistics.links, 1);
+        assert_eq!(statistics.images, 1);
+        assert_eq!(statistics.tables, 1);
+        assert_eq!(statistics.code_blocks, 2);
+        assert_eq!(statistics.footnotes, 1);
+    }
+
+    #[test]
+    fn scores_readability_of_paragraph_prose() {
+        let simple = render("The cat sat on the mat. The dog ran to the park.\n")
+            .statistics
+            .readability
+            .expect("prose should be scored");
+        let dense = render(
+            "Comprehensive architectural documentation necessitates considerable organizational \
+             deliberation regarding interdependent infrastructural responsibilities.\n",
+        )
+        .statistics
+        .readability
+        .expect("prose should be scored");
+
+        assert!(simple.flesch_reading_ease > 90.0);
+        assert!(simple.flesch_kincaid_grade < 3.0);
+        assert!(dense.flesch_reading_ease < simple.flesch_reading_ease);
+        assert!(dense.flesch_kincaid_grade > 15.0);
+    }
+
+    #[test]
+    fn documents_without_prose_have_no_readability() {
+        let rendered = render("# Title\n\n```\ncode only\n```\n");
+
+        assert_eq!(rendered.statistics.readability, None);
+    }
+
+    #[test]
+    fn words_per_minute_preference_sets_reading_time() {
+        let markdown = "word ".repeat(600);
+        let default_speed = render(&markdown);
+        let slow = ComrakMarkdownRenderer::new()
+            .render(
+                &markdown,
+                Path::new("notes.md"),
+                RenderPreferences {
+                    words_per_minute: Some(100),
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("render should succeed");
+
+        assert_eq!(default_speed.reading_time_minutes, 3);
+        assert_eq!(slow.reading_time_minutes, 6);
+    }
+
+    #[test]
+    fn counts_cjk_characters_individually_in_mixed_script_text() {
+        let tally = |han_characters, kana_characters, words| WordTally {
+            words,
+            han_characters,
+            kana_characters,
+        };
+
+        assert_eq!(word_tally("Hello 世界"), tally(2, 0, 1));
+        assert_eq!(word_tally("用Rust写的程序"), tally(5, 0, 1));
+        assert_eq!(word_tally("日本語のテキストです。"), tally(3, 7, 0));
+        assert_eq!(word_tally("안녕하세요 세계"), tally(0, 0, 2));
+        assert_eq!(word_tally("don't stop-now — ok"), tally(0, 0, 4));
+    }
+
+    #[test]
+    fn chinese_documents_use_character_reading_speed() {
+        let markdown = format!("# 概要\n\n{}\n", "中文".repeat(1299));
+        let rendered = render(&markdown);
+        let slow = ComrakMarkdownRenderer::new()
+            .render(
+                &markdown,
+                Path::new("notes.md"),
+                RenderPreferences {
+                    han_characters_per_minute: Some(130),
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("render should succeed");
+
+        assert_eq!(rendered.word_count, 2600);
+        assert_eq!(
+            rendered.statistics.section_word_counts.get("mdv-概要"),
+            Some(&2600)
+        );
+        assert_eq!(rendered.reading_time_minutes, 10);
+        assert_eq!(slow.reading_time_minutes, 20);
+        assert_eq!(rendered.statistics.readability, None);
+    }
+
+    #[test]
+    fn mixed_script_reading_time_adds_per_script_minutes() {
+        let markdown = format!(
+            "{}\n\n{}\n\n{}\n",
+            "word ".repeat(225),
+            "漢".repeat(260),
+            "かな".repeat(180)
+        );
+        let rendered = render(&markdown);
+
+        assert_eq!(rendered.word_count, 225 + 260 + 360);
+        assert_eq!(rendered.reading_time_minutes, 3);
+        assert!(rendered.statistics.readability.is_some());
+    }
+
+    #[test]
+    fn sentence_and_syllable_heuristics() {
+        assert_eq!(sentence_count("Version 3.5 shipped. Really?! Yes"), 2);
+        assert_eq!(syllable_count("cat"), 1);
+        assert_eq!(syllable_count("make"), 1);
+        assert_eq!(syllable_count("table"), 2);
+        assert_eq!(syllable_count("reading"), 2);
+        assert_eq!(syllable_count("HTTP"), 1);
+    }
+}
@@ -0,0 +1,110 @@
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+
+use crate::image_resolution::has_uri_scheme;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+
+/// Replaces links that leave the local file system (web pages, `mailto:` and other URL schemes)
+/// with their text when the preferences block external links. Links to files and headings are
+/// kept.
+pub struct ExternalLinkTransform;
+
+impl ExternalLinkTransform {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ExternalLinkTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownTransform for ExternalLinkTransform {
+    fn transform<'a>(
+        &self,
+        _arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError> {
+        if !context.preferences.block_external_links {
+            return Ok(());
+        }
+
+        let external_links: Vec<_> = root
+            .descendants()
+            .filter(|node| match &node.data.borrow().value {
+                NodeValue::Link(link) => is_external(&link.url),
+                _ => false,
+            })
+            .collect();
+
+        for link in external_links {
+            while let Some(child) = link.first_child() {
+                child.detach();
+                link.insert_before(child);
+            }
+            link.detach();
+        }
+
+        Ok(())
+    }
+}
+
+fn is_external(url: &str) -> bool {
+    let url = url.trim();
+    url.starts_with("//")
+        || (has_uri_scheme(url)
+            && !url
+                .get(..5)
+                .is_some_and(|scheme| scheme.eq_ignore_ascii_case("file:")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::RenderPreferences;
+
+    use super::ExternalLinkTransform;
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    fn render(markdown: &str, block_external_links: bool) -> String {
+        ComrakMarkdownRenderer::with_transforms(vec![Box::new(ExternalLinkTransform::new())])
+            .render(
+                markdown,
+                Path::new("/docs/guide.md"),
+                RenderPreferences {
+                    block_external_links,
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work")
+            .html
+    }
+
+    #[test]
+    fn keeps_the_text_of_blocked_links() {
+        let html = render(
+            "See [the **site**](https://example.com), <mailto:team@example.com>, \
+             [setup](setup.md) and [usage](#usage).",
+            true,
+        );
+
+        assert!(!html.contains("example.com\""), "{html}");
+        assert!(html.contains("the <strong>site</strong>"), "{html}");
+        assert!(html.contains("team@example.com"), "{html}");
+        assert!(html.contains("href=\"setup.md\""), "{html}");
+        assert!(html.contains("href=\"#usage\""), "{html}");
+    }
+
+    #[test]
+    fn leaves_links_alone_unless_blocked() {
+        let html = render("[site](https://example.com)", false);
+
+        assert!(html.contains("href=\"https://example.com\""), "{html}");
+    }
+}
@@ -1,17 +1,94 @@
-use std::fs;
-use std::io::ErrorKind;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Read};
 use std::path::{Path, PathBuf};
 
 use markdown_viewer_application::error::MarkdownViewerError;
 use markdown_viewer_application::ports::MarkdownFileRepository;
+use markdown_viewer_domain::document_source::SourceDocument;
 
-const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdown", "mkd", "mkdn"];
+use crate::markdown_sniffing::looks_like_markdown;
 
-pub struct LocalMarkdownFileRepository;
+/// Extensions opened as markdown unless another list is configured. The bundle's markdown file
+/// association in `tauri.conf.json` lists the same extensions.
+pub const DEFAULT_MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdown", "mkd", "mkdn", "mdx"];
+/// Delimited data files shown as a single table; the extension doubles as the block language.
+pub const TABLE_EXTENSIONS: &[&str] = &["csv", "tsv"];
+/// Plain text files that open as markdown when their contents look like it, like files without
+/// an extension such as `README` or `CHANGELOG`.
+const SNIFFED_EXTENSIONS: &[&str] = &["txt", "text"];
+/// Bytes read from the start of a file to decide whether it holds markdown.
+const SNIFFED_BYTES: u64 = 8 * 1024;
+
+/// Decides which files the viewer opens: files with one of the configured markdown extensions,
+/// CSV/TSV tables, and extensionless or plain text files whose contents look like markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownFileTypes {
+    extensions: Vec<String>,
+}
+
+impl MarkdownFileTypes {
+    /// Extensions are matched case-insensitively; a leading `.` is ignored.
+    pub fn new<I, S>(extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized: Vec<String> = Vec::new();
+        for extension in extensions {
+            let extension = extension.as_ref().trim().trim_start_matches('.');
+            let extension = extension.to_ascii_lowercase();
+            if !extension.is_empty() && !normalized.contains(&extension) {
+                normalized.push(extension);
+            }
+        }
+        Self {
+            extensions: normalized,
+        }
+    }
+
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    pub fn is_markdown_file(&self, path: &Path) -> bool {
+        has_extension(path, &self.extensions)
+    }
+
+    /// Whether `path` opens without looking at its contents.
+    pub fn is_viewable_file(&self, path: &Path) -> bool {
+        self.is_markdown_file(path) || is_table_file(path)
+    }
+
+    /// Whether the contents of `path` decide if it opens, as for `README` or `notes.txt`.
+    pub fn is_sniffed_file(&self, path: &Path) -> bool {
+        path.extension().is_none() || has_extension(path, SNIFFED_EXTENSIONS)
+    }
+
+    /// Whether the viewer opens the existing file at `path`, reading the start of files whose
+    /// type is decided by their contents.
+    pub fn accepts(&self, path: &Path) -> bool {
+        self.is_viewable_file(path)
+            || (self.is_sniffed_file(path) && file_looks_like_markdown(path))
+    }
+}
+
+impl Default for MarkdownFileTypes {
+    fn default() -> Self {
+        Self::new(DEFAULT_MARKDOWN_EXTENSIONS)
+    }
+}
+
+pub struct LocalMarkdownFileRepository {
+    file_types: MarkdownFileTypes,
+}
 
 impl LocalMarkdownFileRepository {
     pub fn new() -> Self {
-        Self
+        Self::with_file_types(MarkdownFileTypes::default())
+    }
+
+    pub fn with_file_types(file_types: MarkdownFileTypes) -> Self {
+        Self { file_types }
     }
 }
 
@@ -22,21 +99,63 @@ impl Default for LocalMarkdownFileRepository {
 }
 
 impl MarkdownFileRepository for LocalMarkdownFileRepository {
-    fn read(&self, path_input: &str) -> Result<(PathBuf, String), MarkdownViewerError> {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
         let canonical_path = resolve_path_input(path_input)?;
-        if !is_markdown_file(&canonical_path) {
+        let viewable = self.file_types.is_viewable_file(&canonical_path);
+        if !viewable && !self.file_types.is_sniffed_file(&canonical_path) {
             return Err(MarkdownViewerError::NotMarkdown(canonical_path));
         }
 
-        let content = fs::read_to_string(&canonical_path).map_err(|source| {
-            MarkdownViewerError::ReadFile {
-                path: canonical_path.clone(),
-                reason: source.to_string(),
-            }
-        })?;
+        let content = read_text(&canonical_path)?;
+        if !viewable && !looks_like_markdown(&content) {
+            return Err(MarkdownViewerError::NotMarkdown(canonical_path));
+        }
 
-        Ok((canonical_path, content))
+        if is_table_file(&canonical_path) {
+            let content = table_document(&canonical_path, &content);
+            return Ok(SourceDocument::file(canonical_path, content));
+        }
+        Ok(SourceDocument::file(canonical_path, content))
     }
+
+    /// Any text file, tables included, is shown as its raw contents.
+    fn read_as_markdown(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        let canonical_path = resolve_path_input(path_input)?;
+        let content = read_text(&canonical_path)?;
+        Ok(SourceDocument::file(canonical_path, content))
+    }
+}
+
+fn read_text(path: &Path) -> Result<String, MarkdownViewerError> {
+    fs::read_to_string(path).map_err(|source| MarkdownViewerError::ReadFile {
+        path: path.to_path_buf(),
+        reason: source.to_string(),
+    })
+}
+
+fn file_looks_like_markdown(path: &Path) -> bool {
+    let mut bytes = Vec::new();
+    let read = File::open(path).and_then(|file| file.take(SNIFFED_BYTES).read_to_end(&mut bytes));
+    // The limit may cut a multi-byte character in half, which must not reject the file.
+    read.is_ok() && looks_like_markdown(&String::from_utf8_lossy(&bytes))
+}
+
+/// Wraps delimited data in a fenced block of its own language, so CSV/TSV files go through the
+/// same table rendering as ` ```csv ` blocks in markdown.
+pub(crate) fn table_document(path: &Path, data: &str) -> String {
+    let language = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("csv")
+        .to_ascii_lowercase();
+    let longest_backtick_run = data
+        .split(|character| character != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_backtick_run.max(2) + 1);
+    let newline = if data.ends_with('\n') { "" } else { "\n" };
+    format!("{fence}{language}\n{data}{newline}{fence}\n")
 }
 
 pub fn resolve_path_input(path_input: &str) -> Result<PathBuf, MarkdownViewerError> {
@@ -71,13 +190,27 @@ pub fn canonicalize_existing_path(path: &Path) -> Result<PathBuf, MarkdownViewer
     Ok(canonical_path)
 }
 
+/// Whether `path` has one of the default markdown extensions.
 pub fn is_markdown_file(path: &Path) -> bool {
+    has_extension(path, DEFAULT_MARKDOWN_EXTENSIONS)
+}
+
+pub fn is_table_file(path: &Path) -> bool {
+    has_extension(path, TABLE_EXTENSIONS)
+}
+
+/// Files the viewer can open: markdown documents and CSV/TSV tables.
+pub fn is_viewable_file(path: &Path) -> bool {
+    is_markdown_file(path) || is_table_file(path)
+}
+
+fn has_extension(path: &Path, extensions: &[impl AsRef<str>]) -> bool {
     path.extension()
         .and_then(|extension| extension.to_str())
         .map(|extension| {
-            MARKDOWN_EXTENSIONS
+            extensions
                 .iter()
-                .any(|candidate| extension.eq_ignore_ascii_case(candidate))
+                .any(|candidate| extension.eq_ignore_ascii_case(candidate.as_ref()))
         })
         .unwrap_or(false)
 }
@@ -89,8 +222,12 @@ mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
 
     use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::MarkdownFileRepository;
 
-    use super::{canonicalize_existing_path, is_markdown_file, resolve_path_input};
+    use super::{
+        canonicalize_existing_path, is_markdown_file, is_viewable_file, resolve_path_input,
+        table_document, LocalMarkdownFileRepository, MarkdownFileTypes,
+    };
 
     fn temp_path(prefix: &str, extension: &str) -> PathBuf {
         let suffix = SystemTime::now()
@@ -109,6 +246,31 @@ mod tests {
         assert!(!is_markdown_file(PathBuf::from("/tmp/spec.txt").as_path()));
     }
 
+    #[test]
+    fn tables_are_viewable_but_not_markdown() {
+        assert!(is_viewable_file(PathBuf::from("/tmp/data.CSV").as_path()));
+        assert!(is_viewable_file(PathBuf::from("/tmp/data.tsv").as_path()));
+        assert!(!is_markdown_file(PathBuf::from("/tmp/data.csv").as_path()));
+        assert!(!is_viewable_file(PathBuf::from("/tmp/data.txt").as_path()));
+    }
+
+    #[test]
+    fn table_files_are_read_as_a_single_table_block() {
+        let file = temp_path("mdv-repo", "csv");
+        fs::write(&file, "name,notes\na,\"```\"").expect("temp csv should be writable");
+
+        let document = LocalMarkdownFileRepository::new()
+            .read(&file.to_string_lossy())
+            .expect("csv files should be readable");
+        assert_eq!(document.source, "````csv\nname,notes\na,\"```\"\n````\n");
+        assert_eq!(
+            table_document(PathBuf::from("/tmp/x.TSV").as_path(), "a\tb\n"),
+            "```tsv\na\tb\n```\n"
+        );
+
+        let _ = fs::remove_file(file);
+    }
+
     #[test]
     fn resolve_path_input_supports_file_url_for_existing_files() {
         let file = temp_path("mdv-repo", "md");
@@ -139,4 +301,72 @@ mod tests {
             other => panic!("unexpected error variant: {other:?}"),
         }
     }
+
+    #[test]
+    fn configured_extensions_replace_the_defaults() {
+        let file_types = MarkdownFileTypes::new([".MD", "txt", "md", " "]);
+
+        assert_eq!(file_types.extensions(), ["md", "txt"]);
+        assert!(file_types.is_markdown_file(PathBuf::from("/tmp/notes.TXT").as_path()));
+        assert!(!file_types.is_markdown_file(PathBuf::from("/tmp/notes.markdown").as_path()));
+        assert!(file_types.is_viewable_file(PathBuf::from("/tmp/data.csv").as_path()));
+        assert!(
+            MarkdownFileTypes::default().is_markdown_file(PathBuf::from("/tmp/page.mdx").as_path())
+        );
+    }
+
+    #[test]
+    fn extensionless_and_text_files_open_when_they_look_like_markdown() {
+        let folder = temp_path("mdv-sniff", "d");
+        fs::create_dir_all(&folder).expect("temp folder should be creatable");
+        let readme = folder.join("README");
+        let notes = folder.join("notes.txt");
+        let script = folder.join("build");
+        let log = folder.join("server.log");
+        fs::write(&readme, "Project\n=======\n\nSee [docs](docs/).\n").expect("fixture");
+        fs::write(&notes, "# Notes\n\n- first\n- second\n").expect("fixture");
+        fs::write(&script, "#!/bin/sh\n# Build it\nmake\n").expect("fixture");
+        fs::write(&log, "# started\n- request\n").expect("fixture");
+        let repository = LocalMarkdownFileRepository::new();
+        let file_types = MarkdownFileTypes::default();
+
+        for path in [&readme, &notes] {
+            assert!(file_types.accepts(path));
+            let document = repository
+                .read(&path.to_string_lossy())
+                .expect("markdown-looking files should be readable");
+            assert_eq!(document.source, fs::read_to_string(path).expect("fixture"));
+        }
+        for path in [&script, &log] {
+            assert!(!file_types.accepts(path));
+            assert!(matches!(
+                repository.read(&path.to_string_lossy()),
+                Err(MarkdownViewerError::NotMarkdown(_))
+            ));
+        }
+
+        let _ = fs::remove_dir_all(folder);
+    }
+
+    #[test]
+    fn read_as_markdown_opens_any_text_file_as_is() {
+        let folder = temp_path("mdv-anyway", "d");
+        fs::create_dir_all(&folder).expect("temp folder should be creatable");
+        let log = folder.join("server.log");
+        let table = folder.join("data.csv");
+        fs::write(&log, "plain text\n").expect("fixture");
+        fs::write(&table, "a,b\n").expect("fixture");
+        let repository = LocalMarkdownFileRepository::new();
+
+        let document = repository
+            .read_as_markdown(&log.to_string_lossy())
+            .expect("any text file should open as markdown");
+        assert_eq!(document.source, "plain text\n");
+        let document = repository
+            .read_as_markdown(&table.to_string_lossy())
+            .expect("tables should open as markdown");
+        assert_eq!(document.source, "a,b\n");
+
+        let _ = fs::remove_dir_all(folder);
+    }
 }
@@ -0,0 +1,176 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::MarkdownFileWriter;
+
+use crate::file_repository::is_table_file;
+
+/// Writes markdown files by writing a sibling temporary file and renaming it over the original,
+/// so watchers and other readers never observe a half-written document. The original file's
+/// permissions are kept.
+pub struct LocalMarkdownFileWriter;
+
+impl LocalMarkdownFileWriter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LocalMarkdownFileWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownFileWriter for LocalMarkdownFileWriter {
+    fn write_atomically(&self, path: &Path, contents: &str) -> Result<(), MarkdownViewerError> {
+        // Tables are shown through a generated markdown wrapper, which must never be written
+        // back over the data file.
+        if is_table_file(path) {
+            return Err(MarkdownViewerError::NotMarkdown(path.to_path_buf()));
+        }
+        let write_error = |reason: String| MarkdownViewerError::WriteFile {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let temporary_path = temporary_path(path).ok_or_else(|| {
+            write_error("the path has no parent directory or file name".to_string())
+        })?;
+        let result = write_and_replace(path, &temporary_path, contents);
+        if result.is_err() {
+            let _ = fs::remove_file(&temporary_path);
+        }
+        result.map_err(|source| write_error(source.to_string()))
+    }
+}
+
+/// Distinguishes temporary files of concurrent writes from the same process.
+static WRITE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn temporary_path(path: &Path) -> Option<PathBuf> {
+    let directory = path.parent()?;
+    let file_name = path.file_name()?.to_string_lossy();
+    let write = WRITE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    Some(directory.join(format!(
+        ".{file_name}.mdv-{}-{write}.tmp",
+        std::process::id()
+    )))
+}
+
+fn write_and_replace(path: &Path, temporary_path: &Path, contents: &str) -> std::io::Result<()> {
+    let permissions = fs::metadata(path)?.permissions();
+    let mut file = File::create(temporary_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    fs::set_permissions(temporary_path, permissions)?;
+    fs::rename(temporary_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::MarkdownFileWriter;
+
+    use super::{temporary_path, LocalMarkdownFileWriter};
+
+    fn temp_folder() -> PathBuf {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic after epoch")
+            .as_nanos();
+        let folder = std::env::temp_dir().join(format!("mdv-writer-{suffix}"));
+        fs::create_dir_all(&folder).expect("temp folder should be creatable");
+        folder
+    }
+
+    #[test]
+    fn replaces_contents_without_leaving_temporary_files() {
+        let folder = temp_folder();
+        let file = folder.join("plan.md");
+        fs::write(&file, "- [ ] write\n").expect("fixture should be writable");
+
+        LocalMarkdownFileWriter::new()
+            .write_atomically(&file, "- [x] write\n")
+            .expect("write should succeed");
+
+        assert_eq!(
+            fs::read_to_string(&file).expect("file should be readable"),
+            "- [x] write\n"
+        );
+        let entries: Vec<_> = fs::read_dir(&folder)
+            .expect("folder should be listable")
+            .flatten()
+            .map(|entry| entry.file_name())
+            .collect();
+        assert_eq!(entries, ["plan.md"]);
+
+        let _ = fs::remove_dir_all(folder);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let folder = temp_folder();
+        let file = folder.join("script-notes.md");
+        fs::write(&file, "old").expect("fixture should be writable");
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o640))
+            .expect("permissions should be settable");
+
+        LocalMarkdownFileWriter::new()
+            .write_atomically(&file, "new")
+            .expect("write should succeed");
+
+        let mode = fs::metadata(&file)
+            .expect("file should exist")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o640);
+
+        let _ = fs::remove_dir_all(folder);
+    }
+
+    #[test]
+    fn temporary_paths_differ_between_writes() {
+        let file = PathBuf::from("/docs/plan.md");
+
+        let first = temporary_path(&file).expect("path should have a parent");
+        let second = temporary_path(&file).expect("path should have a parent");
+
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), file.parent());
+    }
+
+    #[test]
+    fn refuses_non_markdown_and_missing_files() {
+        let folder = temp_folder();
+        let table = folder.join("data.csv");
+        fs::write(&table, "a,b\n").expect("fixture should be writable");
+        let writer = LocalMarkdownFileWriter::new();
+
+        assert!(matches!(
+            writer.write_atomically(&table, "```csv\na,b\n```\n"),
+            Err(MarkdownViewerError::NotMarkdown(_))
+        ));
+        assert!(matches!(
+            writer.write_atomically(&folder.join("missing.md"), "text"),
+            Err(MarkdownViewerError::WriteFile { .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(&table).expect("table should be readable"),
+            "a,b\n"
+        );
+
+        let _ = fs::remove_dir_all(folder);
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use gix::bstr::ByteSlice;
+use gix::revision::walk::Sorting;
+use gix::traverse::commit::simple::CommitTimeOrder;
+use gix::ObjectId;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::{MarkdownFileRepository, MarkdownRevisionRepository};
+use markdown_viewer_domain::document_source::SourceDocument;
+use markdown_viewer_domain::revision::FileRevision;
+
+use crate::file_repository::{
+    is_table_file, resolve_path_input, table_document, MarkdownFileTypes,
+};
+
+pub const HEAD_REVISION: &str = "HEAD";
+
+/// Reads markdown files from the local git repository that contains them, either at a fixed
+/// revision as a [`MarkdownFileRepository`] or at any revision as a
+/// [`MarkdownRevisionRepository`]. Repositories are only read; nothing is fetched or checked out.
+pub struct GitMarkdownFileRepository {
+    revision: String,
+}
+
+impl GitMarkdownFileRepository {
+    /// Reads files as they are committed at `HEAD`.
+    pub fn new() -> Self {
+        Self::at_revision(HEAD_REVISION)
+    }
+
+    /// Reads files as they are committed at `revision`, such as a commit ID, branch or tag.
+    pub fn at_revision(revision: impl Into<String>) -> Self {
+        Self {
+            revision: revision.into(),
+        }
+    }
+}
+
+impl Default for GitMarkdownFileRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownFileRepository for GitMarkdownFileRepository {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        let (path, source) = self.read_at(path_input, &self.revision)?;
+        Ok(SourceDocument::file(path, source))
+    }
+}
+
+impl MarkdownRevisionRepository for GitMarkdownFileRepository {
+    fn read_at(
+        &self,
+        path_input: &str,
+        revision: &str,
+    ) -> Result<(PathBuf, String), MarkdownViewerError> {
+        let file = VersionedFile::open(path_input)?;
+        let unknown_revision =
+            |error: &dyn std::fmt::Display| MarkdownViewerError::UnknownRevision {
+                revision: revision.to_string(),
+                reason: error.to_string(),
+            };
+        let object = file
+            .repository
+            .rev_parse_single(revision)
+            .map_err(|error| unknown_revision(&error))?
+            .object()
+            .map_err(|error| file.error(error))?;
+        let commit_id = object
+            .peel_to_commit()
+            .map_err(|error| unknown_revision(&error))?
+            .id;
+        let blob_id =
+            file.blob_id_at(commit_id)?
+                .ok_or_else(|| MarkdownViewerError::FileNotInRevision {
+                    path: file.path.clone(),
+                    revision: revision.to_string(),
+                })?;
+        let data = file
+            .repository
+            .find_object(blob_id)
+            .map_err(|error| file.error(error))?
+            .detach()
+            .data;
+        let content = String::from_utf8(data).map_err(|_| MarkdownViewerError::ReadFile {
+            path: file.path.clone(),
+            reason: format!("the file at revision `{revision}` is not valid UTF-8"),
+        })?;
+
+        if is_table_file(&file.path) {
+            let content = table_document(&file.path, &content);
+            return Ok((file.path, content));
+        }
+        Ok((file.path, content))
+    }
+
+    /// Walks the history of `HEAD` by commit time and keeps the commits whose version of the file
+    /// differs from that of every parent, which matches `git log -- <path>` for files that were
+    /// never renamed.
+    fn history(
+        &self,
+        path_input: &str,
+    ) -> Result<(PathBuf, Vec<FileRevision>), MarkdownViewerError> {
+        let file = VersionedFile::open(path_input)?;
+        let head = file.repository.head().map_err(|error| file.error(error))?;
+        let Some(head_id) = head.id() else {
+            return Ok((file.path, Vec::new()));
+        };
+        let walk = head_id
+            .ancestors()
+            .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
+            .all()
+            .map_err(|error| file.error(error))?;
+
+        let mut blob_ids = HashMap::new();
+        let mut revisions = Vec::new();
+        for info in walk {
+            let info = info.map_err(|error| file.error(error))?;
+            let Some(blob_id) = file.cached_blob_id_at(&mut blob_ids, info.id)? else {
+                continue;
+            };
+            let mut changed = true;
+            for parent_id in info.parent_ids.iter() {
+                if file.cached_blob_id_at(&mut blob_ids, *parent_id)? == Some(blob_id) {
+                    changed = false;
+                    break;
+                }
+            }
+            if changed {
+                let commit = info.object().map_err(|error| file.error(error))?;
+                revisions.push(file.revision(&commit)?);
+            }
+        }
+
+        Ok((file.path, revisions))
+    }
+}
+
+/// A viewable file in the working tree of a git repository.
+struct VersionedFile {
+    path: PathBuf,
+    repository: gix::Repository,
+    /// The path inside the repository, which is how trees address the file.
+    relative_path: PathBuf,
+}
+
+impl VersionedFile {
+    fn open(path_input: &str) -> Result<Self, MarkdownViewerError> {
+        let path = resolve_path_input(path_input)?;
+        // Files such as `README` qualify by their current contents.
+        if !MarkdownFileTypes::default().accepts(&path) {
+            return Err(MarkdownViewerError::NotMarkdown(path));
+        }
+
+        let folder = path.parent().unwrap_or(Path::new("/"));
+        let repository =
+            gix::discover(folder).map_err(|_| MarkdownViewerError::NotVersioned(path.clone()))?;
+        let relative_path = repository
+            .workdir()
+            .and_then(|workdir| workdir.canonicalize().ok())
+            .and_then(|workdir| path.strip_prefix(workdir).ok().map(Path::to_path_buf))
+            .ok_or_else(|| MarkdownViewerError::NotVersioned(path.clone()))?;
+
+        Ok(Self {
+            path,
+            repository,
+            relative_path,
+        })
+    }
+
+    fn blob_id_at(&self, commit_id: ObjectId) -> Result<Option<ObjectId>, MarkdownViewerError> {
+        let tree = self
+            .repository
+            .find_commit(commit_id)
+            .map_err(|error| self.error(error))?
+            .tree()
+            .map_err(|error| self.error(error))?;
+        let entry = tree
+            .lookup_entry_by_path(&self.relative_path)
+            .map_err(|error| self.error(error))?;
+
+        Ok(entry
+            .filter(|entry| entry.mode().is_blob())
+            .map(|entry| entry.object_id()))
+    }
+
+    fn cached_blob_id_at(
+        &self,
+        cache: &mut HashMap<ObjectId, Option<ObjectId>>,
+        commit_id: ObjectId,
+    ) -> Result<Option<ObjectId>, MarkdownViewerError> {
+        if let Some(blob_id) = cache.get(&commit_id) {
+            return Ok(*blob_id);
+        }
+        let blob_id = self.blob_id_at(commit_id)?;
+        cache.insert(commit_id, blob_id);
+        Ok(blob_id)
+    }
+
+    fn revision(&self, commit: &gix::Commit<'_>) -> Result<FileRevision, MarkdownViewerError> {
+        let author = commit.author().map_err(|error| self.error(error))?.trim();
+        let authored_at = author.time().map_err(|error| self.error(error))?.seconds;
+        let message = commit
+            .message_raw()
+            .map_err(|error| self.error(error))?
+            .to_str_lossy()
+            .trim_end()
+            .to_string();
+
+        Ok(FileRevision {
+            commit_id: commit.id.to_string(),
+            author_name: author.name.to_str_lossy().into_owned(),
+            author_email: author.email.to_str_lossy().into_owned(),
+            authored_at,
+            summary: message.lines().next().unwrap_or_default().to_string(),
+            message,
+        })
+    }
+
+    fn error(&self, error: impl std::fmt::Display) -> MarkdownViewerError {
+        MarkdownViewerError::VersionControl {
+            path: self.path.clone(),
+            reason: error.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use gix::objs::tree::{Entry, EntryKind};
+    use gix::objs::Tree;
+    use gix::ObjectId;
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::{MarkdownFileRepository, MarkdownRevisionRepository};
+
+    use super::GitMarkdownFileRepository;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after the epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("mdv-git-{name}-{unique}"));
+        fs::create_dir_all(&dir).expect("temp dir should be creatable");
+        dir.canonicalize().expect("temp dir should resolve")
+    }
+
+    /// Commits `files` as the complete root tree on top of `parent` and writes them to the
+    /// working tree as well.
+    fn commit(
+        repository: &gix::Repository,
+        files: &[(&str, &str)],
+        message: &str,
+        time: &str,
+        parent: Option<ObjectId>,
+    ) -> ObjectId {
+        let workdir = repository
+            .workdir()
+            .expect("repository should have a workdir");
+        let mut entries = Vec::new();
+        for (name, contents) in files {
+            fs::write(workdir.join(name), contents).expect("file should be writable");
+            entries.push(Entry {
+                mode: EntryKind::Blob.into(),
+                filename: (*name).into(),
+                oid: repository
+                    .write_blob(contents.as_bytes())
+                    .expect("blob should be writable")
+                    .detach(),
+            });
+        }
+        entries.sort_by(|a, b| a.filename.cmp(&b.filename));
+        let tree = repository
+            .write_object(&Tree { entries })
+            .expect("tree should be writable");
+        let author = gix::actor::SignatureRef {
+            name: "Ada Lovelace".into(),
+            email: "ada@example.com".into(),
+            time,
+        };
+
+        repository
+            .commit_as(author, author, "HEAD", message, tree, parent)
+            .expect("commit should be writable")
+            .detach()
+    }
+
+    fn path_input(path: &Path) -> String {
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn reads_files_at_commits_branches_and_tags() {
+        let root = temp_dir("read");
+        let repository = gix::init(&root).expect("repository should be creatable");
+        let first = commit(
+            &repository,
+            &[("guide.md", "# First\n")],
+            "Add guide",
+            "1700000000 +0000",
+            None,
+        );
+        repository
+            .tag_reference("v1", first, gix::refs::transaction::PreviousValue::Any)
+            .expect("tag should be writable");
+        commit(
+            &repository,
+            &[("guide.md", "# Second\n")],
+            "Rewrite guide",
+            "1700000100 +0000",
+            Some(first),
+        );
+        fs::write(root.join("guide.md"), "# Uncommitted\n").expect("file should be writable");
+        let guide = path_input(&root.join("guide.md"));
+        let git = GitMarkdownFileRepository::new();
+
+        assert_eq!(
+            git.read_at(&guide, &first.to_string())
+                .expect("commit should be readable"),
+            (root.join("guide.md"), "# First\n".to_string())
+        );
+        assert_eq!(
+            git.read_at(&guide, "v1").expect("tag should be readable").1,
+            "# First\n"
+        );
+        assert_eq!(
+            git.read_at(&guide, "HEAD~1")
+                .expect("ancestor should be readable")
+                .1,
+            "# First\n"
+        );
+        assert_eq!(
+            git.read(&guide).expect("HEAD should be readable").source,
+            "# Second\n"
+        );
+        let branch = repository
+            .head_name()
+            .expect("HEAD should be readable")
+            .expect("HEAD should point to a branch")
+            .shorten()
+            .to_string();
+        assert_eq!(
+            GitMarkdownFileRepository::at_revision(branch)
+                .read(&guide)
+                .expect("branch should be readable")
+                .source,
+            "# Second\n"
+        );
+
+        fs::remove_dir_all(root).expect("temp dir should be removable");
+    }
+
+    #[test]
+    fn lists_only_the_commits_that_changed_the_file_newest_first() {
+        let root = temp_dir("history");
+        let repository = gix::init(&root).expect("repository should be creatable");
+        let first = commit(
+            &repository,
+            &[("guide.md", "# Guide\n")],
+            "Add guide",
+            "1700000000 +0100",
+            None,
+        );
+        let second = commit(
+            &repository,
+            &[("guide.md", "# Guide\n"), ("other.md", "# Other\n")],
+            "Add other page",
+            "1700000100 +0000",
+            Some(first),
+        );
+        let third = commit(
+            &repository,
+            &[("guide.md", "# Better guide\n"), ("other.md", "# Other\n")],
+            "Improve guide\n\nClearer title.\n",
+            "1700000200 +0000",
+            Some(second),
+        );
+
+        let (path, revisions) = GitMarkdownFileRepository::new()
+            .history(&path_input(&root.join("guide.md")))
+            .expect("history should be listed");
+
+        assert_eq!(path, root.join("guide.md"));
+        let commits: Vec<&str> = revisions
+            .iter()
+            .map(|revision| revision.commit_id.as_str())
+            .collect();
+        assert_eq!(commits, [third.to_string(), first.to_string()]);
+        assert_eq!(revisions[0].summary, "Improve guide");
+        assert_eq!(revisions[0].message, "Improve guide\n\nClearer title.");
+        assert_eq!(revisions[0].author_name, "Ada Lovelace");
+        assert_eq!(revisions[0].author_email, "ada@example.com");
+        assert_eq!(revisions[1].authored_at, 1_700_000_000);
+
+        fs::remove_dir_all(root).expect("temp dir should be removable");
+    }
+
+    #[test]
+    fn reports_unknown_revisions_missing_files_and_unversioned_files() {
+        let root = temp_dir("errors");
+        let repository = gix::init(root.join("repo")).expect("repository should be creatable");
+        commit(
+            &repository,
+            &[("guide.md", "# Guide\n")],
+            "Add guide",
+            "1700000000 +0000",
+            None,
+        );
+        fs::write(root.join("repo").join("new.md"), "# New\n").expect("file should be writable");
+        fs::write(root.join("loose.md"), "# Loose\n").expect("file should be writable");
+        let git = GitMarkdownFileRepository::new();
+
+        assert!(matches!(
+            git.read_at(&path_input(&root.join("repo").join("guide.md")), "no-such-branch"),
+            Err(MarkdownViewerError::UnknownRevision { revision, .. }) if revision == "no-such-branch"
+        ));
+        assert!(matches!(
+            git.read(&path_input(&root.join("repo").join("new.md"))),
+            Err(MarkdownViewerError::FileNotInRevision { .. })
+        ));
+        assert_eq!(
+            git.history(&path_input(&root.join("repo").join("new.md")))
+                .expect("history should be listed")
+                .1,
+            []
+        );
+        assert!(matches!(
+            git.history(&path_input(&root.join("loose.md"))),
+            Err(MarkdownViewerError::NotVersioned(_))
+        ));
+
+        fs::remove_dir_all(root).expect("temp dir should be removable");
+    }
+}
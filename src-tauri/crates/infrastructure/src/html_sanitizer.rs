@@ -0,0 +1,420 @@
+use std::collections::HashSet;
+
+use markdown_viewer_domain::document::HtmlAllowlist;
+
+/// Tags comrak emits for the extensions the renderer enables. They are always allowed so
+/// sanitizing a rendered document never strips markup the renderer produced itself.
+const BASELINE_TAGS: &[&str] = &[
+    "a",
+    "blockquote",
+    "br",
+    "code",
+    "dd",
+    "del",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "img",
+    "input",
+    "li",
+    "mark",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+/// SVG elements the diagram renderers emit.
+const DIAGRAM_TAGS: &[&str] = &[
+    "circle", "clipPath", "defs", "ellipse", "g", "line", "marker", "path", "polygon", "polyline",
+    "rect", "svg", "text", "textPath", "tspan",
+];
+/// Presentation attributes used by diagram SVG. None of them can carry script or a URL other
+/// than a same-document `url(#id)` or `#id` reference.
+const DIAGRAM_ATTRIBUTES: &[&str] = &[
+    "clip-path",
+    "cx",
+    "cy",
+    "d",
+    "dominant-baseline",
+    "dy",
+    "fill",
+    "font-family",
+    "font-size",
+    "height",
+    "marker-end",
+    "marker-start",
+    "markerHeight",
+    "markerWidth",
+    "orient",
+    "points",
+    "r",
+    "refX",
+    "refY",
+    "role",
+    "rx",
+    "ry",
+    "startOffset",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-width",
+    "text-anchor",
+    "transform",
+    "viewBox",
+    "width",
+    "x",
+    "x1",
+    "x2",
+    "y",
+    "y1",
+    "y2",
+    "xmlns",
+];
+const BASELINE_GENERIC_ATTRIBUTES: &[&str] =
+    &["id", "class", "title", "align", "aria-hidden", "aria-label"];
+const BASELINE_URL_SCHEMES: &[&str] = &["http", "https", "mailto", "file", "asset", "data"];
+/// Elements that may use `data:` URLs (inlined images); anywhere else they are dropped.
+const DATA_URL_ELEMENTS: &[&str] = &["img", "source"];
+/// Tags whose content is dropped together with the tag, whatever the allowlist says.
+const REMOVED_WITH_CONTENT: &[&str] = &["script", "style"];
+/// Tags that embed other documents, redirect or re-base the page, submit forms or load
+/// stylesheets. They are never kept, whatever the allowlist says.
+const DENIED_TAGS: &[&str] = &[
+    "applet", "base", "embed", "form", "frame", "frameset", "iframe", "link", "meta", "object",
+];
+/// Attributes that carry an inline document, a form target or styling. They are never kept,
+/// whatever the allowlist says; `rel` is always rewritten on links, so it cannot be passed through.
+const DENIED_ATTRIBUTES: &[&str] = &[
+    "action",
+    "formaction",
+    "http-equiv",
+    "rel",
+    "srcdoc",
+    "style",
+    "xlink:href",
+];
+
+/// Allowlist-based cleaning for HTML that does not come out of comrak's escaping formatter.
+///
+/// Scripts, styles, frames, forms, `meta`/`base` tags, event handler attributes and
+/// non-allowlisted URL schemes (such as `javascript:`) are always removed. The markup comrak itself produces (heading anchors, task
+/// checkboxes, footnotes, alerts, math spans, diagram SVG) is always kept; a user [`HtmlAllowlist`] widens
+/// that baseline for raw HTML written in the document.
+pub struct HtmlSanitizer {
+    tags: Vec<String>,
+    attributes: Vec<String>,
+    url_schemes: Vec<String>,
+}
+
+impl HtmlSanitizer {
+    /// A sanitizer that keeps only the renderer's own markup.
+    pub fn new() -> Self {
+        Self {
+            tags: Vec::new(),
+            attributes: Vec::new(),
+            url_schemes: Vec::new(),
+        }
+    }
+
+    /// A sanitizer that additionally keeps the tags, attributes and URL schemes in `allowlist`.
+    ///
+    /// [`REMOVED_WITH_CONTENT`] and [`DENIED_TAGS`] tags, [`DENIED_ATTRIBUTES`], `on*` event
+    /// handler attributes and `javascript:` URLs are ignored even when listed.
+    pub fn with_allowlist(allowlist: &HtmlAllowlist) -> Self {
+        let normalized = |values: &[String]| -> Vec<String> {
+            values
+                .iter()
+                .map(|value| value.trim().to_ascii_lowercase())
+                .filter(|value| !value.is_empty())
+                .collect()
+        };
+
+        Self {
+            tags: normalized(&allowlist.tags)
+                .into_iter()
+                .filter(|tag| {
+                    !REMOVED_WITH_CONTENT.contains(&tag.as_str())
+                        && !DENIED_TAGS.contains(&tag.as_str())
+                })
+                .collect(),
+            attributes: normalized(&allowlist.attributes)
+                .into_iter()
+                .filter(|attribute| {
+                    !attribute.starts_with("on") && !DENIED_ATTRIBUTES.contains(&attribute.as_str())
+                })
+                .collect(),
+            url_schemes: normalized(&allowlist.url_schemes)
+                .into_iter()
+                .filter(|scheme| scheme != "javascript" && scheme != "vbscript")
+                .collect(),
+        }
+    }
+
+    pub fn sanitize(&self, html: &str) -> String {
+        let mut tags: HashSet<&str> = BASELINE_TAGS.iter().copied().collect();
+        tags.extend(DIAGRAM_TAGS);
+        tags.extend(self.tags.iter().map(String::as_str));
+        let mut url_schemes: HashSet<&str> = BASELINE_URL_SCHEMES.iter().copied().collect();
+        url_schemes.extend(self.url_schemes.iter().map(String::as_str));
+
+        let mut builder = ammonia::Builder::empty();
+        builder
+            .tags(tags)
+            .clean_content_tags(REMOVED_WITH_CONTENT.iter().copied().collect())
+            .add_generic_attributes(BASELINE_GENERIC_ATTRIBUTES)
+            .add_generic_attributes(self.attributes.iter().map(String::as_str))
+            .add_generic_attributes(DIAGRAM_ATTRIBUTES)
+            .add_generic_attribute_prefixes(["data-"])
+            .add_tag_attributes("a", ["href"])
+            .add_tag_attributes("img", ["src", "alt", "width", "height"])
+            .add_tag_attributes("input", ["type", "checked", "disabled"])
+            .add_tag_attributes("ol", ["start"])
+            .add_tag_attributes("textPath", ["href"])
+            .url_schemes(url_schemes)
+            .attribute_filter(|element, attribute, value| {
+                let is_data_url = matches!(attribute, "href" | "src")
+                    && value.trim_start().to_ascii_lowercase().starts_with("data:");
+                if is_data_url && !DATA_URL_ELEMENTS.contains(&element) {
+                    return None;
+                }
+                Some(value.into())
+            })
+            .link_rel(Some("noopener noreferrer"));
+        builder.clean(html).to_string()
+    }
+}
+
+impl Default for HtmlSanitizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Escapes text for use in HTML element content and double-quoted attribute values.
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use markdown_viewer_domain::document::HtmlAllowlist;
+
+    use super::{escape_html, HtmlSanitizer};
+
+    fn allowlist(tags: &[&str], attributes: &[&str], url_schemes: &[&str]) -> HtmlAllowlist {
+        let owned = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        HtmlAllowlist {
+            tags: owned(tags),
+            attributes: owned(attributes),
+            url_schemes: owned(url_schemes),
+        }
+    }
+
+    #[test]
+    fn removes_scripts_event_handlers_and_javascript_urls() {
+        let sanitizer = HtmlSanitizer::new();
+        let cleaned = sanitizer.sanitize(
+            "<div class=\"card\" onclick=\"steal()\"><script>alert(1)</script>\
+             <a href=\"javascript:alert(1)\">x</a></div>",
+        );
+
+        assert!(cleaned.contains("<div class=\"card\">"));
+        assert!(!cleaned.contains("script"));
+        assert!(!cleaned.contains("onclick"));
+        assert!(!cleaned.contains("javascript:"));
+    }
+
+    #[test]
+    fn keeps_markup_the_renderer_emits() {
+        let sanitizer = HtmlSanitizer::new();
+        let cleaned = sanitizer.sanitize(
+            "<h2><a href=\"#setup\" aria-hidden=\"true\" class=\"anchor\" id=\"mdv-setup\"></a>Setup</h2>\
+             <ul><li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> done</li></ul>\
+             <span data-math-style=\"inline\">x^2</span>\
+             <ol start=\"3\"><li>three</li></ol>",
+        );
+
+        assert!(cleaned.contains("id=\"mdv-setup\""));
+        assert!(cleaned.contains("aria-hidden=\"true\""));
+        assert!(cleaned.contains("href=\"#setup\""));
+        assert!(cleaned.contains("type=\"checkbox\""));
+        assert!(cleaned.contains("checked=\"\""));
+        assert!(cleaned.contains("data-math-style=\"inline\""));
+        assert!(cleaned.contains("<ol start=\"3\">"));
+    }
+
+    #[test]
+    fn keeps_resolved_images_but_not_data_url_links() {
+        let sanitizer = HtmlSanitizer::new();
+        let cleaned = sanitizer.sanitize(
+            "<img src=\"asset://localhost/%2Fdocs%2Fa.png\" alt=\"a\" width=\"3\" height=\"2\" />\
+             <img src=\"data:image/png;base64,AAAA\" alt=\"b\" />\
+             <a href=\"data:text/html;base64,PHNjcmlwdD4=\">open</a>",
+        );
+
+        assert!(cleaned.contains("src=\"asset://localhost/%2Fdocs%2Fa.png\""));
+        assert!(cleaned.contains("width=\"3\" height=\"2\""));
+        assert!(cleaned.contains("src=\"data:image/png;base64,AAAA\""));
+        assert!(!cleaned.contains("data:text/html"));
+    }
+
+    #[test]
+    fn keeps_diagram_svg_but_not_external_references() {
+        let sanitizer = HtmlSanitizer::new();
+        let cleaned = sanitizer.sanitize(
+            "<svg role=\"img\" viewBox=\"0 0 10 10\"><defs><marker id=\"m\" refX=\"1\"></marker></defs>\
+             <path d=\"M 0 0 L 5 5\" marker-end=\"url(#m)\"></path>\
+             <textPath href=\"#m\">x</textPath><textPath href=\"javascript:alert(1)\">y</textPath>\
+             <a href=\"data:text/html,hi\"><text x=\"1\">z</text></a></svg>",
+        );
+
+        assert!(cleaned.contains("viewBox=\"0 0 10 10\""));
+        assert!(cleaned.contains("refX=\"1\""));
+        assert!(cleaned.contains("marker-end=\"url(#m)\""));
+        assert!(cleaned.contains("<textPath href=\"#m\">"));
+        assert!(!cleaned.contains("javascript:"));
+        assert!(!cleaned.contains("data:text/html"));
+    }
+
+    #[test]
+    fn allowlist_keeps_common_readme_markup() {
+        let sanitizer = HtmlSanitizer::with_allowlist(&HtmlAllowlist::default());
+        let cleaned = sanitizer.sanitize(
+            "<details open><summary>More</summary>Press <kbd>Ctrl</kbd>+<kbd>C</kbd></details>\
+             <p align=\"center\"><img src=\"https://example.com/logo.png\" width=\"120\" alt=\"logo\"></p>\
+             H<sub>2</sub>O",
+        );
+
+        assert!(cleaned.contains("<details open=\"\"><summary>More</summary>"));
+        assert!(cleaned.contains("<kbd>Ctrl</kbd>"));
+        assert!(cleaned.contains("<p align=\"center\">"));
+        assert!(cleaned.contains("width=\"120\""));
+        assert!(cleaned.contains("<sub>2</sub>"));
+    }
+
+    #[test]
+    fn allowlist_cannot_reenable_dangerous_markup() {
+        let sanitizer = HtmlSanitizer::with_allowlist(&allowlist(
+            &["script", "style", "iframe", "svg"],
+            &["onerror", "onload", "rel"],
+            &["javascript"],
+        ));
+        let cleaned = sanitizer.sanitize(
+            "<img src=x onerror=\"alert(1)\">\
+             <script>alert(1)</script><style>body{}</style>\
+             <a href=\"javascript:alert(1)\" rel=\"opener\">x</a>\
+             <svg onload=\"alert(1)\"></svg>",
+        );
+
+        assert!(!cleaned.contains("onerror"));
+        assert!(!cleaned.contains("onload"));
+        assert!(!cleaned.contains("<script"));
+        assert!(!cleaned.contains("alert(1)</script>"));
+        assert!(!cleaned.contains("<style"));
+        assert!(!cleaned.contains("javascript:"));
+        assert!(cleaned.contains("rel=\"noopener noreferrer\""));
+    }
+
+    #[test]
+    fn allowlist_cannot_reenable_frames_srcdoc_or_inline_styles() {
+        let sanitizer = HtmlSanitizer::with_allowlist(&allowlist(
+            &["iframe", "object", "embed"],
+            &["srcdoc", "style", "src"],
+            &[],
+        ));
+        let cleaned = sanitizer.sanitize(
+            "<iframe srcdoc=\"<script>alert(1)</script>\" src=\"https://example.com\"></iframe>\
+             <object data=\"evil.swf\"></object><embed src=\"evil.swf\">\
+             <p style=\"position:fixed;inset:0\">cover</p>",
+        );
+
+        assert!(!cleaned.contains("iframe"));
+        assert!(!cleaned.contains("srcdoc"));
+        assert!(!cleaned.contains("alert(1)"));
+        assert!(!cleaned.contains("<object"));
+        assert!(!cleaned.contains("<embed"));
+        assert!(!cleaned.contains("style="));
+        assert!(cleaned.contains("<p>cover</p>"));
+    }
+
+    #[test]
+    fn allowlist_cannot_reenable_meta_refresh_or_base_href() {
+        let sanitizer = HtmlSanitizer::with_allowlist(&allowlist(
+            &["meta", "base", "link"],
+            &["http-equiv", "content", "href"],
+            &[],
+        ));
+        let cleaned = sanitizer.sanitize(
+            "<meta http-equiv=\"refresh\" content=\"0;url=https://evil.example\">\
+             <base href=\"https://evil.example/\">\
+             <link rel=\"stylesheet\" href=\"https://evil.example/a.css\">\
+             <a href=\"docs/a.md\">a</a>",
+        );
+
+        assert!(!cleaned.contains("<meta"));
+        assert!(!cleaned.contains("http-equiv"));
+        assert!(!cleaned.contains("<base"));
+        assert!(!cleaned.contains("<link"));
+        assert!(!cleaned.contains("evil.example"));
+        assert!(cleaned.contains("href=\"docs/a.md\""));
+    }
+
+    #[test]
+    fn allowlist_cannot_reenable_forms() {
+        let sanitizer = HtmlSanitizer::with_allowlist(&allowlist(
+            &["form", "button"],
+            &["action", "formaction"],
+            &[],
+        ));
+        let cleaned = sanitizer.sanitize(
+            "<form action=\"https://evil.example\"><button formaction=\"https://evil.example\">Go</button></form>",
+        );
+
+        assert!(!cleaned.contains("<form"));
+        assert!(!cleaned.contains("action"));
+        assert!(cleaned.contains("<button>Go</button>"));
+    }
+
+    #[test]
+    fn tags_outside_the_allowlist_are_unwrapped() {
+        let sanitizer = HtmlSanitizer::with_allowlist(&allowlist(&["kbd"], &[], &[]));
+        let cleaned = sanitizer
+            .sanitize("<iframe src=\"https://example.com\"></iframe><big>hi</big><kbd>K</kbd>");
+
+        assert!(!cleaned.contains("iframe"));
+        assert!(!cleaned.contains("<big>"));
+        assert!(cleaned.contains("hi"));
+        assert!(cleaned.contains("<kbd>K</kbd>"));
+    }
+
+    #[test]
+    fn escapes_text_for_content_and_attributes() {
+        assert_eq!(
+            escape_html("<a title=\"x\">Fish & chips</a>"),
+            "&lt;a title=&quot;x&quot;&gt;Fish &amp; chips&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::MarkdownFileRepository;
+use markdown_viewer_domain::document_source::{DocumentOrigin, SourceDocument};
+use markdown_viewer_domain::markdown_source::content_hash;
+use url::Url;
+
+use crate::document_sources::DocumentSource;
+use crate::file_repository::is_markdown_file;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Larger responses are refused rather than rendered.
+const MAX_DOCUMENT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Fetches markdown documents from `http://` and `https://` URLs.
+///
+/// Every response is kept in `cache_directory` with its `ETag` and `Last-Modified` validators.
+/// Later reads revalidate the copy with a conditional request, and fall back to it while the
+/// server cannot be reached. Fetched documents are read-only and never watched.
+pub struct HttpDocumentSource {
+    agent: ureq::Agent,
+    cache_directory: PathBuf,
+}
+
+impl HttpDocumentSource {
+    pub fn new(cache_directory: PathBuf) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("markdown-viewer/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Self {
+            agent,
+            cache_directory,
+        }
+    }
+
+    fn fetch(&self, url: &Url) -> Result<(Url, String), MarkdownViewerError> {
+        let fetch_error = |reason: String| MarkdownViewerError::Fetch {
+            url: url.to_string(),
+            reason,
+        };
+        let cached = CachedDocument::load(&self.cache_directory, url);
+
+        let mut request = self.agent.request_url("GET", url);
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                return Err(fetch_error(format!(
+                    "server answered {status} {}",
+                    response.status_text()
+                )));
+            }
+            Err(ureq::Error::Transport(transport)) => {
+                return match cached {
+                    Some(cached) => Ok((url.clone(), cached.source)),
+                    None => Err(fetch_error(transport.to_string())),
+                };
+            }
+        };
+
+        if response.status() == 304 {
+            return match cached {
+                Some(cached) => Ok((url.clone(), cached.source)),
+                None => Err(fetch_error(
+                    "server answered 304 Not Modified without a cached copy".to_string(),
+                )),
+            };
+        }
+
+        let final_url = Url::parse(response.get_url()).unwrap_or_else(|_| url.clone());
+        let fetched = CachedDocument {
+            etag: response.header("ETag").map(str::to_string),
+            last_modified: response.header("Last-Modified").map(str::to_string),
+            source: read_body(response).map_err(fetch_error)?,
+        };
+        // The cache only saves round trips; a read-only or full disk must not fail the read.
+        let _ = fetched.store(&self.cache_directory, url);
+
+        Ok((final_url, fetched.source))
+    }
+}
+
+impl DocumentSource for HttpDocumentSource {
+    fn handles(&self, path_input: &str) -> bool {
+        remote_document_url(path_input).is_some()
+    }
+}
+
+impl MarkdownFileRepository for HttpDocumentSource {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        let url = remote_document_url(path_input)
+            .ok_or_else(|| MarkdownViewerError::FileNotFound(PathBuf::from(path_input)))?;
+        if !is_markdown_file(Path::new(url.path())) {
+            return Err(MarkdownViewerError::NotMarkdown(PathBuf::from(path_input)));
+        }
+
+        let (final_url, source) = self.fetch(&url)?;
+        Ok(SourceDocument {
+            path: PathBuf::from(final_url.as_str()),
+            source,
+            origin: DocumentOrigin::Remote,
+        })
+    }
+}
+
+/// The `http`/`https` URL named by `path_input`, without its fragment.
+pub fn remote_document_url(path_input: &str) -> Option<Url> {
+    let mut url = Url::parse(path_input.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") || url.host().is_none() {
+        return None;
+    }
+    url.set_fragment(None);
+    Some(url)
+}
+
+/// Whether `path_input` is a web URL of a markdown document.
+pub fn is_remote_markdown_url(path_input: &str) -> bool {
+    remote_document_url(path_input).is_some_and(|url| is_markdown_file(Path::new(url.path())))
+}
+
+fn read_body(response: ureq::Response) -> Result<String, String> {
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_DOCUMENT_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|error| error.to_string())?;
+    if bytes.len() as u64 > MAX_DOCUMENT_BYTES {
+        return Err(format!(
+            "document is larger than {} MiB",
+            MAX_DOCUMENT_BYTES / 1024 / 1024
+        ));
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// A fetched document on disk: `<key>.md` holds the source, `<key>.json` the validators.
+struct CachedDocument {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    source: String,
+}
+
+impl CachedDocument {
+    fn load(cache_directory: &Path, url: &Url) -> Option<Self> {
+        let (source_path, metadata_path) = cache_paths(cache_directory, url);
+        let metadata: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(metadata_path).ok()?).ok()?;
+        if metadata["url"].as_str() != Some(url.as_str()) {
+            return None;
+        }
+        let validator = |name: &str| metadata[name].as_str().map(str::to_string);
+
+        Some(Self {
+            etag: validator("etag"),
+            last_modified: validator("lastModified"),
+            source: fs::read_to_string(source_path).ok()?,
+        })
+    }
+
+    fn store(&self, cache_directory: &Path, url: &Url) -> std::io::Result<()> {
+        let (source_path, metadata_path) = cache_paths(cache_directory, url);
+        let metadata = serde_json::json!({
+            "url": url.as_str(),
+            "etag": self.etag,
+            "lastModified": self.last_modified,
+        });
+        fs::create_dir_all(cache_directory)?;
+        fs::write(source_path, &self.source)?;
+        fs::write(metadata_path, metadata.to_string())
+    }
+}
+
+fn cache_paths(cache_directory: &Path, url: &Url) -> (PathBuf, PathBuf) {
+    let key = content_hash(url.as_str());
+    (
+        cache_directory.join(format!("{key}.md")),
+        cache_directory.join(format!("{key}.json")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::thread::{self, JoinHandle};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::MarkdownFileRepository;
+    use markdown_viewer_domain::document_source::DocumentOrigin;
+
+    use super::{is_remote_markdown_url, HttpDocumentSource};
+    use crate::document_sources::DocumentSource;
+
+    /// Stand-in HTTP server answering one connection per scripted response, then shutting down.
+    struct StandInServer {
+        base_url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+        handle: JoinHandle<()>,
+    }
+
+    impl StandInServer {
+        fn start(responses: Vec<String>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("loopback should be bindable");
+            let base_url = format!(
+                "http://{}",
+                listener
+                    .local_addr()
+                    .expect("listener should have an address")
+            );
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&requests);
+            let handle = thread::spawn(move || {
+                for response in responses {
+                    let (mut stream, _) = listener.accept().expect("client should connect");
+                    let mut reader = BufReader::new(&mut stream);
+                    let mut request = String::new();
+                    loop {
+                        let mut line = String::new();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                            break;
+                        }
+                        request.push_str(&line);
+                    }
+                    recorded
+                        .lock()
+                        .expect("requests should be lockable")
+                        .push(request);
+                    stream
+                        .write_all(response.as_bytes())
+                        .expect("response should be writable");
+                }
+            });
+            Self {
+                base_url,
+                requests,
+                handle,
+            }
+        }
+
+        /// Waits for every scripted response to be served and returns the requests received.
+        fn finish(self) -> Vec<String> {
+            self.handle.join().expect("server thread should not panic");
+            Arc::try_unwrap(self.requests)
+                .expect("server thread should be done")
+                .into_inner()
+                .expect("requests should be lockable")
+        }
+    }
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut response = format!("HTTP/1.1 {status}\r\nConnection: close\r\n");
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str(&format!("Content-Length: {}\r\n\r\n{body}", body.len()));
+        response
+    }
+
+    fn temp_cache_directory() -> PathBuf {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after unix epoch")
+            .as_nanos();
+        std::env::temp_dir().join(format!("mdv-http-cache-{suffix}"))
+    }
+
+    #[test]
+    fn caches_documents_and_revalidates_them_with_validators() {
+        let server = StandInServer::start(vec![
+            response(
+                "200 OK",
+                &[
+                    "ETag: \"v1\"",
+                    "Last-Modified: Wed, 21 Oct 2026 07:28:00 GMT",
+                ],
+                "# Guide\n",
+            ),
+            response("304 Not Modified", &[], ""),
+        ]);
+        let cache_directory = temp_cache_directory();
+        let source = HttpDocumentSource::new(cache_directory.clone());
+        let url = format!("{}/docs/guide.md", server.base_url);
+
+        let fetched = source.read(&url).expect("document should be fetched");
+        let revalidated = source
+            .read(&format!("{url}#setup"))
+            .expect("document should be revalidated");
+        let requests = server.finish();
+        let offline = source
+            .read(&url)
+            .expect("the cached copy should be served while offline");
+
+        assert_eq!(fetched.path, PathBuf::from(&url));
+        assert_eq!(fetched.origin, DocumentOrigin::Remote);
+        for document in [fetched, revalidated, offline] {
+            assert_eq!(document.source, "# Guide\n");
+        }
+        assert!(requests[0].starts_with("GET /docs/guide.md HTTP/1.1"));
+        assert!(!requests[0].to_ascii_lowercase().contains("if-none-match"));
+        let revalidation = requests[1].to_ascii_lowercase();
+        assert!(revalidation.contains("if-none-match: \"v1\""));
+        assert!(revalidation.contains("if-modified-since: wed, 21 oct 2026 07:28:00 gmt"));
+
+        std::fs::remove_dir_all(cache_directory).ok();
+    }
+
+    #[test]
+    fn replaces_the_cached_copy_when_the_document_changed() {
+        let server = StandInServer::start(vec![
+            response("200 OK", &["ETag: \"v1\""], "# One\n"),
+            response("200 OK", &["ETag: \"v2\""], "# Two\n"),
+        ]);
+        let cache_directory = temp_cache_directory();
+        let source = HttpDocumentSource::new(cache_directory.clone());
+        let url = format!("{}/notes.md", server.base_url);
+
+        source.read(&url).expect("first fetch should succeed");
+        let changed = source.read(&url).expect("second fetch should succeed");
+        server.finish();
+
+        assert_eq!(changed.source, "# Two\n");
+        let cached = super::CachedDocument::load(
+            &cache_directory,
+            &url::Url::parse(&url).expect("url should parse"),
+        )
+        .expect("document should be cached");
+        assert_eq!(cached.etag.as_deref(), Some("\"v2\""));
+        assert_eq!(cached.source, "# Two\n");
+
+        std::fs::remove_dir_all(cache_directory).ok();
+    }
+
+    #[test]
+    fn reports_http_errors_and_non_markdown_urls() {
+        let server = StandInServer::start(vec![response("404 Not Found", &[], "missing")]);
+        let cache_directory = temp_cache_directory();
+        let source = HttpDocumentSource::new(cache_directory.clone());
+
+        let error = source
+            .read(&format!("{}/missing.md", server.base_url))
+            .expect_err("a 404 should fail");
+        server.finish();
+
+        assert!(
+            matches!(error, MarkdownViewerError::Fetch { ref reason, .. } if reason.contains("404"))
+        );
+        assert!(matches!(
+            source.read("https://example.com/report.pdf"),
+            Err(MarkdownViewerError::NotMarkdown(_))
+        ));
+        assert!(!cache_directory.exists());
+    }
+
+    #[test]
+    fn handles_only_web_urls() {
+        let source = HttpDocumentSource::new(temp_cache_directory());
+
+        assert!(source.handles("https://example.com/README.md"));
+        assert!(source.handles("http://localhost:8000/a.txt"));
+        assert!(!source.handles("file:///docs/guide.md"));
+        assert!(!source.handles("/docs/guide.md"));
+        assert!(is_remote_markdown_url(
+            "https://gitlab.example.com/group/repo/-/raw/main/README.md?inline=false"
+        ));
+        assert!(!is_remote_markdown_url("https://example.com/"));
+    }
+}
@@ -0,0 +1,530 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use comrak::nodes::{AstNode, NodeLink, NodeValue};
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_domain::document::{DocumentWarning, DocumentWarningKind, ImageEmbedding};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::archive_source::{normalize_entry_name, read_archive_file, split_archive_path};
+use crate::html_sanitizer::escape_html;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+use crate::path_confinement::{confined_file, confined_relative_path};
+
+/// URL prefix of the webview's asset protocol, matching Tauri's `convertFileSrc`.
+#[cfg(any(windows, target_os = "android"))]
+pub const DEFAULT_ASSET_URL_PREFIX: &str = "http://asset.localhost/";
+#[cfg(not(any(windows, target_os = "android")))]
+pub const DEFAULT_ASSET_URL_PREFIX: &str = "asset://localhost/";
+
+/// Characters `encodeURIComponent` leaves alone; everything else in a path is escaped.
+const URI_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+/// Points relative image references at the files next to the document.
+///
+/// Found images are linked through the asset protocol (or inlined as `data:` URIs when the
+/// preferences ask for [`ImageEmbedding::DataUri`]) and get `width`/`height` attributes from the
+/// file header so the page does not shift while they load. Missing files are left as written
+/// and reported as [`DocumentWarningKind::MissingImage`] warnings, as are references that leave
+/// the document's folder and files that are not images. Images of documents inside a zip
+/// archive are read from the archive and always inlined. Remote and `data:` images, and every
+/// image of documents rendered with a `base_url`, are not touched.
+pub struct ImageResolutionTransform {
+    asset_url_prefix: String,
+}
+
+impl ImageResolutionTransform {
+    pub fn new() -> Self {
+        Self::with_asset_url_prefix(DEFAULT_ASSET_URL_PREFIX)
+    }
+
+    pub fn with_asset_url_prefix(asset_url_prefix: impl Into<String>) -> Self {
+        Self {
+            asset_url_prefix: asset_url_prefix.into(),
+        }
+    }
+
+    fn asset_url(&self, path: &Path) -> String {
+        let encoded = utf8_percent_encode(&path.to_string_lossy(), URI_COMPONENT).to_string();
+        format!("{}{encoded}", self.asset_url_prefix)
+    }
+}
+
+impl Default for ImageResolutionTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownTransform for ImageResolutionTransform {
+    fn transform<'a>(
+        &self,
+        arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError> {
+        // Documents from the web must not pull local files into the page.
+        if context.preferences.base_url.is_some() {
+            return Ok(());
+        }
+        let images: Vec<&'a AstNode<'a>> = root
+            .descendants()
+            .filter(|node| matches!(node.data.borrow().value, NodeValue::Image(..)))
+            .collect();
+
+        let archive = split_archive_path(context.document_path);
+
+        for image in images {
+            let (link, line) = {
+                let data = image.data.borrow();
+                let NodeValue::Image(link) = &data.value else {
+                    continue;
+                };
+                (link.as_ref().clone(), data.sourcepos.start.line)
+            };
+            let Some(reference) = image_reference(&link.url) else {
+                continue;
+            };
+            let warn = |message: String| {
+                context.warn(DocumentWarning {
+                    kind: DocumentWarningKind::MissingImage,
+                    message,
+                    line: Some(line),
+                })
+            };
+            let missing = || warn(format!("Image not found: {}", link.url));
+
+            let html = if let Some((archive_path, document_entry)) = &archive {
+                // Entries of an archive are not files the asset protocol can serve, so they are
+                // always inlined.
+                let bytes = archive_entry_name(document_entry, &reference)
+                    .map(|name| {
+                        read_archive_file(archive_path, &name)
+                            .ok()
+                            .flatten()
+                            .map(|bytes| (name, bytes))
+                    })
+                    .unwrap_or_default();
+                let Some((name, bytes)) = bytes else {
+                    missing();
+                    continue;
+                };
+                let src = format!(
+                    "data:{};base64,{}",
+                    image_mime_type(Path::new(&name)),
+                    BASE64.encode(&bytes)
+                );
+                image_html(
+                    &src,
+                    &link,
+                    &alt_text(image),
+                    imagesize::blob_size(&bytes).ok(),
+                )
+            } else {
+                let Some(path) = confined_file(context.base_directory(), &reference) else {
+                    if confined_relative_path(&reference).is_none() {
+                        warn(format!(
+                            "Image must be inside the document's folder: {}",
+                            link.url
+                        ));
+                    } else {
+                        missing();
+                    }
+                    continue;
+                };
+                if !is_image_file(&path) {
+                    warn(format!("Not an image file: {}", link.url));
+                    continue;
+                }
+                let src = match context.preferences.image_embedding {
+                    ImageEmbedding::AssetUrl => None,
+                    ImageEmbedding::DataUri => data_uri(&path),
+                }
+                .unwrap_or_else(|| self.asset_url(&path));
+                let html = image_html(&src, &link, &alt_text(image), imagesize::size(&path).ok());
+                context.record_local_image(path);
+                html
+            };
+
+            let replacement = arena.alloc(AstNode::from(NodeValue::Raw(html)));
+            replacement.data.borrow_mut().sourcepos = image.data.borrow().sourcepos;
+            image.insert_before(replacement);
+            image.detach();
+        }
+
+        Ok(())
+    }
+}
+
+/// The decoded path an image URL written in the document refers to, or `None` for URLs that do
+/// not refer to the local file system (remote, `data:`, fragment-only or protocol-relative).
+fn image_reference(url: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty() || url.starts_with('#') || url.starts_with("//") || has_uri_scheme(url) {
+        return None;
+    }
+
+    let without_suffix = url.split(['?', '#']).next().unwrap_or(url);
+    let decoded = percent_decode_str(without_suffix).decode_utf8().ok()?;
+    Some(decoded.into_owned())
+}
+
+/// The archive entry an image reference of the entry `document_entry` points at.
+fn archive_entry_name(document_entry: &str, reference: &str) -> Option<String> {
+    let folder = document_entry
+        .rsplit_once('/')
+        .map_or("", |(folder, _)| folder);
+    normalize_entry_name(&format!("{folder}/{reference}"))
+}
+
+pub(crate) fn has_uri_scheme(url: &str) -> bool {
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return false;
+    };
+    let is_scheme = scheme
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "+-.".contains(character));
+    // A single letter followed by a separator is a Windows drive (`C:\images\a.png`).
+    let is_drive = scheme.len() == 1 && (rest.starts_with('\\') || rest.starts_with('/'));
+    is_scheme && !is_drive
+}
+
+/// Whether `path` has an image extension or starts with the header of an image format.
+fn is_image_file(path: &Path) -> bool {
+    if image_mime_type(path) != "application/octet-stream" {
+        return true;
+    }
+    let mut header = Vec::new();
+    fs::File::open(path)
+        .and_then(|file| file.take(64).read_to_end(&mut header))
+        .is_ok_and(|_| imagesize::image_type(&header).is_ok())
+}
+
+fn data_uri(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    Some(format!(
+        "data:{};base64,{}",
+        image_mime_type(path),
+        BASE64.encode(bytes)
+    ))
+}
+
+fn image_mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "avif" => "image/avif",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Plain text of the image description, as comrak writes it into the `alt` attribute.
+fn alt_text<'a>(image: &'a AstNode<'a>) -> String {
+    let mut alt = String::new();
+    for node in image.descendants().skip(1) {
+        match &node.data.borrow().value {
+            NodeValue::Text(text) => alt.push_str(text),
+            NodeValue::Code(code) => alt.push_str(&code.literal),
+            NodeValue::SoftBreak | NodeValue::LineBreak => alt.push(' '),
+            _ => {}
+        }
+    }
+    alt
+}
+
+fn image_html(
+    src: &str,
+    link: &NodeLink,
+    alt: &str,
+    dimensions: Option<imagesize::ImageSize>,
+) -> String {
+    let mut html = format!(
+        "<img src=\"{}\" alt=\"{}\"",
+        escape_html(src),
+        escape_html(alt)
+    );
+    if !link.title.is_empty() {
+        html.push_str(&format!(" title=\"{}\"", escape_html(&link.title)));
+    }
+    if let Some(dimensions) = dimensions {
+        html.push_str(&format!(
+            " width=\"{}\" height=\"{}\"",
+            dimensions.width, dimensions.height
+        ));
+    }
+    html.push_str(" />");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::{
+        DocumentWarningKind, ImageEmbedding, RenderPreferences,
+    };
+
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use super::{archive_entry_name, has_uri_scheme, image_reference, ImageResolutionTransform};
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    /// A 3x2 PNG: signature, IHDR and the IEND trailer are all `imagesize` needs.
+    const PNG_3X2: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x08, 0x06, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60,
+        0x82,
+    ];
+
+    fn temp_document_directory(name: &str) -> PathBuf {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after unix epoch")
+            .as_nanos();
+        let directory = std::env::temp_dir().join(format!("mdv-images-{name}-{suffix}"));
+        fs::create_dir_all(directory.join("img")).expect("temp directory should be writable");
+        fs::write(directory.join("img").join("chart one.png"), PNG_3X2)
+            .expect("fixture image should be writable");
+        directory
+    }
+
+    fn render(directory: &Path, markdown: &str, preferences: RenderPreferences) -> String {
+        let renderer = ComrakMarkdownRenderer::with_transforms(vec![Box::new(
+            ImageResolutionTransform::with_asset_url_prefix("asset://localhost/"),
+        )]);
+        renderer
+            .render(markdown, &directory.join("doc.md"), preferences)
+            .expect("renderer should work")
+            .html
+    }
+
+    #[test]
+    fn rewrites_relative_images_to_asset_urls_with_dimensions() {
+        let directory = temp_document_directory("asset");
+        let html = render(
+            &directory,
+            "![A *chart*](img/chart%20one.png \"Quarterly\")",
+            RenderPreferences::default(),
+        );
+
+        let expected_path = directory.join("img").join("chart one.png");
+        let encoded = percent_encoding::utf8_percent_encode(
+            &expected_path.to_string_lossy(),
+            super::URI_COMPONENT,
+        )
+        .to_string();
+        assert!(html.contains(&format!("src=\"asset://localhost/{encoded}\"")));
+        assert!(html.contains("alt=\"A chart\""));
+        assert!(html.contains("title=\"Quarterly\""));
+        assert!(html.contains("width=\"3\" height=\"2\""));
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn inlines_images_as_data_uris_for_export() {
+        let directory = temp_document_directory("data");
+        let html = render(
+            &directory,
+            "![chart](./img/chart%20one.png)",
+            RenderPreferences {
+                image_embedding: ImageEmbedding::DataUri,
+                ..RenderPreferences::default()
+            },
+        );
+
+        assert!(html.contains("src=\"data:image/png;base64,iVBORw0KGgo"));
+        assert!(html.contains("width=\"3\" height=\"2\""));
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn reports_missing_images_and_records_found_ones() {
+        let directory = temp_document_directory("missing");
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "# Title\n\n![gone](img/missing.png)\n\n![chart](img/chart%20one.png)\n",
+                &directory.join("doc.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert_eq!(rendered.warnings.len(), 1);
+        assert_eq!(rendered.warnings[0].kind, DocumentWarningKind::MissingImage);
+        assert_eq!(rendered.warnings[0].line, Some(3));
+        assert!(rendered.warnings[0].message.contains("img/missing.png"));
+        assert!(rendered.html.contains("src=\"img/missing.png\""));
+        assert_eq!(
+            rendered.local_images,
+            [directory.join("img").join("chart one.png")]
+        );
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn leaves_remote_and_inline_images_untouched() {
+        let directory = temp_document_directory("remote");
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "![r](https://example.com/a.png) ![d](data:image/png;base64,AAAA) ![p](//cdn.example.com/b.png)",
+                &directory.join("doc.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert!(rendered.html.contains("src=\"https://example.com/a.png\""));
+        assert!(rendered.html.contains("src=\"data:image/png;base64,AAAA\""));
+        assert!(rendered.html.contains("src=\"//cdn.example.com/b.png\""));
+        assert!(rendered.warnings.is_empty());
+        assert!(rendered.local_images.is_empty());
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn does_not_read_images_outside_the_document_folder() {
+        let directory = temp_document_directory("outside");
+        let documents = directory.join("docs");
+        fs::create_dir_all(&documents).expect("temp directory should be writable");
+        let secret = directory.join("img").join("chart one.png");
+        let markdown = format!(
+            "![up](../img/chart%20one.png)\n\n![absolute]({})\n",
+            secret.to_string_lossy().replace(' ', "%20")
+        );
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                &markdown,
+                &documents.join("doc.md"),
+                RenderPreferences {
+                    image_embedding: ImageEmbedding::DataUri,
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work");
+
+        assert!(rendered.local_images.is_empty());
+        assert!(!rendered.html.contains("data:image/png"));
+        assert!(!rendered.html.contains("asset://"));
+        assert_eq!(rendered.warnings.len(), 2);
+        assert!(rendered
+            .warnings
+            .iter()
+            .all(|warning| warning.message.contains("inside the document's folder")));
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn only_embeds_files_that_are_images() {
+        let directory = temp_document_directory("kind");
+        fs::write(directory.join("notes.txt"), "not an image").expect("fixture should be written");
+        fs::write(directory.join("chart"), PNG_3X2).expect("fixture should be written");
+        let renderer = ComrakMarkdownRenderer::new();
+        let rendered = renderer
+            .render(
+                "![notes](notes.txt) ![chart](chart)",
+                &directory.join("doc.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert_eq!(rendered.local_images, [directory.join("chart")]);
+        assert_eq!(rendered.warnings.len(), 1);
+        assert!(rendered.warnings[0].message.contains("notes.txt"));
+        assert!(rendered.html.contains("width=\"3\" height=\"2\""));
+
+        fs::remove_dir_all(directory).ok();
+    }
+
+    #[test]
+    fn image_reference_handles_schemes_queries_and_windows_drives() {
+        assert_eq!(
+            image_reference("img/a%20b.png?raw=1#frag").as_deref(),
+            Some("img/a b.png")
+        );
+        assert_eq!(image_reference("mailto:a@example.com"), None);
+        assert_eq!(image_reference("#section"), None);
+        assert!(has_uri_scheme("https://example.com"));
+        assert!(!has_uri_scheme("C:\\images\\a.png"));
+        assert!(!has_uri_scheme("img/a.png"));
+    }
+
+    #[test]
+    fn inlines_images_of_archived_documents_from_the_archive() {
+        let directory = temp_document_directory("archive");
+        let archive_path = directory.join("docs.zip");
+        let mut writer =
+            ZipWriter::new(fs::File::create(&archive_path).expect("archive should be writable"));
+        writer
+            .start_file("guide/intro.md", SimpleFileOptions::default())
+            .expect("entry should start");
+        writer
+            .start_file("img/chart.png", SimpleFileOptions::default())
+            .expect("entry should start");
+        writer.write_all(PNG_3X2).expect("entry should be written");
+        writer.finish().expect("archive should be finished");
+        let document = PathBuf::from(format!("{}!/guide/intro.md", archive_path.display()));
+
+        let rendered = ComrakMarkdownRenderer::with_transforms(vec![Box::new(
+            ImageResolutionTransform::with_asset_url_prefix("asset://localhost/"),
+        )])
+        .render(
+            "![Chart](../img/chart.png)\n\n![Gone](missing.png)\n",
+            &document,
+            RenderPreferences::default(),
+        )
+        .expect("render should succeed");
+
+        assert!(rendered.html.contains("src=\"data:image/png;base64,"));
+        assert!(rendered.html.contains("width=\"3\" height=\"2\""));
+        assert!(!rendered.html.contains("asset://"));
+        assert!(rendered.local_images.is_empty());
+        assert_eq!(rendered.warnings.len(), 1);
+        assert_eq!(rendered.warnings[0].kind, DocumentWarningKind::MissingImage);
+        assert!(rendered.warnings[0].message.contains("missing.png"));
+        assert_eq!(
+            archive_entry_name("guide/intro.md", "../../escape.png"),
+            None
+        );
+
+        fs::remove_dir_all(directory).expect("temp directory should be removable");
+    }
+}
@@ -1,4 +1,34 @@
+pub mod archive_source;
 pub mod comrak_renderer;
+pub mod csv_tables;
+mod diagram_graphviz;
+mod diagram_mermaid;
+pub mod diagrams;
+pub mod document_sources;
+mod document_statistics;
+pub mod external_links;
 pub mod file_repository;
 pub mod file_watcher;
+pub mod file_writer;
+pub mod git_repository;
+pub mod html_sanitizer;
+pub mod http_source;
+pub mod image_resolution;
 pub mod linked_file_opener;
+mod lint_rules;
+pub mod markdown_diff;
+pub mod markdown_lint;
+mod markdown_sniffing;
+pub mod markdown_transform;
+mod mdx;
+mod path_confinement;
+pub mod project_config;
+pub mod remote_links;
+pub mod settings_store;
+pub mod spell_checker;
+pub mod stdin_source;
+mod task_list;
+pub mod text_replacements;
+mod toc_placeholder;
+pub mod wasm_plugin_host;
+pub mod workspace_scanner;
@@ -0,0 +1,622 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::OnceLock;
+
+use comrak::nodes::{AstNode, ListType, NodeValue};
+use comrak::{parse_document, Arena, Options};
+use markdown_viewer_domain::lint::{LintDiagnostic, LintSeverity};
+use regex::Regex;
+
+use crate::comrak_renderer::{has_link_ancestor, inline_text};
+
+/// A built-in rule, named after its markdownlint counterpart so existing configuration files
+/// keep their meaning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LintRule {
+    pub(crate) id: &'static str,
+    pub(crate) name: &'static str,
+    description: &'static str,
+}
+
+pub(crate) const HEADING_INCREMENT: LintRule = LintRule {
+    id: "MD001",
+    name: "heading-increment",
+    description: "Heading levels should only increment by one level at a time",
+};
+pub(crate) const UL_STYLE: LintRule = LintRule {
+    id: "MD004",
+    name: "ul-style",
+    description: "Unordered list style",
+};
+pub(crate) const NO_TRAILING_SPACES: LintRule = LintRule {
+    id: "MD009",
+    name: "no-trailing-spaces",
+    description: "Trailing spaces",
+};
+pub(crate) const LINE_LENGTH: LintRule = LintRule {
+    id: "MD013",
+    name: "line-length",
+    description: "Line length",
+};
+pub(crate) const NO_DUPLICATE_HEADING: LintRule = LintRule {
+    id: "MD024",
+    name: "no-duplicate-heading",
+    description: "Multiple headings with the same content",
+};
+pub(crate) const NO_BARE_URLS: LintRule = LintRule {
+    id: "MD034",
+    name: "no-bare-urls",
+    description: "Bare URL used",
+};
+pub(crate) const NO_ALT_TEXT: LintRule = LintRule {
+    id: "MD045",
+    name: "no-alt-text",
+    description: "Images should have alternate text (alt text)",
+};
+
+pub(crate) const RULES: [LintRule; 7] = [
+    HEADING_INCREMENT,
+    UL_STYLE,
+    NO_TRAILING_SPACES,
+    LINE_LENGTH,
+    NO_DUPLICATE_HEADING,
+    NO_BARE_URLS,
+    NO_ALT_TEXT,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ListMarkerStyle {
+    /// Every unordered list uses the marker of the first one.
+    Consistent,
+    Asterisk,
+    Dash,
+    Plus,
+}
+
+impl ListMarkerStyle {
+    fn marker(self) -> Option<u8> {
+        match self {
+            Self::Consistent => None,
+            Self::Asterisk => Some(b'*'),
+            Self::Dash => Some(b'-'),
+            Self::Plus => Some(b'+'),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RuleSetting {
+    pub(crate) enabled: bool,
+    pub(crate) severity: LintSeverity,
+}
+
+/// Which rules run, at which severity, and the options of the rules that take any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LintConfig {
+    /// Keyed by rule ID; rules without an entry are enabled with warning severity.
+    pub(crate) rules: BTreeMap<&'static str, RuleSetting>,
+    pub(crate) ul_style: ListMarkerStyle,
+    /// Number of trailing spaces allowed as a hard line break; below 2 none are allowed.
+    pub(crate) br_spaces: usize,
+    pub(crate) line_length: usize,
+    pub(crate) line_length_code_blocks: bool,
+    pub(crate) line_length_tables: bool,
+    pub(crate) line_length_headings: bool,
+    /// Only report duplicate headings that share the same parent heading.
+    pub(crate) siblings_only: bool,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            rules: BTreeMap::new(),
+            ul_style: ListMarkerStyle::Consistent,
+            br_spaces: 2,
+            line_length: 80,
+            line_length_code_blocks: true,
+            line_length_tables: true,
+            line_length_headings: true,
+            siblings_only: false,
+        }
+    }
+}
+
+impl LintConfig {
+    fn setting(&self, rule: LintRule) -> RuleSetting {
+        self.rules.get(rule.id).copied().unwrap_or(RuleSetting {
+            enabled: true,
+            severity: LintSeverity::Warning,
+        })
+    }
+}
+
+/// Checks `markdown` against every enabled rule and returns the findings ordered by position.
+pub(crate) fn lint_markdown(markdown: &str, config: &LintConfig) -> Vec<LintDiagnostic> {
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &lint_options());
+    let lines: Vec<&str> = markdown
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    let blocks = BlockLines::collect(root, lines.len());
+    let mut findings = Findings {
+        config,
+        diagnostics: Vec::new(),
+    };
+
+    check_headings(root, &mut findings);
+    check_list_markers(root, &mut findings);
+    check_bare_urls(root, &mut findings);
+    check_alt_text(root, &mut findings);
+    check_lines(&lines, &blocks, &mut findings);
+
+    let mut diagnostics = findings.diagnostics;
+    diagnostics.sort_by(|left, right| {
+        (left.line, left.column, &left.rule_id).cmp(&(right.line, right.column, &right.rule_id))
+    });
+    diagnostics
+}
+
+/// The renderer's syntax, minus the autolink extension so bare URLs stay visible as text.
+fn lint_options() -> Options<'static> {
+    let mut options = Options::default();
+
+    options.extension.strikethrough = true;
+    options.extension.table = true;
+    options.extension.tasklist = true;
+    options.extension.superscript = true;
+    options.extension.footnotes = true;
+    options.extension.description_lists = true;
+    options.extension.front_matter_delimiter = Some("---".to_string());
+    options.extension.multiline_block_quotes = true;
+    options.extension.alerts = true;
+    options.extension.math_dollars = true;
+    options.extension.math_code = true;
+
+    options
+}
+
+struct Findings<'c> {
+    config: &'c LintConfig,
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl Findings<'_> {
+    fn enabled(&self, rule: LintRule) -> bool {
+        self.config.setting(rule).enabled
+    }
+
+    fn report(&mut self, rule: LintRule, line: usize, column: Option<usize>, detail: String) {
+        let setting = self.config.setting(rule);
+        if !setting.enabled {
+            return;
+        }
+        let message = if detail.is_empty() {
+            rule.description.to_string()
+        } else {
+            format!("{} [{detail}]", rule.description)
+        };
+        self.diagnostics.push(LintDiagnostic {
+            rule_id: rule.id.to_string(),
+            rule_name: rule.name.to_string(),
+            severity: setting.severity,
+            message,
+            line,
+            column,
+        });
+    }
+}
+
+/// Which source lines belong to blocks that line-based rules treat specially.
+struct BlockLines {
+    front_matter: Vec<bool>,
+    code: Vec<bool>,
+    table: Vec<bool>,
+    heading: Vec<bool>,
+}
+
+impl BlockLines {
+    fn collect<'a>(root: &'a AstNode<'a>, line_count: usize) -> Self {
+        let mut blocks = Self {
+            front_matter: vec![false; line_count + 1],
+            code: vec![false; line_count + 1],
+            table: vec![false; line_count + 1],
+            heading: vec![false; line_count + 1],
+        };
+
+        for node in root.descendants() {
+            let data = node.data.borrow();
+            let (start, end) = (data.sourcepos.start.line, data.sourcepos.end.line);
+            let lines = match &data.value {
+                NodeValue::FrontMatter(front_matter) => {
+                    // Front matter always opens the document and spans the lines of its literal.
+                    let end = front_matter.trim_end().lines().count();
+                    mark(&mut blocks.front_matter, 1, end);
+                    continue;
+                }
+                NodeValue::CodeBlock(..) => &mut blocks.code,
+                NodeValue::Table(..) => &mut blocks.table,
+                NodeValue::Heading(..) => &mut blocks.heading,
+                _ => continue,
+            };
+            mark(lines, start, end);
+        }
+
+        blocks
+    }
+}
+
+fn mark(lines: &mut [bool], start: usize, end: usize) {
+    let end = end.min(lines.len().saturating_sub(1));
+    for line in lines.iter_mut().take(end + 1).skip(start) {
+        *line = true;
+    }
+}
+
+fn check_headings<'a>(root: &'a AstNode<'a>, findings: &mut Findings) {
+    let mut previous_level = None;
+    // Headings seen so far, per enclosing heading when only siblings are compared.
+    let mut scopes: Vec<(u8, HashSet<String>)> = vec![(0, HashSet::new())];
+
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        let NodeValue::Heading(heading) = &data.value else {
+            continue;
+        };
+        let line = data.sourcepos.start.line;
+
+        if let Some(previous) = previous_level {
+            if heading.level > previous + 1 {
+                findings.report(
+                    HEADING_INCREMENT,
+                    line,
+                    None,
+                    format!("Expected: h{}; Actual: h{}", previous + 1, heading.level),
+                );
+            }
+        }
+        previous_level = Some(heading.level);
+
+        let text = inline_text(node);
+        if findings.config.siblings_only {
+            while scopes.len() > 1
+                && scopes
+                    .last()
+                    .is_some_and(|(level, _)| *level >= heading.level)
+            {
+                scopes.pop();
+            }
+        }
+        let seen = &mut scopes
+            .last_mut()
+            .expect("the document scope is never popped")
+            .1;
+        if !text.is_empty() && !seen.insert(text.clone()) {
+            findings.report(
+                NO_DUPLICATE_HEADING,
+                line,
+                None,
+                format!("Context: \"{text}\""),
+            );
+        }
+        if findings.config.siblings_only {
+            scopes.push((heading.level, HashSet::new()));
+        }
+    }
+}
+
+fn check_list_markers<'a>(root: &'a AstNode<'a>, findings: &mut Findings) {
+    if !findings.enabled(UL_STYLE) {
+        return;
+    }
+    let mut expected = findings.config.ul_style.marker();
+
+    for node in root.descendants() {
+        let NodeValue::List(list) = &node.data.borrow().value else {
+            continue;
+        };
+        if list.list_type != ListType::Bullet {
+            continue;
+        }
+        let expected = *expected.get_or_insert(list.bullet_char);
+        if list.bullet_char == expected {
+            continue;
+        }
+        for item in node.children() {
+            let start = item.data.borrow().sourcepos.start;
+            findings.report(
+                UL_STYLE,
+                start.line,
+                Some(start.column),
+                format!(
+                    "Expected: {}; Actual: {}",
+                    marker_name(expected),
+                    marker_name(list.bullet_char)
+                ),
+            );
+        }
+    }
+}
+
+fn marker_name(marker: u8) -> &'static str {
+    match marker {
+        b'*' => "asterisk",
+        b'+' => "plus",
+        _ => "dash",
+    }
+}
+
+fn check_bare_urls<'a>(root: &'a AstNode<'a>, findings: &mut Findings) {
+    if !findings.enabled(NO_BARE_URLS) {
+        return;
+    }
+
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        let NodeValue::Text(text) = &data.value else {
+            continue;
+        };
+        if has_link_ancestor(node) {
+            continue;
+        }
+        for url in bare_url_pattern().find_iter(text) {
+            let offset = text[..url.start()].chars().count();
+            findings.report(
+                NO_BARE_URLS,
+                data.sourcepos.start.line,
+                Some(data.sourcepos.start.column + offset),
+                format!("Context: \"{}\"", url.as_str()),
+            );
+        }
+    }
+}
+
+fn bare_url_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"(?i)\b(?:https?|ftp)://[^\s<>]*[^\s<>.,;:!?'")\]]"#)
+            .expect("bare URL pattern should compile")
+    })
+}
+
+fn check_alt_text<'a>(root: &'a AstNode<'a>, findings: &mut Findings) {
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        if matches!(data.value, NodeValue::Image(..)) && inline_text(node).is_empty() {
+            let start = data.sourcepos.start;
+            findings.report(NO_ALT_TEXT, start.line, Some(start.column), String::new());
+        }
+    }
+}
+
+fn check_lines(lines: &[&str], blocks: &BlockLines, findings: &mut Findings) {
+    let config = findings.config;
+
+    for (index, text) in lines.iter().enumerate() {
+        let line = index + 1;
+        if blocks.front_matter[line] {
+            continue;
+        }
+
+        let content = text.trim_end_matches([' ', '\t']);
+        let trailing = &text[content.len()..];
+        let hard_break = config.br_spaces >= 2
+            && !content.is_empty()
+            && trailing.len() == config.br_spaces
+            && trailing.bytes().all(|byte| byte == b' ');
+        if !trailing.is_empty() && !hard_break {
+            let expected = if config.br_spaces >= 2 {
+                format!("0 or {}", config.br_spaces)
+            } else {
+                "0".to_string()
+            };
+            findings.report(
+                NO_TRAILING_SPACES,
+                line,
+                Some(content.chars().count() + 1),
+                format!("Expected: {expected}; Actual: {}", trailing.len()),
+            );
+        }
+
+        let checked = (config.line_length_code_blocks || !blocks.code[line])
+            && (config.line_length_tables || !blocks.table[line])
+            && (config.line_length_headings || !blocks.heading[line]);
+        let length = text.chars().count();
+        // Like markdownlint, a line is only too long when it could be wrapped: a long URL or
+        // word that starts before the limit and runs past it is left alone.
+        if checked
+            && length > config.line_length
+            && text
+                .chars()
+                .skip(config.line_length)
+                .any(char::is_whitespace)
+        {
+            findings.report(
+                LINE_LENGTH,
+                line,
+                Some(config.line_length + 1),
+                format!("Expected: {}; Actual: {length}", config.line_length),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use markdown_viewer_domain::lint::LintSeverity;
+
+    use super::{lint_markdown, LintConfig, ListMarkerStyle, RuleSetting};
+
+    fn findings(markdown: &str, config: &LintConfig) -> Vec<(String, usize, Option<usize>)> {
+        lint_markdown(markdown, config)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule_id, diagnostic.line, diagnostic.column))
+            .collect()
+    }
+
+    fn rule_findings(markdown: &str, rule_id: &str) -> Vec<(usize, Option<usize>)> {
+        findings(markdown, &LintConfig::default())
+            .into_iter()
+            .filter(|(id, _, _)| id == rule_id)
+            .map(|(_, line, column)| (line, column))
+            .collect()
+    }
+
+    #[test]
+    fn clean_document_has_no_findings() {
+        let markdown = "---\ntitle: Guide   \n---\n\n# Guide\n\nSee <https://example.com> and [docs](https://example.com/docs).\n\n- one\n- two\n\n![Logo](logo.png)\n";
+
+        assert_eq!(findings(markdown, &LintConfig::default()), []);
+    }
+
+    #[test]
+    fn reports_skipped_heading_levels() {
+        let markdown = "# Title\n\n### Too deep\n\n## Back\n\n#### Again\n";
+
+        assert_eq!(rule_findings(markdown, "MD001"), [(3, None), (7, None)]);
+        let diagnostic = &lint_markdown(markdown, &LintConfig::default())[0];
+        assert_eq!(diagnostic.rule_name, "heading-increment");
+        assert_eq!(
+            diagnostic.message,
+            "Heading levels should only increment by one level at a time [Expected: h2; Actual: h3]"
+        );
+        assert_eq!(diagnostic.severity, LintSeverity::Warning);
+    }
+
+    #[test]
+    fn reports_duplicate_headings_document_wide_or_among_siblings() {
+        let markdown = "# Guide\n\n## Setup\n\n### Notes\n\n## Usage\n\n### Notes\n\n## Setup\n";
+
+        assert_eq!(rule_findings(markdown, "MD024"), [(9, None), (11, None)]);
+
+        let siblings_only = LintConfig {
+            siblings_only: true,
+            ..LintConfig::default()
+        };
+        let sibling_findings: Vec<usize> = findings(markdown, &siblings_only)
+            .into_iter()
+            .filter(|(id, _, _)| id == "MD024")
+            .map(|(_, line, _)| line)
+            .collect();
+        assert_eq!(sibling_findings, [11]);
+    }
+
+    #[test]
+    fn reports_trailing_spaces_but_allows_hard_breaks() {
+        let markdown = "one  \ntwo \nthree\t\n   \nfour   \n";
+
+        assert_eq!(
+            rule_findings(markdown, "MD009"),
+            [(2, Some(4)), (3, Some(6)), (4, Some(1)), (5, Some(5))]
+        );
+        let strict = LintConfig {
+            br_spaces: 0,
+            ..LintConfig::default()
+        };
+        assert_eq!(
+            lint_markdown("one  \n", &strict)[0].message,
+            "Trailing spaces [Expected: 0; Actual: 2]"
+        );
+    }
+
+    #[test]
+    fn reports_bare_urls_outside_links_and_code() {
+        let markdown = "Visit https://example.com/a_b. Or `https://code.example` or\n<https://angle.example> and ftp://files.example/x.\n";
+
+        assert_eq!(
+            rule_findings(markdown, "MD034"),
+            [(1, Some(7)), (2, Some(29))]
+        );
+        assert_eq!(
+            lint_markdown(markdown, &LintConfig::default())[0].message,
+            "Bare URL used [Context: \"https://example.com/a_b\"]"
+        );
+    }
+
+    #[test]
+    fn reports_inconsistent_or_unexpected_list_markers() {
+        let markdown = "- one\n- two\n\n* three\n  + nested\n";
+
+        assert_eq!(
+            rule_findings(markdown, "MD004"),
+            [(4, Some(1)), (5, Some(3))]
+        );
+
+        let asterisks = LintConfig {
+            ul_style: ListMarkerStyle::Asterisk,
+            ..LintConfig::default()
+        };
+        let lines: Vec<usize> = findings(markdown, &asterisks)
+            .into_iter()
+            .map(|(_, line, _)| line)
+            .collect();
+        assert_eq!(lines, [1, 2, 5]);
+    }
+
+    #[test]
+    fn reports_images_without_alt_text() {
+        assert_eq!(
+            rule_findings("Text ![](a.png) and ![Alt](b.png)\n", "MD045"),
+            [(1, Some(6))]
+        );
+    }
+
+    #[test]
+    fn reports_long_lines_that_could_be_wrapped() {
+        let long_prose = "word ".repeat(20);
+        let long_url = format!("see <https://example.com/{}>", "a".repeat(90));
+        let markdown = format!(
+            "{}\n{long_url}\n\n```\n{}\n```\n\n# {}\n",
+            long_prose.trim_end(),
+            long_prose.trim_end(),
+            long_prose.trim_end()
+        );
+
+        assert_eq!(
+            rule_findings(&markdown, "MD013"),
+            [(1, Some(81)), (5, Some(81)), (8, Some(81))]
+        );
+
+        let relaxed = LintConfig {
+            line_length: 100,
+            line_length_code_blocks: false,
+            line_length_headings: false,
+            ..LintConfig::default()
+        };
+        assert_eq!(findings(&markdown, &relaxed), []);
+        let code_only = LintConfig {
+            line_length_headings: false,
+            line_length_tables: false,
+            ..LintConfig::default()
+        };
+        let lines: Vec<usize> = findings(&markdown, &code_only)
+            .into_iter()
+            .map(|(_, line, _)| line)
+            .collect();
+        assert_eq!(lines, [1, 5]);
+    }
+
+    #[test]
+    fn disabled_rules_are_skipped_and_severity_is_configurable() {
+        let mut config = LintConfig::default();
+        config.rules.insert(
+            "MD009",
+            RuleSetting {
+                enabled: false,
+                severity: LintSeverity::Warning,
+            },
+        );
+        config.rules.insert(
+            "MD045",
+            RuleSetting {
+                enabled: true,
+                severity: LintSeverity::Error,
+            },
+        );
+
+        let diagnostics = lint_markdown("![](a.png)   \n", &config);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "MD045");
+        assert_eq!(diagnostics[0].severity, LintSeverity::Error);
+    }
+}
@@ -0,0 +1,530 @@
+use std::mem::{discriminant, Discriminant};
+use std::path::{Path, PathBuf};
+
+use comrak::nodes::NodeValue;
+use comrak::{format_html, parse_document, Arena, Options};
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::MarkdownDiffer;
+use markdown_viewer_domain::diff::DocumentDiff;
+use markdown_viewer_domain::document::RenderPreferences;
+
+use crate::comrak_renderer::{markdown_options, ComrakMarkdownRenderer};
+use crate::html_sanitizer::HtmlSanitizer;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+use crate::mdx::render_source;
+
+/// Largest number of cells in the table used to align two sequences. Bigger changes, which are
+/// left after trimming the common start and end, are shown as a removal followed by an addition.
+const MAX_ALIGNMENT_CELLS: usize = 4_000_000;
+
+/// Diffs two versions of a markdown document at the level of top-level blocks (paragraphs,
+/// headings, lists, tables, code blocks, ...), comparing them by their rendered HTML so that
+/// source-only changes such as re-wrapping a paragraph do not count. Edited blocks get
+/// word-level `<ins>`/`<del>` highlights.
+pub struct ComrakMarkdownDiffer {
+    transforms: Vec<Box<dyn MarkdownTransform>>,
+}
+
+impl ComrakMarkdownDiffer {
+    /// Renders blocks through the same transforms as [`ComrakMarkdownRenderer::new`].
+    pub fn new() -> Self {
+        Self::with_transforms(ComrakMarkdownRenderer::default_transforms())
+    }
+
+    pub fn with_transforms(transforms: Vec<Box<dyn MarkdownTransform>>) -> Self {
+        Self { transforms }
+    }
+
+    fn render_blocks(
+        &self,
+        markdown: &str,
+        document_path: &Path,
+        preferences: &RenderPreferences,
+        options: &Options,
+    ) -> Result<(Vec<RenderedBlock>, Vec<PathBuf>), MarkdownViewerError> {
+        let markdown = render_source(markdown, document_path);
+        let arena = Arena::new();
+        let root = parse_document(&arena, &markdown, options);
+        let context = TransformContext::new(preferences, document_path);
+        for transform in &self.transforms {
+            transform.transform(&arena, root, &context)?;
+        }
+        let (_, local_images) = context.into_findings();
+        let sanitizer = preferences
+            .allow_raw_html
+            .then(|| HtmlSanitizer::with_allowlist(&preferences.html_allowlist));
+
+        let mut blocks = Vec::new();
+        for node in root.children() {
+            let value = &node.data.borrow().value;
+            if matches!(value, NodeValue::FrontMatter(_)) {
+                continue;
+            }
+            let mut html = String::new();
+            format_html(node, options, &mut html).expect("writing HTML into a String cannot fail");
+            if let Some(sanitizer) = &sanitizer {
+                html = sanitizer.sanitize(&html);
+            }
+            // Line breaks inside prose render as plain whitespace, so re-wrapping a paragraph
+            // only counts as a change in code, where whitespace is shown as written.
+            let key = if matches!(value, NodeValue::CodeBlock(_)) {
+                html.clone()
+            } else {
+                html.split_whitespace().collect::<Vec<_>>().join(" ")
+            };
+            blocks.push(RenderedBlock {
+                kind: discriminant(value),
+                key,
+                html,
+            });
+        }
+
+        Ok((blocks, local_images))
+    }
+}
+
+impl Default for ComrakMarkdownDiffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownDiffer for ComrakMarkdownDiffer {
+    fn diff(
+        &self,
+        old: &str,
+        new: &str,
+        document_path: &Path,
+        preferences: RenderPreferences,
+    ) -> Result<DocumentDiff, MarkdownViewerError> {
+        let options = markdown_options(&preferences);
+        let (old_blocks, mut local_images) =
+            self.render_blocks(old, document_path, &preferences, &options)?;
+        let (new_blocks, new_images) =
+            self.render_blocks(new, document_path, &preferences, &options)?;
+        for image in new_images {
+            if !local_images.contains(&image) {
+                local_images.push(image);
+            }
+        }
+
+        let mut diff = DocumentDiff {
+            local_images,
+            ..DocumentDiff::default()
+        };
+        let old_keys: Vec<&str> = old_blocks.iter().map(|block| block.key.as_str()).collect();
+        let new_keys: Vec<&str> = new_blocks.iter().map(|block| block.key.as_str()).collect();
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        for edit in align(&old_keys, &new_keys) {
+            match edit {
+                Edit::Keep(_, new_index) => {
+                    write_changed_blocks(&mut diff, &old_blocks, &new_blocks, &removed, &added);
+                    removed.clear();
+                    added.clear();
+                    diff.html.push_str(&new_blocks[new_index].html);
+                }
+                Edit::Remove(old_index) => removed.push(old_index),
+                Edit::Add(new_index) => added.push(new_index),
+            }
+        }
+        write_changed_blocks(&mut diff, &old_blocks, &new_blocks, &removed, &added);
+
+        Ok(diff)
+    }
+}
+
+struct RenderedBlock {
+    kind: Discriminant<NodeValue>,
+    /// What the block is compared by.
+    key: String,
+    html: String,
+}
+
+/// Writes a run of blocks that differ between two unchanged blocks. Removed and added blocks of
+/// the same kind are paired in order as edits of each other; the rest are shown as removed or
+/// added.
+fn write_changed_blocks(
+    diff: &mut DocumentDiff,
+    old_blocks: &[RenderedBlock],
+    new_blocks: &[RenderedBlock],
+    removed: &[usize],
+    added: &[usize],
+) {
+    let mut paired_removed = vec![false; removed.len()];
+    let mut paired_added = vec![false; added.len()];
+    let mut next_added = 0;
+    for (position, &old_index) in removed.iter().enumerate() {
+        if let Some(offset) = added[next_added..]
+            .iter()
+            .position(|&new_index| new_blocks[new_index].kind == old_blocks[old_index].kind)
+        {
+            paired_removed[position] = true;
+            paired_added[next_added + offset] = true;
+            next_added += offset + 1;
+        }
+    }
+
+    let (mut removed_position, mut added_position) = (0, 0);
+    while removed_position < removed.len() || added_position < added.len() {
+        if removed_position < removed.len() && !paired_removed[removed_position] {
+            write_block(
+                &mut diff.html,
+                "mdv-diff-deleted",
+                &old_blocks[removed[removed_position]].html,
+            );
+            diff.deleted_blocks += 1;
+            removed_position += 1;
+        } else if added_position < added.len() && !paired_added[added_position] {
+            write_block(
+                &mut diff.html,
+                "mdv-diff-inserted",
+                &new_blocks[added[added_position]].html,
+            );
+            diff.inserted_blocks += 1;
+            added_position += 1;
+        } else {
+            let old_html = &old_blocks[removed[removed_position]].html;
+            let new_html = &new_blocks[added[added_position]].html;
+            write_block(
+                &mut diff.html,
+                "mdv-diff-modified",
+                &highlight_words(old_html, new_html),
+            );
+            diff.modified_blocks += 1;
+            removed_position += 1;
+            added_position += 1;
+        }
+    }
+}
+
+fn write_block(output: &mut String, class: &str, html: &str) {
+    output.push_str("<div class=\"");
+    output.push_str(class);
+    output.push_str("\">");
+    output.push_str(html);
+    output.push_str("</div>");
+}
+
+/// Returns `new_html` with the words that are not in `old_html` wrapped in `<ins>` and the words
+/// that were removed inserted in `<del>` where they used to be. The markup of `new_html` is kept
+/// as is, so the result is as well-formed as the new block.
+fn highlight_words(old_html: &str, new_html: &str) -> String {
+    let old_tokens = html_tokens(old_html);
+    let new_tokens = html_tokens(new_html);
+    let mut highlighter = WordHighlighter::default();
+
+    for edit in align(&old_tokens, &new_tokens) {
+        match edit {
+            Edit::Keep(_, new_index) => highlighter.keep(new_tokens[new_index]),
+            Edit::Remove(old_index) => {
+                if !is_tag(old_tokens[old_index]) {
+                    highlighter.change(old_tokens[old_index], "");
+                }
+            }
+            Edit::Add(new_index) => {
+                if is_tag(new_tokens[new_index]) {
+                    highlighter.keep(new_tokens[new_index]);
+                } else {
+                    highlighter.change("", new_tokens[new_index]);
+                }
+            }
+        }
+    }
+    highlighter.flush();
+
+    highlighter.output
+}
+
+#[derive(Default)]
+struct WordHighlighter {
+    output: String,
+    removed_text: String,
+    added_text: String,
+    /// Unchanged whitespace after a change, which joins the change if another one follows so
+    /// that a rewritten phrase is highlighted as a whole rather than word by word.
+    pending_space: String,
+}
+
+impl WordHighlighter {
+    fn keep(&mut self, token: &str) {
+        let in_change = !self.removed_text.is_empty() || !self.added_text.is_empty();
+        if in_change && !is_tag(token) && token.trim().is_empty() {
+            self.pending_space.push_str(token);
+            return;
+        }
+        self.flush();
+        self.output.push_str(token);
+    }
+
+    fn change(&mut self, removed: &str, added: &str) {
+        if !self.pending_space.is_empty() {
+            self.removed_text.push_str(&self.pending_space);
+            self.added_text.push_str(&self.pending_space);
+            self.pending_space.clear();
+        }
+        self.removed_text.push_str(removed);
+        self.added_text.push_str(added);
+    }
+
+    fn flush(&mut self) {
+        if !self.removed_text.trim().is_empty() {
+            self.output.push_str("<del class=\"mdv-diff-word\">");
+            self.output.push_str(&self.removed_text);
+            self.output.push_str("</del>");
+        }
+        if self.added_text.trim().is_empty() {
+            self.output.push_str(&self.added_text);
+        } else {
+            self.output.push_str("<ins class=\"mdv-diff-word\">");
+            self.output.push_str(&self.added_text);
+            self.output.push_str("</ins>");
+        }
+        self.output.push_str(&self.pending_space);
+        self.removed_text.clear();
+        self.added_text.clear();
+        self.pending_space.clear();
+    }
+}
+
+fn is_tag(token: &str) -> bool {
+    token.starts_with('<')
+}
+
+/// Splits HTML into tags, character references, whitespace runs, words and single punctuation
+/// characters.
+fn html_tokens(html: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while let Some(first) = rest.chars().next() {
+        let length = match first {
+            '<' => rest.find('>').map_or(rest.len(), |end| end + 1),
+            '&' => rest
+                .find(';')
+                .filter(|&end| {
+                    rest[1..end]
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '#')
+                })
+                .map_or(1, |end| end + 1),
+            c if c.is_whitespace() => rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len()),
+            c if c.is_alphanumeric() || c == '_' => rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len()),
+            c => c.len_utf8(),
+        };
+        tokens.push(&rest[..length]);
+        rest = &rest[length..];
+    }
+    tokens
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Keep(usize, usize),
+    Remove(usize),
+    Add(usize),
+}
+
+/// Aligns two sequences along their longest common subsequence, listing removals before
+/// additions within each changed run.
+fn align<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old_item, new_item)| old_item == new_item)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old_item, new_item)| old_item == new_item)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut edits: Vec<Edit> = (0..prefix).map(|index| Edit::Keep(index, index)).collect();
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_ALIGNMENT_CELLS {
+        edits.extend((0..old_middle.len()).map(|index| Edit::Remove(prefix + index)));
+        edits.extend((0..new_middle.len()).map(|index| Edit::Add(prefix + index)));
+    } else {
+        align_middle(old_middle, new_middle, prefix, &mut edits);
+    }
+    let old_suffix_start = old.len() - suffix;
+    let new_suffix_start = new.len() - suffix;
+    edits.extend(
+        (0..suffix).map(|index| Edit::Keep(old_suffix_start + index, new_suffix_start + index)),
+    );
+
+    edits
+}
+
+fn align_middle<T: PartialEq>(old: &[T], new: &[T], offset: usize, edits: &mut Vec<Edit>) {
+    let width = new.len() + 1;
+    // common[i * width + j] is the length of the longest common subsequence of old[i..] and
+    // new[j..].
+    let mut common = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i * width + j] = if old[i] == new[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut added = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.append(&mut added);
+            edits.push(Edit::Keep(offset + i, offset + j));
+            i += 1;
+            j += 1;
+        } else if i < old.len()
+            && (j == new.len() || common[(i + 1) * width + j] >= common[i * width + j + 1])
+        {
+            edits.push(Edit::Remove(offset + i));
+            i += 1;
+        } else {
+            added.push(Edit::Add(offset + j));
+            j += 1;
+        }
+    }
+    edits.extend(added);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use markdown_viewer_application::ports::MarkdownDiffer;
+    use markdown_viewer_domain::document::RenderPreferences;
+
+    use super::{align, highlight_words, html_tokens, ComrakMarkdownDiffer, Edit};
+
+    fn diff(old: &str, new: &str) -> markdown_viewer_domain::diff::DocumentDiff {
+        ComrakMarkdownDiffer::new()
+            .diff(
+                old,
+                new,
+                Path::new("/docs/guide.md"),
+                RenderPreferences::default(),
+            )
+            .expect("diff should succeed")
+    }
+
+    #[test]
+    fn aligns_sequences_with_removals_before_additions() {
+        assert_eq!(
+            align(&["a", "b", "c", "d"], &["a", "x", "c", "d", "e"]),
+            [
+                Edit::Keep(0, 0),
+                Edit::Remove(1),
+                Edit::Add(1),
+                Edit::Keep(2, 2),
+                Edit::Keep(3, 3),
+                Edit::Add(4),
+            ]
+        );
+        assert_eq!(align::<&str>(&[], &[]), []);
+    }
+
+    #[test]
+    fn tokenizes_tags_entities_words_and_punctuation() {
+        assert_eq!(
+            html_tokens("<p>Fish &amp; chips, naïve_code!</p>"),
+            [
+                "<p>",
+                "Fish",
+                " ",
+                "&amp;",
+                " ",
+                "chips",
+                ",",
+                " ",
+                "naïve_code",
+                "!",
+                "</p>"
+            ]
+        );
+    }
+
+    #[test]
+    fn highlights_changed_words_inside_the_new_markup() {
+        assert_eq!(
+            highlight_words(
+                "<p>The quick brown fox.</p>\n",
+                "<p>The <em>slow</em> brown fox jumps.</p>\n"
+            ),
+            "<p>The <del class=\"mdv-diff-word\">quick</del><em><ins class=\"mdv-diff-word\">slow</ins></em> brown fox<ins class=\"mdv-diff-word\"> jumps</ins>.</p>\n"
+        );
+    }
+
+    #[test]
+    fn marks_inserted_deleted_and_modified_blocks() {
+        let result = diff(
+            "# Guide\n\nIntro text here.\n\nOld paragraph to drop.\n\n```\ncode\n```\n",
+            "# Guide\n\nIntro text\nhere.\n\nA new first tip.\n\n- item\n\n```\ncode changed\n```\n",
+        );
+
+        assert_eq!(result.inserted_blocks, 1);
+        assert_eq!(result.deleted_blocks, 0);
+        assert_eq!(result.modified_blocks, 2);
+        assert!(result.html.starts_with("<h1>"), "{}", result.html);
+        assert!(result.html.contains("<p>Intro text\nhere.</p>\n<div class=\"mdv-diff-modified\"><p><del class=\"mdv-diff-word\">Old paragraph to drop</del><ins class=\"mdv-diff-word\">A new first tip</ins>.</p>\n</div>"), "{}", result.html);
+        assert!(
+            result
+                .html
+                .contains("<div class=\"mdv-diff-inserted\"><ul>"),
+            "{}",
+            result.html
+        );
+        assert!(
+            result
+                .html
+                .contains("code<ins class=\"mdv-diff-word\"> changed</ins>"),
+            "{}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn blocks_of_different_kinds_are_removed_and_added_rather_than_modified() {
+        let result = diff("Para\n\n# Title\n", "> Quote\n\n# Title\n");
+
+        assert_eq!(result.modified_blocks, 0);
+        assert_eq!(result.deleted_blocks, 1);
+        assert_eq!(result.inserted_blocks, 1);
+        let deleted = result
+            .html
+            .find("mdv-diff-deleted")
+            .expect("removed paragraph should be shown");
+        let inserted = result
+            .html
+            .find("mdv-diff-inserted")
+            .expect("added quote should be shown");
+        assert!(deleted < inserted);
+    }
+
+    #[test]
+    fn identical_documents_have_no_changes_and_front_matter_is_ignored() {
+        let result = diff(
+            "---\ntitle: A\n---\n# Same\n",
+            "---\ntitle: B\n---\n# Same\n",
+        );
+
+        assert_eq!(
+            (
+                result.inserted_blocks,
+                result.deleted_blocks,
+                result.modified_blocks
+            ),
+            (0, 0, 0)
+        );
+        assert!(!result.html.contains("mdv-diff"));
+    }
+}
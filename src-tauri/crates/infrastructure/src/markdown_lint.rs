@@ -0,0 +1,360 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::MarkdownLinter;
+use markdown_viewer_domain::lint::{LintReport, LintSeverity};
+use serde_json::{Map, Value};
+
+use crate::file_repository::is_table_file;
+use crate::lint_rules::{
+    lint_markdown, LintConfig, LintRule, ListMarkerStyle, RuleSetting, LINE_LENGTH,
+    NO_DUPLICATE_HEADING, NO_TRAILING_SPACES, RULES, UL_STYLE,
+};
+
+pub const LINT_CONFIG_FILE_NAME: &str = ".markdownlint.json";
+
+/// Lints markdown files with the built-in rules, configured by the nearest
+/// `.markdownlint.json` in the document's folder or one of its ancestors.
+pub struct ComrakMarkdownLinter;
+
+impl ComrakMarkdownLinter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ComrakMarkdownLinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownLinter for ComrakMarkdownLinter {
+    fn lint(
+        &self,
+        markdown: &str,
+        document_path: &Path,
+    ) -> Result<LintReport, MarkdownViewerError> {
+        // Tables are read through a generated markdown wrapper, so findings would not apply to
+        // the data file.
+        if is_table_file(document_path) {
+            return Err(MarkdownViewerError::NotMarkdown(
+                document_path.to_path_buf(),
+            ));
+        }
+
+        let config_path = find_lint_config(document_path);
+        let config = match &config_path {
+            Some(path) => read_lint_config(path)?,
+            None => LintConfig::default(),
+        };
+
+        Ok(LintReport {
+            diagnostics: lint_markdown(markdown, &config),
+            config_path,
+        })
+    }
+}
+
+fn find_lint_config(document_path: &Path) -> Option<PathBuf> {
+    document_path
+        .ancestors()
+        .skip(1)
+        .map(|folder| folder.join(LINT_CONFIG_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+fn read_lint_config(path: &Path) -> Result<LintConfig, MarkdownViewerError> {
+    let invalid = |reason: String| MarkdownViewerError::LintConfig {
+        path: path.to_path_buf(),
+        reason,
+    };
+    let contents = fs::read_to_string(path).map_err(|error| invalid(error.to_string()))?;
+    let value: Value =
+        serde_json::from_str(&contents).map_err(|error| invalid(error.to_string()))?;
+
+    parse_lint_config(&value).map_err(invalid)
+}
+
+/// Reads the supported subset of the markdownlint configuration format:
+///
+/// - `"default": false` disables every rule that is not enabled explicitly;
+/// - a rule, keyed by ID (`"MD013"`) or alias (`"line-length"`), is turned on or off with a
+///   boolean, set to `"warning"` or `"error"`, or enabled with an object of options, which may
+///   include `"severity"`.
+///
+/// Unknown keys, including other markdownlint rules, tags and options, are ignored so that a
+/// configuration shared with markdownlint itself keeps working.
+fn parse_lint_config(value: &Value) -> Result<LintConfig, String> {
+    let Some(entries) = value.as_object() else {
+        return Err("the configuration must be a JSON object".to_string());
+    };
+    let mut config = LintConfig::default();
+
+    if let Some(default) = entries.get("default") {
+        let enabled = default
+            .as_bool()
+            .ok_or_else(|| "`default` must be true or false".to_string())?;
+        for rule in RULES {
+            config.rules.insert(
+                rule.id,
+                RuleSetting {
+                    enabled,
+                    severity: LintSeverity::Warning,
+                },
+            );
+        }
+    }
+
+    for (key, setting) in entries {
+        let Some(rule) = RULES
+            .into_iter()
+            .find(|rule| key.eq_ignore_ascii_case(rule.id) || key.eq_ignore_ascii_case(rule.name))
+        else {
+            continue;
+        };
+        let rule_setting = match setting {
+            Value::Bool(enabled) => RuleSetting {
+                enabled: *enabled,
+                severity: LintSeverity::Warning,
+            },
+            Value::String(severity) => RuleSetting {
+                enabled: true,
+                severity: parse_severity(key, severity)?,
+            },
+            Value::Object(options) => {
+                apply_rule_options(rule, options, &mut config)?;
+                RuleSetting {
+                    enabled: true,
+                    severity: match options.get("severity") {
+                        Some(Value::String(severity)) => parse_severity(key, severity)?,
+                        Some(_) => return Err(format!("`{key}.severity` must be a string")),
+                        None => LintSeverity::Warning,
+                    },
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "`{key}` must be true, false, \"warning\", \"error\" or an object"
+                ))
+            }
+        };
+        config.rules.insert(rule.id, rule_setting);
+    }
+
+    Ok(config)
+}
+
+fn parse_severity(key: &str, severity: &str) -> Result<LintSeverity, String> {
+    match severity {
+        "warning" => Ok(LintSeverity::Warning),
+        "error" => Ok(LintSeverity::Error),
+        other => Err(format!(
+            "`{key}` severity must be \"warning\" or \"error\", found \"{other}\""
+        )),
+    }
+}
+
+fn apply_rule_options(
+    rule: LintRule,
+    options: &Map<String, Value>,
+    config: &mut LintConfig,
+) -> Result<(), String> {
+    let number = |name: &str| -> Result<Option<usize>, String> {
+        options
+            .get(name)
+            .map(|value| {
+                value
+                    .as_u64()
+                    .and_then(|number| usize::try_from(number).ok())
+                    .ok_or_else(|| format!("`{}.{name}` must be a non-negative integer", rule.id))
+            })
+            .transpose()
+    };
+    let flag = |name: &str| -> Result<Option<bool>, String> {
+        options
+            .get(name)
+            .map(|value| {
+                value
+                    .as_bool()
+                    .ok_or_else(|| format!("`{}.{name}` must be true or false", rule.id))
+            })
+            .transpose()
+    };
+
+    if rule == UL_STYLE {
+        if let Some(style) = options.get("style") {
+            config.ul_style = match style.as_str() {
+                Some("consistent") => ListMarkerStyle::Consistent,
+                Some("asterisk") => ListMarkerStyle::Asterisk,
+                Some("dash") => ListMarkerStyle::Dash,
+                Some("plus") => ListMarkerStyle::Plus,
+                _ => {
+                    return Err(format!(
+                        "`{}.style` must be \"consistent\", \"asterisk\", \"dash\" or \"plus\"",
+                        rule.id
+                    ))
+                }
+            };
+        }
+    } else if rule == NO_TRAILING_SPACES {
+        config.br_spaces = number("br_spaces")?.unwrap_or(config.br_spaces);
+    } else if rule == LINE_LENGTH {
+        config.line_length = number("line_length")?.unwrap_or(config.line_length);
+        config.line_length_code_blocks =
+            flag("code_blocks")?.unwrap_or(config.line_length_code_blocks);
+        config.line_length_tables = flag("tables")?.unwrap_or(config.line_length_tables);
+        config.line_length_headings = flag("headings")?.unwrap_or(config.line_length_headings);
+    } else if rule == NO_DUPLICATE_HEADING {
+        config.siblings_only = flag("siblings_only")?.unwrap_or(config.siblings_only);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::MarkdownLinter;
+    use markdown_viewer_domain::lint::LintSeverity;
+    use serde_json::json;
+
+    use super::{parse_lint_config, ComrakMarkdownLinter, LINT_CONFIG_FILE_NAME};
+    use crate::lint_rules::{LintConfig, ListMarkerStyle};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after the epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("mdv-lint-{name}-{unique}"));
+        fs::create_dir_all(&dir).expect("temp dir should be creatable");
+        dir
+    }
+
+    #[test]
+    fn parses_rule_toggles_aliases_severities_and_options() {
+        let config = parse_lint_config(&json!({
+            "$schema": "https://example.com/markdownlint.schema.json",
+            "default": false,
+            "heading-increment": true,
+            "MD009": { "br_spaces": 0 },
+            "line-length": { "line_length": 120, "code_blocks": false, "severity": "error" },
+            "MD004": { "style": "dash" },
+            "no-duplicate-heading": { "siblings_only": true },
+            "no-alt-text": "error",
+            "MD033": false
+        }))
+        .expect("config should parse");
+
+        let enabled: Vec<&str> = config
+            .rules
+            .iter()
+            .filter(|(_, setting)| setting.enabled)
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(
+            enabled,
+            ["MD001", "MD004", "MD009", "MD013", "MD024", "MD045"]
+        );
+        assert_eq!(config.rules["MD013"].severity, LintSeverity::Error);
+        assert_eq!(config.rules["MD045"].severity, LintSeverity::Error);
+        assert_eq!(config.rules["MD001"].severity, LintSeverity::Warning);
+        assert_eq!(config.br_spaces, 0);
+        assert_eq!(config.line_length, 120);
+        assert!(!config.line_length_code_blocks);
+        assert!(config.line_length_tables);
+        assert_eq!(config.ul_style, ListMarkerStyle::Dash);
+        assert!(config.siblings_only);
+    }
+
+    #[test]
+    fn empty_config_keeps_defaults() {
+        assert_eq!(
+            parse_lint_config(&json!({})).expect("config should parse"),
+            LintConfig::default()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_settings() {
+        for (config, expected) in [
+            (json!([]), "must be a JSON object"),
+            (
+                json!({ "default": "yes" }),
+                "`default` must be true or false",
+            ),
+            (json!({ "MD013": 80 }), "`MD013` must be true"),
+            (
+                json!({ "MD013": { "line_length": -1 } }),
+                "`MD013.line_length`",
+            ),
+            (
+                json!({ "ul-style": { "style": "sublist" } }),
+                "`MD004.style`",
+            ),
+            (json!({ "MD001": "fatal" }), "severity must be"),
+        ] {
+            let error = parse_lint_config(&config).expect_err("config should be rejected");
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+
+    #[test]
+    fn uses_nearest_config_file_above_the_document() {
+        let root = temp_dir("nearest");
+        let nested = root.join("docs").join("guide");
+        fs::create_dir_all(&nested).expect("nested dir should be creatable");
+        fs::write(root.join(LINT_CONFIG_FILE_NAME), r#"{ "MD009": false }"#)
+            .expect("root config should be writable");
+        fs::write(
+            root.join("docs").join(LINT_CONFIG_FILE_NAME),
+            r#"{ "no-trailing-spaces": "error" }"#,
+        )
+        .expect("docs config should be writable");
+        let document = nested.join("intro.md");
+
+        let report = ComrakMarkdownLinter::new()
+            .lint("# Intro \n", &document)
+            .expect("lint should succeed");
+
+        assert_eq!(
+            report.config_path,
+            Some(root.join("docs").join(LINT_CONFIG_FILE_NAME))
+        );
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].rule_id, "MD009");
+        assert_eq!(report.diagnostics[0].severity, LintSeverity::Error);
+
+        fs::remove_dir_all(root).expect("temp dir should be removable");
+    }
+
+    #[test]
+    fn reports_invalid_config_file_and_non_markdown_documents() {
+        let root = temp_dir("invalid");
+        fs::write(root.join(LINT_CONFIG_FILE_NAME), "{ MD009: false }")
+            .expect("config should be writable");
+        let linter = ComrakMarkdownLinter::new();
+
+        let error = linter
+            .lint("text\n", &root.join("notes.md"))
+            .expect_err("invalid JSON should be reported");
+        match error {
+            MarkdownViewerError::LintConfig { path, .. } => {
+                assert_eq!(path, root.join(LINT_CONFIG_FILE_NAME));
+            }
+            other => panic!("unexpected error variant: {other:?}"),
+        }
+        assert!(matches!(
+            linter.lint("a,b\n", &root.join("table.csv")),
+            Err(MarkdownViewerError::NotMarkdown(_))
+        ));
+
+        fs::remove_dir_all(root).expect("temp dir should be removable");
+    }
+}
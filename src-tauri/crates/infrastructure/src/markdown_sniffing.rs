@@ -0,0 +1,119 @@
+/// Lines looked at when deciding whether text is markdown.
+const SNIFFED_LINES: usize = 200;
+
+const HEADING: u8 = 1;
+const LIST: u8 = 1 << 1;
+const QUOTE: u8 = 1 << 2;
+const EMPHASIS: u8 = 1 << 3;
+const INLINE_CODE: u8 = 1 << 4;
+const TABLE: u8 = 1 << 5;
+
+/// Whether `text` reads like markdown rather than plain prose, code or data: it has a construct
+/// plain text rarely contains (a code fence, a setext heading or a link), or at least two
+/// different weaker ones such as `#` headings, list items and emphasis.
+///
+/// `#` headings alone are not enough, since they also start comments in shell scripts and
+/// configuration files.
+pub(crate) fn looks_like_markdown(text: &str) -> bool {
+    if text.contains('\0') {
+        return false;
+    }
+
+    let mut signals = 0u8;
+    let mut previous_line = "";
+    for line in text.lines().take(SNIFFED_LINES) {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```")
+            || trimmed.starts_with("~~~")
+            || is_setext_underline(trimmed, previous_line)
+            || has_link(trimmed)
+        {
+            return true;
+        }
+
+        if is_atx_heading(trimmed) {
+            signals |= HEADING;
+        }
+        if is_list_item(trimmed) {
+            signals |= LIST;
+        }
+        if trimmed.starts_with("> ") {
+            signals |= QUOTE;
+        }
+        if has_paired(trimmed, "**") || has_paired(trimmed, "__") {
+            signals |= EMPHASIS;
+        }
+        if has_paired(trimmed, "`") {
+            signals |= INLINE_CODE;
+        }
+        if trimmed.starts_with('|') && trimmed.ends_with('|') && trimmed.len() > 2 {
+            signals |= TABLE;
+        }
+        previous_line = trimmed;
+    }
+
+    signals.count_ones() >= 2
+}
+
+fn is_atx_heading(line: &str) -> bool {
+    let level = line.bytes().take_while(|byte| *byte == b'#').count();
+    (1..=6).contains(&level) && line[level..].starts_with(' ')
+}
+
+/// `===` or `---` under a line of text; a `---` after a blank line is a thematic break instead.
+fn is_setext_underline(line: &str, previous_line: &str) -> bool {
+    line.len() >= 3
+        && (line.bytes().all(|byte| byte == b'=') || line.bytes().all(|byte| byte == b'-'))
+        && !previous_line.is_empty()
+        && !is_list_item(previous_line)
+}
+
+fn is_list_item(line: &str) -> bool {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return true;
+    }
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    digits > 0 && (line[digits..].starts_with(". ") || line[digits..].starts_with(") "))
+}
+
+/// `[text](target)` or `![alt](target)`.
+fn has_link(line: &str) -> bool {
+    line.match_indices("](")
+        .any(|(index, _)| line[..index].contains('[') && line[index + 2..].contains(')'))
+}
+
+fn has_paired(line: &str, marker: &str) -> bool {
+    line.matches(marker).count() >= 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::looks_like_markdown;
+
+    #[test]
+    fn recognises_typical_readme_content() {
+        assert!(looks_like_markdown(
+            "Project\n=======\n\nA tool for things.\n"
+        ));
+        assert!(looks_like_markdown(
+            "# Changelog\n\n- Fixed the parser\n- Added `--quiet`\n"
+        ));
+        assert!(looks_like_markdown(
+            "See the [docs](https://example.com) for details.\n"
+        ));
+        assert!(looks_like_markdown("Usage:\n\n```sh\nrun it\n```\n"));
+    }
+
+    #[test]
+    fn rejects_plain_text_code_and_binary_data() {
+        assert!(!looks_like_markdown(
+            "Just some notes\nwith a few lines of prose.\n"
+        ));
+        assert!(!looks_like_markdown(
+            "#!/bin/sh\n# Build the project\nmake all\n"
+        ));
+        assert!(!looks_like_markdown("# Settings\nkey = value\n"));
+        assert!(!looks_like_markdown("# Title\0\n- item\n"));
+        assert!(!looks_like_markdown("Before\n\n---\n\nAfter\n"));
+    }
+}
@@ -0,0 +1,64 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use comrak::nodes::AstNode;
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_domain::document::{DocumentWarning, RenderPreferences};
+
+/// Per-render inputs shared with every transform in the pipeline, and the findings transforms
+/// report back to the renderer.
+pub struct TransformContext<'p> {
+    pub preferences: &'p RenderPreferences,
+    /// The file the markdown was read from; relative references resolve against its directory.
+    pub document_path: &'p Path,
+    warnings: RefCell<Vec<DocumentWarning>>,
+    local_images: RefCell<Vec<PathBuf>>,
+}
+
+impl<'p> TransformContext<'p> {
+    pub fn new(preferences: &'p RenderPreferences, document_path: &'p Path) -> Self {
+        Self {
+            preferences,
+            document_path,
+            warnings: RefCell::new(Vec::new()),
+            local_images: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Directory that relative references in the document resolve against.
+    pub fn base_directory(&self) -> &Path {
+        self.document_path.parent().unwrap_or_else(|| Path::new(""))
+    }
+
+    /// Reports a non-fatal problem that is returned alongside the rendered document.
+    pub fn warn(&self, warning: DocumentWarning) {
+        self.warnings.borrow_mut().push(warning);
+    }
+
+    /// Records a local image file the rendered HTML loads.
+    pub fn record_local_image(&self, path: PathBuf) {
+        let mut local_images = self.local_images.borrow_mut();
+        if !local_images.contains(&path) {
+            local_images.push(path);
+        }
+    }
+
+    pub(crate) fn into_findings(self) -> (Vec<DocumentWarning>, Vec<PathBuf>) {
+        (self.warnings.into_inner(), self.local_images.into_inner())
+    }
+}
+
+/// Rewrites the parsed comrak AST before it is formatted as HTML.
+///
+/// Transforms run in the order they were given to the renderer, and the table of contents and
+/// word count are computed from the tree after the last transform has run. New nodes must be
+/// allocated from `arena` so they live as long as the rest of the document.
+pub trait MarkdownTransform: Send + Sync {
+    fn transform<'a>(
+        &self,
+        arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError>;
+}
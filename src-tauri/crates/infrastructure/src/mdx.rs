@@ -0,0 +1,401 @@
+use std::borrow::Cow;
+use std::path::Path;
+
+/// The markdown rendered for the document at `document_path`: MDX documents go through
+/// [`mdx_to_markdown`] first, everything else is rendered as written.
+pub(crate) fn render_source<'a>(markdown: &'a str, document_path: &Path) -> Cow<'a, str> {
+    if is_mdx_file(document_path) {
+        Cow::Owned(mdx_to_markdown(markdown))
+    } else {
+        Cow::Borrowed(markdown)
+    }
+}
+
+fn is_mdx_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("mdx"))
+}
+
+/// Rewrites MDX into markdown comrak can render without running any JavaScript:
+///
+/// - `import`/`export` blocks and `{...}` expression blocks, such as `{/* comments */}`, are
+///   removed;
+/// - component tags on their own lines become quote blocks labelled with the component name
+///   (`` `<Callout>` ``), holding the component's children, which stay markdown;
+/// - component tags inside a line of text are removed, keeping their children.
+///
+/// Lowercase HTML tags are left to the renderer's HTML handling. Every source line becomes
+/// exactly one output line, so source positions such as task item lines still point into the
+/// original file.
+pub(crate) fn mdx_to_markdown(source: &str) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut output: Vec<String> = Vec::with_capacity(lines.len());
+    let mut open_components: Vec<String> = Vec::new();
+    let mut fence: Option<Fence> = None;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let prefix = "> ".repeat(open_components.len());
+        let empty_line = prefix.trim_end().to_string();
+
+        if let Some(open_fence) = fence {
+            if open_fence.is_closed_by(line) {
+                fence = None;
+            }
+            output.push(format!("{prefix}{line}"));
+            index += 1;
+            continue;
+        }
+        if let Some(opened) = Fence::opened_by(line) {
+            fence = Some(opened);
+            output.push(format!("{prefix}{line}"));
+            index += 1;
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let starts_block = index == 0 || lines[index - 1].trim().is_empty();
+        let block_end = if open_components.is_empty() && starts_block && is_esm(line) {
+            Some(esm_end(&lines, index))
+        } else if trimmed.starts_with('{') {
+            expression_end(&lines, index)
+        } else {
+            None
+        };
+        if let Some(end) = block_end {
+            for _ in index..=end {
+                output.push(empty_line.clone());
+            }
+            index = end + 1;
+            continue;
+        }
+
+        let Some(tag) = flow_tag(&lines, index) else {
+            if trimmed.is_empty() {
+                output.push(empty_line);
+            } else {
+                output.push(format!("{prefix}{}", strip_inline_components(line)));
+            }
+            index += 1;
+            continue;
+        };
+        let continuation_prefix = match tag.kind {
+            TagKind::Open => {
+                let label_prefix = format!("{prefix}> ");
+                let rest = tag.rest.trim();
+                let closing = format!("</{}>", tag.name);
+                match rest.strip_suffix(closing.as_str()) {
+                    // `<Note>Short text</Note>` on a single line.
+                    Some(children) => output.push(format!(
+                        "{label_prefix}`<{}>` {}",
+                        tag.name,
+                        strip_inline_components(children.trim())
+                    )),
+                    None => {
+                        let children = strip_inline_components(rest);
+                        // Two trailing spaces break the line before children on the next line.
+                        let separator = if children.is_empty() { "  " } else { " " };
+                        output.push(format!(
+                            "{label_prefix}`<{}>`{separator}{children}",
+                            tag.name
+                        ));
+                        open_components.push(tag.name);
+                    }
+                }
+                label_prefix.trim_end().to_string()
+            }
+            TagKind::SelfClosing => {
+                output.push(format!("{prefix}> `<{} />`", tag.name));
+                empty_line
+            }
+            TagKind::Close => {
+                if let Some(position) = open_components.iter().rposition(|name| *name == tag.name) {
+                    open_components.truncate(position);
+                }
+                let prefix = "> ".repeat(open_components.len());
+                output.push(prefix.trim_end().to_string());
+                prefix.trim_end().to_string()
+            }
+        };
+        for _ in index + 1..=tag.end_line {
+            output.push(continuation_prefix.clone());
+        }
+        index = tag.end_line + 1;
+    }
+
+    let mut markdown = output.join("\n");
+    if source.ends_with('\n') {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+#[derive(Clone, Copy)]
+struct Fence {
+    marker: char,
+    length: usize,
+}
+
+impl Fence {
+    fn opened_by(line: &str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        if line.len() - trimmed.len() > 3 {
+            return None;
+        }
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let length = trimmed.chars().take_while(|c| *c == marker).count();
+        (length >= 3).then_some(Self { marker, length })
+    }
+
+    fn is_closed_by(self, line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.chars().take_while(|c| *c == self.marker).count() >= self.length
+            && trimmed.chars().all(|c| c == self.marker)
+    }
+}
+
+/// MDX reads a block starting with `import ` or `export ` in the first column as JavaScript.
+fn is_esm(line: &str) -> bool {
+    line.starts_with("import ") || line.starts_with("export ")
+}
+
+/// The last line of the ESM block starting at `start`: the line before the first blank line
+/// outside brackets, or the end of the document.
+fn esm_end(lines: &[&str], start: usize) -> usize {
+    let mut depth = 0i32;
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        if line.trim().is_empty() && depth <= 0 {
+            return index - 1;
+        }
+        depth += bracket_balance(line);
+    }
+    lines.len() - 1
+}
+
+/// The last line of the `{...}` expression starting at `start`, when it takes whole lines.
+fn expression_end(lines: &[&str], start: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        for character in line.chars() {
+            match character {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth <= 0 {
+            return line.trim_end().ends_with('}').then_some(index);
+        }
+    }
+    None
+}
+
+fn bracket_balance(line: &str) -> i32 {
+    line.chars()
+        .map(|character| match character {
+            '{' | '(' | '[' => 1,
+            '}' | ')' | ']' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum TagKind {
+    Open,
+    SelfClosing,
+    Close,
+}
+
+struct FlowTag<'a> {
+    name: String,
+    kind: TagKind,
+    /// The line the tag's closing `>` is on.
+    end_line: usize,
+    /// What follows the tag on its last line.
+    rest: &'a str,
+}
+
+/// The component tag starting line `start`, which may span several lines when it has many
+/// attributes.
+fn flow_tag<'a>(lines: &[&'a str], start: usize) -> Option<FlowTag<'a>> {
+    let trimmed = lines[start].trim_start();
+    let (closing, after_bracket) = match trimmed.strip_prefix("</") {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('<')?),
+    };
+    let name = component_name(after_bracket)?;
+
+    let mut scanner = TagScanner::default();
+    let mut text = &after_bracket[name.len()..];
+    for (line_index, line) in lines.iter().enumerate().skip(start) {
+        if line_index > start {
+            text = line;
+        }
+        if let Some((end, self_closing)) = scanner.scan(text) {
+            let kind = match (closing, self_closing) {
+                (true, _) => TagKind::Close,
+                (false, true) => TagKind::SelfClosing,
+                (false, false) => TagKind::Open,
+            };
+            return Some(FlowTag {
+                name: name.to_string(),
+                kind,
+                end_line: line_index,
+                rest: &text[end..],
+            });
+        }
+    }
+    None
+}
+
+/// JSX component names start with an uppercase letter; lowercase names are HTML elements.
+fn component_name(text: &str) -> Option<&str> {
+    if !text.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let length = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$')))
+        .unwrap_or(text.len());
+    Some(&text[..length])
+}
+
+/// Finds the `>` ending a tag's attributes, skipping quoted strings and `{...}` expressions.
+#[derive(Default)]
+struct TagScanner {
+    quote: Option<char>,
+    depth: usize,
+    previous: Option<char>,
+}
+
+impl TagScanner {
+    /// The byte offset just past the closing `>` in `text` and whether the tag closes itself.
+    fn scan(&mut self, text: &str) -> Option<(usize, bool)> {
+        for (offset, character) in text.char_indices() {
+            match (self.quote, character) {
+                (Some(quote), _) if character == quote => self.quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'' | '`') => self.quote = Some(character),
+                (None, '{') => self.depth += 1,
+                (None, '}') => self.depth = self.depth.saturating_sub(1),
+                (None, '>') if self.depth == 0 => {
+                    return Some((offset + 1, self.previous == Some('/')));
+                }
+                _ => {}
+            }
+            if !character.is_whitespace() {
+                self.previous = Some(character);
+            }
+        }
+        None
+    }
+}
+
+/// Removes component tags from a line of text, keeping the text between them and leaving code
+/// spans alone.
+fn strip_inline_components(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(position) = rest.find(['`', '<']) {
+        output.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        if rest.starts_with('`') {
+            let ticks = rest.len() - rest.trim_start_matches('`').len();
+            let fence = &rest[..ticks];
+            let span_end = rest[ticks..]
+                .find(fence)
+                .map_or(ticks, |end| ticks + end + ticks);
+            output.push_str(&rest[..span_end]);
+            rest = &rest[span_end..];
+            continue;
+        }
+
+        let tag_text = rest.strip_prefix("</").unwrap_or(&rest[1..]);
+        let tag_end = component_name(tag_text).and_then(|name| {
+            let attributes_start = rest.len() - tag_text.len() + name.len();
+            TagScanner::default()
+                .scan(&rest[attributes_start..])
+                .map(|(end, _)| attributes_start + end)
+        });
+        match tag_end {
+            Some(end) => rest = &rest[end..],
+            None => {
+                output.push('<');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::RenderPreferences;
+
+    use super::{mdx_to_markdown, render_source};
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    #[test]
+    fn removes_esm_and_expressions_keeping_line_numbers() {
+        let source = "import { Tabs } from './tabs'\nimport Chart from './chart'\n\nexport const meta = {\n  title: 'Guide',\n\n  draft: false,\n}\n\n# Guide\n\n{/* reviewed */}\n\nImport the data first.\n";
+
+        let markdown = mdx_to_markdown(source);
+
+        assert_eq!(
+            markdown,
+            "\n\n\n\n\n\n\n\n\n# Guide\n\n\n\nImport the data first.\n"
+        );
+        assert_eq!(markdown.lines().count(), source.lines().count());
+    }
+
+    #[test]
+    fn replaces_block_components_with_labelled_quotes_around_their_children() {
+        let source = "<Callout type=\"warn\">\n  Read **this** first.\n\n  - [ ] check\n</Callout>\n\n<Chart\n  data={[1, 2]}\n/>\n\n<Note>Short <Kbd>note</Kbd></Note>\n";
+
+        let markdown = mdx_to_markdown(source);
+
+        assert_eq!(
+            markdown,
+            "> `<Callout>`  \n>   Read **this** first.\n>\n>   - [ ] check\n\n\n> `<Chart />`\n\n\n\n> `<Note>` Short note\n"
+        );
+    }
+
+    #[test]
+    fn leaves_code_html_and_inline_code_alone() {
+        let source = "```jsx\n<Button onClick={go} />\nimport x from 'y'\n```\n\n<div>html</div>\n\nUse `<Button />` or <Button kind=\"primary\">this</Button>.\n";
+
+        let markdown = mdx_to_markdown(source);
+
+        assert_eq!(
+            markdown,
+            "```jsx\n<Button onClick={go} />\nimport x from 'y'\n```\n\n<div>html</div>\n\nUse `<Button />` or this.\n"
+        );
+    }
+
+    #[test]
+    fn renders_mdx_documents_through_the_prepass_only() {
+        let renderer = ComrakMarkdownRenderer::new();
+        let source = "import Box from './box'\n\n<Box>\n  ## Inside\n</Box>\n";
+
+        let mdx = renderer
+            .render(
+                source,
+                Path::new("/docs/page.mdx"),
+                RenderPreferences::default(),
+            )
+            .expect("mdx should render");
+        assert!(!mdx.html.contains("import"));
+        assert!(mdx.html.contains("<blockquote>"));
+        assert!(mdx.html.contains("<code>&lt;Box&gt;</code>"));
+        assert_eq!(mdx.toc.len(), 1);
+
+        assert_eq!(render_source(source, Path::new("/docs/page.md")), source);
+    }
+}
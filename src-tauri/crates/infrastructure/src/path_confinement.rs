@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `path` with `.` and `..` segments resolved, or `None` when it is absolute or leaves the folder
+/// it is relative to, so a document cannot display arbitrary files on the reader's disk.
+pub(crate) fn confined_relative_path(path: &str) -> Option<PathBuf> {
+    let drive_letter =
+        path.as_bytes().get(1) == Some(&b':') && path.as_bytes()[0].is_ascii_alphabetic();
+    if Path::new(path).is_absolute() || path.starts_with(['/', '\\']) || drive_letter {
+        return None;
+    }
+    let mut segments = Vec::new();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    (!segments.is_empty()).then(|| segments.iter().collect())
+}
+
+/// The existing file `reference` names inside `directory`, or `None` when the reference is not
+/// [confined](confined_relative_path) to the folder or a symlink on the way leads out of it.
+pub(crate) fn confined_file(directory: &Path, reference: &str) -> Option<PathBuf> {
+    let path = directory.join(confined_relative_path(reference)?);
+    let canonical_directory = fs::canonicalize(directory).ok()?;
+    let canonical_path = fs::canonicalize(&path).ok()?;
+    (canonical_path.starts_with(&canonical_directory) && canonical_path.is_file()).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::{confined_file, confined_relative_path};
+
+    fn temp_directory(name: &str) -> PathBuf {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after unix epoch")
+            .as_nanos();
+        let directory = std::env::temp_dir().join(format!("mdv-confinement-{name}-{nonce}"));
+        fs::create_dir_all(&directory).expect("temp directory should be created");
+        directory
+    }
+
+    #[test]
+    fn relative_paths_stay_inside_their_folder() {
+        assert_eq!(
+            confined_relative_path("data/./a/../b.csv"),
+            Some(PathBuf::from("data").join("b.csv"))
+        );
+        assert_eq!(confined_relative_path("../secret.csv"), None);
+        assert_eq!(confined_relative_path("data/../../secret.csv"), None);
+        assert_eq!(confined_relative_path("/etc/passwd"), None);
+        assert_eq!(confined_relative_path("\\\\server\\share"), None);
+        assert_eq!(confined_relative_path("C:\\secret.csv"), None);
+        assert_eq!(confined_relative_path("."), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_out_of_the_folder_are_not_followed() {
+        let outside = temp_directory("outside");
+        fs::write(outside.join("secret.png"), b"x").expect("file should be written");
+        let directory = temp_directory("inside");
+        fs::write(directory.join("logo.png"), b"x").expect("file should be written");
+        std::os::unix::fs::symlink(outside.join("secret.png"), directory.join("link.png"))
+            .expect("symlink should be created");
+
+        assert_eq!(
+            confined_file(&directory, "logo.png"),
+            Some(directory.join("logo.png"))
+        );
+        assert_eq!(confined_file(&directory, "link.png"), None);
+        assert_eq!(confined_file(&directory, "missing.png"), None);
+
+        let _ = fs::remove_dir_all(&directory);
+        let _ = fs::remove_dir_all(&outside);
+    }
+}
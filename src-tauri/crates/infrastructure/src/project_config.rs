@@ -0,0 +1,470 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::models::{
+    EffectiveConfig, HtmlAllowlistInput, RenderPreferencesInput, TextReplacementRuleInput,
+};
+use markdown_viewer_application::ports::ProjectConfigResolver;
+use markdown_viewer_domain::document::HtmlAllowlist;
+use serde::Deserialize;
+use toml::{Table, Value};
+
+use crate::path_confinement::confined_file;
+use crate::settings_store::{describe_error, parse_render_sections, render_sections};
+
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".mdviewer.toml";
+
+/// Tables of a project configuration file that override the caller's render preferences.
+const RENDER_SECTIONS: [&str; 2] = ["render", "links"];
+
+/// Applies the nearest `.mdviewer.toml` in the document's folder or one of its ancestors.
+///
+/// Its `[render]` and `[links]` tables take the same keys as the settings file and override only
+/// the keys they set, except that they cannot loosen the caller's security settings: raw HTML,
+/// the HTML allowlist, linking text replacements and blocked external links keep the caller's
+/// values, with a warning, when the project asks for more. `[style] css` names a `.css` stylesheet in the configuration file's
+/// folder or below it, which is returned for the viewer to apply.
+pub struct TomlProjectConfigResolver;
+
+impl TomlProjectConfigResolver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TomlProjectConfigResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectConfigResolver for TomlProjectConfigResolver {
+    fn resolve(
+        &self,
+        document_path: &Path,
+        preferences: RenderPreferencesInput,
+    ) -> Result<EffectiveConfig, MarkdownViewerError> {
+        let Some(config_path) = find_project_config(document_path) else {
+            return Ok(preferences.into());
+        };
+        let invalid = |reason: String| MarkdownViewerError::ProjectConfig {
+            path: config_path.clone(),
+            reason,
+        };
+        let contents =
+            fs::read_to_string(&config_path).map_err(|error| invalid(error.to_string()))?;
+        let project: Table = contents
+            .parse()
+            .map_err(|error: toml::de::Error| invalid(error.message().to_string()))?;
+
+        let mut merged = render_sections(&preferences);
+        for section in RENDER_SECTIONS {
+            if let Some(value) = project.get(section) {
+                merge_value(&mut merged, section, value.clone());
+            }
+        }
+
+        let ProjectFile { style } = project
+            .try_into()
+            .map_err(|error| invalid(describe_error(error)))?;
+
+        let (render, ignored_settings) = keep_security_settings(
+            &preferences,
+            parse_render_sections(merged).map_err(invalid)?,
+            &config_path,
+        );
+
+        Ok(EffectiveConfig {
+            render,
+            custom_css: read_custom_css(&config_path, style).map_err(invalid)?,
+            project_config_path: Some(config_path.to_string_lossy().into_owned()),
+            ignored_settings,
+        })
+    }
+}
+
+fn find_project_config(document_path: &Path) -> Option<PathBuf> {
+    document_path
+        .ancestors()
+        .skip(1)
+        .map(|folder| folder.join(PROJECT_CONFIG_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Sets `key` of `table` to `value`, merging the keys of nested tables instead of replacing them.
+fn merge_value(table: &mut Table, key: &str, value: Value) {
+    match (table.get_mut(key), value) {
+        (Some(Value::Table(existing)), Value::Table(overrides)) => {
+            for (nested_key, nested_value) in overrides {
+                merge_value(existing, &nested_key, nested_value);
+            }
+        }
+        (_, value) => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// `project` with the settings that would loosen `user`'s security settings put back to the
+/// user's values, and a warning naming each of them.
+fn keep_security_settings(
+    user: &RenderPreferencesInput,
+    mut project: RenderPreferencesInput,
+    config_path: &Path,
+) -> (RenderPreferencesInput, Vec<String>) {
+    let mut ignored = Vec::new();
+    let mut ignore = |key: &str| {
+        ignored.push(format!(
+            "Ignored `{key}` in {}: a project configuration cannot loosen the viewer's security settings",
+            config_path.display()
+        ));
+    };
+
+    if project.allow_raw_html && !user.allow_raw_html {
+        project.allow_raw_html = false;
+        ignore("render.allow_raw_html");
+    }
+    if widens_allowlist(project.html_allowlist.clone(), user.html_allowlist.clone()) {
+        project.html_allowlist = user.html_allowlist.clone();
+        ignore("render.html_allowlist");
+    }
+    let added_link = |rule: &TextReplacementRuleInput| {
+        rule.link_template.is_some() && !user.text_replacements.contains(rule)
+    };
+    if project.text_replacements.iter().any(added_link) {
+        project.text_replacements.retain(|rule| !added_link(rule));
+        ignore("render.text_replacements.link_template");
+    }
+    if user.block_external_links && !project.block_external_links {
+        project.block_external_links = true;
+        ignore("links.external");
+    }
+    (project, ignored)
+}
+
+/// Whether `project` allows a tag, attribute or URL scheme that `user` does not; `None` stands
+/// for the default allowlist.
+fn widens_allowlist(project: Option<HtmlAllowlistInput>, user: Option<HtmlAllowlistInput>) -> bool {
+    let project: HtmlAllowlist = project.map(Into::into).unwrap_or_default();
+    let user: HtmlAllowlist = user.map(Into::into).unwrap_or_default();
+    let outside = |values: &[String], allowed: &[String]| {
+        values
+            .iter()
+            .any(|value| !allowed.iter().any(|name| name.eq_ignore_ascii_case(value)))
+    };
+    outside(&project.tags, &user.tags)
+        || outside(&project.attributes, &user.attributes)
+        || outside(&project.url_schemes, &user.url_schemes)
+}
+
+/// The tables of a project configuration file besides the render sections.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ProjectFile {
+    style: StyleSection,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct StyleSection {
+    css: Option<String>,
+}
+
+/// The stylesheet `style` names, which must be a `.css` file in the configuration file's folder or
+/// below it, so a project cannot make the viewer read other files.
+fn read_custom_css(config_path: &Path, style: StyleSection) -> Result<Option<String>, String> {
+    let Some(reference) = style.css else {
+        return Ok(None);
+    };
+    let is_css = Path::new(&reference)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("css"));
+    if !is_css {
+        return Err(format!(
+            "`style.css` must name a .css file, not {reference}"
+        ));
+    }
+    let folder = config_path.parent().unwrap_or_else(|| Path::new(""));
+    let css_path = confined_file(folder, &reference).ok_or_else(|| {
+        format!(
+            "`style.css` {reference} must be an existing file inside {}",
+            folder.display()
+        )
+    })?;
+    fs::read_to_string(&css_path)
+        .map(Some)
+        .map_err(|error| format!("`style.css` {}: {error}", css_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::models::{
+        ExtensionProfileInput, HeadingNumberingInput, HtmlAllowlistInput, RenderPreferencesInput,
+        TextReplacementRuleInput,
+    };
+    use markdown_viewer_application::ports::ProjectConfigResolver;
+
+    use super::{TomlProjectConfigResolver, PROJECT_CONFIG_FILE_NAME};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "markdown-viewer-project-config-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("docs").join("guide")).expect("temp dir should be created");
+        dir
+    }
+
+    fn user_preferences() -> RenderPreferencesInput {
+        let mut preferences = RenderPreferencesInput {
+            performance_mode: true,
+            words_per_minute: Some(180),
+            extension_overrides: [("spoiler".to_string(), false)].into_iter().collect(),
+            ..RenderPreferencesInput::default()
+        };
+        preferences.word_count_rules.include_links = false;
+        preferences
+    }
+
+    #[test]
+    fn applies_the_nearest_config_over_the_user_preferences() {
+        let dir = temp_dir("nearest");
+        fs::write(
+            dir.join(PROJECT_CONFIG_FILE_NAME),
+            "[render]\nallow_raw_html = true\n",
+        )
+        .expect("outer config should be written");
+        fs::write(
+            dir.join("docs").join(PROJECT_CONFIG_FILE_NAME),
+            "[render]\nnumber_headings_from = 2\nextension_profile = \"github\"\n\n\
+             [render.word_count]\ninclude_code = true\n\n[render.extensions]\nhighlight = true\n\n\
+             [links]\nexternal = \"block\"\n\n[style]\ncss = \"theme/docs.css\"\n",
+        )
+        .expect("inner config should be written");
+        fs::create_dir_all(dir.join("docs").join("theme")).expect("theme dir should be created");
+        fs::write(
+            dir.join("docs").join("theme").join("docs.css"),
+            "h1 { color: teal; }\n",
+        )
+        .expect("stylesheet should be written");
+
+        let config = TomlProjectConfigResolver::new()
+            .resolve(
+                &dir.join("docs").join("guide").join("intro.md"),
+                user_preferences(),
+            )
+            .expect("project config should apply");
+
+        let render = &config.render;
+        assert!(!render.allow_raw_html, "only the nearest config applies");
+        assert_eq!(
+            render.number_headings,
+            Some(HeadingNumberingInput { start_level: 2 })
+        );
+        assert!(render.word_count_rules.include_code);
+        assert!(!render.word_count_rules.include_links);
+        assert!(render.performance_mode);
+        assert_eq!(render.words_per_minute, Some(180));
+        assert!(render.block_external_links);
+        assert_eq!(render.extension_profile, ExtensionProfileInput::Github);
+        assert_eq!(
+            render.extension_overrides,
+            [
+                ("highlight".to_string(), true),
+                ("spoiler".to_string(), false)
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert_eq!(config.custom_css.as_deref(), Some("h1 { color: teal; }\n"));
+        assert_eq!(
+            config.project_config_path,
+            Some(
+                dir.join("docs")
+                    .join(PROJECT_CONFIG_FILE_NAME)
+                    .to_string_lossy()
+                    .into_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn cannot_switch_raw_html_on() {
+        let dir = temp_dir("raw-html");
+        fs::write(
+            dir.join(PROJECT_CONFIG_FILE_NAME),
+            "[render]\nallow_raw_html = true\nperformance_mode = false\n",
+        )
+        .expect("config should be written");
+
+        let config = TomlProjectConfigResolver::new()
+            .resolve(&dir.join("docs").join("intro.md"), user_preferences())
+            .expect("project config should apply");
+
+        assert!(!config.render.allow_raw_html);
+        assert!(!config.render.performance_mode, "other keys still apply");
+        assert_eq!(config.ignored_settings.len(), 1);
+        assert!(
+            config.ignored_settings[0].contains("`render.allow_raw_html`"),
+            "{:?}",
+            config.ignored_settings
+        );
+    }
+
+    #[test]
+    fn only_tightens_the_security_settings() {
+        let dir = temp_dir("tighten");
+        let mut preferences = user_preferences();
+        preferences.allow_raw_html = true;
+        preferences.block_external_links = true;
+        preferences.html_allowlist = Some(HtmlAllowlistInput {
+            tags: vec!["kbd".to_string(), "sub".to_string()],
+            attributes: Vec::new(),
+            url_schemes: vec!["https".to_string()],
+        });
+        fs::write(
+            dir.join(PROJECT_CONFIG_FILE_NAME),
+            "[render]\nallow_raw_html = false\n\n\
+             [render.html_allowlist]\ntags = [\"kbd\", \"iframe\"]\n\n\
+             [[render.text_replacements]]\npattern = \"TODO\"\nreplacement = \"To do\"\n\n\
+             [[render.text_replacements]]\npattern = \"PR-(\\\\d+)\"\nreplacement = \"PR-$1\"\n\
+             link_template = \"https://example.com/pulls/$1\"\n\n\
+             [links]\nexternal = \"allow\"\n",
+        )
+        .expect("config should be written");
+
+        let config = TomlProjectConfigResolver::new()
+            .resolve(&dir.join("docs").join("intro.md"), preferences.clone())
+            .expect("project config should apply");
+
+        let render = &config.render;
+        assert!(!render.allow_raw_html, "switching raw HTML off applies");
+        assert_eq!(render.html_allowlist, preferences.html_allowlist);
+        assert_eq!(
+            render.text_replacements,
+            [TextReplacementRuleInput {
+                pattern: "TODO".to_string(),
+                replacement: "To do".to_string(),
+                link_template: None,
+            }]
+        );
+        assert!(render.block_external_links);
+        let ignored = config.ignored_settings.join("\n");
+        for key in [
+            "`render.html_allowlist`",
+            "`render.text_replacements.link_template`",
+            "`links.external`",
+        ] {
+            assert!(ignored.contains(key), "{key} in {ignored}");
+        }
+        assert_eq!(config.ignored_settings.len(), 3);
+    }
+
+    #[test]
+    fn accepts_a_narrower_html_allowlist() {
+        let dir = temp_dir("narrower");
+        fs::write(
+            dir.join(PROJECT_CONFIG_FILE_NAME),
+            "[render.html_allowlist]\ntags = [\"KBD\"]\nurl_schemes = [\"https\"]\n",
+        )
+        .expect("config should be written");
+
+        let config = TomlProjectConfigResolver::new()
+            .resolve(&dir.join("docs").join("intro.md"), user_preferences())
+            .expect("project config should apply");
+
+        assert_eq!(
+            config.render.html_allowlist,
+            Some(HtmlAllowlistInput {
+                tags: vec!["KBD".to_string()],
+                attributes: Vec::new(),
+                url_schemes: vec!["https".to_string()],
+            })
+        );
+        assert!(config.ignored_settings.is_empty());
+    }
+
+    #[test]
+    fn only_reads_stylesheets_inside_the_config_folder() {
+        let dir = temp_dir("css-outside");
+        let project = dir.join("docs");
+        fs::write(dir.join("outside.css"), "body { color: red; }\n")
+            .expect("stylesheet should be written");
+        let absolute = dir.join("outside.css").to_string_lossy().replace('\\', "/");
+
+        for reference in [
+            "../outside.css",
+            "guide/../../outside.css",
+            absolute.as_str(),
+        ] {
+            fs::write(
+                project.join(PROJECT_CONFIG_FILE_NAME),
+                format!("[style]\ncss = \"{reference}\"\n"),
+            )
+            .expect("config should be written");
+
+            let error = TomlProjectConfigResolver::new()
+                .resolve(&project.join("intro.md"), user_preferences())
+                .expect_err("stylesheets outside the folder should be rejected");
+
+            let MarkdownViewerError::ProjectConfig { reason, .. } = &error else {
+                panic!("unexpected error for {reference:?}: {error:?}");
+            };
+            assert!(
+                reason.contains("must be an existing file inside"),
+                "{reason}"
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_the_user_preferences_outside_projects() {
+        let dir = temp_dir("none");
+
+        let config = TomlProjectConfigResolver::new()
+            .resolve(&dir.join("docs").join("intro.md"), user_preferences())
+            .expect("resolving should succeed");
+
+        assert_eq!(config.render, user_preferences());
+        assert_eq!(config.project_config_path, None);
+        assert_eq!(config.custom_css, None);
+    }
+
+    #[test]
+    fn reports_invalid_configs_and_missing_stylesheets() {
+        let dir = temp_dir("invalid");
+        let document = dir.join("docs").join("intro.md");
+        let cases = [
+            (
+                "[render]\nallow_raw_html = \"yes\"\n",
+                "`render.allow_raw_html`",
+            ),
+            (
+                "[style]\ncss = \"missing.css\"\n",
+                "`style.css` missing.css",
+            ),
+            ("[style]\ncss = \"notes.md\"\n", "must name a .css file"),
+            ("[render\n", "invalid"),
+        ];
+
+        for (contents, expected) in cases {
+            fs::write(dir.join(PROJECT_CONFIG_FILE_NAME), contents)
+                .expect("config should be written");
+
+            let error = TomlProjectConfigResolver::new()
+                .resolve(&document, RenderPreferencesInput::default())
+                .expect_err("invalid config should be rejected");
+
+            let MarkdownViewerError::ProjectConfig { reason, .. } = &error else {
+                panic!("unexpected error for {contents:?}: {error:?}");
+            };
+            assert!(reason.contains(expected), "{contents:?}: {reason}");
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+use url::Url;
+
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+
+/// Makes relative link and image URLs of documents fetched from the web absolute, resolved
+/// against the document's `base_url`, so they keep pointing at the remote site instead of files
+/// next to some local path. Fragment-only links stay in-page, and documents without a base URL
+/// are not touched.
+pub struct RemoteLinkTransform;
+
+impl RemoteLinkTransform {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RemoteLinkTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownTransform for RemoteLinkTransform {
+    fn transform<'a>(
+        &self,
+        _arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError> {
+        let Some(base_url) = context
+            .preferences
+            .base_url
+            .as_deref()
+            .and_then(|base_url| Url::parse(base_url).ok())
+        else {
+            return Ok(());
+        };
+
+        for node in root.descendants() {
+            let mut data = node.data.borrow_mut();
+            let (NodeValue::Link(link) | NodeValue::Image(link)) = &mut data.value else {
+                continue;
+            };
+            if let Some(resolved) = resolve_against(&base_url, &link.url) {
+                link.url = resolved;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `url` made absolute against `base_url`, or `None` when it already is absolute, only names a
+/// fragment, or cannot be resolved.
+fn resolve_against(base_url: &Url, url: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty() || url.starts_with('#') || Url::parse(url).is_ok() {
+        return None;
+    }
+    base_url.join(url).ok().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::RenderPreferences;
+
+    use super::RemoteLinkTransform;
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+    use crate::image_resolution::ImageResolutionTransform;
+
+    fn render(markdown: &str, base_url: Option<&str>) -> String {
+        let renderer = ComrakMarkdownRenderer::with_transforms(vec![
+            Box::new(RemoteLinkTransform::new()),
+            Box::new(ImageResolutionTransform::new()),
+        ]);
+        renderer
+            .render(
+                markdown,
+                Path::new(base_url.unwrap_or("/docs/guide.md")),
+                RenderPreferences {
+                    base_url: base_url.map(str::to_string),
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("renderer should work")
+            .html
+    }
+
+    #[test]
+    fn resolves_relative_links_and_images_against_the_base_url() {
+        let html = render(
+            "[setup](setup.md#install) [up](../README.md) [root](/LICENSE) [here](#usage) \
+             [site](https://example.org/) ![logo](img/logo.png)",
+            Some("https://example.com/org/repo/raw/main/docs/guide.md"),
+        );
+
+        assert!(
+            html.contains("href=\"https://example.com/org/repo/raw/main/docs/setup.md#install\"")
+        );
+        assert!(html.contains("href=\"https://example.com/org/repo/raw/main/README.md\""));
+        assert!(html.contains("href=\"https://example.com/LICENSE\""));
+        assert!(html.contains("href=\"#usage\""));
+        assert!(html.contains("href=\"https://example.org/\""));
+        assert!(html.contains("src=\"https://example.com/org/repo/raw/main/docs/img/logo.png\""));
+    }
+
+    #[test]
+    fn leaves_local_documents_alone() {
+        let html = render("[setup](setup.md)", None);
+
+        assert!(html.contains("href=\"setup.md\""));
+    }
+}
@@ -0,0 +1,732 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::models::{
+    ExtensionProfileInput, HeadingNumberingInput, HtmlAllowlistInput, ImageEmbeddingInput,
+    RenderPreferencesInput, TextReplacementRuleInput, ThemePreset, ViewerSettings,
+    WordCountRulesInput,
+};
+use markdown_viewer_application::ports::SettingsStore;
+use markdown_viewer_domain::extensions::MarkdownExtension;
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+pub const SETTINGS_FILE_NAME: &str = "settings.toml";
+/// The desktop app's bundle identifier, which names its configuration folder.
+pub const APP_IDENTIFIER: &str = "com.markdown.viewer";
+
+/// Layout written by this version. Files without a `version` key are read as version 0.
+const SCHEMA_VERSION: i64 = 1;
+
+/// Upgrades from each older layout: `MIGRATIONS[n]` turns a version `n` table into version
+/// `n + 1`. Every change to the layout bumps [`SCHEMA_VERSION`] and adds a step here.
+const MIGRATIONS: [fn(&mut Table); SCHEMA_VERSION as usize] = [nest_flat_settings];
+
+/// Where the desktop app keeps its settings: `settings.toml` in the platform configuration
+/// folder for [`APP_IDENTIFIER`]. `None` when the environment names no home folder.
+pub fn default_settings_path() -> Option<PathBuf> {
+    Some(
+        platform_config_dir()?
+            .join(APP_IDENTIFIER)
+            .join(SETTINGS_FILE_NAME),
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn platform_config_dir() -> Option<PathBuf> {
+    env::var_os("APPDATA").map(PathBuf::from)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_config_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+/// Keeps the settings in a TOML file. Keys the viewer does not know are ignored, so a file
+/// shared with a newer version of the same schema still loads.
+pub struct TomlSettingsStore {
+    path: PathBuf,
+}
+
+impl TomlSettingsStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn invalid(&self, reason: impl Into<String>) -> MarkdownViewerError {
+        MarkdownViewerError::Settings {
+            path: self.path.clone(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl SettingsStore for TomlSettingsStore {
+    fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    fn exists(&self) -> bool {
+        self.path.is_file()
+    }
+
+    fn load(&self) -> Result<ViewerSettings, MarkdownViewerError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Ok(ViewerSettings::default())
+            }
+            Err(error) => {
+                return Err(MarkdownViewerError::ReadFile {
+                    path: self.path.clone(),
+                    reason: error.to_string(),
+                })
+            }
+        };
+
+        let mut table: Table = contents
+            .parse()
+            .map_err(|error: toml::de::Error| self.invalid(error.message()))?;
+        let migrated = migrate(&mut table).map_err(|reason| self.invalid(reason))?;
+        let settings = parse_settings(table).map_err(|reason| self.invalid(reason))?;
+        if migrated {
+            self.save(&settings)?;
+        }
+        Ok(settings)
+    }
+
+    fn save(&self, settings: &ViewerSettings) -> Result<(), MarkdownViewerError> {
+        let write_error = |error: std::io::Error| MarkdownViewerError::WriteFile {
+            path: self.path.clone(),
+            reason: error.to_string(),
+        };
+        if let Some(folder) = self.path.parent() {
+            fs::create_dir_all(folder).map_err(write_error)?;
+        }
+        let contents = toml::to_string(&SettingsFile::from(settings))
+            .map_err(|error| self.invalid(error.to_string()))?;
+
+        // Written beside the file and renamed over it, so a watcher or another process never
+        // reads half of it.
+        let temp_path = temp_path(&self.path);
+        fs::write(&temp_path, contents).map_err(write_error)?;
+        fs::rename(&temp_path, &self.path).map_err(|error| {
+            let _ = fs::remove_file(&temp_path);
+            write_error(error)
+        })
+    }
+}
+
+/// Distinguishes temporary files of concurrent saves from the same process.
+static WRITE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let write = WRITE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(
+        ".{file_name}.mdv-{}-{write}.tmp",
+        std::process::id()
+    ))
+}
+
+/// Upgrades `table` to [`SCHEMA_VERSION`] by running the [`MIGRATIONS`] after its version,
+/// returning whether they changed anything. A file without a `version` that already uses the
+/// current layout is left as it is.
+fn migrate(table: &mut Table) -> Result<bool, String> {
+    let version = match table.get("version") {
+        None => 0,
+        Some(Value::Integer(version)) if *version >= 0 => *version,
+        Some(_) => return Err("`version` must be a whole number".to_string()),
+    };
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "written by a newer version of the viewer (schema {version}, this version reads up to {SCHEMA_VERSION})"
+        ));
+    }
+
+    let original = table.clone();
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(table);
+    }
+    let changed = *table != original;
+    table.insert("version".to_string(), Value::Integer(SCHEMA_VERSION));
+    Ok(changed)
+}
+
+/// Version 0 to 1: the first settings files mirrored the viewer's old local storage record, with
+/// the appearance keys and `performance_mode` at the top level and the word count rules in a
+/// `[word_count_rules]` table. They move to `[appearance]`, `[render]` and `[render.word_count]`
+/// unless the file already sets them there.
+fn nest_flat_settings(table: &mut Table) {
+    const APPEARANCE_KEYS: [&str; 6] = [
+        "safe_mode",
+        "theme",
+        "font_scale",
+        "line_height",
+        "measure_width",
+        "toc_auto_expand",
+    ];
+    for key in APPEARANCE_KEYS {
+        move_key(table, key, "appearance", key);
+    }
+    move_key(table, "performance_mode", "render", "performance_mode");
+    move_key(table, "word_count_rules", "render", "word_count");
+}
+
+/// Moves `table[key]` to `new_key` in the `section` table, creating it as needed. Leaves the
+/// value where it is when `section` is not a table or already has `new_key`.
+fn move_key(table: &mut Table, key: &str, section: &str, new_key: &str) {
+    let Some(value) = table.remove(key) else {
+        return;
+    };
+    if let Value::Table(section) = table
+        .entry(section)
+        .or_insert_with(|| Value::Table(Table::new()))
+    {
+        if !section.contains_key(new_key) {
+            section.insert(new_key.to_string(), value);
+            return;
+        }
+    }
+    table.insert(key.to_string(), value);
+}
+
+/// The layout of the settings file.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct SettingsFile {
+    version: i64,
+    appearance: AppearanceSection,
+    render: RenderSection,
+    links: LinksSection,
+}
+
+impl Default for SettingsFile {
+    fn default() -> Self {
+        Self::from(&ViewerSettings::default())
+    }
+}
+
+impl From<&ViewerSettings> for SettingsFile {
+    fn from(settings: &ViewerSettings) -> Self {
+        let RenderSections { render, links } = RenderSections::from(&settings.render);
+        Self {
+            version: SCHEMA_VERSION,
+            appearance: AppearanceSection {
+                safe_mode: settings.safe_mode,
+                theme: settings.theme,
+                font_scale: settings.font_scale,
+                line_height: settings.line_height,
+                measure_width: settings.measure_width,
+                toc_auto_expand: settings.toc_auto_expand,
+            },
+            render,
+            links,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct AppearanceSection {
+    safe_mode: bool,
+    #[serde(with = "ThemeName")]
+    theme: ThemePreset,
+    font_scale: f64,
+    line_height: f64,
+    measure_width: u32,
+    toc_auto_expand: bool,
+}
+
+impl Default for AppearanceSection {
+    fn default() -> Self {
+        SettingsFile::default().appearance
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ThemePreset", rename_all = "kebab-case")]
+enum ThemeName {
+    Paper,
+    Slate,
+    ContrastLight,
+    ContrastDark,
+}
+
+/// The `[render]` and `[links]` tables, which project configuration files share with the
+/// settings file.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct RenderSections {
+    render: RenderSection,
+    links: LinksSection,
+}
+
+impl From<&RenderPreferencesInput> for RenderSections {
+    fn from(preferences: &RenderPreferencesInput) -> Self {
+        let rules = preferences.word_count_rules;
+        Self {
+            render: RenderSection {
+                performance_mode: preferences.performance_mode,
+                word_count: WordCountSection {
+                    include_links: rules.include_links,
+                    include_code: rules.include_code,
+                    include_front_matter: rules.include_front_matter,
+                },
+                text_replacements: preferences
+                    .text_replacements
+                    .iter()
+                    .map(|rule| TextReplacementEntry {
+                        pattern: rule.pattern.clone(),
+                        replacement: rule.replacement.clone(),
+                        link_template: rule.link_template.clone(),
+                    })
+                    .collect(),
+                allow_raw_html: preferences.allow_raw_html,
+                html_allowlist: preferences.html_allowlist.as_ref().map(|allowlist| {
+                    HtmlAllowlistSection {
+                        tags: allowlist.tags.clone(),
+                        attributes: allowlist.attributes.clone(),
+                        url_schemes: allowlist.url_schemes.clone(),
+                    }
+                }),
+                image_embedding: preferences.image_embedding,
+                csv_row_limit: preferences.csv_row_limit,
+                number_headings_from: preferences
+                    .number_headings
+                    .map(|numbering| numbering.start_level),
+                words_per_minute: preferences.words_per_minute,
+                han_characters_per_minute: preferences.han_characters_per_minute,
+                kana_characters_per_minute: preferences.kana_characters_per_minute,
+                extension_profile: preferences.extension_profile,
+                extensions: preferences.extension_overrides.clone(),
+            },
+            links: LinksSection {
+                external: if preferences.block_external_links {
+                    ExternalLinks::Block
+                } else {
+                    ExternalLinks::Allow
+                },
+            },
+        }
+    }
+}
+
+impl TryFrom<RenderSections> for RenderPreferencesInput {
+    type Error = String;
+
+    fn try_from(sections: RenderSections) -> Result<Self, String> {
+        let RenderSections { render, links } = sections;
+        if let Some(name) = render
+            .extensions
+            .keys()
+            .find(|name| MarkdownExtension::from_name(name).is_none())
+        {
+            return Err(format!(
+                "`render.extensions.{name}` is not a markdown extension"
+            ));
+        }
+        let number_headings = match render.number_headings_from {
+            None => None,
+            Some(start_level @ 1..=6) => Some(HeadingNumberingInput { start_level }),
+            Some(_) => {
+                return Err(
+                    "`render.number_headings_from` must be a heading level from 1 to 6".to_string(),
+                )
+            }
+        };
+
+        Ok(Self {
+            performance_mode: render.performance_mode,
+            word_count_rules: WordCountRulesInput {
+                include_links: render.word_count.include_links,
+                include_code: render.word_count.include_code,
+                include_front_matter: render.word_count.include_front_matter,
+            },
+            text_replacements: render
+                .text_replacements
+                .into_iter()
+                .map(|rule| TextReplacementRuleInput {
+                    pattern: rule.pattern,
+                    replacement: rule.replacement,
+                    link_template: rule.link_template,
+                })
+                .collect(),
+            allow_raw_html: render.allow_raw_html,
+            html_allowlist: render.html_allowlist.map(|allowlist| HtmlAllowlistInput {
+                tags: allowlist.tags,
+                attributes: allowlist.attributes,
+                url_schemes: allowlist.url_schemes,
+            }),
+            image_embedding: render.image_embedding,
+            csv_row_limit: render.csv_row_limit,
+            number_headings,
+            words_per_minute: render.words_per_minute,
+            han_characters_per_minute: render.han_characters_per_minute,
+            kana_characters_per_minute: render.kana_characters_per_minute,
+            block_external_links: links.external == ExternalLinks::Block,
+            extension_profile: render.extension_profile,
+            extension_overrides: render.extensions,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct RenderSection {
+    performance_mode: bool,
+    word_count: WordCountSection,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    text_replacements: Vec<TextReplacementEntry>,
+    allow_raw_html: bool,
+    html_allowlist: Option<HtmlAllowlistSection>,
+    #[serde(with = "ImageEmbeddingName")]
+    image_embedding: ImageEmbeddingInput,
+    csv_row_limit: Option<usize>,
+    number_headings_from: Option<u8>,
+    words_per_minute: Option<usize>,
+    han_characters_per_minute: Option<usize>,
+    kana_characters_per_minute: Option<usize>,
+    #[serde(with = "ExtensionProfileName")]
+    extension_profile: ExtensionProfileInput,
+    /// Extensions turned on or off regardless of the profile.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    extensions: BTreeMap<String, bool>,
+}
+
+impl Default for RenderSection {
+    fn default() -> Self {
+        RenderSections::from(&RenderPreferencesInput::default()).render
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct WordCountSection {
+    include_links: bool,
+    include_code: bool,
+    include_front_matter: bool,
+}
+
+impl Default for WordCountSection {
+    fn default() -> Self {
+        RenderSection::default().word_count
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TextReplacementEntry {
+    pattern: String,
+    replacement: String,
+    link_template: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct HtmlAllowlistSection {
+    tags: Vec<String>,
+    attributes: Vec<String>,
+    url_schemes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ImageEmbeddingInput", rename_all = "kebab-case")]
+enum ImageEmbeddingName {
+    AssetUrl,
+    DataUri,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ExtensionProfileInput", rename_all = "lowercase")]
+enum ExtensionProfileName {
+    Default,
+    Github,
+    CommonMark,
+    Full,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct LinksSection {
+    external: ExternalLinks,
+}
+
+#[derive(Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ExternalLinks {
+    #[default]
+    Allow,
+    Block,
+}
+
+fn parse_settings(table: Table) -> Result<ViewerSettings, String> {
+    let file: SettingsFile = table.try_into().map_err(describe_error)?;
+    let render = RenderSections {
+        render: file.render,
+        links: file.links,
+    };
+    Ok(ViewerSettings {
+        render: render.try_into()?,
+        safe_mode: file.appearance.safe_mode,
+        theme: file.appearance.theme,
+        font_scale: file.appearance.font_scale,
+        line_height: file.appearance.line_height,
+        measure_width: file.appearance.measure_width,
+        toc_auto_expand: file.appearance.toc_auto_expand,
+    })
+}
+
+/// Reads the render preferences kept in the `[render]` and `[links]` tables of `root`.
+pub(crate) fn parse_render_sections(root: Table) -> Result<RenderPreferencesInput, String> {
+    let sections: RenderSections = root.try_into().map_err(describe_error)?;
+    sections.try_into()
+}
+
+/// The `[render]` and `[links]` tables holding `preferences`.
+pub(crate) fn render_sections(preferences: &RenderPreferencesInput) -> Table {
+    Table::try_from(RenderSections::from(preferences))
+        .expect("render preferences should convert to a TOML table")
+}
+
+/// The error on one line, naming the key it is about (`... in \`appearance.theme\``).
+pub(crate) fn describe_error(error: toml::de::Error) -> String {
+    error.to_string().trim_end().replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::models::{
+        ExtensionProfileInput, HeadingNumberingInput, HtmlAllowlistInput, ImageEmbeddingInput,
+        TextReplacementRuleInput, ThemePreset, ViewerSettings,
+    };
+    use markdown_viewer_application::ports::SettingsStore;
+
+    use toml::Table;
+
+    use super::{nest_flat_settings, temp_path, TomlSettingsStore, SETTINGS_FILE_NAME};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "markdown-viewer-settings-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        dir
+    }
+
+    #[test]
+    fn loads_the_defaults_without_creating_a_missing_file() {
+        let path = temp_dir("missing").join("nested").join(SETTINGS_FILE_NAME);
+        let store = TomlSettingsStore::new(&path);
+
+        let settings = store.load().expect("settings should load");
+
+        assert_eq!(settings, ViewerSettings::default());
+        assert!(!path.exists());
+        assert!(!path.parent().expect("path has a parent").exists());
+    }
+
+    #[test]
+    fn writes_the_version_before_the_sections() {
+        let path = temp_dir("defaults").join(SETTINGS_FILE_NAME);
+        let store = TomlSettingsStore::new(&path);
+
+        store
+            .save(&ViewerSettings::default())
+            .expect("settings should save");
+
+        let written = fs::read_to_string(&path).expect("settings file should be written");
+        assert!(written.starts_with("version = 1\n"), "{written}");
+        assert!(written.contains("[appearance]"), "{written}");
+        assert_eq!(
+            store.load().expect("settings should load"),
+            ViewerSettings::default()
+        );
+    }
+
+    #[test]
+    fn round_trips_every_setting() {
+        let path = temp_dir("round-trip").join(SETTINGS_FILE_NAME);
+        let store = TomlSettingsStore::new(&path);
+        let mut settings = ViewerSettings {
+            safe_mode: true,
+            theme: ThemePreset::ContrastDark,
+            font_scale: 1.15,
+            line_height: 1.8,
+            measure_width: 90,
+            toc_auto_expand: false,
+            ..ViewerSettings::default()
+        };
+        settings.render.performance_mode = true;
+        settings.render.word_count_rules.include_code = true;
+        settings.render.allow_raw_html = true;
+        settings.render.block_external_links = true;
+        settings.render.html_allowlist = Some(HtmlAllowlistInput {
+            tags: vec!["kbd".to_string()],
+            attributes: Vec::new(),
+            url_schemes: vec!["https".to_string()],
+        });
+        settings.render.image_embedding = ImageEmbeddingInput::DataUri;
+        settings.render.csv_row_limit = Some(500);
+        settings.render.number_headings = Some(HeadingNumberingInput { start_level: 2 });
+        settings.render.words_per_minute = Some(180);
+        settings.render.extension_profile = ExtensionProfileInput::Github;
+        settings.render.extension_overrides =
+            [("highlight".to_string(), true)].into_iter().collect();
+        settings.render.text_replacements = vec![TextReplacementRuleInput {
+            pattern: "JIRA-(\\d+)".to_string(),
+            replacement: "JIRA-$1".to_string(),
+            link_template: Some("https://jira.example.com/browse/JIRA-$1".to_string()),
+        }];
+
+        store.save(&settings).expect("settings should save");
+
+        assert_eq!(store.load().expect("settings should load"), settings);
+        let files = fs::read_dir(path.parent().expect("path has a parent"))
+            .expect("settings folder should be readable")
+            .map(|entry| entry.expect("entry should be readable").file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(files, [SETTINGS_FILE_NAME]);
+    }
+
+    #[test]
+    fn temp_paths_differ_between_saves() {
+        let path = PathBuf::from("config").join(SETTINGS_FILE_NAME);
+
+        let first = temp_path(&path);
+        let second = temp_path(&path);
+
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), path.parent());
+    }
+
+    #[test]
+    fn version_0_moves_the_flat_keys_into_their_sections() {
+        let mut table: Table = "theme = \"slate\"\nfont_scale = 1.2\nperformance_mode = true\n\
+             [word_count_rules]\ninclude_code = true\n\n[appearance]\nfont_scale = 1.1\n"
+            .parse()
+            .expect("table should parse");
+
+        nest_flat_settings(&mut table);
+
+        let expected: Table = "font_scale = 1.2\n\
+             [appearance]\ntheme = \"slate\"\nfont_scale = 1.1\n\n\
+             [render]\nperformance_mode = true\n\n[render.word_count]\ninclude_code = true\n"
+            .parse()
+            .expect("table should parse");
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn migrates_and_rewrites_version_0_files() {
+        let path = temp_dir("version-0").join(SETTINGS_FILE_NAME);
+        fs::write(
+            &path,
+            "safe_mode = true\ntheme = \"contrast-dark\"\n\n[word_count_rules]\ninclude_links = false\n",
+        )
+        .expect("settings should be written");
+        let store = TomlSettingsStore::new(&path);
+
+        let settings = store.load().expect("settings should load");
+
+        assert!(settings.safe_mode);
+        assert_eq!(settings.theme, ThemePreset::ContrastDark);
+        assert!(!settings.render.word_count_rules.include_links);
+        let written = fs::read_to_string(&path).expect("settings should be readable");
+        assert!(written.starts_with("version = 1\n"), "{written}");
+        assert!(!written.contains("word_count_rules"), "{written}");
+        assert_eq!(store.load().expect("settings should load"), settings);
+    }
+
+    #[test]
+    fn reads_unversioned_files_as_the_current_layout() {
+        let path = temp_dir("unversioned").join(SETTINGS_FILE_NAME);
+        let contents =
+            "[appearance]\ntheme = \"slate\"\n\n[render.word_count]\ninclude_links = false\n";
+        fs::write(&path, contents).expect("settings should be written");
+        let store = TomlSettingsStore::new(&path);
+
+        let settings = store.load().expect("settings should load");
+
+        assert_eq!(settings.theme, ThemePreset::Slate);
+        assert!(!settings.render.word_count_rules.include_links);
+        assert_eq!(settings.font_scale, ViewerSettings::default().font_scale);
+        assert_eq!(
+            fs::read_to_string(&path).expect("settings should be readable"),
+            contents
+        );
+    }
+
+    #[test]
+    fn reports_the_offending_key() {
+        let dir = temp_dir("invalid");
+        let cases = [
+            (
+                "version = 1\n[appearance]\ntheme = \"neon\"\n",
+                "`appearance.theme`",
+            ),
+            (
+                "version = 1\n[render.word_count]\ninclude_code = \"yes\"\n",
+                "`render.word_count.include_code`",
+            ),
+            (
+                "version = 1\n[render]\nnumber_headings_from = 9\n",
+                "`render.number_headings_from`",
+            ),
+            (
+                "version = 1\n[[render.text_replacements]]\npattern = \"a\"\n",
+                "missing field `replacement` in `render.text_replacements`",
+            ),
+            (
+                "version = 1\n[links]\nexternal = \"ask\"\n",
+                "`links.external`",
+            ),
+            (
+                "version = 1\n[render]\nextension_profile = \"gfm\"\n",
+                "`render.extension_profile`",
+            ),
+            (
+                "version = 1\n[render.extensions]\nsparkles = true\n",
+                "`render.extensions.sparkles`",
+            ),
+            (
+                "version = 1\n[render.extensions]\nspoiler = \"off\"\n",
+                "`render.extensions.spoiler`",
+            ),
+            ("version = \"two\"\n", "`version`"),
+            ("version = 2\n", "newer version"),
+        ];
+
+        for (index, (contents, expected)) in cases.into_iter().enumerate() {
+            let path = dir.join(format!("{index}.toml"));
+            fs::write(&path, contents).expect("settings should be written");
+
+            let error = TomlSettingsStore::new(&path)
+                .load()
+                .expect_err("invalid settings should be rejected");
+
+            let MarkdownViewerError::Settings { reason, .. } = &error else {
+                panic!("unexpected error for {contents:?}: {error:?}");
+            };
+            assert!(reason.contains(expected), "{contents:?}: {reason}");
+        }
+    }
+}
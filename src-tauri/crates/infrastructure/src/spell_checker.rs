@@ -0,0 +1,409 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use comrak::nodes::NodeValue;
+use comrak::{parse_document, Arena};
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::SpellChecker;
+use markdown_viewer_domain::document::RenderPreferences;
+use markdown_viewer_domain::spelling::{Misspelling, SpellCheckReport};
+use spellbook::Dictionary;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::comrak_renderer::{has_link_ancestor, markdown_options};
+
+pub const WORD_LIST_FILE_NAME: &str = ".mdviewer-words.txt";
+const MAX_SUGGESTIONS: usize = 5;
+
+const EN_US_AFF: &str = include_str!("../dictionaries/en_US/en_US.aff");
+const EN_US_DIC: &str = include_str!("../dictionaries/en_US/en_US.dic");
+
+/// Spell checks document prose against the bundled en_US Hunspell dictionary, plus the nearest
+/// `.mdviewer-words.txt` in the document's folder or one of its ancestors.
+pub struct HunspellSpellChecker {
+    /// Parsed on first use; loading the dictionary takes noticeably longer than a check.
+    dictionary: OnceLock<Dictionary>,
+}
+
+impl HunspellSpellChecker {
+    pub fn new() -> Self {
+        Self {
+            dictionary: OnceLock::new(),
+        }
+    }
+
+    fn dictionary(&self) -> &Dictionary {
+        self.dictionary.get_or_init(|| {
+            Dictionary::new(EN_US_AFF, EN_US_DIC).expect("bundled dictionary should parse")
+        })
+    }
+}
+
+impl Default for HunspellSpellChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpellChecker for HunspellSpellChecker {
+    fn check(
+        &self,
+        markdown: &str,
+        document_path: &Path,
+    ) -> Result<SpellCheckReport, MarkdownViewerError> {
+        let word_list_path = find_word_list(document_path);
+        let word_list = match &word_list_path {
+            Some(path) => read_word_list(path)?,
+            None => WordList::default(),
+        };
+
+        Ok(SpellCheckReport {
+            misspellings: find_misspellings(markdown, self.dictionary(), &word_list),
+            word_list_path,
+        })
+    }
+}
+
+/// Words a workspace accepts on top of the dictionary. An all-lowercase entry also accepts its
+/// capitalized forms, like Hunspell personal dictionaries.
+#[derive(Debug, Default)]
+struct WordList {
+    words: HashSet<String>,
+}
+
+impl WordList {
+    /// One word per line; blank lines and lines starting with `#` are ignored.
+    fn parse(contents: &str) -> Self {
+        Self {
+            words: contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    fn accepts(&self, word: &str) -> bool {
+        self.words.contains(word) || self.words.contains(&word.to_lowercase())
+    }
+}
+
+fn find_word_list(document_path: &Path) -> Option<PathBuf> {
+    document_path
+        .ancestors()
+        .skip(1)
+        .map(|folder| folder.join(WORD_LIST_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+fn read_word_list(path: &Path) -> Result<WordList, MarkdownViewerError> {
+    fs::read_to_string(path)
+        .map(|contents| WordList::parse(&contents))
+        .map_err(|error| MarkdownViewerError::WordList {
+            path: path.to_path_buf(),
+            reason: error.to_string(),
+        })
+}
+
+/// Checks the words of prose text nodes: the text `count_words` counts with the default rules,
+/// so code, math, link text, HTML tags and blocks, and front matter are skipped.
+fn find_misspellings(
+    markdown: &str,
+    dictionary: &Dictionary,
+    word_list: &WordList,
+) -> Vec<Misspelling> {
+    let mut options = markdown_options(&RenderPreferences::default());
+    // Smart punctuation would make the parsed text differ from the source it is located in.
+    options.parse.smart = false;
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut suggestions: HashMap<String, Vec<String>> = HashMap::new();
+    let mut misspellings = Vec::new();
+
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        let NodeValue::Text(text) = &data.value else {
+            continue;
+        };
+        if has_link_ancestor(node) {
+            continue;
+        }
+        let start = data.sourcepos.start;
+        let Some(line) = lines.get(start.line.saturating_sub(1)) else {
+            continue;
+        };
+        let mut locator = WordLocator::new(line, start.column.saturating_sub(1), text);
+
+        for (offset, word) in text.unicode_word_indices() {
+            let byte = locator.locate(offset, word);
+            if !should_check(word) || word_list.accepts(word) || dictionary.check(word) {
+                continue;
+            }
+            let start_column = line[..byte].chars().count() + 1;
+            misspellings.push(Misspelling {
+                word: word.to_string(),
+                line: start.line,
+                start_column,
+                end_column: start_column + word.chars().count(),
+                suggestions: suggestions
+                    .entry(word.to_string())
+                    .or_insert_with(|| suggest(dictionary, word))
+                    .clone(),
+            });
+        }
+    }
+
+    misspellings
+}
+
+fn suggest(dictionary: &Dictionary, word: &str) -> Vec<String> {
+    let mut suggestions = Vec::new();
+    dictionary.suggest(word, &mut suggestions);
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+/// Maps words of a text node back to byte offsets in its source line. When the node's text is
+/// the source verbatim the offset is exact; otherwise (escapes, entities) each word is searched
+/// for after the previous one, falling back to the start of the node.
+struct WordLocator<'a> {
+    line: &'a str,
+    node_start: usize,
+    verbatim: bool,
+    cursor: usize,
+}
+
+impl<'a> WordLocator<'a> {
+    fn new(line: &'a str, node_start: usize, text: &str) -> Self {
+        let node_start = if line.is_char_boundary(node_start) {
+            node_start.min(line.len())
+        } else {
+            0
+        };
+        Self {
+            line,
+            node_start,
+            verbatim: line[node_start..].starts_with(text),
+            cursor: node_start,
+        }
+    }
+
+    fn locate(&mut self, offset: usize, word: &str) -> usize {
+        if self.verbatim {
+            return self.node_start + offset;
+        }
+        match self.line[self.cursor..].find(word) {
+            Some(found) => {
+                let byte = self.cursor + found;
+                self.cursor = byte + word.len();
+                byte
+            }
+            None => self.node_start,
+        }
+    }
+}
+
+/// Skips tokens a natural-language dictionary cannot judge: single letters, numbers, acronyms,
+/// identifiers such as `snake_case` or `camelCase`, and words in scripts other than Latin.
+fn should_check(word: &str) -> bool {
+    let mut characters = word.chars();
+    let Some(first) = characters.next() else {
+        return false;
+    };
+    if characters.next().is_none() {
+        return false;
+    }
+    let latin_letters = word.chars().all(|character| {
+        matches!(character, '\'' | '\u{2019}')
+            || (character.is_alphabetic() && character <= '\u{024F}')
+    });
+    let letters = word.chars().filter(|character| character.is_alphabetic());
+    let all_uppercase = letters.clone().all(char::is_uppercase);
+    let inner_uppercase = word.chars().skip(1).any(char::is_uppercase);
+    latin_letters && first.is_alphabetic() && !all_uppercase && !inner_uppercase
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::SpellChecker;
+    use markdown_viewer_domain::spelling::Misspelling;
+
+    use super::{should_check, HunspellSpellChecker, WORD_LIST_FILE_NAME};
+
+    /// Shared so the dictionary is only parsed once for the whole test run.
+    fn checker() -> &'static HunspellSpellChecker {
+        static CHECKER: OnceLock<HunspellSpellChecker> = OnceLock::new();
+        CHECKER.get_or_init(HunspellSpellChecker::new)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after the epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("mdv-spelling-{name}-{unique}"));
+        fs::create_dir_all(&dir).expect("temp dir should be creatable");
+        dir
+    }
+
+    fn misspelled_words(markdown: &str) -> Vec<String> {
+        let dir = temp_dir("words");
+        let report = checker()
+            .check(markdown, &dir.join("doc.md"))
+            .expect("spell check should succeed");
+        fs::remove_dir_all(dir).expect("temp dir should be removable");
+        report
+            .misspellings
+            .into_iter()
+            .map(|misspelling| misspelling.word)
+            .collect()
+    }
+
+    #[test]
+    fn reports_misspelled_prose_with_ranges_and_suggestions() {
+        let dir = temp_dir("ranges");
+        let report = checker()
+            .check(
+                "# Welcome\n\nThe quick brown fox jumpd over teh dog.\n",
+                &dir.join("guide.md"),
+            )
+            .expect("spell check should succeed");
+
+        assert_eq!(report.word_list_path, None);
+        let locations: Vec<(&str, usize, usize, usize)> = report
+            .misspellings
+            .iter()
+            .map(|misspelling| {
+                (
+                    misspelling.word.as_str(),
+                    misspelling.line,
+                    misspelling.start_column,
+                    misspelling.end_column,
+                )
+            })
+            .collect();
+        assert_eq!(locations, [("jumpd", 3, 21, 26), ("teh", 3, 32, 35)]);
+        assert!(report.misspellings[0]
+            .suggestions
+            .contains(&"jumped".to_string()));
+        assert!(report.misspellings[1]
+            .suggestions
+            .contains(&"the".to_string()));
+        assert!(report.misspellings[1].suggestions.len() <= 5);
+
+        fs::remove_dir_all(dir).expect("temp dir should be removable");
+    }
+
+    #[test]
+    fn skips_code_math_links_html_and_front_matter() {
+        let markdown = "---\ntitle: Gudie\n---\n\n\
+                        Use `frobnicte` and $qwzx$ with [linkk text](https://exmaple.com).\n\n\
+                        <div>htmml</div>\n\n\
+                        See https://exmaple.org/pathh\n\n\
+                        ```\nmisspeled code\n```\n\n\
+                        Only thiss is checked.\n";
+
+        assert_eq!(misspelled_words(markdown), ["thiss"]);
+    }
+
+    #[test]
+    fn locates_words_after_escapes_and_in_non_ascii_lines() {
+        let dir = temp_dir("escapes");
+        let report = checker()
+            .check("Wait — \\*not\\* wrogn.\n", &dir.join("doc.md"))
+            .expect("spell check should succeed");
+
+        assert_eq!(
+            report.misspellings,
+            [Misspelling {
+                word: "wrogn".to_string(),
+                line: 1,
+                start_column: 16,
+                end_column: 21,
+                suggestions: report.misspellings[0].suggestions.clone(),
+            }]
+        );
+
+        fs::remove_dir_all(dir).expect("temp dir should be removable");
+    }
+
+    #[test]
+    fn skips_tokens_a_dictionary_cannot_judge() {
+        for word in [
+            "a",
+            "HTTP",
+            "snake_case",
+            "camelCase",
+            "GitHub",
+            "v2",
+            "日本語",
+            "x86",
+        ] {
+            assert!(!should_check(word), "{word}");
+        }
+        for word in ["Teh", "don't", "don\u{2019}t", "naïve"] {
+            assert!(should_check(word), "{word}");
+        }
+        assert_eq!(
+            misspelled_words("Don\u{2019}t worry, it isn't broken.\n"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn applies_the_nearest_workspace_word_list() {
+        let root = temp_dir("word-list");
+        let docs = root.join("docs");
+        fs::create_dir_all(&docs).expect("docs dir should be creatable");
+        fs::write(
+            root.join(WORD_LIST_FILE_NAME),
+            "# project words\ncomrak\n\nTauri\n",
+        )
+        .expect("word list should be writable");
+
+        let report = checker()
+            .check(
+                "Comrak and comrak render; Tauri ships, tauri does not.\n",
+                &docs.join("intro.md"),
+            )
+            .expect("spell check should succeed");
+
+        assert_eq!(report.word_list_path, Some(root.join(WORD_LIST_FILE_NAME)));
+        let words: Vec<&str> = report
+            .misspellings
+            .iter()
+            .map(|misspelling| misspelling.word.as_str())
+            .collect();
+        assert_eq!(words, ["tauri"]);
+
+        fs::remove_dir_all(root).expect("temp dir should be removable");
+    }
+
+    #[test]
+    fn reports_unreadable_word_list() {
+        let root = temp_dir("unreadable");
+        fs::create_dir_all(root.join(WORD_LIST_FILE_NAME).join("not-a-file"))
+            .expect("dir should be creatable");
+        // A directory with the word list's name is not a word list, so nothing applies.
+        let report = checker()
+            .check("Fine text.\n", &root.join("doc.md"))
+            .expect("spell check should succeed");
+        assert_eq!(report.word_list_path, None);
+
+        let missing = super::read_word_list(&root.join("missing.txt"))
+            .expect_err("missing word list should fail");
+        assert!(matches!(missing, MarkdownViewerError::WordList { .. }));
+
+        fs::remove_dir_all(root).expect("temp dir should be removable");
+    }
+}
@@ -0,0 +1,98 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::MarkdownFileRepository;
+use markdown_viewer_domain::document_source::{DocumentOrigin, SourceDocument};
+
+use crate::document_sources::DocumentSource;
+
+/// Command line argument that opens the markdown piped into standard input.
+pub const STDIN_PATH_INPUT: &str = "-";
+
+/// Markdown piped into the viewer, read once at startup and kept in memory.
+///
+/// The document gets the synthetic path `-` inside the working directory, so relative links and
+/// images resolve against the directory the viewer was started from, and reloading the document
+/// by that path serves the same content again.
+pub struct StdinDocumentSource {
+    path: PathBuf,
+    source: String,
+}
+
+impl StdinDocumentSource {
+    pub fn new(source: String, working_directory: &Path) -> Self {
+        Self {
+            path: working_directory.join(STDIN_PATH_INPUT),
+            source,
+        }
+    }
+
+    /// Reads `reader` to the end; invalid UTF-8 is replaced rather than rejected, since piped
+    /// output often mixes encodings.
+    pub fn read_from(mut reader: impl Read, working_directory: &Path) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let source = String::from_utf8_lossy(&bytes).into_owned();
+        Ok(Self::new(source, working_directory))
+    }
+
+    /// The synthetic path the document is shown and reloaded under.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl DocumentSource for StdinDocumentSource {
+    fn handles(&self, path_input: &str) -> bool {
+        path_input == STDIN_PATH_INPUT || Path::new(path_input) == self.path
+    }
+}
+
+impl MarkdownFileRepository for StdinDocumentSource {
+    fn read(&self, path_input: &str) -> Result<SourceDocument, MarkdownViewerError> {
+        if !self.handles(path_input) {
+            return Err(MarkdownViewerError::FileNotFound(PathBuf::from(path_input)));
+        }
+        Ok(SourceDocument {
+            path: self.path.clone(),
+            source: self.source.clone(),
+            origin: DocumentOrigin::Stdin,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use markdown_viewer_application::ports::MarkdownFileRepository;
+    use markdown_viewer_domain::document_source::DocumentOrigin;
+
+    use super::StdinDocumentSource;
+    use crate::document_sources::DocumentSource;
+
+    #[test]
+    fn serves_piped_content_under_a_path_in_the_working_directory() {
+        let input: &[u8] = b"# Report\n\nbad byte: \xff\n";
+        let source = StdinDocumentSource::read_from(input, Path::new("/work/reports"))
+            .expect("reading from a slice should succeed");
+
+        assert_eq!(source.path(), Path::new("/work/reports/-"));
+        for path_input in ["-", "/work/reports/-"] {
+            let document = source.read(path_input).expect("stdin should be readable");
+            assert_eq!(document.path, PathBuf::from("/work/reports/-"));
+            assert_eq!(document.source, "# Report\n\nbad byte: \u{fffd}\n");
+            assert_eq!(document.origin, DocumentOrigin::Stdin);
+        }
+    }
+
+    #[test]
+    fn leaves_other_inputs_to_other_sources() {
+        let source = StdinDocumentSource::new(String::new(), Path::new("/work"));
+
+        assert!(!source.handles("/work/notes.md"));
+        assert!(!source.handles("/elsewhere/-"));
+        assert!(source.read("/work/notes.md").is_err());
+    }
+}
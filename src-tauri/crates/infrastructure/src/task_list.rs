@@ -0,0 +1,120 @@
+use comrak::nodes::{AstNode, NodeValue};
+use markdown_viewer_domain::document::{TaskListItem, TocEntry};
+
+use crate::comrak_renderer::inline_text;
+
+/// Lists every task list item in document order.
+///
+/// `toc` must be the table of contents built from the same tree: headings with text are matched
+/// to its entries in order to find each task's nearest heading.
+pub(crate) fn extract_tasks<'a>(root: &'a AstNode<'a>, toc: &[TocEntry]) -> Vec<TaskListItem> {
+    let mut tasks = Vec::new();
+    let mut toc_entries = toc.iter();
+    let mut heading_id: Option<&str> = None;
+
+    for node in root.descendants() {
+        let data = node.data.borrow();
+        match &data.value {
+            NodeValue::Heading(..) if !inline_text(node).is_empty() => {
+                heading_id = toc_entries.next().map(|entry| entry.id.as_str());
+            }
+            NodeValue::TaskItem(task) => tasks.push(TaskListItem {
+                text: task_text(node),
+                checked: task.symbol.is_some(),
+                depth: list_item_depth(node),
+                heading_id: heading_id.map(str::to_string),
+                line: data.sourcepos.start.line,
+            }),
+            _ => {}
+        }
+    }
+
+    tasks
+}
+
+/// The text of the item's first paragraph; nested lists and later blocks are left out.
+fn task_text<'a>(item: &'a AstNode<'a>) -> String {
+    item.first_child()
+        .filter(|child| matches!(child.data.borrow().value, NodeValue::Paragraph))
+        .map(inline_text)
+        .unwrap_or_default()
+}
+
+fn list_item_depth<'a>(item: &'a AstNode<'a>) -> usize {
+    item.ancestors()
+        .skip(1)
+        .filter(|ancestor| {
+            matches!(
+                ancestor.data.borrow().value,
+                NodeValue::Item(..) | NodeValue::TaskItem(..)
+            )
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::{RenderPreferences, TaskListItem};
+
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    fn tasks(markdown: &str) -> Vec<TaskListItem> {
+        ComrakMarkdownRenderer::new()
+            .render(
+                markdown,
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("render should succeed")
+            .tasks
+    }
+
+    #[test]
+    fn extracts_text_state_nesting_heading_and_line() {
+        let extracted = tasks(
+            "- [ ] before any heading\n\n\
+             # Release `v2`\n\n\
+             - [x] Tag the **release**\n  - [ ] Publish notes\n    with details\n- [X] Announce\n\n\
+             ## Follow-up\n\n\
+             1. [ ] Collect feedback\n   - plain item\n     - [ ] deep task\n",
+        );
+
+        let summary: Vec<(&str, bool, usize, Option<&str>, usize)> = extracted
+            .iter()
+            .map(|task| {
+                (
+                    task.text.as_str(),
+                    task.checked,
+                    task.depth,
+                    task.heading_id.as_deref(),
+                    task.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("before any heading", false, 0, None, 1),
+                ("Tag the release", true, 0, Some("mdv-release-v2"), 5),
+                (
+                    "Publish notes with details",
+                    false,
+                    1,
+                    Some("mdv-release-v2"),
+                    6
+                ),
+                ("Announce", true, 0, Some("mdv-release-v2"), 8),
+                ("Collect feedback", false, 0, Some("mdv-follow-up"), 12),
+                ("deep task", false, 2, Some("mdv-follow-up"), 14),
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_lists_have_no_tasks() {
+        assert!(tasks("- one\n- [link](x.md)\n").is_empty());
+    }
+}
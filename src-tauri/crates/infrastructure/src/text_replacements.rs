@@ -0,0 +1,177 @@
+use comrak::nodes::{AstNode, NodeLink, NodeValue};
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_domain::document::TextReplacementRule;
+use regex::{Captures, Regex};
+
+use crate::comrak_renderer::has_link_ancestor;
+use crate::image_resolution::has_uri_scheme;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+
+/// Applies the user's [`TextReplacementRule`]s from the render preferences.
+pub struct TextReplacementTransform;
+
+impl TextReplacementTransform {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TextReplacementTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownTransform for TextReplacementTransform {
+    fn transform<'a>(
+        &self,
+        arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError> {
+        let replacements = compile_text_replacements(&context.preferences.text_replacements)?;
+        apply_text_replacements(arena, root, &replacements);
+        Ok(())
+    }
+}
+
+struct CompiledTextReplacement {
+    regex: Regex,
+    replacement: String,
+    link_template: Option<String>,
+}
+
+fn compile_text_replacements(
+    rules: &[TextReplacementRule],
+) -> Result<Vec<CompiledTextReplacement>, MarkdownViewerError> {
+    rules
+        .iter()
+        .map(|rule| {
+            let regex = Regex::new(&rule.pattern).map_err(|error| {
+                MarkdownViewerError::InvalidTextReplacement {
+                    pattern: rule.pattern.clone(),
+                    reason: error.to_string(),
+                }
+            })?;
+            Ok(CompiledTextReplacement {
+                regex,
+                replacement: rule.replacement.clone(),
+                link_template: rule.link_template.clone(),
+            })
+        })
+        .collect()
+}
+
+/// URL schemes a link template may expand to.
+const LINK_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Applies each replacement in order to prose text nodes.
+///
+/// Text that already sits inside a link or image is left alone so replacements never produce
+/// nested links or rewrite alt text.
+fn apply_text_replacements<'a>(
+    arena: &'a Arena<'a>,
+    root: &'a AstNode<'a>,
+    replacements: &[CompiledTextReplacement],
+) {
+    for replacement in replacements {
+        let text_nodes: Vec<&'a AstNode<'a>> = root
+            .descendants()
+            .filter(|node| matches!(node.data.borrow().value, NodeValue::Text(..)))
+            .filter(|node| !has_link_ancestor(node))
+            .collect();
+
+        for node in text_nodes {
+            replace_in_text_node(arena, node, replacement);
+        }
+    }
+}
+
+fn replace_in_text_node<'a>(
+    arena: &'a Arena<'a>,
+    node: &'a AstNode<'a>,
+    replacement: &CompiledTextReplacement,
+) {
+    let text = match &node.data.borrow().value {
+        NodeValue::Text(value) => value.to_string(),
+        _ => return,
+    };
+
+    let mut pieces = Vec::new();
+    let mut last_end = 0;
+    for captures in replacement.regex.captures_iter(&text) {
+        let Some(matched) = captures.get(0) else {
+            continue;
+        };
+        if matched.is_empty() {
+            continue;
+        }
+
+        if matched.start() > last_end {
+            pieces.push(text_node(arena, &text[last_end..matched.start()]));
+        }
+        pieces.push(replacement_node(arena, &captures, replacement));
+        last_end = matched.end();
+    }
+
+    if pieces.is_empty() {
+        return;
+    }
+    if last_end < text.len() {
+        pieces.push(text_node(arena, &text[last_end..]));
+    }
+
+    for piece in pieces {
+        node.insert_before(piece);
+    }
+    node.detach();
+}
+
+fn replacement_node<'a>(
+    arena: &'a Arena<'a>,
+    captures: &Captures<'_>,
+    replacement: &CompiledTextReplacement,
+) -> &'a AstNode<'a> {
+    let mut replaced = String::new();
+    captures.expand(&replacement.replacement, &mut replaced);
+
+    let Some(link_template) = &replacement.link_template else {
+        return text_node(arena, &replaced);
+    };
+
+    let mut url = String::new();
+    captures.expand(link_template, &mut url);
+    if !is_allowed_link_url(&url) {
+        return text_node(arena, &replaced);
+    }
+    let link = arena.alloc(AstNode::from(NodeValue::Link(Box::new(NodeLink {
+        url,
+        title: String::new(),
+    }))));
+    link.append(text_node(arena, &replaced));
+    link
+}
+
+/// Whether an expanded link template is a web, `mailto:` or relative URL.
+///
+/// Captured text can put any scheme into a template, so anything else (`javascript:`, `data:`)
+/// is kept as plain text. Whitespace and control characters are ignored the way browsers do.
+fn is_allowed_link_url(url: &str) -> bool {
+    let normalized: String = url
+        .chars()
+        .filter(|character| !character.is_ascii_whitespace() && !character.is_control())
+        .collect();
+    if !has_uri_scheme(&normalized) {
+        return true;
+    }
+    normalized.split_once(':').is_some_and(|(scheme, _)| {
+        LINK_URL_SCHEMES
+            .iter()
+            .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+    })
+}
+
+fn text_node<'a>(arena: &'a Arena<'a>, text: &str) -> &'a AstNode<'a> {
+    arena.alloc(AstNode::from(NodeValue::Text(text.to_string().into())))
+}
@@ -0,0 +1,220 @@
+use std::sync::OnceLock;
+
+use comrak::nodes::{AstNode, NodeValue};
+use markdown_viewer_domain::document::TocEntry;
+use regex::Regex;
+
+use crate::html_sanitizer::escape_html;
+
+/// Replaces table-of-contents markers left by other tools with a nested list of `toc`.
+///
+/// Recognized markers are a paragraph consisting only of `[TOC]` and an HTML block consisting
+/// only of `<!-- toc -->`, both case-insensitive and optionally carrying a `maxdepth=N` limit on
+/// the heading levels listed. A list previously generated between `<!-- toc -->` and
+/// `<!-- tocstop -->` is replaced as well.
+pub(crate) fn expand_toc_placeholders<'a>(root: &'a AstNode<'a>, toc: &[TocEntry]) {
+    let markers: Vec<(&'a AstNode<'a>, u8)> = root
+        .children()
+        .filter_map(|node| toc_marker(node).map(|max_level| (node, max_level)))
+        .collect();
+
+    for (marker, max_level) in markers {
+        if is_html_marker(marker) {
+            remove_previous_expansion(marker);
+        }
+        while let Some(child) = marker.first_child() {
+            child.detach();
+        }
+        marker.data.borrow_mut().value = NodeValue::Raw(toc_list_html(toc, max_level));
+    }
+}
+
+/// The deepest heading level a marker node asks for, or `None` if the node is not a marker.
+fn toc_marker<'a>(node: &'a AstNode<'a>) -> Option<u8> {
+    static BRACKET_MARKER: OnceLock<Regex> = OnceLock::new();
+    static COMMENT_MARKER: OnceLock<Regex> = OnceLock::new();
+    let bracket_marker = BRACKET_MARKER
+        .get_or_init(|| Regex::new(r"(?i)^\[toc(?:\s+maxdepth=(\d+))?\]$").expect("valid regex"));
+    let comment_marker = COMMENT_MARKER.get_or_init(|| {
+        Regex::new(r"(?i)^<!--\s*toc(?:\s+maxdepth=(\d+))?\s*-->$").expect("valid regex")
+    });
+
+    let captures = match &node.data.borrow().value {
+        NodeValue::Paragraph => {
+            let mut text = String::new();
+            for child in node.children() {
+                match &child.data.borrow().value {
+                    NodeValue::Text(value) => text.push_str(value),
+                    _ => return None,
+                }
+            }
+            bracket_marker
+                .captures(text.trim())?
+                .get(1)
+                .map(|depth| depth.as_str().to_string())
+        }
+        NodeValue::HtmlBlock(block) => comment_marker
+            .captures(block.literal.trim())?
+            .get(1)
+            .map(|depth| depth.as_str().to_string()),
+        _ => return None,
+    };
+
+    Some(
+        captures
+            .and_then(|depth| depth.parse::<u8>().ok())
+            .unwrap_or(6)
+            .clamp(1, 6),
+    )
+}
+
+fn is_html_marker<'a>(node: &'a AstNode<'a>) -> bool {
+    matches!(node.data.borrow().value, NodeValue::HtmlBlock(..))
+}
+
+/// Drops the blocks between a `<!-- toc -->` marker and a following `<!-- tocstop -->`,
+/// including the stop marker. Without a stop marker nothing is removed.
+fn remove_previous_expansion<'a>(marker: &'a AstNode<'a>) {
+    let is_stop = |node: &'a AstNode<'a>| {
+        matches!(
+            &node.data.borrow().value,
+            NodeValue::HtmlBlock(block)
+                if block.literal.trim().eq_ignore_ascii_case("<!-- tocstop -->")
+        )
+    };
+
+    let mut following = Vec::new();
+    let mut sibling = marker.next_sibling();
+    while let Some(node) = sibling {
+        following.push(node);
+        if is_stop(node) {
+            for node in following {
+                node.detach();
+            }
+            return;
+        }
+        sibling = node.next_sibling();
+    }
+}
+
+fn toc_list_html(toc: &[TocEntry], max_level: u8) -> String {
+    let entries: Vec<&TocEntry> = toc
+        .iter()
+        .filter(|entry| entry.level <= max_level)
+        .collect();
+    let mut html = String::from("<nav class=\"mdv-toc\" aria-label=\"Table of contents\">\n");
+    if !entries.is_empty() {
+        push_toc_items(&entries, &mut html);
+    }
+    html.push_str("</nav>\n");
+    html
+}
+
+/// Writes `entries` as a list whose items nest every following entry of a deeper level.
+fn push_toc_items(entries: &[&TocEntry], html: &mut String) {
+    html.push_str("<ul>\n");
+    let mut index = 0;
+    while index < entries.len() {
+        let entry = entries[index];
+        let children_end = entries[index + 1..]
+            .iter()
+            .position(|candidate| candidate.level <= entry.level)
+            .map(|offset| index + 1 + offset)
+            .unwrap_or(entries.len());
+
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a>",
+            escape_html(&entry.id),
+            escape_html(&entry.text)
+        ));
+        if children_end > index + 1 {
+            html.push('\n');
+            push_toc_items(&entries[index + 1..children_end], html);
+        }
+        html.push_str("</li>\n");
+        index = children_end;
+    }
+    html.push_str("</ul>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::{RenderPreferences, RenderedMarkdown};
+
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    fn render(markdown: &str) -> RenderedMarkdown {
+        ComrakMarkdownRenderer::new()
+            .render(
+                markdown,
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("render should succeed")
+    }
+
+    #[test]
+    fn bracket_marker_expands_to_nested_list() {
+        let rendered = render("# Guide\n\n[TOC]\n\n## Install\n\n### Linux\n\n## Use & abuse\n");
+
+        assert!(rendered.html.contains(
+            "<nav class=\"mdv-toc\" aria-label=\"Table of contents\">\n<ul>\n\
+             <li><a href=\"#mdv-guide\">Guide</a>\n<ul>\n\
+             <li><a href=\"#mdv-install\">Install</a>\n<ul>\n\
+             <li><a href=\"#mdv-linux\">Linux</a></li>\n</ul>\n</li>\n\
+             <li><a href=\"#mdv-use--abuse\">Use &amp; abuse</a></li>\n</ul>\n</li>\n</ul>\n</nav>"
+        ));
+        assert!(!rendered.html.contains("[TOC]"));
+        assert_eq!(rendered.toc.len(), 4);
+    }
+
+    #[test]
+    fn maxdepth_limits_listed_levels() {
+        let rendered = render("[toc maxdepth=2]\n\n# A\n\n## B\n\n### C\n");
+
+        assert!(rendered.html.contains("href=\"#mdv-b\""));
+        assert!(!rendered.html.contains("href=\"#mdv-c\""));
+        assert_eq!(rendered.toc.len(), 3);
+    }
+
+    #[test]
+    fn comment_marker_replaces_previously_generated_list() {
+        let rendered =
+            render("<!-- toc -->\n\n- [Old](#old)\n\n<!-- tocstop -->\n\n# Fresh\n\nBody\n");
+
+        assert!(rendered.html.contains("href=\"#mdv-fresh\""));
+        assert!(!rendered.html.contains("Old"));
+        assert!(!rendered.html.contains("tocstop"));
+        assert!(rendered.html.contains("Body"));
+    }
+
+    #[test]
+    fn expanded_list_survives_raw_html_sanitization() {
+        let rendered = ComrakMarkdownRenderer::new()
+            .render(
+                "<!-- TOC maxdepth=1 -->\n\n# Only\n",
+                Path::new("notes.md"),
+                RenderPreferences {
+                    allow_raw_html: true,
+                    ..RenderPreferences::default()
+                },
+            )
+            .expect("render should succeed");
+
+        assert!(rendered
+            .html
+            .contains("<nav class=\"mdv-toc\" aria-label=\"Table of contents\">"));
+        assert!(rendered.html.contains("<a href=\"#mdv-only\""));
+    }
+
+    #[test]
+    fn markers_inside_other_text_or_code_are_left_alone() {
+        let rendered = render("# A\n\nSee [TOC] here.\n\n```\n[TOC]\n```\n");
+
+        assert!(!rendered.html.contains("mdv-toc"));
+        assert!(rendered.html.contains("See [TOC] here."));
+    }
+}
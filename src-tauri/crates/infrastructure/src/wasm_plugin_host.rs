@@ -0,0 +1,433 @@
+//! Sandboxed WebAssembly render plugins for fenced code blocks.
+//!
+//! A plugin is a `.wasm` (or `.wat`) module in the plugins directory. It gets no imports, so it
+//! cannot reach the filesystem, network or clock, and every call runs with a fuel and memory
+//! budget. Plugins must export:
+//!
+//! - `memory`: the module's linear memory.
+//! - `mdv_alloc(len: i32) -> i32`: reserves `len` bytes for host-provided input.
+//! - `mdv_info_strings() -> i64`: a packed pointer/length (`ptr << 32 | len`) to a UTF-8 list
+//!   of the fenced code block info strings the plugin claims, separated by commas or whitespace.
+//! - `mdv_render(info_ptr: i32, info_len: i32, code_ptr: i32, code_len: i32) -> i64`: renders
+//!   one block and returns a packed pointer/length to a UTF-8 HTML fragment, or `0` to decline.
+//!
+//! Plugin output is sanitized before it reaches the document. Any failure (trap, exhausted fuel,
+//! malformed output) leaves the original code block in place and adds a
+//! [`DocumentWarningKind::PluginError`] warning naming the plugin.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::Arena;
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_domain::document::{DocumentWarning, DocumentWarningKind};
+use wasmi::{
+    Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+};
+
+use crate::html_sanitizer::HtmlSanitizer;
+use crate::markdown_transform::{MarkdownTransform, TransformContext};
+
+const PLUGIN_EXTENSIONS: &[&str] = &["wasm", "wat"];
+const FUEL_PER_CALL: u64 = 50_000_000;
+const MEMORY_LIMIT_BYTES: usize = 16 * 1024 * 1024;
+
+pub struct WasmPluginHost {
+    engine: Engine,
+    plugins: Vec<WasmPlugin>,
+    load_failures: Vec<MarkdownViewerError>,
+    sanitizer: HtmlSanitizer,
+}
+
+struct WasmPlugin {
+    path: PathBuf,
+    module: Module,
+    info_strings: Vec<String>,
+}
+
+struct PluginInstance {
+    store: Store<StoreLimits>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl WasmPluginHost {
+    /// Loads every plugin module in `directory`, in file name order.
+    ///
+    /// A missing directory yields a host without plugins. Modules that fail to compile or do not
+    /// implement the plugin ABI are skipped and reported through [`WasmPluginHost::load_failures`].
+    pub fn load_from_directory(directory: &Path) -> Result<Self, MarkdownViewerError> {
+        let engine = plugin_engine();
+        let mut host = Self {
+            engine,
+            plugins: Vec::new(),
+            load_failures: Vec::new(),
+            sanitizer: HtmlSanitizer::new(),
+        };
+
+        if !directory.is_dir() {
+            return Ok(host);
+        }
+
+        let entries = fs::read_dir(directory).map_err(|source| MarkdownViewerError::Plugin {
+            path: directory.to_path_buf(),
+            reason: source.to_string(),
+        })?;
+        let mut module_paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && is_plugin_file(path))
+            .collect();
+        module_paths.sort();
+
+        for path in module_paths {
+            match host.load_plugin(&path) {
+                Ok(plugin) => host.plugins.push(plugin),
+                Err(reason) => host
+                    .load_failures
+                    .push(MarkdownViewerError::Plugin { path, reason }),
+            }
+        }
+
+        Ok(host)
+    }
+
+    pub fn plugin_count(&self) -> usize {
+        self.plugins.len()
+    }
+
+    pub fn load_failures(&self) -> &[MarkdownViewerError] {
+        &self.load_failures
+    }
+
+    fn load_plugin(&self, path: &Path) -> Result<WasmPlugin, String> {
+        let bytes = fs::read(path).map_err(|source| source.to_string())?;
+        let module = Module::new(&self.engine, bytes).map_err(|error| error.to_string())?;
+        let mut instance = self.instantiate(&module)?;
+        let info_strings_call = instance
+            .instance
+            .get_typed_func::<(), i64>(&instance.store, "mdv_info_strings")
+            .map_err(|error| error.to_string())?;
+        let packed = info_strings_call
+            .call(&mut instance.store, ())
+            .map_err(|error| error.to_string())?;
+        let info_strings = instance
+            .read_string(packed)?
+            .split(|character: char| character == ',' || character.is_whitespace())
+            .filter(|info_string| !info_string.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect::<Vec<_>>();
+        if info_strings.is_empty() {
+            return Err("plugin does not claim any info strings".to_string());
+        }
+
+        Ok(WasmPlugin {
+            path: path.to_path_buf(),
+            module,
+            info_strings,
+        })
+    }
+
+    fn instantiate(&self, module: &Module) -> Result<PluginInstance, String> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MEMORY_LIMIT_BYTES)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store
+            .set_fuel(FUEL_PER_CALL)
+            .map_err(|error| error.to_string())?;
+
+        // No host functions are linked, which is what keeps plugins sandboxed.
+        let linker = Linker::<StoreLimits>::new(&self.engine);
+        let instance = linker
+            .instantiate_and_start(&mut store, module)
+            .map_err(|error| error.to_string())?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| "plugin does not export `memory`".to_string())?;
+
+        Ok(PluginInstance {
+            store,
+            instance,
+            memory,
+        })
+    }
+
+    fn plugin_for(&self, info: &str) -> Option<&WasmPlugin> {
+        let language = info.split_whitespace().next()?.to_ascii_lowercase();
+        self.plugins.iter().find(|plugin| {
+            plugin
+                .info_strings
+                .iter()
+                .any(|candidate| candidate == &language)
+        })
+    }
+
+    /// Renders one block, returning `Ok(None)` when the plugin declines it.
+    fn render_block(
+        &self,
+        plugin: &WasmPlugin,
+        info: &str,
+        code: &str,
+    ) -> Result<Option<String>, String> {
+        let mut instance = self.instantiate(&plugin.module)?;
+        let (info_ptr, info_len) = instance.write_input(info)?;
+        let (code_ptr, code_len) = instance.write_input(code)?;
+        let render = instance
+            .instance
+            .get_typed_func::<(i32, i32, i32, i32), i64>(&instance.store, "mdv_render")
+            .map_err(|error| error.to_string())?;
+        let packed = render
+            .call(
+                &mut instance.store,
+                (info_ptr, info_len, code_ptr, code_len),
+            )
+            .map_err(|error| error.to_string())?;
+        if packed == 0 {
+            return Ok(None);
+        }
+        instance.read_string(packed).map(Some)
+    }
+}
+
+impl MarkdownTransform for WasmPluginHost {
+    fn transform<'a>(
+        &self,
+        _arena: &'a Arena<'a>,
+        root: &'a AstNode<'a>,
+        context: &TransformContext<'_>,
+    ) -> Result<(), MarkdownViewerError> {
+        if self.plugins.is_empty() {
+            return Ok(());
+        }
+
+        let code_blocks: Vec<&'a AstNode<'a>> = root
+            .descendants()
+            .filter(|node| {
+                matches!(&node.data.borrow().value, NodeValue::CodeBlock(block) if block.fenced)
+            })
+            .collect();
+
+        for node in code_blocks {
+            let (info, literal, line) = match &node.data.borrow().value {
+                NodeValue::CodeBlock(block) => (
+                    block.info.clone(),
+                    block.literal.clone(),
+                    node.data.borrow().sourcepos.start.line,
+                ),
+                _ => continue,
+            };
+            let Some(plugin) = self.plugin_for(&info) else {
+                continue;
+            };
+            match self.render_block(plugin, &info, &literal) {
+                Ok(Some(html)) => {
+                    node.data.borrow_mut().value = NodeValue::Raw(self.sanitizer.sanitize(&html));
+                }
+                Ok(None) => {}
+                Err(reason) => context.warn(DocumentWarning {
+                    kind: DocumentWarningKind::PluginError,
+                    message: format!("Plugin {} failed: {reason}", plugin.path.display()),
+                    line: Some(line),
+                }),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PluginInstance {
+    fn write_input(&mut self, input: &str) -> Result<(i32, i32), String> {
+        let length = i32::try_from(input.len()).map_err(|_| "plugin input is too large")?;
+        let alloc = self
+            .instance
+            .get_typed_func::<i32, i32>(&self.store, "mdv_alloc")
+            .map_err(|error| error.to_string())?;
+        let pointer = alloc
+            .call(&mut self.store, length)
+            .map_err(|error| error.to_string())?;
+        let offset = usize::try_from(pointer).map_err(|_| "plugin returned a negative pointer")?;
+        self.memory
+            .write(&mut self.store, offset, input.as_bytes())
+            .map_err(|error| error.to_string())?;
+        Ok((pointer, length))
+    }
+
+    fn read_string(&self, packed: i64) -> Result<String, String> {
+        let (offset, length) = unpack_pointer(packed)
+            .ok_or_else(|| "plugin declined to produce output".to_string())?;
+        if length > MEMORY_LIMIT_BYTES {
+            return Err("plugin output exceeds the memory limit".to_string());
+        }
+        let mut buffer = vec![0_u8; length];
+        self.memory
+            .read(&self.store, offset, &mut buffer)
+            .map_err(|error| error.to_string())?;
+        String::from_utf8(buffer).map_err(|error| error.to_string())
+    }
+}
+
+fn plugin_engine() -> Engine {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config)
+}
+
+fn is_plugin_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| {
+            PLUGIN_EXTENSIONS
+                .iter()
+                .any(|candidate| extension.eq_ignore_ascii_case(candidate))
+        })
+        .unwrap_or(false)
+}
+
+fn unpack_pointer(packed: i64) -> Option<(usize, usize)> {
+    if packed <= 0 {
+        return None;
+    }
+    let packed = packed as u64;
+    let offset = (packed >> 32) as usize;
+    let length = (packed & 0xFFFF_FFFF) as usize;
+    Some((offset, length))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::ports::MarkdownRenderer;
+    use markdown_viewer_domain::document::{DocumentWarningKind, RenderPreferences};
+
+    use super::{unpack_pointer, WasmPluginHost};
+    use crate::comrak_renderer::ComrakMarkdownRenderer;
+
+    const TRAPPING_PLUGIN: &str = r#"(module
+      (memory (export "memory") 1)
+      (data (i32.const 0) "boom")
+      (func (export "mdv_alloc") (param i32) (result i32) i32.const 64)
+      (func (export "mdv_info_strings") (result i64) i64.const 4)
+      (func (export "mdv_render") (param i32 i32 i32 i32) (result i64) unreachable))"#;
+
+    const LOOPING_PLUGIN: &str = r#"(module
+      (memory (export "memory") 1)
+      (data (i32.const 0) "spin")
+      (func (export "mdv_alloc") (param i32) (result i32) i32.const 64)
+      (func (export "mdv_info_strings") (result i64) i64.const 4)
+      (func (export "mdv_render") (param i32 i32 i32 i32) (result i64)
+        (loop $forever (br $forever))
+        i64.const 0))"#;
+
+    fn fixture_plugins_directory() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../test-fixtures/wasm-plugins")
+    }
+
+    fn temp_plugins_directory(plugins: &[(&str, &str)]) -> PathBuf {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic after epoch")
+            .as_nanos();
+        let directory = std::env::temp_dir().join(format!("mdv-plugins-{suffix}"));
+        fs::create_dir_all(&directory).expect("temp plugin directory should be creatable");
+        for (file_name, contents) in plugins {
+            fs::write(directory.join(file_name), contents).expect("temp plugin should be writable");
+        }
+        directory
+    }
+
+    fn renderer_with(host: WasmPluginHost) -> ComrakMarkdownRenderer {
+        let mut transforms = ComrakMarkdownRenderer::default_transforms();
+        transforms.push(Box::new(host));
+        ComrakMarkdownRenderer::with_transforms(transforms)
+    }
+
+    #[test]
+    fn example_plugin_renders_claimed_blocks_as_sanitized_html() {
+        let host = WasmPluginHost::load_from_directory(&fixture_plugins_directory())
+            .expect("fixture plugins should load");
+        assert_eq!(host.plugin_count(), 1);
+        assert!(host.load_failures().is_empty());
+
+        let rendered = renderer_with(host)
+            .render(
+                "```ticket\nABC-123 <script>alert(1)</script>\n```\n\n```rust\nlet x = 1;\n```",
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert!(rendered
+            .html
+            .contains("<div class=\"mdv-ticket\">ABC-123 \n</div>"));
+        assert!(!rendered.html.contains("<script>"));
+        assert!(rendered.html.contains("<code class=\"language-rust\">"));
+    }
+
+    #[test]
+    fn plugin_failures_keep_the_raw_code_block() {
+        let directory =
+            temp_plugins_directory(&[("boom.wat", TRAPPING_PLUGIN), ("spin.wat", LOOPING_PLUGIN)]);
+        let host = WasmPluginHost::load_from_directory(&directory).expect("plugins should load");
+        assert_eq!(host.plugin_count(), 2);
+
+        let rendered = renderer_with(host)
+            .render(
+                "```boom\nfirst\n```\n\n```spin\nsecond\n```",
+                Path::new("notes.md"),
+                RenderPreferences::default(),
+            )
+            .expect("renderer should work");
+
+        assert!(rendered
+            .html
+            .contains("<code class=\"language-boom\">first\n</code>"));
+        assert!(rendered
+            .html
+            .contains("<code class=\"language-spin\">second\n</code>"));
+        assert_eq!(rendered.warnings.len(), 2);
+        assert!(rendered
+            .warnings
+            .iter()
+            .all(|warning| warning.kind == DocumentWarningKind::PluginError));
+        assert!(rendered.warnings[0].message.contains("boom.wat"));
+        assert_eq!(rendered.warnings[0].line, Some(1));
+        assert!(rendered.warnings[1].message.contains("spin.wat"));
+        assert_eq!(rendered.warnings[1].line, Some(5));
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn invalid_modules_are_reported_and_skipped() {
+        let directory =
+            temp_plugins_directory(&[("broken.wasm", "not a module"), ("notes.txt", "ignored")]);
+
+        let host = WasmPluginHost::load_from_directory(&directory).expect("directory should load");
+
+        assert_eq!(host.plugin_count(), 0);
+        assert_eq!(host.load_failures().len(), 1);
+        assert!(host.load_failures()[0].to_string().contains("broken.wasm"));
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn missing_plugins_directory_yields_empty_host() {
+        let host = WasmPluginHost::load_from_directory(Path::new("/tmp/mdv-no-such-plugins-dir"))
+            .expect("missing directory should not fail");
+        assert_eq!(host.plugin_count(), 0);
+    }
+
+    #[test]
+    fn unpack_pointer_rejects_zero_and_negative_values() {
+        assert_eq!(unpack_pointer(0), None);
+        assert_eq!(unpack_pointer(-1), None);
+        assert_eq!(unpack_pointer((16_i64 << 32) | 5), Some((16, 5)));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use markdown_viewer_application::error::MarkdownViewerError;
+use markdown_viewer_application::ports::MarkdownWorkspaceScanner;
+
+use crate::file_repository::is_markdown_file;
+
+/// Dependency folders that hold other projects' docs rather than the workspace's own.
+const SKIPPED_DIRECTORIES: &[&str] = &["node_modules", "target"];
+/// Upper bound on files returned for one folder, so pointing at a home directory stays bounded.
+const MAX_WORKSPACE_FILES: usize = 5000;
+
+/// Walks a local folder for markdown files, skipping hidden entries and dependency folders.
+/// Symbolic links to directories are not followed.
+pub struct LocalWorkspaceScanner;
+
+impl LocalWorkspaceScanner {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LocalWorkspaceScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownWorkspaceScanner for LocalWorkspaceScanner {
+    fn list_markdown_files(
+        &self,
+        folder_input: &str,
+    ) -> Result<(PathBuf, Vec<PathBuf>), MarkdownViewerError> {
+        let folder = resolve_folder(Path::new(folder_input))?;
+        let mut files = Vec::new();
+        collect_markdown_files(&folder, &mut files);
+        files.sort();
+        Ok((folder, files))
+    }
+}
+
+fn resolve_folder(path: &Path) -> Result<PathBuf, MarkdownViewerError> {
+    let folder = path.canonicalize().map_err(|source| {
+        if source.kind() == ErrorKind::NotFound {
+            MarkdownViewerError::FileNotFound(path.to_path_buf())
+        } else {
+            MarkdownViewerError::ReadFile {
+                path: path.to_path_buf(),
+                reason: source.to_string(),
+            }
+        }
+    })?;
+    if !folder.is_dir() {
+        return Err(MarkdownViewerError::NotAFolder(folder));
+    }
+    Ok(folder)
+}
+
+/// Adds the markdown files below `directory` to `files`. Unreadable subdirectories are skipped.
+fn collect_markdown_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+    let mut subdirectories = Vec::new();
+    for entry in entries.flatten() {
+        if files.len() >= MAX_WORKSPACE_FILES {
+            return;
+        }
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            if !SKIPPED_DIRECTORIES.contains(&name.as_ref()) {
+                subdirectories.push(path);
+            }
+        } else if is_markdown_file(&path) && path.is_file() {
+            files.push(path);
+        }
+    }
+    for subdirectory in subdirectories {
+        collect_markdown_files(&subdirectory, files);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use markdown_viewer_application::error::MarkdownViewerError;
+    use markdown_viewer_application::ports::MarkdownWorkspaceScanner;
+
+    use super::LocalWorkspaceScanner;
+
+    fn temp_folder() -> PathBuf {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic after epoch")
+            .as_nanos();
+        let folder = std::env::temp_dir().join(format!("mdv-workspace-{suffix}"));
+        fs::create_dir_all(&folder).expect("temp folder should be creatable");
+        folder
+            .canonicalize()
+            .expect("temp folder should canonicalize")
+    }
+
+    #[test]
+    fn lists_markdown_files_recursively_in_path_order() {
+        let folder = temp_folder();
+        for (relative, contents) in [
+            ("b.md", "- [ ] b"),
+            ("a.markdown", "- [ ] a"),
+            ("notes.txt", "- [ ] not markdown"),
+            ("data.csv", "a,b"),
+            ("guide/intro.md", "# Intro"),
+            (".git/HEAD.md", "hidden"),
+            ("node_modules/pkg/README.md", "dependency"),
+        ] {
+            let path = folder.join(relative);
+            fs::create_dir_all(path.parent().expect("fixture has a parent"))
+                .expect("fixture folder should be creatable");
+            fs::write(path, contents).expect("fixture should be writable");
+        }
+
+        let (resolved, files) = LocalWorkspaceScanner::new()
+            .list_markdown_files(&folder.to_string_lossy())
+            .expect("scan should succeed");
+
+        assert_eq!(resolved, folder);
+        assert_eq!(
+            files,
+            [
+                folder.join("a.markdown"),
+                folder.join("b.md"),
+                folder.join("guide/intro.md")
+            ]
+        );
+
+        let _ = fs::remove_dir_all(folder);
+    }
+
+    #[test]
+    fn rejects_files_and_missing_paths() {
+        let folder = temp_folder();
+        let file = folder.join("one.md");
+        fs::write(&file, "# One").expect("fixture should be writable");
+        let scanner = LocalWorkspaceScanner::new();
+
+        assert!(matches!(
+            scanner.list_markdown_files(&file.to_string_lossy()),
+            Err(MarkdownViewerError::NotAFolder(_))
+        ));
+        assert!(matches!(
+            scanner.list_markdown_files(&folder.join("missing").to_string_lossy()),
+            Err(MarkdownViewerError::FileNotFound(_))
+        ));
+
+        let _ = fs::remove_dir_all(folder);
+    }
+}
@@ -1,5 +1,14 @@
+use std::collections::BTreeMap;
+
 use markdown_viewer_application::models::{
-    MarkdownDocumentOutput, RenderPreferencesInput, TocEntryOutput, WordCountRulesInput,
+    ArchiveEntryOutput, ArchiveListingOutput, DocumentDiffOutput, DocumentOriginOutput,
+    DocumentStatisticsOutput, DocumentWarningKindOutput, DocumentWarningOutput, EffectiveConfig,
+    ExtensionProfileInput, FileHistoryOutput, FileRevisionOutput, HeadingNumberingInput,
+    HtmlAllowlistInput, ImageEmbeddingInput, LintDiagnosticOutput, LintReportOutput,
+    LintSeverityOutput, MarkdownDocumentOutput, MisspellingOutput, RenderPreferencesInput,
+    SpellCheckOutput, TaskListItemOutput, TextReplacementRuleInput, ThemePreset, TocEntryOutput,
+    ToggleTaskItemOutput, ViewerSettings, WordCountRulesInput, WorkspaceTaskOutput,
+    WorkspaceTasksOutput,
 };
 use serde::Deserialize;
 use serde::Serialize;
@@ -22,6 +31,131 @@ impl From<TocEntryOutput> for TocEntryDto {
     }
 }
 
+/// Only `file` documents are watched for changes and can be edited.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DocumentOriginDto {
+    File,
+    Stdin,
+    Remote,
+    Archive,
+}
+
+impl From<DocumentOriginOutput> for DocumentOriginDto {
+    fn from(value: DocumentOriginOutput) -> Self {
+        match value {
+            DocumentOriginOutput::File => Self::File,
+            DocumentOriginOutput::Stdin => Self::Stdin,
+            DocumentOriginOutput::Remote => Self::Remote,
+            DocumentOriginOutput::Archive => Self::Archive,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DocumentWarningKindDto {
+    MissingImage,
+    DiagramError,
+    CsvError,
+    PluginError,
+    ProjectConfig,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentWarningDto {
+    pub kind: DocumentWarningKindDto,
+    pub message: String,
+    pub line: Option<usize>,
+}
+
+impl From<DocumentWarningOutput> for DocumentWarningDto {
+    fn from(value: DocumentWarningOutput) -> Self {
+        Self {
+            kind: match value.kind {
+                DocumentWarningKindOutput::MissingImage => DocumentWarningKindDto::MissingImage,
+                DocumentWarningKindOutput::DiagramError => DocumentWarningKindDto::DiagramError,
+                DocumentWarningKindOutput::CsvError => DocumentWarningKindDto::CsvError,
+                DocumentWarningKindOutput::PluginError => DocumentWarningKindDto::PluginError,
+                DocumentWarningKindOutput::ProjectConfig => DocumentWarningKindDto::ProjectConfig,
+            },
+            message: value.message,
+            line: value.line,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCompletionDto {
+    pub checked: usize,
+    pub unchecked: usize,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadabilityDto {
+    pub flesch_reading_ease: f64,
+    pub flesch_kincaid_grade: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentStatisticsDto {
+    pub section_word_counts: BTreeMap<String, usize>,
+    pub code_blocks: usize,
+    pub images: usize,
+    pub tables: usize,
+    pub links: usize,
+    pub footnotes: usize,
+    pub tasks: TaskCompletionDto,
+    pub readability: Option<ReadabilityDto>,
+}
+
+impl From<DocumentStatisticsOutput> for DocumentStatisticsDto {
+    fn from(value: DocumentStatisticsOutput) -> Self {
+        Self {
+            section_word_counts: value.section_word_counts,
+            code_blocks: value.code_blocks,
+            images: value.images,
+            tables: value.tables,
+            links: value.links,
+            footnotes: value.footnotes,
+            tasks: TaskCompletionDto {
+                checked: value.tasks.checked,
+                unchecked: value.tasks.unchecked,
+            },
+            readability: value.readability.map(|readability| ReadabilityDto {
+                flesch_reading_ease: readability.flesch_reading_ease,
+                flesch_kincaid_grade: readability.flesch_kincaid_grade,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskListItemDto {
+    pub text: String,
+    pub checked: bool,
+    pub depth: usize,
+    pub heading_id: Option<String>,
+    pub line: usize,
+}
+
+impl From<TaskListItemOutput> for TaskListItemDto {
+    fn from(value: TaskListItemOutput) -> Self {
+        Self {
+            text: value.text,
+            checked: value.checked,
+            depth: value.depth,
+            heading_id: value.heading_id,
+            line: value.line,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarkdownDocumentDto {
@@ -32,6 +166,12 @@ pub struct MarkdownDocumentDto {
     pub toc: Vec<TocEntryDto>,
     pub word_count: usize,
     pub reading_time_minutes: u16,
+    pub statistics: DocumentStatisticsDto,
+    pub tasks: Vec<TaskListItemDto>,
+    pub warnings: Vec<DocumentWarningDto>,
+    pub content_hash: String,
+    pub origin: DocumentOriginDto,
+    pub config: EffectiveConfigDto,
 }
 
 impl From<MarkdownDocumentOutput> for MarkdownDocumentDto {
@@ -44,11 +184,302 @@ impl From<MarkdownDocumentOutput> for MarkdownDocumentDto {
             toc: value.toc.into_iter().map(TocEntryDto::from).collect(),
             word_count: value.word_count,
             reading_time_minutes: value.reading_time_minutes,
+            statistics: value.statistics.into(),
+            tasks: value.tasks.into_iter().map(TaskListItemDto::from).collect(),
+            warnings: value
+                .warnings
+                .into_iter()
+                .map(DocumentWarningDto::from)
+                .collect(),
+            content_hash: value.content_hash,
+            origin: value.origin.into(),
+            config: value.config.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveConfigDto {
+    pub render: RenderPreferencesDto,
+    pub project_config_path: Option<String>,
+    pub custom_css: Option<String>,
+}
+
+impl From<EffectiveConfig> for EffectiveConfigDto {
+    fn from(value: EffectiveConfig) -> Self {
+        Self {
+            render: value.render.into(),
+            project_config_path: value.project_config_path,
+            custom_css: value.custom_css,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToggleTaskItemDto {
+    pub path: String,
+    pub checked: bool,
+    pub content_hash: String,
+}
+
+impl From<ToggleTaskItemOutput> for ToggleTaskItemDto {
+    fn from(value: ToggleTaskItemOutput) -> Self {
+        Self {
+            path: value.path,
+            checked: value.checked,
+            content_hash: value.content_hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTaskDto {
+    pub path: String,
+    pub document_title: String,
+    pub task: TaskListItemDto,
+}
+
+impl From<WorkspaceTaskOutput> for WorkspaceTaskDto {
+    fn from(value: WorkspaceTaskOutput) -> Self {
+        Self {
+            path: value.path,
+            document_title: value.document_title,
+            task: value.task.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTasksDto {
+    pub folder: String,
+    pub tasks: Vec<WorkspaceTaskDto>,
+    pub unreadable_files: Vec<String>,
+}
+
+impl From<WorkspaceTasksOutput> for WorkspaceTasksDto {
+    fn from(value: WorkspaceTasksOutput) -> Self {
+        Self {
+            folder: value.folder,
+            tasks: value
+                .tasks
+                .into_iter()
+                .map(WorkspaceTaskDto::from)
+                .collect(),
+            unreadable_files: value.unreadable_files,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LintSeverityDto {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintDiagnosticDto {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub severity: LintSeverityDto,
+    pub message: String,
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+impl From<LintDiagnosticOutput> for LintDiagnosticDto {
+    fn from(value: LintDiagnosticOutput) -> Self {
+        Self {
+            rule_id: value.rule_id,
+            rule_name: value.rule_name,
+            severity: match value.severity {
+                LintSeverityOutput::Warning => LintSeverityDto::Warning,
+                LintSeverityOutput::Error => LintSeverityDto::Error,
+            },
+            message: value.message,
+            line: value.line,
+            column: value.column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReportDto {
+    pub path: String,
+    pub diagnostics: Vec<LintDiagnosticDto>,
+    pub config_path: Option<String>,
+}
+
+impl From<LintReportOutput> for LintReportDto {
+    fn from(value: LintReportOutput) -> Self {
+        Self {
+            path: value.path,
+            diagnostics: value
+                .diagnostics
+                .into_iter()
+                .map(LintDiagnosticDto::from)
+                .collect(),
+            config_path: value.config_path,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MisspellingDto {
+    pub word: String,
+    pub line: usize,
+    pub start_column: usize,
+    pub end_column: usize,
+    pub suggestions: Vec<String>,
+}
+
+impl From<MisspellingOutput> for MisspellingDto {
+    fn from(value: MisspellingOutput) -> Self {
+        Self {
+            word: value.word,
+            line: value.line,
+            start_column: value.start_column,
+            end_column: value.end_column,
+            suggestions: value.suggestions,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpellCheckDto {
+    pub path: String,
+    pub misspellings: Vec<MisspellingDto>,
+    pub word_list_path: Option<String>,
+}
+
+impl From<SpellCheckOutput> for SpellCheckDto {
+    fn from(value: SpellCheckOutput) -> Self {
+        Self {
+            path: value.path,
+            misspellings: value
+                .misspellings
+                .into_iter()
+                .map(MisspellingDto::from)
+                .collect(),
+            word_list_path: value.word_list_path,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiffDto {
+    pub path: String,
+    pub html: String,
+    pub inserted_blocks: usize,
+    pub deleted_blocks: usize,
+    pub modified_blocks: usize,
+}
+
+impl From<DocumentDiffOutput> for DocumentDiffDto {
+    fn from(value: DocumentDiffOutput) -> Self {
+        Self {
+            path: value.path,
+            html: value.html,
+            inserted_blocks: value.inserted_blocks,
+            deleted_blocks: value.deleted_blocks,
+            modified_blocks: value.modified_blocks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRevisionDto {
+    pub commit_id: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Seconds since the Unix epoch.
+    pub authored_at: i64,
+    pub summary: String,
+    pub message: String,
+}
+
+impl From<FileRevisionOutput> for FileRevisionDto {
+    fn from(value: FileRevisionOutput) -> Self {
+        Self {
+            commit_id: value.commit_id,
+            author_name: value.author_name,
+            author_email: value.author_email,
+            authored_at: value.authored_at,
+            summary: value.summary,
+            message: value.message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHistoryDto {
+    pub path: String,
+    pub revisions: Vec<FileRevisionDto>,
+}
+
+impl From<FileHistoryOutput> for FileHistoryDto {
+    fn from(value: FileHistoryOutput) -> Self {
+        Self {
+            path: value.path,
+            revisions: value
+                .revisions
+                .into_iter()
+                .map(FileRevisionDto::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveEntryDto {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+}
+
+impl From<ArchiveEntryOutput> for ArchiveEntryDto {
+    fn from(value: ArchiveEntryOutput) -> Self {
+        Self {
+            name: value.name,
+            path: value.path,
+            size: value.size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveListingDto {
+    pub path: String,
+    pub entries: Vec<ArchiveEntryDto>,
+}
+
+impl From<ArchiveListingOutput> for ArchiveListingDto {
+    fn from(value: ArchiveListingOutput) -> Self {
+        Self {
+            path: value.path,
+            entries: value
+                .entries
+                .into_iter()
+                .map(ArchiveEntryDto::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WordCountRulesDto {
     pub include_links: bool,
@@ -66,13 +497,193 @@ impl From<WordCountRulesDto> for WordCountRulesInput {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+impl From<WordCountRulesInput> for WordCountRulesDto {
+    fn from(value: WordCountRulesInput) -> Self {
+        Self {
+            include_links: value.include_links,
+            include_code: value.include_code,
+            include_front_matter: value.include_front_matter,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextReplacementRuleDto {
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub link_template: Option<String>,
+}
+
+impl From<TextReplacementRuleDto> for TextReplacementRuleInput {
+    fn from(value: TextReplacementRuleDto) -> Self {
+        Self {
+            pattern: value.pattern,
+            replacement: value.replacement,
+            link_template: value.link_template,
+        }
+    }
+}
+
+impl From<TextReplacementRuleInput> for TextReplacementRuleDto {
+    fn from(value: TextReplacementRuleInput) -> Self {
+        Self {
+            pattern: value.pattern,
+            replacement: value.replacement,
+            link_template: value.link_template,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HtmlAllowlistDto {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub attributes: Vec<String>,
+    #[serde(default)]
+    pub url_schemes: Vec<String>,
+}
+
+impl From<HtmlAllowlistDto> for HtmlAllowlistInput {
+    fn from(value: HtmlAllowlistDto) -> Self {
+        Self {
+            tags: value.tags,
+            attributes: value.attributes,
+            url_schemes: value.url_schemes,
+        }
+    }
+}
+
+impl From<HtmlAllowlistInput> for HtmlAllowlistDto {
+    fn from(value: HtmlAllowlistInput) -> Self {
+        Self {
+            tags: value.tags,
+            attributes: value.attributes,
+            url_schemes: value.url_schemes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadingNumberingDto {
+    #[serde(default = "default_heading_numbering_start_level")]
+    pub start_level: u8,
+}
+
+fn default_heading_numbering_start_level() -> u8 {
+    HeadingNumberingInput::default().start_level
+}
+
+impl From<HeadingNumberingDto> for HeadingNumberingInput {
+    fn from(value: HeadingNumberingDto) -> Self {
+        Self {
+            start_level: value.start_level,
+        }
+    }
+}
+
+impl From<HeadingNumberingInput> for HeadingNumberingDto {
+    fn from(value: HeadingNumberingInput) -> Self {
+        Self {
+            start_level: value.start_level,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImageEmbeddingDto {
+    #[default]
+    AssetUrl,
+    DataUri,
+}
+
+impl From<ImageEmbeddingDto> for ImageEmbeddingInput {
+    fn from(value: ImageEmbeddingDto) -> Self {
+        match value {
+            ImageEmbeddingDto::AssetUrl => Self::AssetUrl,
+            ImageEmbeddingDto::DataUri => Self::DataUri,
+        }
+    }
+}
+
+impl From<ImageEmbeddingInput> for ImageEmbeddingDto {
+    fn from(value: ImageEmbeddingInput) -> Self {
+        match value {
+            ImageEmbeddingInput::AssetUrl => Self::AssetUrl,
+            ImageEmbeddingInput::DataUri => Self::DataUri,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtensionProfileDto {
+    #[default]
+    Default,
+    Github,
+    CommonMark,
+    Full,
+}
+
+impl From<ExtensionProfileDto> for ExtensionProfileInput {
+    fn from(value: ExtensionProfileDto) -> Self {
+        match value {
+            ExtensionProfileDto::Default => Self::Default,
+            ExtensionProfileDto::Github => Self::Github,
+            ExtensionProfileDto::CommonMark => Self::CommonMark,
+            ExtensionProfileDto::Full => Self::Full,
+        }
+    }
+}
+
+impl From<ExtensionProfileInput> for ExtensionProfileDto {
+    fn from(value: ExtensionProfileInput) -> Self {
+        match value {
+            ExtensionProfileInput::Default => Self::Default,
+            ExtensionProfileInput::Github => Self::Github,
+            ExtensionProfileInput::CommonMark => Self::CommonMark,
+            ExtensionProfileInput::Full => Self::Full,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RenderPreferencesDto {
     #[serde(default)]
     pub performance_mode: bool,
     #[serde(default)]
     pub word_count_rules: Option<WordCountRulesDto>,
+    #[serde(default)]
+    pub text_replacements: Vec<TextReplacementRuleDto>,
+    #[serde(default)]
+    pub allow_raw_html: bool,
+    #[serde(default)]
+    pub html_allowlist: Option<HtmlAllowlistDto>,
+    #[serde(default)]
+    pub image_embedding: ImageEmbeddingDto,
+    #[serde(default)]
+    pub csv_row_limit: Option<usize>,
+    #[serde(default)]
+    pub number_headings: Option<HeadingNumberingDto>,
+    #[serde(default)]
+    pub words_per_minute: Option<usize>,
+    #[serde(default)]
+    pub han_characters_per_minute: Option<usize>,
+    #[serde(default)]
+    pub kana_characters_per_minute: Option<usize>,
+    #[serde(default)]
+    pub block_external_links: bool,
+    #[serde(default)]
+    pub extension_profile: ExtensionProfileDto,
+    /// Extensions turned on or off regardless of the profile, keyed by snake_case name.
+    #[serde(default)]
+    pub extensions: BTreeMap<String, bool>,
 }
 
 impl RenderPreferencesDto {
@@ -80,6 +691,18 @@ impl RenderPreferencesDto {
         RenderPreferencesInput {
             performance_mode: self.performance_mode,
             word_count_rules: self.word_count_rules.map(Into::into).unwrap_or_default(),
+            text_replacements: self.text_replacements.into_iter().map(Into::into).collect(),
+            allow_raw_html: self.allow_raw_html,
+            html_allowlist: self.html_allowlist.map(Into::into),
+            image_embedding: self.image_embedding.into(),
+            csv_row_limit: self.csv_row_limit,
+            number_headings: self.number_headings.map(Into::into),
+            words_per_minute: self.words_per_minute,
+            han_characters_per_minute: self.han_characters_per_minute,
+            kana_characters_per_minute: self.kana_characters_per_minute,
+            block_external_links: self.block_external_links,
+            extension_profile: self.extension_profile.into(),
+            extension_overrides: self.extensions,
         }
     }
 }
@@ -90,12 +713,124 @@ pub fn to_render_preferences(value: Option<RenderPreferencesDto>) -> RenderPrefe
         .unwrap_or_default()
 }
 
+impl From<RenderPreferencesInput> for RenderPreferencesDto {
+    fn from(value: RenderPreferencesInput) -> Self {
+        Self {
+            performance_mode: value.performance_mode,
+            word_count_rules: Some(value.word_count_rules.into()),
+            text_replacements: value
+                .text_replacements
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            allow_raw_html: value.allow_raw_html,
+            html_allowlist: value.html_allowlist.map(Into::into),
+            image_embedding: value.image_embedding.into(),
+            csv_row_limit: value.csv_row_limit,
+            number_headings: value.number_headings.map(Into::into),
+            words_per_minute: value.words_per_minute,
+            han_characters_per_minute: value.han_characters_per_minute,
+            kana_characters_per_minute: value.kana_characters_per_minute,
+            block_external_links: value.block_external_links,
+            extension_profile: value.extension_profile.into(),
+            extensions: value.extension_overrides,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePresetDto {
+    #[default]
+    Paper,
+    Slate,
+    ContrastLight,
+    ContrastDark,
+}
+
+impl From<ThemePreset> for ThemePresetDto {
+    fn from(value: ThemePreset) -> Self {
+        match value {
+            ThemePreset::Paper => Self::Paper,
+            ThemePreset::Slate => Self::Slate,
+            ThemePreset::ContrastLight => Self::ContrastLight,
+            ThemePreset::ContrastDark => Self::ContrastDark,
+        }
+    }
+}
+
+impl From<ThemePresetDto> for ThemePreset {
+    fn from(value: ThemePresetDto) -> Self {
+        match value {
+            ThemePresetDto::Paper => Self::Paper,
+            ThemePresetDto::Slate => Self::Slate,
+            ThemePresetDto::ContrastLight => Self::ContrastLight,
+            ThemePresetDto::ContrastDark => Self::ContrastDark,
+        }
+    }
+}
+
+/// The persisted settings, sent whole by `update_settings` and returned by `get_settings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsDto {
+    pub render: RenderPreferencesDto,
+    pub safe_mode: bool,
+    pub theme: ThemePresetDto,
+    pub font_scale: f64,
+    pub line_height: f64,
+    pub measure_width: u32,
+    pub toc_auto_expand: bool,
+}
+
+impl From<ViewerSettings> for SettingsDto {
+    fn from(value: ViewerSettings) -> Self {
+        Self {
+            render: value.render.into(),
+            safe_mode: value.safe_mode,
+            theme: value.theme.into(),
+            font_scale: value.font_scale,
+            line_height: value.line_height,
+            measure_width: value.measure_width,
+            toc_auto_expand: value.toc_auto_expand,
+        }
+    }
+}
+
+impl From<SettingsDto> for ViewerSettings {
+    fn from(value: SettingsDto) -> Self {
+        Self {
+            render: value.render.to_application(),
+            safe_mode: value.safe_mode,
+            theme: value.theme.into(),
+            font_scale: value.font_scale,
+            line_height: value.line_height,
+            measure_width: value.measure_width,
+            toc_auto_expand: value.toc_auto_expand,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use markdown_viewer_application::models::{MarkdownDocumentOutput, TocEntryOutput};
+    use std::collections::BTreeMap;
+
+    use markdown_viewer_application::models::{
+        ArchiveEntryOutput, ArchiveListingOutput, DocumentDiffOutput, DocumentOriginOutput,
+        DocumentStatisticsOutput, DocumentWarningKindOutput, DocumentWarningOutput,
+        EffectiveConfig, ExtensionProfileInput, FileHistoryOutput, FileRevisionOutput,
+        ImageEmbeddingInput, LintDiagnosticOutput, LintReportOutput, LintSeverityOutput,
+        MarkdownDocumentOutput, MisspellingOutput, ReadabilityOutput, SpellCheckOutput,
+        TaskCompletionOutput, TaskListItemOutput, ThemePreset, TocEntryOutput, ViewerSettings,
+        WorkspaceTaskOutput, WorkspaceTasksOutput,
+    };
 
     use crate::dto::{
-        to_render_preferences, MarkdownDocumentDto, RenderPreferencesDto, WordCountRulesDto,
+        to_render_preferences, ArchiveListingDto, DocumentDiffDto, DocumentOriginDto,
+        DocumentWarningKindDto, ExtensionProfileDto, FileHistoryDto, HeadingNumberingDto,
+        HtmlAllowlistDto, ImageEmbeddingDto, LintReportDto, MarkdownDocumentDto,
+        RenderPreferencesDto, SettingsDto, SpellCheckDto, TextReplacementRuleDto,
+        WordCountRulesDto, WorkspaceTasksDto,
     };
 
     #[test]
@@ -105,6 +840,13 @@ mod tests {
         assert!(preferences.word_count_rules.include_links);
         assert!(!preferences.word_count_rules.include_code);
         assert!(!preferences.word_count_rules.include_front_matter);
+        assert!(preferences.text_replacements.is_empty());
+        assert!(!preferences.allow_raw_html);
+        assert!(preferences.html_allowlist.is_none());
+        assert_eq!(preferences.image_embedding, ImageEmbeddingInput::AssetUrl);
+        assert_eq!(preferences.csv_row_limit, None);
+        assert_eq!(preferences.words_per_minute, None);
+        assert!(preferences.number_headings.is_none());
     }
 
     #[test]
@@ -116,12 +858,62 @@ mod tests {
                 include_code: true,
                 include_front_matter: true,
             }),
+            text_replacements: vec![TextReplacementRuleDto {
+                pattern: r"\bABC-\d+\b".to_string(),
+                replacement: "$0".to_string(),
+                link_template: Some("https://tracker.example.com/$0".to_string()),
+            }],
+            allow_raw_html: true,
+            html_allowlist: Some(HtmlAllowlistDto {
+                tags: vec!["details".to_string(), "summary".to_string()],
+                attributes: vec!["open".to_string()],
+                url_schemes: vec!["https".to_string()],
+            }),
+            image_embedding: ImageEmbeddingDto::DataUri,
+            csv_row_limit: Some(50),
+            number_headings: Some(HeadingNumberingDto { start_level: 2 }),
+            words_per_minute: Some(180),
+            han_characters_per_minute: Some(260),
+            kana_characters_per_minute: None,
+            block_external_links: true,
+            extension_profile: ExtensionProfileDto::CommonMark,
+            extensions: [("table".to_string(), true)].into_iter().collect(),
         }));
 
         assert!(preferences.performance_mode);
         assert!(!preferences.word_count_rules.include_links);
         assert!(preferences.word_count_rules.include_code);
         assert!(preferences.word_count_rules.include_front_matter);
+        assert_eq!(preferences.text_replacements.len(), 1);
+        assert_eq!(preferences.text_replacements[0].pattern, r"\bABC-\d+\b");
+        assert_eq!(
+            preferences.text_replacements[0].link_template.as_deref(),
+            Some("https://tracker.example.com/$0")
+        );
+        assert!(preferences.allow_raw_html);
+        let allowlist = preferences
+            .html_allowlist
+            .expect("html allowlist should be mapped");
+        assert_eq!(allowlist.tags, ["details", "summary"]);
+        assert_eq!(allowlist.attributes, ["open"]);
+        assert_eq!(allowlist.url_schemes, ["https"]);
+        assert_eq!(preferences.image_embedding, ImageEmbeddingInput::DataUri);
+        assert_eq!(preferences.csv_row_limit, Some(50));
+        assert_eq!(
+            preferences
+                .number_headings
+                .map(|numbering| numbering.start_level),
+            Some(2)
+        );
+        assert_eq!(preferences.words_per_minute, Some(180));
+        assert_eq!(preferences.han_characters_per_minute, Some(260));
+        assert_eq!(preferences.kana_characters_per_minute, None);
+        assert!(preferences.block_external_links);
+        assert_eq!(
+            preferences.extension_profile,
+            ExtensionProfileInput::CommonMark
+        );
+        assert_eq!(preferences.extension_overrides.get("table"), Some(&true));
     }
 
     #[test]
@@ -138,6 +930,39 @@ mod tests {
             }],
             word_count: 320,
             reading_time_minutes: 2,
+            statistics: DocumentStatisticsOutput {
+                section_word_counts: [("mdv-spec".to_string(), 320)].into_iter().collect(),
+                code_blocks: 3,
+                tasks: TaskCompletionOutput {
+                    checked: 1,
+                    unchecked: 2,
+                },
+                readability: Some(ReadabilityOutput {
+                    flesch_reading_ease: 61.2,
+                    flesch_kincaid_grade: 8.4,
+                }),
+                ..DocumentStatisticsOutput::default()
+            },
+            tasks: vec![TaskListItemOutput {
+                text: "Review".to_string(),
+                checked: false,
+                depth: 1,
+                heading_id: Some("mdv-spec".to_string()),
+                line: 7,
+            }],
+            warnings: vec![DocumentWarningOutput {
+                kind: DocumentWarningKindOutput::MissingImage,
+                message: "Image not found: ./diagram.png".to_string(),
+                line: Some(4),
+            }],
+            local_images: Vec::new(),
+            content_hash: "0123456789abcdef".to_string(),
+            origin: DocumentOriginOutput::Stdin,
+            config: EffectiveConfig {
+                project_config_path: Some("/tmp/.mdviewer.toml".to_string()),
+                custom_css: Some("h1 { color: teal; }".to_string()),
+                ..EffectiveConfig::default()
+            },
         };
 
         let dto: MarkdownDocumentDto = app_output.into();
@@ -150,6 +975,35 @@ mod tests {
         assert_eq!(dto.toc[0].id, "mdv-spec");
         assert_eq!(dto.word_count, 320);
         assert_eq!(dto.reading_time_minutes, 2);
+        assert_eq!(
+            dto.statistics.section_word_counts.get("mdv-spec"),
+            Some(&320)
+        );
+        assert_eq!(dto.statistics.code_blocks, 3);
+        assert_eq!(dto.statistics.tasks.unchecked, 2);
+        assert_eq!(
+            dto.statistics
+                .readability
+                .map(|readability| readability.flesch_kincaid_grade),
+            Some(8.4)
+        );
+        assert_eq!(dto.tasks.len(), 1);
+        assert_eq!(dto.tasks[0].heading_id.as_deref(), Some("mdv-spec"));
+        assert_eq!(dto.tasks[0].line, 7);
+        assert_eq!(dto.content_hash, "0123456789abcdef");
+        assert_eq!(dto.origin, DocumentOriginDto::Stdin);
+        assert_eq!(dto.warnings.len(), 1);
+        assert_eq!(dto.warnings[0].kind, DocumentWarningKindDto::MissingImage);
+        assert_eq!(dto.warnings[0].line, Some(4));
+        assert_eq!(
+            dto.config.project_config_path.as_deref(),
+            Some("/tmp/.mdviewer.toml")
+        );
+        assert_eq!(
+            dto.config.custom_css.as_deref(),
+            Some("h1 { color: teal; }")
+        );
+        assert!(!dto.config.render.block_external_links);
     }
 
     #[test]
@@ -161,9 +1015,21 @@ mod tests {
                 include_code: false,
                 include_front_matter: true,
             }),
+            text_replacements: Vec::new(),
+            allow_raw_html: false,
+            html_allowlist: None,
+            image_embedding: ImageEmbeddingDto::default(),
+            csv_row_limit: None,
+            number_headings: None,
+            words_per_minute: Some(200),
+            han_characters_per_minute: None,
+            kana_characters_per_minute: None,
+            block_external_links: false,
+            extension_profile: ExtensionProfileDto::Github,
+            extensions: BTreeMap::new(),
         };
 
-        let direct = dto.to_application();
+        let direct = dto.clone().to_application();
         let helper = to_render_preferences(Some(dto));
 
         assert_eq!(direct.performance_mode, helper.performance_mode);
@@ -180,4 +1046,178 @@ mod tests {
             helper.word_count_rules.include_front_matter
         );
     }
+
+    #[test]
+    fn workspace_tasks_serialize_with_camel_case_fields() {
+        let dto: WorkspaceTasksDto = WorkspaceTasksOutput {
+            folder: "/notes".to_string(),
+            tasks: vec![WorkspaceTaskOutput {
+                path: "/notes/plan.md".to_string(),
+                document_title: "Plan".to_string(),
+                task: TaskListItemOutput {
+                    text: "Write docs".to_string(),
+                    checked: false,
+                    depth: 0,
+                    heading_id: None,
+                    line: 3,
+                },
+            }],
+            unreadable_files: vec!["/notes/broken.md".to_string()],
+        }
+        .into();
+
+        let json = serde_json::to_value(&dto).expect("dto should serialize");
+
+        assert_eq!(json["tasks"][0]["documentTitle"], "Plan");
+        assert_eq!(
+            json["tasks"][0]["task"]["headingId"],
+            serde_json::Value::Null
+        );
+        assert_eq!(json["tasks"][0]["task"]["line"], 3);
+        assert_eq!(json["unreadableFiles"][0], "/notes/broken.md");
+    }
+
+    #[test]
+    fn lint_report_serializes_with_camel_case_fields() {
+        let dto: LintReportDto = LintReportOutput {
+            path: "/docs/guide.md".to_string(),
+            diagnostics: vec![LintDiagnosticOutput {
+                rule_id: "MD009".to_string(),
+                rule_name: "no-trailing-spaces".to_string(),
+                severity: LintSeverityOutput::Error,
+                message: "Trailing spaces [Expected: 0 or 2; Actual: 1]".to_string(),
+                line: 4,
+                column: Some(12),
+            }],
+            config_path: None,
+        }
+        .into();
+
+        let json = serde_json::to_value(&dto).expect("dto should serialize");
+
+        assert_eq!(json["diagnostics"][0]["ruleId"], "MD009");
+        assert_eq!(json["diagnostics"][0]["ruleName"], "no-trailing-spaces");
+        assert_eq!(json["diagnostics"][0]["severity"], "error");
+        assert_eq!(json["diagnostics"][0]["column"], 12);
+        assert_eq!(json["configPath"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn spell_check_serializes_with_camel_case_fields() {
+        let dto: SpellCheckDto = SpellCheckOutput {
+            path: "/docs/guide.md".to_string(),
+            misspellings: vec![MisspellingOutput {
+                word: "teh".to_string(),
+                line: 2,
+                start_column: 5,
+                end_column: 8,
+                suggestions: vec!["the".to_string(), "tech".to_string()],
+            }],
+            word_list_path: Some("/docs/.mdviewer-words.txt".to_string()),
+        }
+        .into();
+
+        let json = serde_json::to_value(&dto).expect("dto should serialize");
+
+        assert_eq!(json["misspellings"][0]["startColumn"], 5);
+        assert_eq!(json["misspellings"][0]["endColumn"], 8);
+        assert_eq!(json["misspellings"][0]["suggestions"][1], "tech");
+        assert_eq!(json["wordListPath"], "/docs/.mdviewer-words.txt");
+    }
+
+    #[test]
+    fn document_diff_serializes_counts_without_local_images() {
+        let dto: DocumentDiffDto = DocumentDiffOutput {
+            path: "/docs/guide.md".to_string(),
+            html: "<div class=\"mdv-diff-inserted\"><p>new</p>\n</div>".to_string(),
+            inserted_blocks: 1,
+            deleted_blocks: 0,
+            modified_blocks: 2,
+            local_images: vec!["/docs/chart.png".into()],
+        }
+        .into();
+
+        let json = serde_json::to_value(&dto).expect("dto should serialize");
+
+        assert_eq!(json["insertedBlocks"], 1);
+        assert_eq!(json["deletedBlocks"], 0);
+        assert_eq!(json["modifiedBlocks"], 2);
+        assert!(json.get("localImages").is_none());
+    }
+
+    #[test]
+    fn file_history_serializes_with_camel_case_fields() {
+        let dto: FileHistoryDto = FileHistoryOutput {
+            path: "/repo/guide.md".to_string(),
+            revisions: vec![FileRevisionOutput {
+                commit_id: "0123456789abcdef0123456789abcdef01234567".to_string(),
+                author_name: "Ada Lovelace".to_string(),
+                author_email: "ada@example.com".to_string(),
+                authored_at: 1_700_000_000,
+                summary: "Rewrite the guide".to_string(),
+                message: "Rewrite the guide\n\nShorter sections.".to_string(),
+            }],
+        }
+        .into();
+
+        let json = serde_json::to_value(&dto).expect("dto should serialize");
+
+        assert_eq!(
+            json["revisions"][0]["commitId"],
+            "0123456789abcdef0123456789abcdef01234567"
+        );
+        assert_eq!(json["revisions"][0]["authorName"], "Ada Lovelace");
+        assert_eq!(json["revisions"][0]["authorEmail"], "ada@example.com");
+        assert_eq!(json["revisions"][0]["authoredAt"], 1_700_000_000);
+        assert_eq!(json["revisions"][0]["summary"], "Rewrite the guide");
+    }
+
+    #[test]
+    fn archive_listing_serializes_entry_paths() {
+        let dto: ArchiveListingDto = ArchiveListingOutput {
+            path: "/exports/docs.zip".to_string(),
+            entries: vec![ArchiveEntryOutput {
+                name: "guide/intro.md".to_string(),
+                path: "/exports/docs.zip!/guide/intro.md".to_string(),
+                size: 120,
+            }],
+        }
+        .into();
+
+        let json = serde_json::to_value(&dto).expect("dto should serialize");
+
+        assert_eq!(json["path"], "/exports/docs.zip");
+        assert_eq!(json["entries"][0]["name"], "guide/intro.md");
+        assert_eq!(
+            json["entries"][0]["path"],
+            "/exports/docs.zip!/guide/intro.md"
+        );
+        assert_eq!(json["entries"][0]["size"], 120);
+    }
+
+    #[test]
+    fn settings_round_trip_through_json() {
+        let mut settings = ViewerSettings {
+            theme: ThemePreset::ContrastLight,
+            measure_width: 64,
+            ..ViewerSettings::default()
+        };
+        settings.render.word_count_rules.include_code = true;
+        settings.render.csv_row_limit = Some(200);
+        settings.render.extension_profile = ExtensionProfileInput::CommonMark;
+        settings.render.extension_overrides =
+            [("math_dollars".to_string(), true)].into_iter().collect();
+
+        let json = serde_json::to_value(SettingsDto::from(settings.clone()))
+            .expect("dto should serialize");
+
+        assert_eq!(json["theme"], "contrast-light");
+        assert_eq!(json["measureWidth"], 64);
+        assert_eq!(json["render"]["wordCountRules"]["includeCode"], true);
+        assert_eq!(json["render"]["imageEmbedding"], "assetUrl");
+        assert_eq!(json["render"]["extensionProfile"], "commonmark");
+        assert_eq!(json["render"]["extensions"]["math_dollars"], true);
+        let dto: SettingsDto = serde_json::from_value(json).expect("dto should deserialize");
+        assert_eq!(ViewerSettings::from(dto), settings);
+    }
 }
@@ -1,25 +1,63 @@
 use std::sync::Arc;
 
 use markdown_viewer_application::input_ports::{
-    LoadMarkdownFileInputPort, OpenLinkedFileInputPort, WatchMarkdownFileInputPort,
+    CheckSpellingInputPort, DiffMarkdownFileInputPort, GetSettingsInputPort,
+    LintMarkdownFileInputPort, ListArchiveEntriesInputPort, ListFileHistoryInputPort,
+    ListWorkspaceTasksInputPort, LoadMarkdownFileInputPort, LoadMarkdownRevisionInputPort,
+    OpenLinkedFileInputPort, ToggleTaskItemInputPort, UpdateSettingsInputPort,
+    WatchMarkdownFileInputPort, WatchSettingsInputPort,
 };
 
 pub struct AppState {
     pub load_markdown_file: Arc<dyn LoadMarkdownFileInputPort>,
     pub watch_markdown_file: Arc<dyn WatchMarkdownFileInputPort>,
     pub open_linked_file: Arc<dyn OpenLinkedFileInputPort>,
+    pub list_workspace_tasks: Arc<dyn ListWorkspaceTasksInputPort>,
+    pub toggle_task_item: Arc<dyn ToggleTaskItemInputPort>,
+    pub lint_markdown_file: Arc<dyn LintMarkdownFileInputPort>,
+    pub check_spelling: Arc<dyn CheckSpellingInputPort>,
+    pub load_markdown_revision: Arc<dyn LoadMarkdownRevisionInputPort>,
+    pub list_file_history: Arc<dyn ListFileHistoryInputPort>,
+    pub diff_markdown_file: Arc<dyn DiffMarkdownFileInputPort>,
+    pub list_archive_entries: Arc<dyn ListArchiveEntriesInputPort>,
+    pub get_settings: Arc<dyn GetSettingsInputPort>,
+    pub update_settings: Arc<dyn UpdateSettingsInputPort>,
+    pub watch_settings: Arc<dyn WatchSettingsInputPort>,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         load_markdown_file: Arc<dyn LoadMarkdownFileInputPort>,
         watch_markdown_file: Arc<dyn WatchMarkdownFileInputPort>,
         open_linked_file: Arc<dyn OpenLinkedFileInputPort>,
+        list_workspace_tasks: Arc<dyn ListWorkspaceTasksInputPort>,
+        toggle_task_item: Arc<dyn ToggleTaskItemInputPort>,
+        lint_markdown_file: Arc<dyn LintMarkdownFileInputPort>,
+        check_spelling: Arc<dyn CheckSpellingInputPort>,
+        load_markdown_revision: Arc<dyn LoadMarkdownRevisionInputPort>,
+        list_file_history: Arc<dyn ListFileHistoryInputPort>,
+        diff_markdown_file: Arc<dyn DiffMarkdownFileInputPort>,
+        list_archive_entries: Arc<dyn ListArchiveEntriesInputPort>,
+        get_settings: Arc<dyn GetSettingsInputPort>,
+        update_settings: Arc<dyn UpdateSettingsInputPort>,
+        watch_settings: Arc<dyn WatchSettingsInputPort>,
     ) -> Self {
         Self {
             load_markdown_file,
             watch_markdown_file,
             open_linked_file,
+            list_workspace_tasks,
+            toggle_task_item,
+            lint_markdown_file,
+            check_spelling,
+            load_markdown_revision,
+            list_file_history,
+            diff_markdown_file,
+            list_archive_entries,
+            get_settings,
+            update_settings,
+            watch_settings,
         }
     }
 }
@@ -1,22 +1,49 @@
-use std::path::Path;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use markdown_viewer_application::error::MarkdownViewerError;
 use markdown_viewer_application::input_ports::{
-    LoadMarkdownFileInputPort, OpenLinkedFileInputPort, WatchMarkdownFileInputPort,
+    CheckSpellingInputPort, DiffMarkdownFileInputPort, GetSettingsInputPort,
+    LintMarkdownFileInputPort, ListArchiveEntriesInputPort, ListFileHistoryInputPort,
+    ListWorkspaceTasksInputPort, LoadMarkdownFileInputPort, LoadMarkdownRevisionInputPort,
+    OpenLinkedFileInputPort, ToggleTaskItemInputPort, UpdateSettingsInputPort,
+    WatchMarkdownFileInputPort, WatchSettingsInputPort,
 };
+use markdown_viewer_application::models::{DiffBaseInput, MarkdownDocumentOutput, ViewerSettings};
 use markdown_viewer_application::use_cases::{
-    LoadMarkdownFileUseCase, OpenLinkedFileUseCase, WatchMarkdownFileUseCase,
+    CheckSpellingUseCase, DiffMarkdownFileUseCase, GetSettingsUseCase, LintMarkdownFileUseCase,
+    ListArchiveEntriesUseCase, ListFileHistoryUseCase, ListWorkspaceTasksUseCase,
+    LoadMarkdownFileUseCase, LoadMarkdownRevisionUseCase, OpenLinkedFileUseCase,
+    ToggleTaskItemUseCase, UpdateSettingsUseCase, WatchMarkdownFileUseCase, WatchSettingsUseCase,
+};
+use markdown_viewer_infrastructure::archive_source::{
+    is_compressed_markdown_file, ArchiveDocumentSource,
 };
 use markdown_viewer_infrastructure::comrak_renderer::ComrakMarkdownRenderer;
+use markdown_viewer_infrastructure::document_sources::DocumentSources;
 use markdown_viewer_infrastructure::file_repository::{
-    is_markdown_file, resolve_path_input, LocalMarkdownFileRepository,
+    resolve_path_input, LocalMarkdownFileRepository, MarkdownFileTypes, TABLE_EXTENSIONS,
 };
 use markdown_viewer_infrastructure::file_watcher::MarkdownFileWatchService;
+use markdown_viewer_infrastructure::file_writer::LocalMarkdownFileWriter;
+use markdown_viewer_infrastructure::git_repository::GitMarkdownFileRepository;
+use markdown_viewer_infrastructure::http_source::{is_remote_markdown_url, HttpDocumentSource};
 use markdown_viewer_infrastructure::linked_file_opener::{
     DetachedLinkedFileOpener, StdPathCanonicalizer,
 };
-use markdown_viewer_presentation::dto::{MarkdownDocumentDto, RenderPreferencesDto};
+use markdown_viewer_infrastructure::markdown_diff::ComrakMarkdownDiffer;
+use markdown_viewer_infrastructure::markdown_lint::ComrakMarkdownLinter;
+use markdown_viewer_infrastructure::project_config::TomlProjectConfigResolver;
+use markdown_viewer_infrastructure::settings_store::{TomlSettingsStore, SETTINGS_FILE_NAME};
+use markdown_viewer_infrastructure::spell_checker::HunspellSpellChecker;
+use markdown_viewer_infrastructure::stdin_source::{StdinDocumentSource, STDIN_PATH_INPUT};
+use markdown_viewer_infrastructure::wasm_plugin_host::WasmPluginHost;
+use markdown_viewer_infrastructure::workspace_scanner::LocalWorkspaceScanner;
+use markdown_viewer_presentation::dto::{
+    ArchiveListingDto, DocumentDiffDto, FileHistoryDto, LintReportDto, MarkdownDocumentDto,
+    RenderPreferencesDto, SettingsDto, SpellCheckDto, ToggleTaskItemDto, WorkspaceTasksDto,
+};
 use markdown_viewer_presentation::state::AppState;
 use serde::Serialize;
 use tauri::Emitter;
@@ -24,6 +51,11 @@ use tauri::{AppHandle, Manager, State};
 
 const MARKDOWN_FILE_UPDATED_EVENT: &str = "markdown://file-updated";
 const MARKDOWN_OPEN_PATH_EVENT: &str = "markdown://open-path";
+const SETTINGS_UPDATED_EVENT: &str = "settings://updated";
+const RENDER_PLUGINS_DIRECTORY: &str = "plugins";
+const REMOTE_DOCUMENT_CACHE_DIRECTORY: &str = "remote-documents";
+/// Identifies the bundle file association whose extensions open as markdown.
+const MARKDOWN_MIME_TYPE: &str = "text/markdown";
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -57,21 +89,203 @@ impl LaunchOpenPathState {
 }
 
 #[tauri::command]
-fn pick_markdown_file() -> Option<String> {
+fn pick_markdown_file(file_types: State<'_, MarkdownFileTypes>) -> Option<String> {
     rfd::FileDialog::new()
         .set_title("Open Markdown File")
-        .add_filter("Markdown", &["md", "markdown", "mdown", "mkd", "mkdn"])
+        .add_filter("Markdown", file_types.extensions())
+        .add_filter("Tables", TABLE_EXTENSIONS)
+        // Extensionless files such as `README` open when their contents look like markdown.
+        .add_filter("All Files", &["*"])
         .pick_file()
         .map(|path| path.to_string_lossy().into_owned())
 }
 
+/// Whether `load_markdown_file` opens `path` without `as_markdown`, so the webview can route
+/// links and dropped files by the same rules as the open dialog and launch arguments.
 #[tauri::command]
-fn load_markdown_file(
+async fn is_viewable_document(app_handle: AppHandle, path: String) -> Result<bool, String> {
+    run_blocking(app_handle, move |app_handle, _| {
+        let file_types = app_handle.state::<MarkdownFileTypes>();
+        Ok(markdown_path_from_arg(&path, None, &file_types).is_some())
+    })
+    .await
+}
+
+/// `as_markdown` opens files that are not recognised as markdown anyway, instead of failing.
+/// Without `preferences` the document renders with those kept in `settings.toml`.
+#[tauri::command]
+async fn load_markdown_file(
+    app_handle: AppHandle,
     path: String,
     preferences: Option<RenderPreferencesDto>,
-    state: State<'_, AppState>,
+    as_markdown: Option<bool>,
 ) -> Result<MarkdownDocumentDto, String> {
-    load_markdown_file_inner(&path, preferences, state.inner())
+    run_blocking(app_handle, move |app_handle, state| {
+        let asset_scope = app_handle.asset_protocol_scope();
+        let as_markdown = as_markdown.unwrap_or(false);
+        load_markdown_file_inner(&path, preferences, as_markdown, state, |image| {
+            if let Err(error) = asset_scope.allow_file(image) {
+                log::warn!("failed to allow image {}: {error}", image.display());
+            }
+        })
+    })
+    .await
+}
+
+/// Renders the document as committed at `revision` (a commit ID, branch or tag) of the local git
+/// repository that contains it.
+#[tauri::command]
+async fn load_markdown_revision(
+    app_handle: AppHandle,
+    path: String,
+    revision: String,
+    preferences: Option<RenderPreferencesDto>,
+) -> Result<MarkdownDocumentDto, String> {
+    run_blocking(app_handle, move |app_handle, state| {
+        let asset_scope = app_handle.asset_protocol_scope();
+        let doc = state
+            .load_markdown_revision
+            .execute(
+                &path,
+                &revision,
+                markdown_viewer_presentation::dto::to_render_preferences(preferences),
+            )
+            .map_err(to_user_error)?;
+        Ok(allow_document_images(doc, |image| {
+            if let Err(error) = asset_scope.allow_file(image) {
+                log::warn!("failed to allow image {}: {error}", image.display());
+            }
+        }))
+    })
+    .await
+}
+
+/// Renders the current file with the changes since `previous_source`, such as the version shown
+/// before the file reloaded, marked.
+#[tauri::command]
+async fn diff_markdown_file(
+    app_handle: AppHandle,
+    path: String,
+    previous_source: String,
+    preferences: Option<RenderPreferencesDto>,
+) -> Result<DocumentDiffDto, String> {
+    run_blocking(app_handle, move |app_handle, state| {
+        diff_markdown_inner(
+            app_handle,
+            &path,
+            DiffBaseInput::Source(previous_source),
+            preferences,
+            state,
+        )
+    })
+    .await
+}
+
+/// Renders the current file with the changes since it was committed at `revision` marked.
+#[tauri::command]
+async fn diff_markdown_revision(
+    app_handle: AppHandle,
+    path: String,
+    revision: String,
+    preferences: Option<RenderPreferencesDto>,
+) -> Result<DocumentDiffDto, String> {
+    run_blocking(app_handle, move |app_handle, state| {
+        diff_markdown_inner(
+            app_handle,
+            &path,
+            DiffBaseInput::Revision(revision),
+            preferences,
+            state,
+        )
+    })
+    .await
+}
+
+fn diff_markdown_inner(
+    app_handle: &AppHandle,
+    path: &str,
+    base: DiffBaseInput,
+    preferences: Option<RenderPreferencesDto>,
+    state: &AppState,
+) -> Result<DocumentDiffDto, String> {
+    let diff = state
+        .diff_markdown_file
+        .execute(
+            path,
+            base,
+            markdown_viewer_presentation::dto::to_render_preferences(preferences),
+        )
+        .map_err(to_user_error)?;
+    let asset_scope = app_handle.asset_protocol_scope();
+    for image in &diff.local_images {
+        if let Err(error) = asset_scope.allow_file(image) {
+            log::warn!("failed to allow image {}: {error}", image.display());
+        }
+    }
+    Ok(diff.into())
+}
+
+/// Async because walking a long history reads many commits.
+#[tauri::command]
+async fn list_file_history(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<FileHistoryDto, String> {
+    state
+        .list_file_history
+        .execute(&path)
+        .map(Into::into)
+        .map_err(to_user_error)
+}
+
+#[tauri::command]
+async fn list_archive_entries(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<ArchiveListingDto, String> {
+    state
+        .list_archive_entries
+        .execute(&path)
+        .map(Into::into)
+        .map_err(to_user_error)
+}
+
+#[tauri::command]
+fn get_settings(state: State<'_, AppState>) -> Result<SettingsDto, String> {
+    state
+        .get_settings
+        .execute()
+        .map(Into::into)
+        .map_err(to_user_error)
+}
+
+#[tauri::command]
+fn update_settings(
+    settings: SettingsDto,
+    state: State<'_, AppState>,
+) -> Result<SettingsDto, String> {
+    state
+        .update_settings
+        .execute(settings.into())
+        .map(Into::into)
+        .map_err(to_user_error)
+}
+
+/// Tells every window when the settings file changes, whether edited by hand or saved by
+/// another window, so they all apply the same settings.
+fn start_settings_watch(app_handle: &AppHandle, state: &AppState) {
+    let app_for_emit = app_handle.clone();
+    let started = state.watch_settings.start(Arc::new(
+        move |result: Result<ViewerSettings, MarkdownViewerError>| match result {
+            Ok(settings) => {
+                let _ = app_for_emit.emit(SETTINGS_UPDATED_EVENT, SettingsDto::from(settings));
+            }
+            Err(error) => log::warn!("failed to reload settings: {error}"),
+        },
+    ));
+    if let Err(error) = started {
+        log::warn!("settings will not reload when edited: {error}");
+    }
 }
 
 #[tauri::command]
@@ -108,24 +322,111 @@ fn open_linked_file(
         .map_err(to_user_error)
 }
 
+#[tauri::command]
+async fn toggle_task_item(
+    app_handle: AppHandle,
+    path: String,
+    line: usize,
+    expected_content_hash: String,
+) -> Result<ToggleTaskItemDto, String> {
+    run_blocking(app_handle, move |_, state| {
+        state
+            .toggle_task_item
+            .execute(&path, line, &expected_content_hash)
+            .map(Into::into)
+            .map_err(to_user_error)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn lint_markdown_file(app_handle: AppHandle, path: String) -> Result<LintReportDto, String> {
+    run_blocking(app_handle, move |_, state| {
+        state
+            .lint_markdown_file
+            .execute(&path)
+            .map(Into::into)
+            .map_err(to_user_error)
+    })
+    .await
+}
+
+/// Async because computing suggestions, and loading the dictionary on first use, can take a
+/// noticeable moment.
+#[tauri::command]
+async fn check_spelling(path: String, state: State<'_, AppState>) -> Result<SpellCheckDto, String> {
+    state
+        .check_spelling
+        .execute(&path)
+        .map(Into::into)
+        .map_err(to_user_error)
+}
+
+/// Async so that rendering every file in a large folder runs off the main thread.
+#[tauri::command]
+async fn list_workspace_tasks(
+    folder: String,
+    preferences: Option<RenderPreferencesDto>,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceTasksDto, String> {
+    state
+        .list_workspace_tasks
+        .execute(
+            &folder,
+            markdown_viewer_presentation::dto::to_render_preferences(preferences),
+        )
+        .map(Into::into)
+        .map_err(to_user_error)
+}
+
 #[tauri::command]
 fn consume_launch_open_path(state: State<'_, LaunchOpenPathState>) -> Option<String> {
     state.take()
 }
 
+/// Loads and renders a document; `allow_local_image` is called for every local image the HTML
+/// embeds so the webview is only granted the files the document actually shows.
+/// Runs a command on the blocking thread pool. Documents go through [`DocumentSources`], which
+/// may fetch a remote file, so reading one must never hold up the main thread or an async worker.
+async fn run_blocking<T: Send + 'static>(
+    app_handle: AppHandle,
+    command: impl FnOnce(&AppHandle, &AppState) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        command(&app_handle, state.inner())
+    })
+    .await
+    .map_err(|error| format!("command did not finish: {error}"))?
+}
+
 fn load_markdown_file_inner(
     path: &str,
     preferences: Option<RenderPreferencesDto>,
+    as_markdown: bool,
     state: &AppState,
+    allow_local_image: impl Fn(&Path),
 ) -> Result<MarkdownDocumentDto, String> {
-    let doc = state
-        .load_markdown_file
-        .execute(
-            path,
-            markdown_viewer_presentation::dto::to_render_preferences(preferences),
-        )
-        .map_err(to_user_error)?;
-    Ok(doc.into())
+    let preferences = preferences.map(RenderPreferencesDto::to_application);
+    let doc = if as_markdown {
+        state
+            .load_markdown_file
+            .execute_as_markdown(path, preferences)
+    } else {
+        state.load_markdown_file.execute(path, preferences)
+    }
+    .map_err(to_user_error)?;
+    Ok(allow_document_images(doc, allow_local_image))
+}
+
+fn allow_document_images(
+    doc: MarkdownDocumentOutput,
+    allow_local_image: impl Fn(&Path),
+) -> MarkdownDocumentDto {
+    for image in &doc.local_images {
+        allow_local_image(image);
+    }
+    doc.into()
 }
 
 fn start_markdown_watch_inner<F>(
@@ -158,17 +459,50 @@ where
     })
 }
 
-fn first_markdown_path_from_args(args: &[String], cwd: Option<&Path>) -> Option<String> {
+fn first_markdown_path_from_args(
+    args: &[String],
+    cwd: Option<&Path>,
+    file_types: &MarkdownFileTypes,
+) -> Option<String> {
     for arg in args.iter().skip(1) {
-        if let Some(path) = markdown_path_from_arg(arg, cwd) {
+        if let Some(path) = markdown_path_from_arg(arg, cwd, file_types) {
             return Some(path);
         }
     }
     None
 }
 
+/// Reads the markdown piped into the viewer when it was started with `-`. Standard input is left
+/// alone when it is a terminal, so `-` never blocks waiting for typed input.
+fn read_piped_stdin(args: &[String], cwd: Option<&Path>) -> Option<StdinDocumentSource> {
+    if !args
+        .iter()
+        .skip(1)
+        .any(|arg| arg.trim() == STDIN_PATH_INPUT)
+    {
+        return None;
+    }
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        log::warn!("`{STDIN_PATH_INPUT}` was given but standard input is not piped");
+        return None;
+    }
+
+    let working_directory = cwd.unwrap_or(Path::new("."));
+    match StdinDocumentSource::read_from(stdin.lock(), working_directory) {
+        Ok(source) => Some(source),
+        Err(error) => {
+            log::warn!("failed to read standard input: {error}");
+            None
+        }
+    }
+}
+
 #[cfg(any(target_os = "macos", target_os = "ios", test))]
-fn first_markdown_path_from_urls(urls: &[tauri::Url]) -> Option<String> {
+fn first_markdown_path_from_urls(
+    urls: &[tauri::Url],
+    file_types: &MarkdownFileTypes,
+) -> Option<String> {
     for url in urls {
         if url.scheme() != "file" {
             continue;
@@ -178,21 +512,28 @@ fn first_markdown_path_from_urls(urls: &[tauri::Url]) -> Option<String> {
             continue;
         };
         let path_input = path.to_string_lossy();
-        if let Some(resolved) = markdown_path_from_arg(path_input.as_ref(), None) {
+        if let Some(resolved) = markdown_path_from_arg(path_input.as_ref(), None, file_types) {
             return Some(resolved);
         }
     }
     None
 }
 
-fn markdown_path_from_arg(arg: &str, cwd: Option<&Path>) -> Option<String> {
+fn markdown_path_from_arg(
+    arg: &str,
+    cwd: Option<&Path>,
+    file_types: &MarkdownFileTypes,
+) -> Option<String> {
     let trimmed = arg.trim();
     if trimmed.is_empty() || trimmed.starts_with('-') {
         return None;
     }
+    if is_remote_markdown_url(trimmed) {
+        return Some(trimmed.to_string());
+    }
 
     if let Ok(path) = resolve_path_input(trimmed) {
-        if is_markdown_file(&path) {
+        if file_types.accepts(&path) || is_compressed_markdown_file(&path) {
             return Some(path.to_string_lossy().into_owned());
         }
     }
@@ -201,7 +542,7 @@ fn markdown_path_from_arg(arg: &str, cwd: Option<&Path>) -> Option<String> {
     let joined = cwd.join(trimmed);
     let joined_string = joined.to_string_lossy().into_owned();
     if let Ok(path) = resolve_path_input(&joined_string) {
-        if is_markdown_file(&path) {
+        if file_types.accepts(&path) || is_compressed_markdown_file(&path) {
             return Some(path.to_string_lossy().into_owned());
         }
     }
@@ -217,28 +558,143 @@ fn to_user_error(error: MarkdownViewerError) -> String {
     error.to_string()
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    let startup_args: Vec<String> = std::env::args().collect();
-    let startup_cwd = std::env::current_dir().ok();
-    let startup_open_path = first_markdown_path_from_args(&startup_args, startup_cwd.as_deref());
+fn build_renderer(plugins_directory: Option<&Path>) -> ComrakMarkdownRenderer {
+    let mut transforms = ComrakMarkdownRenderer::default_transforms();
+    if let Some(plugins_directory) = plugins_directory {
+        match WasmPluginHost::load_from_directory(plugins_directory) {
+            Ok(plugin_host) => {
+                for failure in plugin_host.load_failures() {
+                    log::warn!("{failure}");
+                }
+                transforms.push(Box::new(plugin_host));
+            }
+            Err(error) => log::warn!("{error}"),
+        }
+    }
+    ComrakMarkdownRenderer::with_transforms(transforms)
+}
+
+/// The extensions of the bundle's markdown file association in `tauri.conf.json`, so the open
+/// dialog, launch arguments and loading accept the files the OS hands to the app.
+fn markdown_file_types(config: &tauri::Config) -> MarkdownFileTypes {
+    let extensions: Vec<String> = config
+        .bundle
+        .file_associations
+        .iter()
+        .flatten()
+        .filter(|association| association.mime_type.as_deref() == Some(MARKDOWN_MIME_TYPE))
+        .flat_map(|association| association.ext.iter().map(|extension| extension.0.clone()))
+        .collect();
+    if extensions.is_empty() {
+        MarkdownFileTypes::default()
+    } else {
+        MarkdownFileTypes::new(extensions)
+    }
+}
 
-    let repository = Arc::new(LocalMarkdownFileRepository::new());
-    let renderer = Arc::new(ComrakMarkdownRenderer::new());
+fn build_app_state(
+    plugins_directory: Option<PathBuf>,
+    remote_cache_directory: PathBuf,
+    stdin_source: Option<StdinDocumentSource>,
+    file_types: MarkdownFileTypes,
+    settings_path: PathBuf,
+) -> AppState {
+    let archive_source = Arc::new(ArchiveDocumentSource::new());
+    let files = LocalMarkdownFileRepository::with_file_types(file_types);
+    let mut sources = DocumentSources::new(Arc::new(files))
+        .with_source(Arc::new(HttpDocumentSource::new(remote_cache_directory)))
+        .with_source(archive_source.clone());
+    if let Some(stdin_source) = stdin_source {
+        sources = sources.with_source(Arc::new(stdin_source));
+    }
+    let repository = Arc::new(sources);
+    let renderer = Arc::new(build_renderer(plugins_directory.as_deref()));
     let watch_service = Arc::new(MarkdownFileWatchService::new());
     let path_canonicalizer = Arc::new(StdPathCanonicalizer::new());
     let linked_file_opener = Arc::new(DetachedLinkedFileOpener::new());
-    let load_use_case: Arc<dyn LoadMarkdownFileInputPort> =
-        Arc::new(LoadMarkdownFileUseCase::new(repository, renderer));
+    let workspace_scanner = Arc::new(LocalWorkspaceScanner::new());
+    let file_writer = Arc::new(LocalMarkdownFileWriter::new());
+    let settings_store = Arc::new(TomlSettingsStore::new(settings_path));
+    let load_use_case: Arc<dyn LoadMarkdownFileInputPort> = Arc::new(
+        LoadMarkdownFileUseCase::new(repository.clone(), renderer.clone())
+            .with_project_config(Arc::new(TomlProjectConfigResolver::new()))
+            .with_settings(settings_store.clone()),
+    );
     let watch_use_case: Arc<dyn WatchMarkdownFileInputPort> =
         Arc::new(WatchMarkdownFileUseCase::new(watch_service));
     let open_linked_file_use_case: Arc<dyn OpenLinkedFileInputPort> = Arc::new(
         OpenLinkedFileUseCase::new(path_canonicalizer, linked_file_opener),
     );
+    let toggle_task_item_use_case: Arc<dyn ToggleTaskItemInputPort> = Arc::new(
+        ToggleTaskItemUseCase::new(repository.clone(), renderer.clone(), file_writer),
+    );
+    let lint_use_case: Arc<dyn LintMarkdownFileInputPort> = Arc::new(LintMarkdownFileUseCase::new(
+        repository.clone(),
+        Arc::new(ComrakMarkdownLinter::new()),
+    ));
+    let check_spelling_use_case: Arc<dyn CheckSpellingInputPort> = Arc::new(
+        CheckSpellingUseCase::new(repository.clone(), Arc::new(HunspellSpellChecker::new())),
+    );
+    let git_repository = Arc::new(GitMarkdownFileRepository::new());
+    let load_revision_use_case: Arc<dyn LoadMarkdownRevisionInputPort> = Arc::new(
+        LoadMarkdownRevisionUseCase::new(git_repository.clone(), renderer.clone()),
+    );
+    let list_file_history_use_case: Arc<dyn ListFileHistoryInputPort> =
+        Arc::new(ListFileHistoryUseCase::new(git_repository.clone()));
+    let diff_use_case: Arc<dyn DiffMarkdownFileInputPort> = Arc::new(DiffMarkdownFileUseCase::new(
+        repository.clone(),
+        git_repository,
+        Arc::new(ComrakMarkdownDiffer::new()),
+    ));
+    let list_workspace_tasks_use_case: Arc<dyn ListWorkspaceTasksInputPort> = Arc::new(
+        ListWorkspaceTasksUseCase::new(workspace_scanner, repository, renderer),
+    );
+    let list_archive_entries_use_case: Arc<dyn ListArchiveEntriesInputPort> =
+        Arc::new(ListArchiveEntriesUseCase::new(archive_source));
+    let get_settings_use_case: Arc<dyn GetSettingsInputPort> =
+        Arc::new(GetSettingsUseCase::new(settings_store.clone()));
+    let update_settings_use_case: Arc<dyn UpdateSettingsInputPort> =
+        Arc::new(UpdateSettingsUseCase::new(settings_store.clone()));
+    let watch_settings_use_case: Arc<dyn WatchSettingsInputPort> = Arc::new(
+        WatchSettingsUseCase::new(settings_store, Arc::new(MarkdownFileWatchService::new())),
+    );
+
+    AppState::new(
+        load_use_case,
+        watch_use_case,
+        open_linked_file_use_case,
+        list_workspace_tasks_use_case,
+        toggle_task_item_use_case,
+        lint_use_case,
+        check_spelling_use_case,
+        load_revision_use_case,
+        list_file_history_use_case,
+        diff_use_case,
+        list_archive_entries_use_case,
+        get_settings_use_case,
+        update_settings_use_case,
+        watch_settings_use_case,
+    )
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    let context = tauri::generate_context!();
+    let file_types = markdown_file_types(context.config());
+    let startup_args: Vec<String> = std::env::args().collect();
+    let startup_cwd = std::env::current_dir().ok();
+    let startup_stdin = read_piped_stdin(&startup_args, startup_cwd.as_deref());
+    let startup_open_path = match &startup_stdin {
+        Some(stdin_source) => Some(stdin_source.path().to_string_lossy().into_owned()),
+        None => first_markdown_path_from_args(&startup_args, startup_cwd.as_deref(), &file_types),
+    };
 
     let app = tauri::Builder::default()
         .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
-            if let Some(path) = first_markdown_path_from_args(&args, Some(Path::new(&cwd))) {
+            let file_types = app.state::<MarkdownFileTypes>();
+            if let Some(path) =
+                first_markdown_path_from_args(&args, Some(Path::new(&cwd)), &file_types)
+            {
                 emit_open_path_event(app, path);
             }
             if let Some(window) = app.get_webview_window("main") {
@@ -247,7 +703,7 @@ pub fn run() {
         }))
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
-        .setup(|app| {
+        .setup(move |app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
                     tauri_plugin_log::Builder::default()
@@ -255,29 +711,61 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            // The plugins directory and settings file live under the app config dir, which is only
+            // known once the app is built, so application state is assembled here rather than up
+            // front.
+            let config_dir = app.path().app_config_dir().ok();
+            let plugins_directory = config_dir
+                .as_ref()
+                .map(|config_dir| config_dir.join(RENDER_PLUGINS_DIRECTORY));
+            let settings_path = config_dir
+                .unwrap_or_else(|| std::env::temp_dir().join("markdown-viewer"))
+                .join(SETTINGS_FILE_NAME);
+            let remote_cache_directory = app
+                .path()
+                .app_cache_dir()
+                .unwrap_or_else(|_| std::env::temp_dir().join("markdown-viewer"))
+                .join(REMOTE_DOCUMENT_CACHE_DIRECTORY);
+            app.manage(build_app_state(
+                plugins_directory,
+                remote_cache_directory,
+                startup_stdin,
+                app.state::<MarkdownFileTypes>().inner().clone(),
+                settings_path,
+            ));
+            start_settings_watch(app.handle(), &app.state::<AppState>());
             Ok(())
         })
         .manage(LaunchOpenPathState::new(startup_open_path))
-        .manage(AppState::new(
-            load_use_case,
-            watch_use_case,
-            open_linked_file_use_case,
-        ))
+        .manage(file_types)
         .invoke_handler(tauri::generate_handler![
             pick_markdown_file,
+            is_viewable_document,
             load_markdown_file,
             start_markdown_watch,
             stop_markdown_watch,
             open_linked_file,
+            list_workspace_tasks,
+            toggle_task_item,
+            lint_markdown_file,
+            check_spelling,
+            load_markdown_revision,
+            list_file_history,
+            list_archive_entries,
+            get_settings,
+            update_settings,
+            diff_markdown_file,
+            diff_markdown_revision,
             consume_launch_open_path
         ])
-        .build(tauri::generate_context!())
+        .build(context)
         .expect("error while building tauri application");
 
     app.run(|_app_handle, _event| {
         #[cfg(any(target_os = "macos", target_os = "ios"))]
         if let tauri::RunEvent::Opened { urls } = _event {
-            if let Some(path) = first_markdown_path_from_urls(&urls) {
+            let file_types = _app_handle.state::<MarkdownFileTypes>();
+            if let Some(path) = first_markdown_path_from_urls(&urls, &file_types) {
                 emit_open_path_event(_app_handle, path);
             }
         }
@@ -291,20 +779,39 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use markdown_viewer_application::input_ports::OpenLinkedFileInputPort;
+    use markdown_viewer_application::input_ports::{
+        CheckSpellingInputPort, DiffMarkdownFileInputPort, LintMarkdownFileInputPort,
+        ListArchiveEntriesInputPort, ListFileHistoryInputPort, ListWorkspaceTasksInputPort,
+        LoadMarkdownRevisionInputPort, OpenLinkedFileInputPort, ToggleTaskItemInputPort,
+    };
     use markdown_viewer_application::ports::MarkdownWatchService;
     use markdown_viewer_application::use_cases::{
-        LoadMarkdownFileUseCase, OpenLinkedFileUseCase, WatchMarkdownFileUseCase,
+        CheckSpellingUseCase, DiffMarkdownFileUseCase, GetSettingsUseCase, LintMarkdownFileUseCase,
+        ListArchiveEntriesUseCase, ListFileHistoryUseCase, ListWorkspaceTasksUseCase,
+        LoadMarkdownFileUseCase, LoadMarkdownRevisionUseCase, OpenLinkedFileUseCase,
+        ToggleTaskItemUseCase, UpdateSettingsUseCase, WatchMarkdownFileUseCase,
+        WatchSettingsUseCase,
     };
+    use markdown_viewer_infrastructure::archive_source::ArchiveDocumentSource;
+    use markdown_viewer_infrastructure::file_writer::LocalMarkdownFileWriter;
+    use markdown_viewer_infrastructure::git_repository::GitMarkdownFileRepository;
     use markdown_viewer_infrastructure::linked_file_opener::{
         DetachedLinkedFileOpener, StdPathCanonicalizer,
     };
+    use markdown_viewer_infrastructure::markdown_diff::ComrakMarkdownDiffer;
+    use markdown_viewer_infrastructure::markdown_lint::ComrakMarkdownLinter;
+    use markdown_viewer_infrastructure::settings_store::{
+        TomlSettingsStore, APP_IDENTIFIER, SETTINGS_FILE_NAME,
+    };
+    use markdown_viewer_infrastructure::spell_checker::HunspellSpellChecker;
+    use markdown_viewer_infrastructure::workspace_scanner::LocalWorkspaceScanner;
 
     use super::{
         first_markdown_path_from_args, first_markdown_path_from_urls, load_markdown_file_inner,
-        markdown_path_from_arg, start_markdown_watch_inner, stop_markdown_watch_inner, AppState,
-        ComrakMarkdownRenderer, LocalMarkdownFileRepository, MarkdownFileUpdatedEvent,
-        MarkdownViewerError, RenderPreferencesDto, MARKDOWN_FILE_UPDATED_EVENT,
+        markdown_file_types, markdown_path_from_arg, start_markdown_watch_inner,
+        stop_markdown_watch_inner, AppState, ComrakMarkdownRenderer, LocalMarkdownFileRepository,
+        MarkdownFileTypes, MarkdownFileUpdatedEvent, MarkdownViewerError, RenderPreferencesDto,
+        MARKDOWN_FILE_UPDATED_EVENT,
     };
 
     struct TestWatchService {
@@ -356,12 +863,71 @@ mod tests {
         let watch_service = Arc::new(TestWatchService::new(false));
         let path_canonicalizer = Arc::new(StdPathCanonicalizer::new());
         let linked_file_opener = Arc::new(DetachedLinkedFileOpener::new());
-        let load_use_case = Arc::new(LoadMarkdownFileUseCase::new(repository, renderer));
+        let load_use_case = Arc::new(LoadMarkdownFileUseCase::new(
+            repository.clone(),
+            renderer.clone(),
+        ));
         let watch_use_case = Arc::new(WatchMarkdownFileUseCase::new(watch_service));
         let open_linked_file_use_case: Arc<dyn OpenLinkedFileInputPort> = Arc::new(
             OpenLinkedFileUseCase::new(path_canonicalizer, linked_file_opener),
         );
-        AppState::new(load_use_case, watch_use_case, open_linked_file_use_case)
+        let toggle_task_item_use_case: Arc<dyn ToggleTaskItemInputPort> =
+            Arc::new(ToggleTaskItemUseCase::new(
+                repository.clone(),
+                renderer.clone(),
+                Arc::new(LocalMarkdownFileWriter::new()),
+            ));
+        let lint_use_case: Arc<dyn LintMarkdownFileInputPort> = Arc::new(
+            LintMarkdownFileUseCase::new(repository.clone(), Arc::new(ComrakMarkdownLinter::new())),
+        );
+        let check_spelling_use_case: Arc<dyn CheckSpellingInputPort> = Arc::new(
+            CheckSpellingUseCase::new(repository.clone(), Arc::new(HunspellSpellChecker::new())),
+        );
+        let git_repository = Arc::new(GitMarkdownFileRepository::new());
+        let load_revision_use_case: Arc<dyn LoadMarkdownRevisionInputPort> = Arc::new(
+            LoadMarkdownRevisionUseCase::new(git_repository.clone(), renderer.clone()),
+        );
+        let list_file_history_use_case: Arc<dyn ListFileHistoryInputPort> =
+            Arc::new(ListFileHistoryUseCase::new(git_repository.clone()));
+        let diff_use_case: Arc<dyn DiffMarkdownFileInputPort> =
+            Arc::new(DiffMarkdownFileUseCase::new(
+                repository.clone(),
+                git_repository,
+                Arc::new(ComrakMarkdownDiffer::new()),
+            ));
+        let list_workspace_tasks_use_case: Arc<dyn ListWorkspaceTasksInputPort> =
+            Arc::new(ListWorkspaceTasksUseCase::new(
+                Arc::new(LocalWorkspaceScanner::new()),
+                repository,
+                renderer,
+            ));
+        let list_archive_entries_use_case: Arc<dyn ListArchiveEntriesInputPort> = Arc::new(
+            ListArchiveEntriesUseCase::new(Arc::new(ArchiveDocumentSource::new())),
+        );
+        let settings_store = Arc::new(TomlSettingsStore::new(
+            std::env::temp_dir()
+                .join("mdv-command-wiring-settings")
+                .join(SETTINGS_FILE_NAME),
+        ));
+        AppState::new(
+            load_use_case,
+            watch_use_case,
+            open_linked_file_use_case,
+            list_workspace_tasks_use_case,
+            toggle_task_item_use_case,
+            lint_use_case,
+            check_spelling_use_case,
+            load_revision_use_case,
+            list_file_history_use_case,
+            diff_use_case,
+            list_archive_entries_use_case,
+            Arc::new(GetSettingsUseCase::new(settings_store.clone())),
+            Arc::new(UpdateSettingsUseCase::new(settings_store.clone())),
+            Arc::new(WatchSettingsUseCase::new(
+                settings_store,
+                Arc::new(TestWatchService::new(false)),
+            )),
+        )
     }
 
     fn write_temp_markdown(contents: &str) -> PathBuf {
@@ -395,8 +961,22 @@ mod tests {
             Some(RenderPreferencesDto {
                 performance_mode: true,
                 word_count_rules: None,
+                text_replacements: Vec::new(),
+                allow_raw_html: false,
+                html_allowlist: None,
+                image_embedding: Default::default(),
+                csv_row_limit: None,
+                number_headings: None,
+                words_per_minute: None,
+                han_characters_per_minute: None,
+                kana_characters_per_minute: None,
+                block_external_links: false,
+                extension_profile: Default::default(),
+                extensions: Default::default(),
             }),
+            false,
             &state,
+            |_| {},
         )
         .expect("load should succeed");
 
@@ -408,6 +988,38 @@ mod tests {
         let _ = std::fs::remove_file(path);
     }
 
+    #[test]
+    fn load_markdown_file_inner_allows_only_resolved_local_images() {
+        let state = make_state_for_load();
+        let image = write_temp_file("png", "not really a png");
+        let image_name = image
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("fixture should have a file name")
+            .to_string();
+        let path = write_temp_markdown(&format!("![shown]({image_name})\n\n![gone](missing.png)"));
+        let path_input = path.to_string_lossy().into_owned();
+        let allowed = Mutex::new(Vec::new());
+
+        let result = load_markdown_file_inner(&path_input, None, false, &state, |image| {
+            allowed
+                .lock()
+                .expect("allowed images should be lockable")
+                .push(image.to_path_buf());
+        })
+        .expect("load should succeed");
+
+        let allowed = allowed
+            .into_inner()
+            .expect("allowed images should be lockable");
+        assert_eq!(allowed.len(), 1);
+        assert!(allowed[0].ends_with(&image_name));
+        assert_eq!(result.warnings.len(), 1);
+
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(image);
+    }
+
     #[test]
     fn start_markdown_watch_inner_emits_event_with_expected_payload_shape() {
         let watch_service = Arc::new(TestWatchService::new(false));
@@ -485,8 +1097,9 @@ mod tests {
             .to_string_lossy()
             .into_owned();
 
-        let resolved = markdown_path_from_arg(&file_name, Some(parent))
-            .expect("relative markdown arg should resolve");
+        let resolved =
+            markdown_path_from_arg(&file_name, Some(parent), &MarkdownFileTypes::default())
+                .expect("relative markdown arg should resolve");
         assert_eq!(
             resolved,
             markdown
@@ -499,6 +1112,23 @@ mod tests {
         let _ = std::fs::remove_file(markdown);
     }
 
+    #[test]
+    fn markdown_path_from_arg_accepts_remote_markdown_urls() {
+        assert_eq!(
+            markdown_path_from_arg(
+                "https://example.com/docs/README.md",
+                None,
+                &MarkdownFileTypes::default()
+            )
+            .as_deref(),
+            Some("https://example.com/docs/README.md")
+        );
+        assert_eq!(
+            markdown_path_from_arg("https://example.com/", None, &MarkdownFileTypes::default()),
+            None
+        );
+    }
+
     #[test]
     fn first_markdown_path_from_args_uses_first_valid_markdown_candidate() {
         let text = write_temp_file("txt", "ignore");
@@ -509,7 +1139,7 @@ mod tests {
             markdown.to_string_lossy().into_owned(),
         ];
 
-        let resolved = first_markdown_path_from_args(&args, None)
+        let resolved = first_markdown_path_from_args(&args, None, &MarkdownFileTypes::default())
             .expect("first valid markdown launch arg should resolve");
         assert_eq!(
             resolved,
@@ -535,7 +1165,7 @@ mod tests {
             tauri::Url::from_file_path(&markdown).expect("markdown file url should build"),
         ];
 
-        let resolved = first_markdown_path_from_urls(&urls)
+        let resolved = first_markdown_path_from_urls(&urls, &MarkdownFileTypes::default())
             .expect("first valid markdown file url should resolve");
         assert_eq!(
             resolved,
@@ -549,4 +1179,20 @@ mod tests {
         let _ = std::fs::remove_file(text);
         let _ = std::fs::remove_file(markdown);
     }
+
+    #[test]
+    fn markdown_file_types_come_from_the_bundle_file_association() {
+        let config: tauri::Config = serde_json::from_str(include_str!("../tauri.conf.json"))
+            .expect("tauri.conf.json should parse");
+
+        assert_eq!(markdown_file_types(&config), MarkdownFileTypes::default());
+    }
+
+    #[test]
+    fn settings_folder_matches_the_bundle_identifier() {
+        let config: tauri::Config = serde_json::from_str(include_str!("../tauri.conf.json"))
+            .expect("tauri.conf.json should parse");
+
+        assert_eq!(config.identifier, APP_IDENTIFIER);
+    }
 }
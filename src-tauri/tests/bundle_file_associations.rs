@@ -2,6 +2,7 @@ use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 
+use markdown_viewer_infrastructure::file_repository::DEFAULT_MARKDOWN_EXTENSIONS;
 use serde_json::Value;
 
 fn tauri_config() -> Value {
@@ -35,9 +36,14 @@ fn bundle_registers_markdown_file_association() {
         .map(|value| value.as_str().expect("extension should be a string"))
         .collect::<BTreeSet<_>>();
 
+    // The repository falls back to DEFAULT_MARKDOWN_EXTENSIONS when no association is
+    // configured, so the two lists must not drift apart.
     assert_eq!(
         ext,
-        BTreeSet::from(["markdown", "md", "mdown", "mkd", "mkdn"])
+        DEFAULT_MARKDOWN_EXTENSIONS
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
     );
     assert_eq!(
         markdown_association["mimeType"].as_str(),
@@ -157,6 +157,7 @@ fn crate_dependency_graph_follows_clean_architecture() {
     let presentation_manifest = manifest("crates/presentation/Cargo.toml");
     let infrastructure_manifest = manifest("crates/infrastructure/Cargo.toml");
     let app_manifest = manifest("Cargo.toml");
+    let cli_manifest = manifest("crates/cli/Cargo.toml");
 
     assert_eq!(
         local_markdown_dependencies(&domain_manifest),
@@ -190,6 +191,22 @@ fn crate_dependency_graph_follows_clean_architecture() {
         ]),
         "app should wire application, infrastructure, and presentation"
     );
+    assert_eq!(
+        local_markdown_dependencies(&cli_manifest),
+        BTreeSet::from([
+            String::from("markdown_viewer_application"),
+            String::from("markdown_viewer_infrastructure"),
+            String::from("markdown_viewer_presentation"),
+        ]),
+        "cli should wire application, infrastructure, and presentation"
+    );
+}
+
+#[test]
+fn cli_does_not_depend_on_the_desktop_runtime() {
+    let cli_manifest = manifest("crates/cli/Cargo.toml");
+
+    assert_forbidden_dependencies_absent("cli", &cli_manifest, &["tauri", "rfd"]);
 }
 
 #[test]
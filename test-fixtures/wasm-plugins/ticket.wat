@@ -0,0 +1,47 @@
+;; Example render plugin: wraps ```ticket fenced blocks in a styled container.
+;;
+;; See `src-tauri/crates/infrastructure/src/wasm_plugin_host.rs` for the plugin ABI.
+(module
+  (memory (export "memory") 1)
+
+  (data (i32.const 0) "ticket")
+  (data (i32.const 16) "<div class=\"mdv-ticket\">")
+  (data (i32.const 64) "</div>")
+
+  (global $heap (mut i32) (i32.const 1024))
+
+  (func $alloc (export "mdv_alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    (local.get $ptr))
+
+  ;; "ticket" at offset 0, length 6.
+  (func (export "mdv_info_strings") (result i64)
+    (i64.const 6))
+
+  (func (export "mdv_render")
+    (param $info_ptr i32) (param $info_len i32) (param $code_ptr i32) (param $code_len i32)
+    (result i64)
+    (local $out i32)
+    (local $total i32)
+
+    ;; Decline empty blocks so the host keeps the original code block.
+    (if (i32.eqz (local.get $code_len))
+      (then (return (i64.const 0))))
+
+    (local.set $total (i32.add (local.get $code_len) (i32.const 30)))
+    (local.set $out (call $alloc (local.get $total)))
+    (memory.copy (local.get $out) (i32.const 16) (i32.const 24))
+    (memory.copy
+      (i32.add (local.get $out) (i32.const 24))
+      (local.get $code_ptr)
+      (local.get $code_len))
+    (memory.copy
+      (i32.add (i32.add (local.get $out) (i32.const 24)) (local.get $code_len))
+      (i32.const 64)
+      (i32.const 6))
+
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $out)) (i64.const 32))
+      (i64.extend_i32_u (local.get $total)))))